@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use benches_common_2d::bench_app;
+use bevy::prelude::*;
+use bevy_xpbd_2d::math::*;
+use bevy_xpbd_2d::prelude::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn setup_capsules(app: &mut App, size: u32, locked_axes: LockedAxes) {
+    app.add_systems(Startup, move |mut commands: Commands| {
+        commands.spawn((
+            RigidBody::Static,
+            Position(-2.0 * Vector::Y),
+            Collider::rectangle(1000.0, 1.0),
+        ));
+
+        for x in 0..size {
+            for y in 0..size {
+                commands.spawn((
+                    RigidBody::Dynamic,
+                    Position(Vector::new(x as Scalar * 2.0, y as Scalar * 2.5)),
+                    Collider::capsule(1.0, 0.4),
+                    locked_axes,
+                ));
+            }
+        }
+    });
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("capsules 10x10, 30 steps", |b| {
+        bench_app(b, 30, |app| setup_capsules(app, 10, LockedAxes::new()))
+    });
+
+    c.bench_function("rotation-locked capsules 10x10, 30 steps", |b| {
+        bench_app(b, 30, |app| {
+            setup_capsules(app, 10, LockedAxes::ROTATION_LOCKED)
+        })
+    });
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().measurement_time(Duration::from_secs(20));
+    targets = criterion_benchmark
+);
+criterion_main!(benches);
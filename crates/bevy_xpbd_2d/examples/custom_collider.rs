@@ -112,7 +112,11 @@ impl AnyCollider for CircleCollider {
                     // Impulses are computed by the constraint solver
                     normal_impulse: 0.0,
                     tangent_impulse: 0.0,
+                    feature1: FeatureId::Unknown,
+                    feature2: FeatureId::Unknown,
                 }],
+                subshape1: None,
+                subshape2: None,
             }]
         } else {
             vec![]
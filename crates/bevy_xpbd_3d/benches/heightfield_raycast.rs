@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_xpbd_3d::math::*;
+use bevy_xpbd_3d::parry::query::{Ray, RayCast};
+use bevy_xpbd_3d::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn heightfield(size: usize) -> Collider {
+    let heights = (0..size)
+        .map(|i| {
+            (0..size)
+                .map(|j| ((i as Scalar * 0.3).sin() + (j as Scalar * 0.2).cos()) * 0.5)
+                .collect()
+        })
+        .collect();
+    Collider::heightfield(heights, Vector::new(size as Scalar, 1.0, size as Scalar))
+}
+
+fn setup_pipeline(collider: &Collider) -> SpatialQueryPipeline {
+    let entity = Entity::from_raw(0);
+    let mut pipeline = SpatialQueryPipeline::new();
+    pipeline.update(
+        std::iter::once((
+            entity,
+            &Position::default(),
+            &Rotation::default(),
+            collider,
+            None,
+        )),
+        std::iter::once(entity),
+    );
+    pipeline
+}
+
+/// Long, nearly horizontal rays that skim the terrain and cross most of the heightfield.
+fn rays(size: usize) -> Vec<(Vector, Direction3d)> {
+    let half_size = size as Scalar * 0.5;
+    (0..64)
+        .map(|i| {
+            let offset = (i as Scalar / 64.0 - 0.5) * size as Scalar;
+            let origin = Vector::new(-half_size, 1.2, offset);
+            let direction = Direction3d::new(Vector::new(1.0, -0.002, 0.1).f32()).unwrap();
+            (origin, direction)
+        })
+        .collect()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    for size in [64, 256, 1024] {
+        let collider = heightfield(size);
+        let pipeline = setup_pipeline(&collider);
+        let rays = rays(size);
+        let max_time_of_impact = size as Scalar * 2.0;
+
+        c.bench_function(
+            &format!("heightfield {size}x{size}, 64 long rays, DDA"),
+            |b| {
+                b.iter(|| {
+                    for (origin, direction) in rays.iter() {
+                        black_box(pipeline.cast_ray(
+                            *origin,
+                            *direction,
+                            max_time_of_impact,
+                            true,
+                            SpatialQueryFilter::default(),
+                        ));
+                    }
+                })
+            },
+        );
+
+        c.bench_function(
+            &format!("heightfield {size}x{size}, 64 long rays, Parry"),
+            |b| {
+                b.iter(|| {
+                    for (origin, direction) in rays.iter() {
+                        let ray = Ray::new((*origin).into(), direction.adjust_precision().into());
+                        black_box(collider.shape_scaled().cast_local_ray_and_get_normal(
+                            &ray,
+                            max_time_of_impact,
+                            true,
+                        ));
+                    }
+                })
+            },
+        );
+    }
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().measurement_time(Duration::from_secs(10));
+    targets = criterion_benchmark
+);
+criterion_main!(benches);
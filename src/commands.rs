@@ -0,0 +1,524 @@
+//! Physics-related extensions for [`Commands`].
+//!
+//! See [`PhysicsCommandsExt`] and [`PhysicsEntityCommandsExt`].
+
+use crate::{plugins::prepare::DisabledRecursively, prelude::*};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        system::{Command, EntityCommands},
+    },
+    prelude::*,
+};
+
+/// An extension trait for [`Commands`] with physics-related commands.
+pub trait PhysicsCommandsExt {
+    /// Merges the given dynamic [rigid bodies](RigidBody) into a single body and returns its entity.
+    ///
+    /// The new body is positioned at the combined center of mass of the merged bodies. It inherits
+    /// their linear and angular momentum, and its mass properties are computed from their colliders,
+    /// which become [child colliders](ColliderParent) of the new body.
+    ///
+    /// The original entities are kept as fixed children of the new body, and their [`RigidBody`]
+    /// and mass property components are removed. The original entities are stored in
+    /// the [`MergedBody`] component of the new body, and can be restored with
+    /// [`split_body`](Self::split_body).
+    ///
+    /// Entities that are not dynamic rigid bodies are ignored.
+    /// Joints between merged bodies are not solved while the bodies are merged.
+    ///
+    /// Mass properties that were set manually instead of being computed from colliders
+    /// are not preserved.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    #[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+    ///
+    /// #[derive(Component)]
+    /// struct Wall;
+    ///
+    /// fn weld_walls(mut commands: Commands, walls: Query<Entity, With<Wall>>) {
+    ///     let walls = walls.iter().collect::<Vec<_>>();
+    ///     let merged = commands.merge_bodies(&walls);
+    ///     println!("Merged walls into {merged:?}");
+    /// }
+    /// ```
+    fn merge_bodies(&mut self, bodies: &[Entity]) -> Entity;
+
+    /// Splits a body created with [`merge_bodies`](Self::merge_bodies) back into the original bodies.
+    ///
+    /// Each original body gets its current position and rotation from the merged body, and
+    /// the velocity of the merged body at the body's position. The merged body is despawned.
+    fn split_body(&mut self, merged_body: Entity);
+}
+
+impl PhysicsCommandsExt for Commands<'_, '_> {
+    fn merge_bodies(&mut self, bodies: &[Entity]) -> Entity {
+        let merged_body = self.spawn_empty().id();
+        self.add(MergeBodies {
+            merged_body,
+            bodies: bodies.to_vec(),
+        });
+        merged_body
+    }
+
+    fn split_body(&mut self, merged_body: Entity) {
+        self.add(SplitBody { merged_body });
+    }
+}
+
+/// An extension trait for [`EntityCommands`] with physics-related commands.
+pub trait PhysicsEntityCommandsExt {
+    /// Disables the physics of the entity and all of its descendants.
+    ///
+    /// [`RigidBodyDisabled`] is added to [rigid bodies](RigidBody) and [`ColliderDisabled`] is added
+    /// to [colliders](Collider) in the hierarchy. [Joints](joints) attached to the disabled bodies are disabled
+    /// with [`JointDisabled`], regardless of [`DisablePropagation`].
+    ///
+    /// Velocities are preserved, so the bodies continue moving where they left off when physics
+    /// is enabled again with [`enable_physics_recursive`](Self::enable_physics_recursive).
+    /// This is useful for things like object pooling and handing control over to a cutscene.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    #[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+    ///
+    /// #[derive(Component)]
+    /// struct Ragdoll;
+    ///
+    /// fn freeze_ragdolls(mut commands: Commands, ragdolls: Query<Entity, With<Ragdoll>>) {
+    ///     for entity in &ragdolls {
+    ///         commands.entity(entity).disable_physics_recursive();
+    ///     }
+    /// }
+    /// ```
+    fn disable_physics_recursive(&mut self) -> &mut Self;
+
+    /// Enables the physics of the entity and all of its descendants again after
+    /// [`disable_physics_recursive`](Self::disable_physics_recursive).
+    ///
+    /// Only the components that were added by [`disable_physics_recursive`](Self::disable_physics_recursive)
+    /// are removed, so bodies, colliders and joints that were disabled manually stay disabled.
+    fn enable_physics_recursive(&mut self) -> &mut Self;
+}
+
+impl PhysicsEntityCommandsExt for EntityCommands<'_> {
+    fn disable_physics_recursive(&mut self) -> &mut Self {
+        let root = self.id();
+        self.commands().add(DisablePhysicsRecursive { root });
+        self
+    }
+
+    fn enable_physics_recursive(&mut self) -> &mut Self {
+        let root = self.id();
+        self.commands().add(EnablePhysicsRecursive { root });
+        self
+    }
+}
+
+/// A [`Command`] that disables the physics of an entity and its descendants.
+///
+/// See [`PhysicsEntityCommandsExt::disable_physics_recursive`].
+pub struct DisablePhysicsRecursive {
+    /// The root entity of the hierarchy.
+    pub root: Entity,
+}
+
+impl Command for DisablePhysicsRecursive {
+    fn apply(self, world: &mut World) {
+        let mut stack = vec![self.root];
+        while let Some(entity) = stack.pop() {
+            let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+                continue;
+            };
+            if let Some(children) = entity_mut.get::<Children>() {
+                stack.extend(children.iter().copied());
+            }
+            if entity_mut.contains::<DisabledRecursively>() {
+                continue;
+            }
+
+            let marker = DisabledRecursively {
+                body: entity_mut.contains::<RigidBody>()
+                    && !entity_mut.contains::<RigidBodyDisabled>(),
+                collider: entity_mut.contains::<Collider>()
+                    && !entity_mut.contains::<ColliderDisabled>(),
+            };
+            if marker.body {
+                entity_mut.insert(RigidBodyDisabled);
+            }
+            if marker.collider {
+                entity_mut.insert(ColliderDisabled);
+            }
+            if marker.body || marker.collider {
+                entity_mut.insert(marker);
+            }
+        }
+    }
+}
+
+/// A [`Command`] that enables the physics of an entity and its descendants after [`DisablePhysicsRecursive`].
+///
+/// See [`PhysicsEntityCommandsExt::enable_physics_recursive`].
+pub struct EnablePhysicsRecursive {
+    /// The root entity of the hierarchy.
+    pub root: Entity,
+}
+
+impl Command for EnablePhysicsRecursive {
+    fn apply(self, world: &mut World) {
+        let mut stack = vec![self.root];
+        while let Some(entity) = stack.pop() {
+            let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+                continue;
+            };
+            if let Some(children) = entity_mut.get::<Children>() {
+                stack.extend(children.iter().copied());
+            }
+            let Some(marker) = entity_mut.take::<DisabledRecursively>() else {
+                continue;
+            };
+            if marker.body {
+                entity_mut.remove::<RigidBodyDisabled>();
+            }
+            if marker.collider {
+                entity_mut.remove::<ColliderDisabled>();
+            }
+        }
+    }
+}
+
+/// A component that stores the original bodies of a body that was created with
+/// [`merge_bodies`](PhysicsCommandsExt::merge_bodies).
+#[derive(Reflect, Clone, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct MergedBody(pub(crate) Vec<Entity>);
+
+impl MergedBody {
+    /// Returns the entities of the bodies that were merged.
+    pub fn bodies(&self) -> &[Entity] {
+        &self.0
+    }
+}
+
+impl MapEntities for MergedBody {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for entity in self.0.iter_mut() {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
+/// A [`Command`] that merges dynamic rigid bodies into a single body.
+///
+/// See [`PhysicsCommandsExt::merge_bodies`].
+pub struct MergeBodies {
+    /// The entity that the merged body is inserted on.
+    pub merged_body: Entity,
+    /// The bodies to merge.
+    pub bodies: Vec<Entity>,
+}
+
+/// The state of a body that is being merged.
+struct MergedBodyState {
+    entity: Entity,
+    position: Vector,
+    rotation: Rotation,
+    world_center_of_mass: Vector,
+    linear_velocity: Vector,
+    angular_velocity: AngularVelocity,
+    mass: Scalar,
+    world_inertia: Inertia,
+}
+
+impl Command for MergeBodies {
+    fn apply(self, world: &mut World) {
+        let states = self
+            .bodies
+            .iter()
+            .filter_map(|&entity| {
+                let entity_ref = world.get_entity(entity)?;
+                if !entity_ref.get::<RigidBody>()?.is_dynamic() {
+                    return None;
+                }
+                let position = entity_ref.get::<Position>().copied().unwrap_or_default().0;
+                let rotation = entity_ref.get::<Rotation>().copied().unwrap_or_default();
+                let center_of_mass = entity_ref
+                    .get::<CenterOfMass>()
+                    .copied()
+                    .unwrap_or_default();
+                Some(MergedBodyState {
+                    entity,
+                    position,
+                    rotation,
+                    world_center_of_mass: position + rotation.rotate(center_of_mass.0),
+                    linear_velocity: entity_ref
+                        .get::<LinearVelocity>()
+                        .copied()
+                        .unwrap_or_default()
+                        .0,
+                    angular_velocity: entity_ref
+                        .get::<AngularVelocity>()
+                        .copied()
+                        .unwrap_or_default(),
+                    mass: entity_ref.get::<Mass>().copied().unwrap_or_default().0,
+                    world_inertia: entity_ref
+                        .get::<Inertia>()
+                        .copied()
+                        .unwrap_or_default()
+                        .rotated(&rotation),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if states.is_empty() {
+            world.despawn(self.merged_body);
+            return;
+        }
+
+        // Compute the combined center of mass and linear momentum.
+        let total_mass = states.iter().map(|state| state.mass).sum::<Scalar>();
+        let (center_of_mass, linear_velocity) = if total_mass > 0.0 {
+            (
+                states
+                    .iter()
+                    .map(|state| state.world_center_of_mass * state.mass)
+                    .sum::<Vector>()
+                    / total_mass,
+                states
+                    .iter()
+                    .map(|state| state.linear_velocity * state.mass)
+                    .sum::<Vector>()
+                    / total_mass,
+            )
+        } else {
+            let count = states.len() as Scalar;
+            (
+                states
+                    .iter()
+                    .map(|state| state.world_center_of_mass)
+                    .sum::<Vector>()
+                    / count,
+                states
+                    .iter()
+                    .map(|state| state.linear_velocity)
+                    .sum::<Vector>()
+                    / count,
+            )
+        };
+
+        // Compute the angular velocity that conserves the total angular momentum
+        // around the new center of mass.
+        let mut inertia = Inertia::ZERO;
+        #[cfg(feature = "2d")]
+        let mut angular_momentum: Scalar = 0.0;
+        #[cfg(feature = "3d")]
+        let mut angular_momentum = Vector::ZERO;
+        for state in states.iter() {
+            let offset = state.world_center_of_mass - center_of_mass;
+            inertia.0 += state.world_inertia.shifted(state.mass, offset);
+            #[cfg(feature = "2d")]
+            {
+                angular_momentum += state.world_inertia.0 * state.angular_velocity.0
+                    + state.mass * offset.perp_dot(state.linear_velocity);
+            }
+            #[cfg(feature = "3d")]
+            {
+                angular_momentum += state.world_inertia.0 * state.angular_velocity.0
+                    + state.mass * offset.cross(state.linear_velocity);
+            }
+        }
+        #[cfg(feature = "2d")]
+        let angular_velocity = if inertia.0 > Scalar::EPSILON {
+            AngularVelocity(angular_momentum / inertia.0)
+        } else {
+            AngularVelocity::ZERO
+        };
+        #[cfg(feature = "3d")]
+        let angular_velocity = if inertia.0.determinant().abs() > Scalar::EPSILON {
+            AngularVelocity(inertia.inverse().0 * angular_momentum)
+        } else {
+            AngularVelocity::ZERO
+        };
+
+        let rotation = states[0].rotation;
+        #[cfg(feature = "2d")]
+        let merged_transform = {
+            let z = world
+                .get::<Transform>(states[0].entity)
+                .map_or(0.0, |transform| transform.translation.z);
+            Transform::from_translation(center_of_mass.f32().extend(z))
+                .with_rotation(Quaternion::from(rotation).f32())
+        };
+        #[cfg(feature = "3d")]
+        let merged_transform =
+            Transform::from_translation(center_of_mass.f32()).with_rotation(rotation.f32());
+
+        let Some(mut merged_body) = world.get_entity_mut(self.merged_body) else {
+            return;
+        };
+        merged_body.insert((
+            RigidBody::Dynamic,
+            Position(center_of_mass),
+            rotation,
+            LinearVelocity(linear_velocity),
+            angular_velocity,
+            TransformBundle::from_transform(merged_transform),
+            MergedBody(states.iter().map(|state| state.entity).collect()),
+        ));
+
+        for state in states.iter() {
+            // Compute the transform of the body relative to the merged body.
+            let inverse_rotation = rotation.inverse();
+            let local_position = inverse_rotation.rotate(state.position - center_of_mass);
+            #[cfg(feature = "2d")]
+            let local_rotation = Quaternion::from(inverse_rotation.mul(state.rotation)).f32();
+            #[cfg(feature = "3d")]
+            let local_rotation = (inverse_rotation.0 * state.rotation.0).f32();
+            let mut transform = world
+                .get::<Transform>(state.entity)
+                .copied()
+                .unwrap_or_default();
+            #[cfg(feature = "2d")]
+            {
+                transform.translation = local_position
+                    .f32()
+                    .extend(transform.translation.z - merged_transform.translation.z);
+            }
+            #[cfg(feature = "3d")]
+            {
+                transform.translation = local_position.f32();
+            }
+            transform.rotation = local_rotation;
+
+            world
+                .entity_mut(state.entity)
+                .remove::<(
+                    RigidBody,
+                    Mass,
+                    InverseMass,
+                    Inertia,
+                    InverseInertia,
+                    CenterOfMass,
+                    Sleeping,
+                )>()
+                .insert(transform)
+                .set_parent(self.merged_body);
+
+            reinsert_colliders(world, state.entity);
+        }
+    }
+}
+
+/// A [`Command`] that splits a body created with [`MergeBodies`] back into the original bodies.
+///
+/// See [`PhysicsCommandsExt::split_body`].
+pub struct SplitBody {
+    /// The merged body to split.
+    pub merged_body: Entity,
+}
+
+impl Command for SplitBody {
+    fn apply(self, world: &mut World) {
+        let Some(merged_body) = world.get_entity(self.merged_body) else {
+            return;
+        };
+        let Some(MergedBody(bodies)) = merged_body.get::<MergedBody>().cloned() else {
+            return;
+        };
+        let position = merged_body.get::<Position>().copied().unwrap_or_default().0;
+        let rotation = merged_body.get::<Rotation>().copied().unwrap_or_default();
+        let center_of_mass = merged_body
+            .get::<CenterOfMass>()
+            .copied()
+            .unwrap_or_default();
+        let linear_velocity = merged_body
+            .get::<LinearVelocity>()
+            .copied()
+            .unwrap_or_default();
+        let angular_velocity = merged_body
+            .get::<AngularVelocity>()
+            .copied()
+            .unwrap_or_default();
+        #[cfg(feature = "2d")]
+        let merged_transform = merged_body.get::<Transform>().copied().unwrap_or_default();
+        let world_center_of_mass = position + rotation.rotate(center_of_mass.0);
+
+        for entity in bodies {
+            let Some(mut body) = world.get_entity_mut(entity) else {
+                continue;
+            };
+            // Detach the body so that it isn't left with a dangling parent when the merged body is despawned.
+            body.remove_parent();
+            // A body without a `Transform` is placed at the origin of the merged body.
+            let transform = body.get::<Transform>().copied().unwrap_or_default();
+
+            // Compute the global position and rotation of the body.
+            #[cfg(feature = "2d")]
+            let local_position = transform.translation.truncate().adjust_precision();
+            #[cfg(feature = "3d")]
+            let local_position = transform.translation.adjust_precision();
+            let body_position = position + rotation.rotate(local_position);
+            #[cfg(feature = "2d")]
+            let body_rotation = rotation.mul(Rotation::from(transform.rotation.adjust_precision()));
+            #[cfg(feature = "3d")]
+            let body_rotation = Rotation(rotation.0 * transform.rotation.adjust_precision());
+
+            // Inherit the velocity of the merged body at the body's position.
+            let offset = body_position - world_center_of_mass;
+            #[cfg(feature = "2d")]
+            let body_linear_velocity = linear_velocity.0 + angular_velocity.0 * offset.perp();
+            #[cfg(feature = "3d")]
+            let body_linear_velocity = linear_velocity.0 + angular_velocity.0.cross(offset);
+
+            let mut global_transform = transform;
+            #[cfg(feature = "2d")]
+            {
+                global_transform.translation = body_position
+                    .f32()
+                    .extend(merged_transform.translation.z + transform.translation.z);
+                global_transform.rotation = Quaternion::from(body_rotation).f32();
+            }
+            #[cfg(feature = "3d")]
+            {
+                global_transform.translation = body_position.f32();
+                global_transform.rotation = body_rotation.f32();
+            }
+
+            world.entity_mut(entity).insert((
+                RigidBody::Dynamic,
+                Position(body_position),
+                body_rotation,
+                LinearVelocity(body_linear_velocity),
+                angular_velocity,
+                global_transform,
+            ));
+
+            reinsert_colliders(world, entity);
+        }
+
+        world.despawn(self.merged_body);
+    }
+}
+
+/// Removes and reinserts the colliders of the given entity and its descendants so that their
+/// mass properties are added to the rigid body that they are attached to from scratch.
+fn reinsert_colliders(world: &mut World, entity: Entity) {
+    let mut stack = vec![entity];
+    while let Some(entity) = stack.pop() {
+        if let Some(children) = world.get::<Children>(entity) {
+            stack.extend(children.iter().copied());
+        }
+        let mut entity_mut = world.entity_mut(entity);
+        if let Some(collider) = entity_mut.take::<Collider>() {
+            entity_mut.insert(collider);
+        }
+    }
+}
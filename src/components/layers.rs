@@ -402,6 +402,120 @@ impl Default for CollisionLayers {
     }
 }
 
+/// The Z layer of an entity in a 2D game, for example the floor level in a top-down game.
+///
+/// Colliders only interact with colliders on the same Z layer, or on nearby layers if they have
+/// a [`ZLayerAdjacency`]. The Z layers are compiled into the [`CollisionLayers`] of the entity automatically,
+/// so the bitmasks don't need to be managed manually.
+///
+/// There are 32 Z layers from [`ZLayer::MIN`] to [`ZLayer::MAX`], one for each bit of a [`LayerMask`].
+/// Layers outside of this range are clamped.
+///
+/// Note that the [`CollisionLayers`] of entities with a [`ZLayer`] are overwritten,
+/// so Z layers can't be combined with other collision layers.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_xpbd_2d::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // The player is on the ground floor
+///     commands.spawn((RigidBody::Dynamic, Collider::circle(0.5), ZLayer(0)));
+///
+///     // A wall on the first floor doesn't collide with the player
+///     commands.spawn((RigidBody::Static, Collider::rectangle(1.0, 5.0), ZLayer(1)));
+///
+///     // Stairs collide with both floors
+///     commands.spawn((
+///         RigidBody::Static,
+///         Collider::rectangle(1.0, 2.0),
+///         ZLayer(0),
+///         ZLayerAdjacency(1),
+///     ));
+/// }
+/// ```
+#[cfg(feature = "2d")]
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct ZLayer(pub i8);
+
+#[cfg(feature = "2d")]
+impl ZLayer {
+    /// The lowest Z layer.
+    pub const MIN: Self = Self(-16);
+    /// The highest Z layer.
+    pub const MAX: Self = Self(15);
+
+    /// Returns the [`LayerMask`] of this Z layer.
+    pub fn layer_mask(self) -> LayerMask {
+        LayerMask(1 << self.bit())
+    }
+
+    /// Returns the [`CollisionLayers`] for a collider on this Z layer that interacts with colliders
+    /// that are at most `adjacency` layers away.
+    ///
+    /// The collider is a member of all of these layers, so an adjacency on one side is enough
+    /// for two colliders to interact.
+    pub fn collision_layers(self, adjacency: ZLayerAdjacency) -> CollisionLayers {
+        let bit = self.bit();
+        let lowest = bit.saturating_sub(adjacency.0 as u32);
+        let highest = (bit + adjacency.0 as u32).min(31);
+        // Set the bits from the lowest to the highest layer
+        let span = (u32::MAX >> (31 - highest)) & (u32::MAX << lowest);
+        CollisionLayers::from_bits(span, span)
+    }
+
+    /// The bit of the Z layer in a [`LayerMask`].
+    fn bit(self) -> u32 {
+        (self.0.clamp(Self::MIN.0, Self::MAX.0) - Self::MIN.0) as u32
+    }
+}
+
+/// The number of neighboring [Z layers](ZLayer) above and below an entity's own Z layer
+/// that its colliders can interact with.
+///
+/// The default is `0`, which only allows interactions on the same Z layer.
+/// The adjacencies of two colliders add up, so colliders interact if their Z layers are at most
+/// the sum of their adjacencies apart.
+#[cfg(feature = "2d")]
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct ZLayerAdjacency(pub u8);
+
+/// Compiles the [`ZLayer`]s of entities into their [`CollisionLayers`].
+#[cfg(feature = "2d")]
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_z_layers(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &ZLayer,
+            Option<&ZLayerAdjacency>,
+            Option<&mut CollisionLayers>,
+        ),
+        Or<(Changed<ZLayer>, Changed<ZLayerAdjacency>)>,
+    >,
+) {
+    for (entity, z_layer, adjacency, layers) in &mut query {
+        let new_layers = z_layer.collision_layers(adjacency.copied().unwrap_or_default());
+        match layers {
+            Some(mut layers) => {
+                if *layers != new_layers {
+                    *layers = new_layers;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(new_layers);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // Needed for PhysicsLayer derive macro
@@ -438,4 +552,27 @@ mod tests {
             .has_all([GameLayer::Player, GameLayer::Ground]));
         assert!(!with_bitmask.filters.has_all(GameLayer::Enemy));
     }
+
+    #[test]
+    #[cfg(feature = "2d")]
+    fn z_layers() {
+        let ground = ZLayer(0).collision_layers(ZLayerAdjacency(0));
+        let first_floor = ZLayer(1).collision_layers(ZLayerAdjacency(0));
+        let stairs = ZLayer(0).collision_layers(ZLayerAdjacency(1));
+
+        assert!(ground.interacts_with(ground));
+        assert!(!ground.interacts_with(first_floor));
+        assert!(stairs.interacts_with(first_floor));
+        assert!(first_floor.interacts_with(stairs));
+        assert!(stairs.interacts_with(ground));
+        assert!(!stairs.interacts_with(ZLayer(2).collision_layers(ZLayerAdjacency(0))));
+
+        // The extreme layers are clamped to the range of the layer mask
+        let lowest = ZLayer(i8::MIN).collision_layers(ZLayerAdjacency(u8::MAX));
+        assert_eq!(lowest.memberships, LayerMask::ALL);
+        assert_eq!(lowest.filters, LayerMask::ALL);
+        let highest = ZLayer(i8::MAX).collision_layers(ZLayerAdjacency(0));
+        assert_eq!(highest.memberships, ZLayer::MAX.layer_mask());
+        assert_eq!(highest.filters, ZLayer::MAX.layer_mask());
+    }
 }
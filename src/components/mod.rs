@@ -4,6 +4,11 @@ mod forces;
 mod layers;
 mod locked_axes;
 mod mass_properties;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+mod presets;
 mod rotation;
 mod world_queries;
 
@@ -11,11 +16,19 @@ pub use forces::*;
 pub use layers::*;
 pub use locked_axes::*;
 pub use mass_properties::*;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use presets::*;
 pub use rotation::*;
 pub use world_queries::*;
 
 use crate::prelude::*;
-use bevy::prelude::*;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
 use derive_more::From;
 
 /// A non-deformable body used for the simulation of most physics objects.
@@ -243,6 +256,9 @@ pub enum RigidBody {
     /// Unlike static bodies, kinematic bodies can have velocity.
     /// The engine doesn't modify the values of a kinematic body's components,
     /// so you have full control of them.
+    ///
+    /// Kinematic bodies pass through static geometry unless they have
+    /// [`KinematicCollideMode::StopAtContacts`].
     Kinematic,
 }
 
@@ -263,10 +279,45 @@ impl RigidBody {
     }
 }
 
+/// Controls how a [kinematic](RigidBody::Kinematic) body responds to contacts with static geometry.
+///
+/// By default, kinematic bodies move exactly according to their velocity and pass through everything.
+/// With [`KinematicCollideMode::StopAtContacts`], the motion of the body is clipped by contacts with
+/// [static](RigidBody::Static) bodies inside the solver, so simple movers like doors and platforms
+/// don't push through walls. The body stays kinematic, so it isn't affected by forces or dynamic bodies,
+/// and its velocity is left as is.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         RigidBody::Kinematic,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(1.0, 1.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(1.0, 1.0, 1.0),")]
+///         KinematicCollideMode::StopAtContacts,
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub enum KinematicCollideMode {
+    /// The kinematic body moves according to its velocity and passes through static geometry.
+    #[default]
+    PassThrough,
+    /// The motion of the kinematic body is stopped by contacts with static geometry.
+    StopAtContacts,
+}
+
 /// Indicates that a [rigid body](RigidBody) is not simulated by the physics engine until woken up again.
 /// This is done to improve performance and to help prevent small jitter that is typically present in collisions.
 ///
-/// Bodies are marked as sleeping when their linear and angular velocity is below the [`SleepingThreshold`] for a time
+/// Bodies are marked as sleeping when their velocity or kinetic energy is below the [`SleepingThreshold`] for a time
 /// indicated by [`DeactivationTime`]. A sleeping body is woken up when an active body interacts with it through
 /// collisions or other constraints, or when gravity changes, or when the body's
 /// position, rotation, velocity, or external forces are modified.
@@ -293,6 +344,44 @@ pub struct TimeSleeping(pub Scalar);
 #[reflect(Component)]
 pub struct SleepingDisabled;
 
+/// Groups bodies that must fall asleep and wake up together, like the parts of a parked articulated truck.
+///
+/// Bodies with the same [`SleepGroup`] are only marked as [`Sleeping`] once all of them are ready to sleep,
+/// and when one of them is woken up, the rest of the group is woken up as well.
+/// Bodies that are connected by [joints] share their sleep state in the same way, even without a [`SleepGroup`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // The cab and the trailer sleep and wake up together
+///     commands.spawn((RigidBody::Dynamic, SleepGroup(1)));
+///     commands.spawn((RigidBody::Dynamic, SleepGroup(1)));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq, Hash, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SleepGroup(pub u32);
+
+/// A component that disables a [rigid body](RigidBody), removing it from the simulation.
+///
+/// A disabled body is not moved by the physics engine, it doesn't have a collision response,
+/// and [joints] attached to it are not solved. Its colliders still detect collisions and send
+/// [collision events](ContactReportingPlugin#collision-events) unless they are disabled with [`ColliderDisabled`].
+/// The body's velocity is preserved, so it continues moving when it is enabled again.
+///
+/// With the [`DisablePropagation`] resource, disabling a body can also disable its colliders and attached joints.
+/// This can be used for cleanly turning off a whole vehicle or ragdoll.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct RigidBodyDisabled;
+
 /// The global position of a [rigid body](RigidBody) or a [collider](Collider).
 ///
 /// ## Relation to `Transform` and `GlobalTransform`
@@ -523,6 +612,73 @@ pub(crate) struct PreSolveAngularVelocity(pub Vector);
 #[reflect(Component)]
 pub struct GravityScale(pub Scalar);
 
+/// Controls how strongly the wind of [`PhysicsAmbientForces`] affects a [rigid body](RigidBody).
+///
+/// The value is a drag coefficient: the body is pushed towards the wind velocity with a force equal to
+/// the coefficient multiplied by the velocity of the wind relative to the body. Bodies without this component
+/// are not affected by the wind.
+///
+/// Because the force doesn't depend on mass, light bodies like leaves and debris follow the wind much faster
+/// than heavy ones with the same coefficient. Very large coefficients on very light bodies can make
+/// the simulation unstable.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// // Spawn a leaf that is carried by the wind
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         Mass(0.01),
+///         WindInfluence(0.05),
+///     ));
+/// }
+/// ```
+#[derive(
+    Component, Reflect, Debug, Clone, Copy, PartialEq, PartialOrd, Default, Deref, DerefMut, From,
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct WindInfluence(pub Scalar);
+
+/// A stable user-provided identifier for a collider entity that persists across despawning and respawning.
+///
+/// When an entity with a [`PhysicsId`] is despawned while it is in contact with another entity
+/// that also has a [`PhysicsId`], the persistent state of the contact pair, like [`Contacts::user_data`],
+/// is kept for a short time. If an entity with the same identifier starts touching the other entity again
+/// within that time, the state is re-associated with the new pair. This is useful for networking,
+/// where entities can be despawned and respawned when their authority changes.
+///
+/// The identifiers are not managed by the engine, and they should be unique among the existing entities.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn spawn_replicated_crate(mut commands: Commands) {
+///     // The identifier could come from the server, for example
+#[cfg_attr(
+    feature = "2d",
+    doc = "    commands.spawn((RigidBody::Dynamic, Collider::rectangle(1.0, 1.0), PhysicsId(42)));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    commands.spawn((RigidBody::Dynamic, Collider::cuboid(1.0, 1.0, 1.0), PhysicsId(42)));"
+)]
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct PhysicsId(pub u64);
+
 /// Determines how coefficients are combined for [`Restitution`] and [`Friction`].
 /// The default is `Average`.
 ///
@@ -900,6 +1056,60 @@ pub struct AngularDamping(pub Scalar);
 #[reflect(Component)]
 pub struct Dominance(pub i8);
 
+/// Makes a dynamic [rigid body](RigidBody) move in the reference frame of another body,
+/// for example a box inside of a moving ship or train.
+///
+/// Each substep, the body is carried along by the rigid motion of the reference body,
+/// and only its velocity relative to the reference body is integrated on top of that.
+/// Because the motion of the reference frame is followed exactly, bodies resting on large,
+/// fast-moving or rotating bodies don't jitter or slowly slide off due to integration errors.
+///
+/// Velocities are still stored in world space, so the fictitious forces caused by the acceleration
+/// and rotation of the reference frame, like the centrifugal and Coriolis forces, are taken into account
+/// through the body's relative velocity. Contacts and joints work like they normally do.
+///
+/// Reference bodies can not be relative to other bodies themselves.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands) {
+///     // Spawn a rotating platform
+///     let platform = commands
+///         .spawn((
+///             RigidBody::Kinematic,
+#[cfg_attr(feature = "2d", doc = "            Collider::rectangle(20.0, 1.0),")]
+#[cfg_attr(feature = "3d", doc = "            Collider::cuboid(20.0, 1.0, 20.0),")]
+#[cfg_attr(feature = "2d", doc = "            AngularVelocity(0.5),")]
+#[cfg_attr(feature = "3d", doc = "            AngularVelocity(Vec3::Y * 0.5),")]
+///         ))
+///         .id();
+///
+///     // Spawn a box that moves in the reference frame of the platform
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(1.0, 1.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(1.0, 1.0, 1.0),")]
+///         Transform::from_xyz(5.0, 1.0, 0.0),
+///         RelativeTo(platform),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelativeTo(pub Entity);
+
+impl MapEntities for RelativeTo {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.0 = entity_mapper.map_entity(self.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -0,0 +1,179 @@
+use crate::prelude::*;
+use bevy::{ecs::query::Has, prelude::*};
+
+/// A bundle for a [dynamic](RigidBody::Dynamic) rigid body with a [`Collider`].
+///
+/// The body is [validated](ValidatedBody) when it is spawned, and warnings are logged
+/// for common mistakes like zero-size colliders or a missing `Transform`.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+#[cfg_attr(
+    feature = "2d",
+    doc = "        DynamicBodyBundle::new(Collider::circle(0.5))"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "        DynamicBodyBundle::new(Collider::sphere(0.5))"
+)]
+///             .with_density(2.0)
+///             .with_restitution(0.7),
+///         SpatialBundle::default(),
+///     ));
+/// }
+/// ```
+#[allow(missing_docs)]
+#[derive(Bundle, Clone)]
+pub struct DynamicBodyBundle {
+    pub rigid_body: RigidBody,
+    pub collider: Collider,
+    pub density: ColliderDensity,
+    pub friction: Friction,
+    pub restitution: Restitution,
+    pub validated: ValidatedBody,
+}
+
+/// A bundle for a [kinematic](RigidBody::Kinematic) rigid body with a [`Collider`].
+///
+/// The body is [validated](ValidatedBody) when it is spawned, and warnings are logged
+/// for common mistakes like zero-size colliders or a missing `Transform`.
+#[allow(missing_docs)]
+#[derive(Bundle, Clone)]
+pub struct KinematicBodyBundle {
+    pub rigid_body: RigidBody,
+    pub collider: Collider,
+    pub density: ColliderDensity,
+    pub friction: Friction,
+    pub restitution: Restitution,
+    pub validated: ValidatedBody,
+}
+
+/// A bundle for a [static](RigidBody::Static) rigid body with a [`Collider`].
+///
+/// The body is [validated](ValidatedBody) when it is spawned, and warnings are logged
+/// for common mistakes like zero-size colliders or a missing `Transform`.
+#[allow(missing_docs)]
+#[derive(Bundle, Clone)]
+pub struct StaticBodyBundle {
+    pub rigid_body: RigidBody,
+    pub collider: Collider,
+    pub density: ColliderDensity,
+    pub friction: Friction,
+    pub restitution: Restitution,
+    pub validated: ValidatedBody,
+}
+
+macro_rules! impl_body_preset {
+    ($bundle:ident, $rigid_body:expr) => {
+        impl $bundle {
+            /// Creates a new bundle with the given collider and default material properties.
+            pub fn new(collider: Collider) -> Self {
+                Self {
+                    rigid_body: $rigid_body,
+                    collider,
+                    density: ColliderDensity::default(),
+                    friction: Friction::default(),
+                    restitution: Restitution::default(),
+                    validated: ValidatedBody,
+                }
+            }
+
+            /// Sets the [`ColliderDensity`] used for computing the mass properties of the collider.
+            pub fn with_density(mut self, density: Scalar) -> Self {
+                self.density = ColliderDensity(density);
+                self
+            }
+
+            /// Sets the [`Friction`] coefficient.
+            pub fn with_friction(mut self, coefficient: Scalar) -> Self {
+                self.friction = Friction::new(coefficient);
+                self
+            }
+
+            /// Sets the [`Restitution`] coefficient.
+            pub fn with_restitution(mut self, coefficient: Scalar) -> Self {
+                self.restitution = Restitution::new(coefficient);
+                self
+            }
+        }
+    };
+}
+
+impl_body_preset!(DynamicBodyBundle, RigidBody::Dynamic);
+impl_body_preset!(KinematicBodyBundle, RigidBody::Kinematic);
+impl_body_preset!(StaticBodyBundle, RigidBody::Static);
+
+/// A marker component that enables validation of a rigid body when it is spawned.
+///
+/// The following common mistakes are detected, and a warning is logged for each of them:
+///
+/// - A dynamic [`Sensor`] body without any [`Mass`]. Sensors don't contribute to mass properties,
+///   so the body would be treated as having infinite mass.
+/// - A zero-size [`Collider`]. Dynamic bodies also need extent along each axis
+///   for their mass properties to be valid.
+/// - A missing `Transform` and [`Position`], which places the body at the origin.
+///
+/// This is included in the [`DynamicBodyBundle`], [`KinematicBodyBundle`] and [`StaticBodyBundle`] presets,
+/// but it can also be added to other bodies manually.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct ValidatedBody;
+
+/// Logs warnings for common mistakes in newly spawned [validated bodies](ValidatedBody).
+pub(crate) fn validate_bodies(
+    bodies: Query<
+        (
+            Entity,
+            Option<&Name>,
+            &RigidBody,
+            Option<&Collider>,
+            Option<&Mass>,
+            Has<Sensor>,
+            Has<Transform>,
+            Has<Position>,
+        ),
+        Added<ValidatedBody>,
+    >,
+) {
+    for (entity, name, rb, collider, mass, is_sensor, has_transform, has_position) in &bodies {
+        let label = name.map_or(format!("{entity:?}"), |name| format!("{name} ({entity:?})"));
+
+        if rb.is_dynamic() && is_sensor && !mass.is_some_and(|mass| mass.0 > 0.0) {
+            warn!(
+                "dynamic body {label} is a sensor without any mass, so it will behave as if it had infinite mass. \
+                Add a `MassPropertiesBundle`, or a non-sensor collider as a child entity"
+            );
+        }
+
+        if let Some(collider) = collider {
+            let size = ColliderAabb::from_shape(collider.shape_scaled()).size();
+            let is_empty = size.max_element() <= Scalar::EPSILON;
+            let is_flat = size.min_element() <= Scalar::EPSILON;
+            if is_empty || (rb.is_dynamic() && is_flat) {
+                warn!(
+                    "body {label} has a zero-size collider, so it will not have valid contacts{}. \
+                    Check the dimensions and the scale of the collider",
+                    if rb.is_dynamic() {
+                        " or mass properties"
+                    } else {
+                        ""
+                    }
+                );
+            }
+        }
+
+        if !has_transform && !has_position {
+            warn!(
+                "body {label} has no `Transform` or `Position`, so it will be placed at the origin. \
+                Add a `SpatialBundle`, a `TransformBundle` or a `Position` to place it in the world"
+            );
+        }
+    }
+}
@@ -356,3 +356,49 @@ impl From<DQuat> for Rotation {
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[reflect(Component)]
 pub struct PreviousRotation(pub Rotation);
+
+/// The total rotation of a body in radians, accumulated across physics steps without wrapping.
+///
+/// [`Rotation`] wraps around after a full turn and loses the number of turns, while [`AccumulatedAngle`]
+/// keeps counting, which is useful for things like winches, screws and scoring spins.
+/// The angle is increased by counterclockwise rotation and decreased by clockwise rotation.
+///
+/// The angle starts from the value that the component is inserted with. Only rotation caused by the simulation
+/// is accumulated, so directly setting the [`Rotation`] of a body doesn't count towards the angle.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_xpbd_2d::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((RigidBody::Dynamic, AngularVelocity(10.0), AccumulatedAngle::default()));
+/// }
+///
+/// fn print_turns(query: Query<&AccumulatedAngle>) {
+///     for angle in &query {
+///         println!("Turns: {}", angle.turns());
+///     }
+/// }
+/// ```
+#[cfg(feature = "2d")]
+#[derive(
+    Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq, PartialOrd,
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct AccumulatedAngle(pub Scalar);
+
+#[cfg(feature = "2d")]
+impl AccumulatedAngle {
+    /// Returns the accumulated angle in degrees.
+    pub fn as_degrees(&self) -> Scalar {
+        self.0.to_degrees()
+    }
+
+    /// Returns the number of full turns, rounded towards zero.
+    pub fn turns(&self) -> i32 {
+        (self.0 / TAU).trunc() as i32
+    }
+}
@@ -54,6 +54,15 @@ impl<'w> RigidBodyQueryItem<'w> {
         inv_inertia
     }
 
+    /// Returns true if the rotation of the body is locked using [`LockedAxes`].
+    ///
+    /// The solver uses this as a fast path to skip angular terms entirely for such bodies.
+    #[cfg(feature = "2d")]
+    pub fn is_rotation_locked(&self) -> bool {
+        self.locked_axes
+            .is_some_and(|locked_axes| locked_axes.is_rotation_locked())
+    }
+
     /// Computes the effective world-space inverse inertia tensor, taking into account any rotation locking.
     #[cfg(feature = "3d")]
     pub fn effective_world_inv_inertia(&self) -> Matrix3 {
@@ -29,11 +29,17 @@ pub trait AngularConstraint: XpbdConstraint<2> {
         let inv_inertia1 = body1.effective_world_inv_inertia();
         let inv_inertia2 = body2.effective_world_inv_inertia();
 
-        // Apply rotational updates
-        if body1.rb.is_dynamic() && body1.dominance() <= body2.dominance() {
+        // Apply rotational updates, skipping rotation-locked bodies entirely
+        if body1.rb.is_dynamic()
+            && !body1.is_rotation_locked()
+            && body1.dominance() <= body2.dominance()
+        {
             *body1.rotation += Self::get_delta_rot(rot1, inv_inertia1, p);
         }
-        if body2.rb.is_dynamic() && body2.dominance() <= body1.dominance() {
+        if body2.rb.is_dynamic()
+            && !body2.is_rotation_locked()
+            && body2.dominance() <= body1.dominance()
+        {
             *body2.rotation -= Self::get_delta_rot(rot2, inv_inertia2, p);
         }
 
@@ -91,7 +97,7 @@ pub trait AngularConstraint: XpbdConstraint<2> {
     /// clockwise rotation.
     #[cfg(feature = "2d")]
     fn compute_generalized_inverse_mass(&self, body: &RigidBodyQueryItem, axis: Vector3) -> Scalar {
-        if body.rb.is_dynamic() {
+        if body.rb.is_dynamic() && !body.is_rotation_locked() {
             axis.dot(body.inverse_inertia.0 * axis)
         } else {
             // Static and kinematic bodies are a special case, where 0.0 can be thought of as infinite mass.
@@ -0,0 +1,168 @@
+//! [`FaceTarget`] component.
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// What a [`FaceTarget`] rotates a body towards.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum AimTarget {
+    /// Rotate the body so that its forward axis points towards the given point in world space.
+    Point(Vector),
+    /// Rotate the body so that its forward axis points in the direction of the given angle in radians.
+    Angle(Scalar),
+}
+
+/// A torque-limited angular motor that rotates a 2D body smoothly towards a target point or angle,
+/// like a ship or a top-down character turning towards where it's aiming.
+///
+/// The motor is solved as a soft constraint in the [solver], so it behaves like a critically damped spring
+/// with the given `frequency` and `damping_ratio` regardless of the tick rate, unlike PD controllers written
+/// in user space that apply torques once per frame and become unstable at low tick rates.
+/// The torque applied by the motor is limited by `max_torque`.
+///
+/// The component is added to the rigid body itself, and the body's local forward axis is given by `forward`.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_xpbd_2d::{math::*, prelude::*};
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         Collider::triangle(Vector::new(0.5, 0.0), Vector::new(-0.5, 0.3), Vector::new(-0.5, -0.3)),
+///         FaceTarget::point(Vector::new(5.0, 5.0))
+///             .with_max_torque(20.0)
+///             .with_frequency(3.0),
+///     ));
+/// }
+///
+/// fn aim_at_cursor(mut ships: Query<&mut FaceTarget>, cursor: Res<CursorPosition>) {
+///     for mut face_target in &mut ships {
+///         face_target.target = AimTarget::Point(cursor.0);
+///     }
+/// }
+/// # #[derive(Resource)]
+/// # struct CursorPosition(Vector);
+/// ```
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct FaceTarget {
+    /// The point or angle that the body should face.
+    pub target: AimTarget,
+    /// The local forward axis of the body that should point towards the target. Defaults to `Vector::X`.
+    pub forward: Vector,
+    /// The maximum torque that the motor can apply. Unlimited by default.
+    pub max_torque: Scalar,
+    /// The frequency of the motor's spring in Hz. Higher values turn the body faster.
+    pub frequency: Scalar,
+    /// The damping ratio of the motor's spring. A value of 1.0 is critically damped,
+    /// smaller values overshoot the target and larger values approach it more slowly.
+    pub damping_ratio: Scalar,
+    /// The torque exerted by the motor during the latest substep.
+    pub torque: Scalar,
+}
+
+impl Default for FaceTarget {
+    fn default() -> Self {
+        Self::angle(0.0)
+    }
+}
+
+impl FaceTarget {
+    /// Creates a [`FaceTarget`] that rotates the body to face the given point in world space.
+    pub fn point(point: Vector) -> Self {
+        Self {
+            target: AimTarget::Point(point),
+            ..Self::angle(0.0)
+        }
+    }
+
+    /// Creates a [`FaceTarget`] that rotates the body to face the direction of the given angle in radians.
+    pub fn angle(angle: Scalar) -> Self {
+        Self {
+            target: AimTarget::Angle(angle),
+            forward: Vector::X,
+            max_torque: Scalar::MAX,
+            frequency: 5.0,
+            damping_ratio: 1.0,
+            torque: 0.0,
+        }
+    }
+
+    /// Sets the local forward axis of the body that should point towards the target.
+    pub fn with_forward(self, forward: Vector) -> Self {
+        Self { forward, ..self }
+    }
+
+    /// Sets the maximum torque that the motor can apply.
+    pub fn with_max_torque(self, max_torque: Scalar) -> Self {
+        Self { max_torque, ..self }
+    }
+
+    /// Sets the frequency of the motor's spring in Hz.
+    pub fn with_frequency(self, frequency: Scalar) -> Self {
+        Self { frequency, ..self }
+    }
+
+    /// Sets the damping ratio of the motor's spring.
+    pub fn with_damping_ratio(self, damping_ratio: Scalar) -> Self {
+        Self {
+            damping_ratio,
+            ..self
+        }
+    }
+
+    /// Returns the angle of the body's [`Rotation`] at which it faces the target,
+    /// given the position of the body.
+    pub fn target_rotation(&self, position: Vector) -> Scalar {
+        let forward_angle = self.forward.y.atan2(self.forward.x);
+        let target_angle = match self.target {
+            AimTarget::Point(point) => {
+                let direction = point - position;
+                direction.y.atan2(direction.x)
+            }
+            AimTarget::Angle(angle) => angle,
+        };
+        target_angle - forward_angle
+    }
+
+    /// Rotates the body towards the target, limiting the applied torque to `max_torque`.
+    pub(crate) fn solve(&mut self, body: &mut RigidBodyQueryItem, dt: Scalar) {
+        self.torque = 0.0;
+
+        let inv_inertia = body.effective_world_inv_inertia();
+        if !body.rb.is_dynamic()
+            || body.is_rotation_locked()
+            || inv_inertia <= Scalar::EPSILON
+            || self.frequency <= 0.0
+            || dt <= Scalar::EPSILON
+        {
+            return;
+        }
+
+        // The angle from the target rotation to the current rotation, wrapped to [-PI, PI]
+        let target = self.target_rotation(body.current_position());
+        let c = (*body.rotation - Rotation::from_radians(target)).as_radians();
+
+        // The rotation during the current substep, used for damping
+        let delta_angle = (*body.rotation - body.previous_rotation.0).as_radians();
+
+        // A spring with the stiffness `inertia * omega^2` and the damping `2 * zeta * omega * inertia`
+        let omega = TAU * self.frequency;
+        let compliance = inv_inertia / (omega * omega);
+        let tilde_compliance = compliance / dt.powi(2);
+        let gamma = 2.0 * self.damping_ratio / (omega * dt);
+
+        let max_lagrange = self.max_torque * dt.powi(2);
+        let delta_lagrange = ((-c - gamma * delta_angle)
+            / ((1.0 + gamma) * inv_inertia + tilde_compliance))
+            .clamp(-max_lagrange, max_lagrange);
+
+        *body.rotation += Rotation::from_radians(inv_inertia * delta_lagrange);
+        self.torque = delta_lagrange / dt.powi(2);
+    }
+}
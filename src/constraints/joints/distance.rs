@@ -118,6 +118,10 @@ impl Joint for DistanceJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn total_force(&self) -> Vector {
+        self.force
+    }
 }
 
 impl DistanceJoint {
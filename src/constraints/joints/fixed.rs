@@ -139,6 +139,14 @@ impl Joint for FixedJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn total_force(&self) -> Vector {
+        self.force
+    }
+
+    fn total_torque(&self) -> Torque {
+        self.align_torque
+    }
 }
 
 impl FixedJoint {
@@ -0,0 +1,284 @@
+//! [`GearJoint`] component.
+
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+
+/// A gear joint couples the rotation of two bodies with a gear `ratio`, like meshing gears or a belt drive.
+///
+/// When the first body rotates by an angle `θ`, the second body rotates by `-ratio * θ`. A ratio of 2 makes
+/// the second body rotate twice as fast in the opposite direction, and negative ratios make the bodies rotate
+/// in the same direction, like gears connected by a chain.
+///
+/// The gear joint only constrains the rotation of the bodies, so the bodies are typically also attached
+/// to a frame with [revolute joints](RevoluteJoint). The rotation is tracked over multiple turns,
+/// and it is measured from the rotations of the bodies when the joint is first solved.
+#[cfg_attr(
+    feature = "3d",
+    doc = "\nIn 3D, the rotation of each body is measured around its own local `axis1` or `axis2`, which is the `Z` axis by default."
+)]
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// fn setup(mut commands: Commands) {
+///     let frame = commands.spawn(RigidBody::Static).id();
+///     let small_gear = commands.spawn(RigidBody::Dynamic).id();
+///     let large_gear = commands.spawn(RigidBody::Dynamic).id();
+///
+///     commands.spawn(RevoluteJoint::new(frame, small_gear));
+///     commands.spawn(RevoluteJoint::new(frame, large_gear).with_local_anchor_1(Vector::X));
+///
+///     // The large gear has twice as many teeth, so it rotates at half the speed
+///     commands.spawn(GearJoint::new(small_gear, large_gear).with_ratio(0.5));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GearJoint {
+    /// First entity constrained by the joint.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint.
+    pub entity2: Entity,
+    /// Attachment point on the first body. This doesn't affect the gear joint, but it is used for debug rendering.
+    pub local_anchor1: Vector,
+    /// Attachment point on the second body. This doesn't affect the gear joint, but it is used for debug rendering.
+    pub local_anchor2: Vector,
+    /// The local axis that the rotation of the first body is measured around.
+    #[cfg(feature = "3d")]
+    pub axis1: Vector,
+    /// The local axis that the rotation of the second body is measured around.
+    #[cfg(feature = "3d")]
+    pub axis2: Vector,
+    /// The gear ratio. When the first body rotates by an angle `θ`, the second body rotates by `-ratio * θ`.
+    pub ratio: Scalar,
+    /// The total rotation of the bodies around their axes since the joint was first solved.
+    pub accumulated_angles: [Scalar; 2],
+    /// The rotations of the bodies when their rotation was last measured.
+    pub reference_rotations: Option<[Rotation; 2]>,
+    /// Linear damping applied by the joint.
+    pub damping_linear: Scalar,
+    /// Angular damping applied by the joint.
+    pub damping_angular: Scalar,
+    /// Lagrange multiplier for the angular correction.
+    pub lagrange: Scalar,
+    /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// The torque exerted by the joint on the second body.
+    pub torque: Torque,
+}
+
+impl XpbdConstraint<2> for GearJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.lagrange = 0.0;
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+
+        #[cfg(feature = "2d")]
+        let (axis1, axis2) = (Vector3::Z, Vector3::Z);
+        #[cfg(feature = "3d")]
+        let (axis1, axis2) = (
+            body1.rotation.rotate(self.axis1),
+            body2.rotation.rotate(self.axis2),
+        );
+
+        // Track the total rotation of the bodies around their axes
+        let rotations = [*body1.rotation, *body2.rotation];
+        let [previous1, previous2] = self.reference_rotations.unwrap_or(rotations);
+        self.accumulated_angles[0] += Self::angle_delta(previous1, rotations[0], axis1);
+        self.accumulated_angles[1] += Self::angle_delta(previous2, rotations[1], axis2);
+        self.reference_rotations = Some(rotations);
+
+        let c = self.ratio * self.accumulated_angles[0] + self.accumulated_angles[1];
+
+        if c.abs() <= Scalar::EPSILON {
+            self.torque = Torque::ZERO;
+            return;
+        }
+
+        // Compute generalized inverse masses
+        let (w1, w2) = (
+            AngularConstraint::compute_generalized_inverse_mass(self, body1, axis1),
+            AngularConstraint::compute_generalized_inverse_mass(self, body2, axis2),
+        );
+        let w_sum = self.ratio.powi(2) * w1 + w2;
+
+        if w_sum <= Scalar::EPSILON {
+            self.torque = Torque::ZERO;
+            return;
+        }
+
+        // Compute Lagrange multiplier update
+        let tilde_compliance = self.compliance / dt.powi(2);
+        let delta_lagrange = (-c - tilde_compliance * self.lagrange) / (w_sum + tilde_compliance);
+        self.lagrange += delta_lagrange;
+
+        // Rotate the bodies around their axes
+        Self::rotate_body(body1, self.ratio * delta_lagrange, axis1);
+        Self::rotate_body(body2, delta_lagrange, axis2);
+
+        self.torque = self.compute_torque(self.lagrange, axis2, dt);
+    }
+}
+
+impl Joint for GearJoint {
+    fn new(entity1: Entity, entity2: Entity) -> Self {
+        Self {
+            entity1,
+            entity2,
+            local_anchor1: Vector::ZERO,
+            local_anchor2: Vector::ZERO,
+            #[cfg(feature = "3d")]
+            axis1: Vector::Z,
+            #[cfg(feature = "3d")]
+            axis2: Vector::Z,
+            ratio: 1.0,
+            accumulated_angles: [0.0; 2],
+            reference_rotations: None,
+            damping_linear: 0.0,
+            damping_angular: 0.0,
+            lagrange: 0.0,
+            compliance: 0.0,
+            torque: Torque::ZERO,
+        }
+    }
+
+    fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    fn with_local_anchor_1(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor1: anchor,
+            ..self
+        }
+    }
+
+    fn with_local_anchor_2(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor2: anchor,
+            ..self
+        }
+    }
+
+    fn with_linear_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_linear: damping,
+            ..self
+        }
+    }
+
+    fn with_angular_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_angular: damping,
+            ..self
+        }
+    }
+
+    fn local_anchor_1(&self) -> Vector {
+        self.local_anchor1
+    }
+
+    fn local_anchor_2(&self) -> Vector {
+        self.local_anchor2
+    }
+
+    fn damping_linear(&self) -> Scalar {
+        self.damping_linear
+    }
+
+    fn damping_angular(&self) -> Scalar {
+        self.damping_angular
+    }
+
+    fn total_torque(&self) -> Torque {
+        self.torque
+    }
+}
+
+impl GearJoint {
+    /// Sets the gear ratio. When the first body rotates by an angle `θ`, the second body rotates by `-ratio * θ`.
+    pub fn with_ratio(self, ratio: Scalar) -> Self {
+        Self { ratio, ..self }
+    }
+
+    /// Sets the local axes that the rotations of the first and second body are measured around.
+    #[cfg(feature = "3d")]
+    pub fn with_axes(self, axis1: Vector, axis2: Vector) -> Self {
+        Self {
+            axis1: axis1.normalize_or_zero(),
+            axis2: axis2.normalize_or_zero(),
+            ..self
+        }
+    }
+
+    /// Returns the angle that a body has rotated around the given world-space axis between two rotations.
+    #[cfg(feature = "2d")]
+    fn angle_delta(previous: Rotation, current: Rotation, _axis: Vector3) -> Scalar {
+        current.mul(previous.inverse()).as_radians()
+    }
+
+    /// Returns the angle that a body has rotated around the given world-space axis between two rotations.
+    #[cfg(feature = "3d")]
+    fn angle_delta(previous: Rotation, current: Rotation, axis: Vector) -> Scalar {
+        let mut delta = current.0 * previous.0.inverse();
+        if delta.w < 0.0 {
+            delta = -delta;
+        }
+        2.0 * delta.xyz().dot(axis).atan2(delta.w)
+    }
+
+    /// Rotates a dynamic body around the given world-space axis by an angular correction scaled by its inverse inertia.
+    fn rotate_body(body: &mut RigidBodyQueryItem, delta_lagrange: Scalar, axis: Vector3) {
+        if !body.rb.is_dynamic() {
+            return;
+        }
+
+        #[cfg(feature = "2d")]
+        if !body.is_rotation_locked() {
+            let inv_inertia = body.effective_world_inv_inertia();
+            let delta_rot = <Self as AngularConstraint>::get_delta_rot(
+                *body.rotation,
+                inv_inertia,
+                delta_lagrange * axis.z,
+            );
+            *body.rotation += delta_rot;
+        }
+        #[cfg(feature = "3d")]
+        {
+            let inv_inertia = body.effective_world_inv_inertia();
+            let delta_rot = <Self as AngularConstraint>::get_delta_rot(
+                *body.rotation,
+                inv_inertia,
+                delta_lagrange * axis,
+            );
+            *body.rotation += delta_rot;
+
+            // Subtracting and adding quaternions can result in unnormalized rotations.
+            body.rotation.0 = body.rotation.0.normalize();
+        }
+    }
+}
+
+impl PositionConstraint for GearJoint {}
+
+impl AngularConstraint for GearJoint {}
+
+impl MapEntities for GearJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity1 = entity_mapper.map_entity(self.entity1);
+        self.entity2 = entity_mapper.map_entity(self.entity2);
+    }
+}
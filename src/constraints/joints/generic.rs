@@ -0,0 +1,556 @@
+//! [`GenericJoint`] component.
+
+use super::wrap_angle;
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+
+/// The number of linear axes of a [`GenericJoint`].
+#[cfg(feature = "2d")]
+const LINEAR_AXES: usize = 2;
+/// The number of linear axes of a [`GenericJoint`].
+#[cfg(feature = "3d")]
+const LINEAR_AXES: usize = 3;
+
+/// The number of angular axes of a [`GenericJoint`].
+#[cfg(feature = "2d")]
+const ANGULAR_AXES: usize = 1;
+/// The number of angular axes of a [`GenericJoint`].
+#[cfg(feature = "3d")]
+const ANGULAR_AXES: usize = 3;
+
+/// Determines how a [`GenericJoint`] constrains the relative motion of the bodies along or around one axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum JointAxisMode {
+    /// No relative motion is allowed along or around the axis.
+    #[default]
+    Locked,
+    /// The bodies can move freely along or around the axis.
+    Free,
+    /// The relative translation or angle along or around the axis is limited to be between `min` and `max`.
+    Limited {
+        /// The minimum translation in meters or angle in radians.
+        min: Scalar,
+        /// The maximum translation in meters or angle in radians.
+        max: Scalar,
+    },
+    /// The bodies can move freely along or around the axis, but a motor drives them towards the `target`.
+    Motorized {
+        /// The target speed or translation or angle of the motor.
+        target: MotorTarget,
+        /// The maximum force or torque that the motor can apply.
+        max_force: Scalar,
+    },
+}
+
+impl JointAxisMode {
+    /// Returns how far the given translation or angle is past the allowed range,
+    /// or `None` if the axis isn't locked or limited.
+    fn limit_error(self, value: Scalar) -> Option<Scalar> {
+        match self {
+            Self::Locked => Some(value),
+            Self::Limited { min, max } => Some(value - value.clamp(min, max)),
+            Self::Free | Self::Motorized { .. } => None,
+        }
+    }
+}
+
+/// A generic joint where each linear and angular axis can independently be
+/// [locked, free, limited or motorized](JointAxisMode).
+///
+/// The axes are the `X` and `Y` axes (and the `Z` axis in 3D) in the local space of the first body.
+/// Linear axes constrain the relative translation of the attachment points along the axes,
+/// and angular axes constrain the relative rotation of the bodies around them.
+/// In 2D, there is only one angular axis, the rotation around the `Z` axis.
+///
+/// This is useful for combinations of degrees of freedom that the other joints don't cover,
+/// like a free twist with limited swing and a locked translation. By default, all axes are locked,
+/// and the joint behaves like a [`FixedJoint`].
+///
+/// In 3D, the angle around an angular axis is measured using the next axis as a reference,
+/// so the angles are only accurate when the rotation around the other axes is moderate.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     let torso = commands.spawn(RigidBody::Dynamic).id();
+///     let arm = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // Limit the rotation, but let the first angular axis (the twist around the X axis in 3D) rotate freely,
+///     // and drive the translation along the X axis with a motor
+///     commands.spawn(
+///         GenericJoint::new(torso, arm)
+///             .with_angular_axes(JointAxisMode::Limited { min: -0.5, max: 0.5 })
+///             .with_angular_axis(0, JointAxisMode::Free)
+///             .with_linear_axis(
+///                 0,
+///                 JointAxisMode::Motorized {
+///                     target: MotorTarget::Position(0.5),
+///                     max_force: 100.0,
+///                 },
+///             ),
+///     );
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenericJoint {
+    /// First entity constrained by the joint.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint.
+    pub entity2: Entity,
+    /// Attachment point on the first body.
+    pub local_anchor1: Vector,
+    /// Attachment point on the second body.
+    pub local_anchor2: Vector,
+    /// The modes of the linear axes, in the order `X`, `Y` (and `Z` in 3D).
+    pub linear_axes: [JointAxisMode; LINEAR_AXES],
+    /// The modes of the angular axes, in the order `X`, `Y` and `Z` in 3D. In 2D, there is only the `Z` axis.
+    pub angular_axes: [JointAxisMode; ANGULAR_AXES],
+    /// Linear damping applied by the joint.
+    pub damping_linear: Scalar,
+    /// Angular damping applied by the joint.
+    pub damping_angular: Scalar,
+    /// Lagrange multiplier for the positional correction caused by the locked and limited linear axes.
+    pub position_lagrange: Scalar,
+    /// Lagrange multipliers for the angular corrections caused by the locked and limited angular axes.
+    pub angular_lagrange: [Scalar; ANGULAR_AXES],
+    /// Lagrange multipliers for the motors of the linear axes.
+    pub linear_motor_lagrange: [Scalar; LINEAR_AXES],
+    /// Lagrange multipliers for the motors of the angular axes.
+    pub angular_motor_lagrange: [Scalar; ANGULAR_AXES],
+    /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// The force exerted by the locked and limited linear axes.
+    pub force: Vector,
+    /// The torque exerted by the locked and limited angular axes.
+    pub torque: Torque,
+    /// The force exerted by the motors of the linear axes.
+    pub motor_force: Vector,
+    /// The torque exerted by the motors of the angular axes.
+    pub motor_torque: Torque,
+}
+
+impl XpbdConstraint<2> for GenericJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.position_lagrange = 0.0;
+        self.angular_lagrange = [0.0; ANGULAR_AXES];
+        self.linear_motor_lagrange = [0.0; LINEAR_AXES];
+        self.angular_motor_lagrange = [0.0; ANGULAR_AXES];
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+
+        // Constrain the relative rotation around the locked and limited angular axes
+        self.torque = self.constrain_orientation(body1, body2, dt);
+
+        // Drive the motorized axes
+        self.motor_force = Vector::ZERO;
+        self.motor_torque = Torque::ZERO;
+        for (index, mode) in self.linear_axes.into_iter().enumerate() {
+            if let JointAxisMode::Motorized { target, max_force } = mode {
+                let force = self.apply_linear_motor(body1, body2, index, target, max_force, dt);
+                self.motor_force += force;
+            }
+        }
+        for (index, mode) in self.angular_axes.into_iter().enumerate() {
+            if let JointAxisMode::Motorized { target, max_force } = mode {
+                let torque = self.apply_angular_motor(body1, body2, index, target, max_force, dt);
+                self.motor_torque += torque;
+            }
+        }
+
+        // Constrain the relative positions along the locked and limited linear axes
+        self.force = self.constrain_positions(body1, body2, dt);
+    }
+}
+
+impl Joint for GenericJoint {
+    fn new(entity1: Entity, entity2: Entity) -> Self {
+        Self {
+            entity1,
+            entity2,
+            local_anchor1: Vector::ZERO,
+            local_anchor2: Vector::ZERO,
+            linear_axes: [JointAxisMode::Locked; LINEAR_AXES],
+            angular_axes: [JointAxisMode::Locked; ANGULAR_AXES],
+            damping_linear: 1.0,
+            damping_angular: 1.0,
+            position_lagrange: 0.0,
+            angular_lagrange: [0.0; ANGULAR_AXES],
+            linear_motor_lagrange: [0.0; LINEAR_AXES],
+            angular_motor_lagrange: [0.0; ANGULAR_AXES],
+            compliance: 0.0,
+            force: Vector::ZERO,
+            torque: Torque::ZERO,
+            motor_force: Vector::ZERO,
+            motor_torque: Torque::ZERO,
+        }
+    }
+
+    fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    fn with_local_anchor_1(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor1: anchor,
+            ..self
+        }
+    }
+
+    fn with_local_anchor_2(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor2: anchor,
+            ..self
+        }
+    }
+
+    fn with_linear_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_linear: damping,
+            ..self
+        }
+    }
+
+    fn with_angular_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_angular: damping,
+            ..self
+        }
+    }
+
+    fn local_anchor_1(&self) -> Vector {
+        self.local_anchor1
+    }
+
+    fn local_anchor_2(&self) -> Vector {
+        self.local_anchor2
+    }
+
+    fn damping_linear(&self) -> Scalar {
+        self.damping_linear
+    }
+
+    fn damping_angular(&self) -> Scalar {
+        self.damping_angular
+    }
+
+    fn total_force(&self) -> Vector {
+        self.force + self.motor_force
+    }
+
+    fn total_torque(&self) -> Torque {
+        self.torque + self.motor_torque
+    }
+}
+
+impl GenericJoint {
+    /// Sets the mode of the linear axis at the given index, where `0` is the `X` axis,
+    /// `1` is the `Y` axis and `2` is the `Z` axis in 3D.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn with_linear_axis(mut self, index: usize, mode: JointAxisMode) -> Self {
+        self.linear_axes[index] = mode;
+        self
+    }
+
+    /// Sets the mode of the angular axis at the given index, where `0` is the `X` axis,
+    /// `1` is the `Y` axis and `2` is the `Z` axis in 3D. In 2D, the only angular axis has the index `0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn with_angular_axis(mut self, index: usize, mode: JointAxisMode) -> Self {
+        self.angular_axes[index] = mode;
+        self
+    }
+
+    /// Sets the mode of all linear axes.
+    pub fn with_linear_axes(self, mode: JointAxisMode) -> Self {
+        Self {
+            linear_axes: [mode; LINEAR_AXES],
+            ..self
+        }
+    }
+
+    /// Sets the mode of all angular axes.
+    pub fn with_angular_axes(self, mode: JointAxisMode) -> Self {
+        Self {
+            angular_axes: [mode; ANGULAR_AXES],
+            ..self
+        }
+    }
+
+    /// Returns the relative rotation angle of the bodies around the angular axis at the given index,
+    /// and the axis in world space.
+    #[cfg(feature = "2d")]
+    fn axis_angle(rot1: &Rotation, rot2: &Rotation, _index: usize) -> (Scalar, Vector3) {
+        (rot2.mul(rot1.inverse()).as_radians(), Vector3::Z)
+    }
+
+    /// Returns the relative rotation angle of the bodies around the angular axis at the given index,
+    /// and the axis in world space.
+    #[cfg(feature = "3d")]
+    fn axis_angle(rot1: &Rotation, rot2: &Rotation, index: usize) -> (Scalar, Vector3) {
+        let n = rot1.rotate(Vector::AXES[index]);
+        let reference = Vector::AXES[(index + 1) % 3];
+        let n1 = rot1.rotate(reference);
+        let n2 = rot2.rotate(reference);
+        (n1.cross(n2).dot(n).atan2(n1.dot(n2)), n)
+    }
+
+    /// Constrains the relative rotation of the bodies around the locked and limited angular axes.
+    ///
+    /// Returns the torque exerted by this constraint.
+    fn constrain_orientation(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Torque {
+        let mut torque = Torque::ZERO;
+
+        for (index, mode) in self.angular_axes.into_iter().enumerate() {
+            let (angle, axis) = Self::axis_angle(&body1.rotation, &body2.rotation, index);
+
+            let Some(c) = mode.limit_error(angle) else {
+                continue;
+            };
+
+            if c.abs() <= Scalar::EPSILON {
+                continue;
+            }
+
+            // Compute generalized inverse masses
+            let w1 = AngularConstraint::compute_generalized_inverse_mass(self, body1, axis);
+            let w2 = AngularConstraint::compute_generalized_inverse_mass(self, body2, axis);
+            let w_sum = w1 + w2;
+
+            if w_sum <= Scalar::EPSILON {
+                continue;
+            }
+
+            // Compute Lagrange multiplier update
+            let tilde_compliance = self.compliance / dt.powi(2);
+            let delta_lagrange =
+                (-c - tilde_compliance * self.angular_lagrange[index]) / (w_sum + tilde_compliance);
+            self.angular_lagrange[index] += delta_lagrange;
+
+            // Apply angular correction around the axis
+            self.apply_angular_correction(body1, body2, delta_lagrange, axis);
+
+            torque += self.compute_torque(self.angular_lagrange[index], axis, dt);
+        }
+
+        torque
+    }
+
+    /// Constrains the relative positions of the attachment points along the locked and limited linear axes.
+    ///
+    /// Returns the force exerted by this constraint.
+    fn constrain_positions(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Vector {
+        let world_r1 = body1.rotation.rotate(self.local_anchor1);
+        let world_r2 = body2.rotation.rotate(self.local_anchor2);
+        let anchor1 = body1.current_position() + world_r1;
+        let anchor2 = body2.current_position() + world_r2;
+
+        let mut delta_x = Vector::ZERO;
+
+        for (index, mode) in self.linear_axes.into_iter().enumerate() {
+            let limit = match mode {
+                JointAxisMode::Locked => DistanceLimit::ZERO,
+                JointAxisMode::Limited { min, max } => DistanceLimit::new(min, max),
+                JointAxisMode::Free | JointAxisMode::Motorized { .. } => continue,
+            };
+            let axis = body1.rotation.rotate(Vector::AXES[index]);
+            delta_x += limit.compute_correction_along_axis(anchor1, anchor2, axis);
+        }
+
+        let magnitude = delta_x.length();
+
+        if magnitude <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let dir = delta_x / magnitude;
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+
+        // Constraint gradients and inverse masses
+        let gradients = [dir, -dir];
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update
+        let delta_lagrange = self.compute_lagrange_update(
+            self.position_lagrange,
+            magnitude,
+            &gradients,
+            &w,
+            self.compliance,
+            dt,
+        );
+        self.position_lagrange += delta_lagrange;
+
+        // Apply positional correction to align the positions of the bodies
+        self.apply_positional_correction(body1, body2, delta_lagrange, dir, world_r1, world_r2);
+
+        // Return constraint force
+        self.compute_force(self.position_lagrange, dir, dt)
+    }
+
+    /// Moves the bodies along the linear axis at the given index towards the motor's target,
+    /// limiting the applied force to `max_force`.
+    ///
+    /// Returns the force exerted by the motor.
+    fn apply_linear_motor(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        index: usize,
+        target: MotorTarget,
+        max_force: Scalar,
+        dt: Scalar,
+    ) -> Vector {
+        if max_force <= 0.0 {
+            return Vector::ZERO;
+        }
+
+        let world_r1 = body1.rotation.rotate(self.local_anchor1);
+        let world_r2 = body2.rotation.rotate(self.local_anchor2);
+        let axis1 = body1.rotation.rotate(Vector::AXES[index]);
+
+        // The change in translation needed to reach the target during this substep
+        let error = match target {
+            MotorTarget::Velocity(speed) => {
+                let delta_pos1 = body1.current_position() - body1.previous_position.0;
+                let delta_pos2 = body2.current_position() - body2.previous_position.0;
+                speed * dt - (delta_pos2 - delta_pos1).dot(axis1)
+            }
+            MotorTarget::Position(translation) => {
+                let anchor1 = body1.current_position() + world_r1;
+                let anchor2 = body2.current_position() + world_r2;
+                translation - (anchor2 - anchor1).dot(axis1)
+            }
+        };
+
+        if error.abs() <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let dir = axis1 * error.signum();
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+
+        let gradients = [dir, -dir];
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update and limit it by the maximum motor force
+        let lagrange = self.linear_motor_lagrange[index];
+        let delta_lagrange =
+            self.compute_lagrange_update(lagrange, error.abs(), &gradients, &w, 0.0, dt);
+        let max_lagrange = max_force * dt.powi(2);
+        let motor_lagrange = (lagrange + delta_lagrange).clamp(-max_lagrange, max_lagrange);
+        let delta_lagrange = motor_lagrange - lagrange;
+        self.linear_motor_lagrange[index] = motor_lagrange;
+
+        // Apply positional correction along the axis
+        self.apply_positional_correction(body1, body2, delta_lagrange, dir, world_r1, world_r2);
+
+        // Return motor force
+        self.compute_force(motor_lagrange, dir, dt)
+    }
+
+    /// Rotates the bodies around the angular axis at the given index towards the motor's target,
+    /// limiting the applied torque to `max_torque`.
+    ///
+    /// Returns the torque exerted by the motor.
+    fn apply_angular_motor(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        index: usize,
+        target: MotorTarget,
+        max_torque: Scalar,
+        dt: Scalar,
+    ) -> Torque {
+        if max_torque <= 0.0 {
+            return Torque::ZERO;
+        }
+
+        let (angle, axis) = Self::axis_angle(&body1.rotation, &body2.rotation, index);
+
+        // The change in angle needed to reach the target during this substep
+        let error = match target {
+            MotorTarget::Velocity(speed) => {
+                let (previous_angle, _) = Self::axis_angle(
+                    &body1.previous_rotation.0,
+                    &body2.previous_rotation.0,
+                    index,
+                );
+                speed * dt - wrap_angle(angle - previous_angle)
+            }
+            MotorTarget::Position(target_angle) => wrap_angle(target_angle - angle),
+        };
+
+        if error.abs() <= Scalar::EPSILON {
+            return Torque::ZERO;
+        }
+
+        // Compute generalized inverse masses
+        let w1 = AngularConstraint::compute_generalized_inverse_mass(self, body1, axis);
+        let w2 = AngularConstraint::compute_generalized_inverse_mass(self, body2, axis);
+        let w_sum = w1 + w2;
+
+        if w_sum <= Scalar::EPSILON {
+            return Torque::ZERO;
+        }
+
+        // Compute Lagrange multiplier update and limit it by the maximum motor torque
+        let lagrange = self.angular_motor_lagrange[index];
+        let max_lagrange = max_torque * dt.powi(2);
+        let motor_lagrange = (lagrange + error / w_sum).clamp(-max_lagrange, max_lagrange);
+        let delta_lagrange = motor_lagrange - lagrange;
+        self.angular_motor_lagrange[index] = motor_lagrange;
+
+        // Apply angular correction around the axis
+        self.apply_angular_correction(body1, body2, delta_lagrange, axis);
+
+        self.compute_torque(motor_lagrange, axis, dt)
+    }
+}
+
+impl PositionConstraint for GenericJoint {}
+
+impl AngularConstraint for GenericJoint {}
+
+impl MapEntities for GenericJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity1 = entity_mapper.map_entity(self.entity1);
+        self.entity2 = entity_mapper.map_entity(self.entity2);
+    }
+}
@@ -11,13 +11,18 @@
 //!
 //! Below is a table containing the joints that are currently implemented.
 //!
-//! | Joint              | Allowed 2D DOF            | Allowed 3D DOF              |
-//! | ------------------ | ------------------------- | --------------------------- |
-//! | [`FixedJoint`]     | None                      | None                        |
-//! | [`DistanceJoint`]  | 1 Translation, 1 Rotation | 2 Translations, 3 Rotations |
-//! | [`PrismaticJoint`] | 1 Translation             | 1 Translation               |
-//! | [`RevoluteJoint`]  | 1 Rotation                | 1 Rotation                  |
-//! | [`SphericalJoint`] | 1 Rotation                | 3 Rotations                 |
+//! | Joint                    | Allowed 2D DOF            | Allowed 3D DOF              |
+//! | ------------------------ | ------------------------- | --------------------------- |
+//! | [`FixedJoint`]           | None                      | None                        |
+//! | [`DistanceJoint`]        | 1 Translation, 1 Rotation | 2 Translations, 3 Rotations |
+//! | [`PrismaticJoint`]       | 1 Translation             | 1 Translation               |
+//! | [`RevoluteJoint`]        | 1 Rotation                | 1 Rotation                  |
+//! | [`SphericalJoint`]       | 1 Rotation                | 3 Rotations                 |
+//! | [`SplineDriveJoint`]     | 1 Translation, 1 Rotation | 1 Translation, 3 Rotations  |
+//! | [`SuspensionConstraint`] | 1 Translation, 1 Rotation | 1 Translation, 3 Rotations  |
+//! | [`GenericJoint`]         | Configurable              | Configurable                |
+//! | [`GearJoint`]            | Coupled rotation          | Coupled rotation            |
+//! | [`PulleyJoint`]          | Coupled translation       | Coupled translation         |
 //!
 //! ## Using joints
 //!
@@ -65,6 +70,20 @@
 //!
 //! Take a look at the documentation and methods of each joint to see all of the configuration options.
 //!
+//! ### Disabling joints
+//!
+//! A joint can be temporarily disabled by adding the [`JointDisabled`] component to the joint entity.
+//!
+//! ### Joint iterations
+//!
+//! A joint can be made stiffer by solving it multiple times per substep using the [`JointIterations`] component.
+//!
+//! ## Despawning bodies
+//!
+//! When a body attached to a joint is despawned or its [`RigidBody`] component is removed, the joint is *orphaned*.
+//! Orphaned joints are cleaned up automatically based on the [`OrphanedJointPolicy`] resource,
+//! and a [`JointOrphaned`] event is sent for each of them.
+//!
 //! ## Custom joints
 //!
 //! Joints are [constraints] that implement [`Joint`] and [`XpbdConstraint`].
@@ -76,20 +95,33 @@
 //! Many joints also have joint limits. You can use [`DistanceLimit`] and [`AngleLimit`] to help store these limits
 //! and to compute the current distance from the specified limits.
 //!
+//! [`RevoluteJoint`], [`PrismaticJoint`] and [`GenericJoint`] also support motors that drive the joint towards a [`MotorTarget`]
+//! with a limited torque or force, for things like wheels, turrets and doors.
+//!
 //! [See the code implementations](https://github.com/Jondolf/bevy_xpbd/tree/main/src/constraints/joints)
 //! of the implemented joints to get a better idea of how to create joints.
 
 mod distance;
 mod fixed;
+mod gear;
+mod generic;
 mod prismatic;
+mod pulley;
 mod revolute;
 mod spherical;
+mod spline_drive;
+mod suspension;
 
 pub use distance::*;
 pub use fixed::*;
+pub use gear::*;
+pub use generic::*;
 pub use prismatic::*;
+pub use pulley::*;
 pub use revolute::*;
 pub use spherical::*;
+pub use spline_drive::*;
+pub use suspension::*;
 
 use crate::prelude::*;
 use bevy::prelude::*;
@@ -126,6 +158,20 @@ pub trait Joint: Component + PositionConstraint + AngularConstraint {
     /// Returns the angular velocity damping of the joint.
     fn damping_angular(&self) -> Scalar;
 
+    /// Returns the total force exerted by the joint during the latest substep.
+    ///
+    /// This is used for [breaking](BreakableJoint) joints.
+    fn total_force(&self) -> Vector {
+        Vector::ZERO
+    }
+
+    /// Returns the total torque exerted by the joint during the latest substep.
+    ///
+    /// This is used for [breaking](BreakableJoint) joints.
+    fn total_torque(&self) -> Torque {
+        Torque::ZERO
+    }
+
     /// Applies a positional correction that aligns the positions of the local attachment points `r1` and `r2`.
     ///
     /// Returns the force exerted by the alignment.
@@ -225,6 +271,183 @@ pub trait Joint: Component + PositionConstraint + AngularConstraint {
     }
 }
 
+/// A marker component that disables a joint, so that it is not solved.
+///
+/// The joint is solved again when the component is removed.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct JointDisabled;
+
+/// A component that makes a joint break when the force or torque that it exerts exceeds the given thresholds,
+/// for things like destructible structures and ragdoll dismemberment.
+///
+/// The impulses applied by the joint are accumulated over each physics step, and the average force and torque
+/// during the step are compared to `break_force` and `break_torque`. When a threshold is exceeded, the joint is
+/// disabled by adding the [`JointDisabled`] component to the joint entity, and a [`JointBroken`] event is sent.
+/// The joint can be despawned in response to the event if it isn't needed anymore.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     let wall = commands.spawn(RigidBody::Static).id();
+///     let plank = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // Break the joint when it exerts more than 500 N of force or 200 Nm of torque
+///     commands.spawn((
+///         FixedJoint::new(wall, plank),
+///         BreakableJoint::new(500.0, 200.0),
+///     ));
+/// }
+///
+/// fn despawn_broken_joints(mut commands: Commands, mut broken: EventReader<JointBroken>) {
+///     for event in broken.read() {
+///         commands.entity(event.joint).despawn();
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct BreakableJoint {
+    /// The force at which the joint breaks. If `None`, the joint can't be broken by force.
+    pub break_force: Option<Scalar>,
+    /// The torque at which the joint breaks. If `None`, the joint can't be broken by torque.
+    pub break_torque: Option<Scalar>,
+    /// The linear impulse applied by the joint during the current physics step.
+    pub accumulated_impulse: Vector,
+    /// The angular impulse applied by the joint during the current physics step.
+    pub accumulated_angular_impulse: Torque,
+}
+
+impl Default for BreakableJoint {
+    fn default() -> Self {
+        Self {
+            break_force: None,
+            break_torque: None,
+            accumulated_impulse: Vector::ZERO,
+            accumulated_angular_impulse: Torque::ZERO,
+        }
+    }
+}
+
+impl BreakableJoint {
+    /// Creates a new [`BreakableJoint`] with the given force and torque thresholds.
+    pub fn new(break_force: Scalar, break_torque: Scalar) -> Self {
+        Self {
+            break_force: Some(break_force),
+            break_torque: Some(break_torque),
+            ..default()
+        }
+    }
+
+    /// Creates a new [`BreakableJoint`] that only breaks when the given force is exceeded.
+    pub fn from_force(break_force: Scalar) -> Self {
+        Self {
+            break_force: Some(break_force),
+            ..default()
+        }
+    }
+
+    /// Creates a new [`BreakableJoint`] that only breaks when the given torque is exceeded.
+    pub fn from_torque(break_torque: Scalar) -> Self {
+        Self {
+            break_torque: Some(break_torque),
+            ..default()
+        }
+    }
+}
+
+/// An event that is sent when a [`BreakableJoint`] breaks because its force or torque exceeded the thresholds.
+///
+/// The joint is disabled by adding the [`JointDisabled`] component to the joint entity.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct JointBroken {
+    /// The joint entity.
+    pub joint: Entity,
+    /// The average force exerted by the joint during the physics step in which it broke.
+    pub force: Scalar,
+    /// The average torque exerted by the joint during the physics step in which it broke.
+    pub torque: Scalar,
+}
+
+/// The number of times a joint is solved per substep. Defaults to 1.
+///
+/// Solving a joint multiple times makes it stiffer and reduces stretching, which can be useful
+/// for joints like crane cables that carry heavy loads. This is cheaper than increasing
+/// the [`SubstepCount`], since it only affects the joints that need it.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     let hook = commands.spawn(RigidBody::Dynamic).id();
+///     let crane = commands.spawn(RigidBody::Static).id();
+///
+///     // Solve the cable four times per substep
+///     commands.spawn((
+///         DistanceJoint::new(crane, hook).with_rest_length(10.0),
+///         JointIterations(4),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct JointIterations(pub u8);
+
+impl Default for JointIterations {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+/// Determines what happens to a joint when a body attached to it is despawned
+/// or its [`RigidBody`] component is removed.
+///
+/// A [`JointOrphaned`] event is sent in both cases.
+#[derive(Reflect, Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub enum OrphanedJointPolicy {
+    /// The joint entity is despawned.
+    #[default]
+    Despawn,
+    /// The joint is disabled by adding the [`JointDisabled`] component to the joint entity.
+    Disable,
+}
+
+/// An event that is sent when a body attached to a joint is despawned or its [`RigidBody`] component is removed.
+///
+/// The joint is despawned or disabled based on the [`OrphanedJointPolicy`].
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JointOrphaned {
+    /// The joint entity.
+    pub joint: Entity,
+    /// The body entity that no longer exists or is no longer a rigid body.
+    pub body: Entity,
+}
+
+/// Wraps the given angle to the range `[-PI, PI]`.
+pub(crate) fn wrap_angle(angle: Scalar) -> Scalar {
+    if angle > PI {
+        angle - TAU
+    } else if angle < -PI {
+        angle + TAU
+    } else {
+        angle
+    }
+}
+
 /// A limit that indicates that the distance between two points should be between `min` and `max`.
 #[derive(Clone, Copy, Debug, PartialEq, Reflect)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -348,3 +571,121 @@ impl AngleLimit {
         None
     }
 }
+
+/// The target of a joint motor, used by [`RevoluteJoint`], [`PrismaticJoint`] and [`GenericJoint`].
+///
+/// For revolute joints, the target is an angular speed in radians per second or an angle in radians
+/// around the `aligned_axis`. For prismatic joints, the target is a linear speed in meters per second
+/// or a translation in meters along the `free_axis`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum MotorTarget {
+    /// The motor tries to keep the relative speed of the bodies at the given value.
+    Velocity(Scalar),
+    /// The motor tries to move the bodies to the given relative angle or translation and hold them there.
+    Position(Scalar),
+}
+
+/// Configures how a joint responds when it reaches its limits, used by [`RevoluteJoint::with_angle_limit_response`]
+/// and [`PrismaticJoint::with_limit_response`].
+///
+/// By default, joint limits are rigid and stop the joint without bouncing. With a limit response,
+/// the limit acts like a spring with the given `compliance` and `damping` when the joint goes past it,
+/// so the joint eases into the limit. The `restitution` coefficient makes the joint bounce back from the limit,
+/// like a limb or a door hitting its stop.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     let upper_arm = commands.spawn(RigidBody::Dynamic).id();
+///     let forearm = commands.spawn(RigidBody::Dynamic).id();
+///
+///     commands.spawn(
+///         RevoluteJoint::new(upper_arm, forearm)
+///             .with_angle_limits(0.0, 2.5)
+///             .with_angle_limit_response(
+///                 JointLimitResponse::new(0.001)
+///                     .with_damping(5.0)
+///                     .with_restitution(0.3),
+///             ),
+///     );
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct JointLimitResponse {
+    /// The compliance of the limit, the inverse of stiffness. Zero makes the limit rigid.
+    pub compliance: Scalar,
+    /// The damping coefficient of the limit. Damping only has an effect when the limit is compliant.
+    pub damping: Scalar,
+    /// The coefficient of restitution of the limit, from 0 (no bounce) to 1 (perfectly elastic bounce).
+    pub restitution: Scalar,
+}
+
+impl JointLimitResponse {
+    /// A rigid limit without restitution.
+    pub const RIGID: Self = Self {
+        compliance: 0.0,
+        damping: 0.0,
+        restitution: 0.0,
+    };
+
+    /// Creates a new `JointLimitResponse` with the given compliance, no damping and no restitution.
+    pub fn new(compliance: Scalar) -> Self {
+        Self {
+            compliance,
+            ..Self::RIGID
+        }
+    }
+
+    /// Sets the damping coefficient of the limit.
+    pub fn with_damping(self, damping: Scalar) -> Self {
+        Self { damping, ..self }
+    }
+
+    /// Sets the coefficient of restitution of the limit.
+    pub fn with_restitution(self, restitution: Scalar) -> Self {
+        Self {
+            restitution,
+            ..self
+        }
+    }
+}
+
+/// A soft zone before a joint limit, where a compliant and damped constraint slows the joint down
+/// before it reaches the hard limit.
+///
+/// Without a soft zone, hitting a hard limit stops the joint instantly, which causes a visible snap
+/// for things like doors and levers. Inside the soft zone, the joint is pushed back towards the inner edge
+/// of the zone by a spring with the given `compliance`, and its motion is resisted by a damper.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoftLimitZone {
+    /// The width of the soft zone, measured inwards from the hard limit.
+    pub width: Scalar,
+    /// The compliance of the spring in the soft zone, the inverse of stiffness.
+    pub compliance: Scalar,
+    /// The damping coefficient of the damper in the soft zone.
+    pub damping: Scalar,
+}
+
+impl SoftLimitZone {
+    /// Creates a new `SoftLimitZone` with the given width and compliance and no damping.
+    pub fn new(width: Scalar, compliance: Scalar) -> Self {
+        Self {
+            width,
+            compliance,
+            damping: 0.0,
+        }
+    }
+
+    /// Sets the damping coefficient of the damper in the soft zone.
+    pub fn with_damping(self, damping: Scalar) -> Self {
+        Self { damping, ..self }
+    }
+}
@@ -9,6 +9,33 @@ use bevy::{
 /// A prismatic joint prevents relative movement of the attached bodies, except for translation along one `free_axis`.
 ///
 /// Prismatic joints can be useful for things like elevators, pistons, sliding doors and moving platforms.
+///
+/// Joint friction can be added using [`PrismaticJoint::with_friction_force`], so that drawers and slides
+/// hold their position under small loads.
+///
+/// The joint can be driven by a motor with a [target](MotorTarget) speed or translation and a maximum force,
+/// see [`PrismaticJoint::with_velocity_motor`] and [`PrismaticJoint::with_position_motor`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// fn setup(mut commands: Commands) {
+///     let frame = commands.spawn(RigidBody::Static).id();
+///     let door = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // Slide the door open by 2 meters with a maximum force of 1000 N
+///     commands.spawn(
+///         PrismaticJoint::new(frame, door)
+///             .with_free_axis(Vector::X)
+///             .with_limits(0.0, 2.0)
+///             .with_position_motor(2.0, 1000.0),
+///     );
+/// }
+/// ```
 #[derive(Component, Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct PrismaticJoint {
@@ -24,6 +51,15 @@ pub struct PrismaticJoint {
     pub free_axis: Vector,
     /// The extents of the allowed relative translation along the free axis.
     pub free_axis_limits: Option<DistanceLimit>,
+    /// How the joint responds when it reaches the limits along the free axis. If `None`, the limits are rigid
+    /// and use the joint's `compliance`.
+    pub free_axis_limit_response: Option<JointLimitResponse>,
+    /// The maximum force that the joint friction can apply to resist relative translation along the free axis.
+    pub friction_force: Scalar,
+    /// The target speed or translation of the motor along the free axis. The motor is disabled when this is `None`.
+    pub motor_target: Option<MotorTarget>,
+    /// The maximum force that the motor can apply along the free axis.
+    pub max_motor_force: Scalar,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
@@ -32,12 +68,21 @@ pub struct PrismaticJoint {
     pub position_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the alignment of the bodies.
     pub align_lagrange: Scalar,
+    /// Lagrange multiplier for the positional correction caused by the limits along the free axis,
+    /// if they have a [limit response](JointLimitResponse).
+    pub limit_lagrange: Scalar,
+    /// Lagrange multiplier for the motor.
+    pub motor_lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
     /// The force exerted by the joint.
     pub force: Vector,
     /// The torque exerted by the joint when aligning the bodies.
     pub align_torque: Torque,
+    /// The force exerted by the limits along the free axis, if they have a [limit response](JointLimitResponse).
+    pub limit_force: Vector,
+    /// The force exerted by the motor.
+    pub motor_force: Vector,
 }
 
 impl XpbdConstraint<2> for PrismaticJoint {
@@ -48,6 +93,8 @@ impl XpbdConstraint<2> for PrismaticJoint {
     fn clear_lagrange_multipliers(&mut self) {
         self.position_lagrange = 0.0;
         self.align_lagrange = 0.0;
+        self.limit_lagrange = 0.0;
+        self.motor_lagrange = 0.0;
     }
 
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
@@ -60,8 +107,20 @@ impl XpbdConstraint<2> for PrismaticJoint {
         self.align_torque = self.align_orientation(body1, body2, dq, &mut lagrange, compliance, dt);
         self.align_lagrange = lagrange;
 
+        // Drive the translation along the free axis
+        if let Some(target) = self.motor_target {
+            self.motor_force = self.apply_motor(body1, body2, target, dt);
+        }
+
         // Constrain the relative positions of the bodies, only allowing translation along one free axis
         self.force = self.constrain_positions(body1, body2, dt);
+
+        // Apply compliant limits along the free axis
+        if let (Some(limits), Some(response)) =
+            (self.free_axis_limits, self.free_axis_limit_response)
+        {
+            self.limit_force = self.apply_limit_response(body1, body2, limits, response, dt);
+        }
     }
 }
 
@@ -74,16 +133,24 @@ impl Joint for PrismaticJoint {
             local_anchor2: Vector::ZERO,
             free_axis: Vector::X,
             free_axis_limits: None,
+            free_axis_limit_response: None,
+            friction_force: 0.0,
+            motor_target: None,
+            max_motor_force: 0.0,
             damping_linear: 1.0,
             damping_angular: 1.0,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
+            limit_lagrange: 0.0,
+            motor_lagrange: 0.0,
             compliance: 0.0,
             force: Vector::ZERO,
             #[cfg(feature = "2d")]
             align_torque: 0.0,
             #[cfg(feature = "3d")]
             align_torque: Vector::ZERO,
+            limit_force: Vector::ZERO,
+            motor_force: Vector::ZERO,
         }
     }
 
@@ -134,6 +201,14 @@ impl Joint for PrismaticJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn total_force(&self) -> Vector {
+        self.force + self.limit_force + self.motor_force
+    }
+
+    fn total_torque(&self) -> Torque {
+        self.align_torque
+    }
 }
 
 impl PrismaticJoint {
@@ -152,7 +227,9 @@ impl PrismaticJoint {
         let mut delta_x = Vector::ZERO;
 
         let axis1 = body1.rotation.rotate(self.free_axis);
-        if let Some(limits) = self.free_axis_limits {
+
+        // Limits with a limit response are solved separately
+        if let (Some(limits), None) = (self.free_axis_limits, self.free_axis_limit_response) {
             delta_x += limits.compute_correction_along_axis(
                 body1.current_position() + world_r1,
                 body2.current_position() + world_r2,
@@ -222,6 +299,126 @@ impl PrismaticJoint {
         self.compute_force(self.position_lagrange, dir, dt)
     }
 
+    /// Applies a compliant and damped correction when the bodies are past the limits along the free axis.
+    ///
+    /// Returns the force exerted by the limits.
+    fn apply_limit_response(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        limits: DistanceLimit,
+        response: JointLimitResponse,
+        dt: Scalar,
+    ) -> Vector {
+        let world_r1 = body1.rotation.rotate(self.local_anchor1);
+        let world_r2 = body2.rotation.rotate(self.local_anchor2);
+        let axis1 = body1.rotation.rotate(self.free_axis);
+
+        let delta_x = limits.compute_correction_along_axis(
+            body1.current_position() + world_r1,
+            body2.current_position() + world_r2,
+            axis1,
+        );
+        let magnitude = delta_x.length();
+
+        if magnitude <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let dir = delta_x / magnitude;
+
+        // How much the constraint changed during this substep, used by the damper
+        let delta_pos1 = body1.current_position() - body1.previous_position.0;
+        let delta_pos2 = body2.current_position() - body2.previous_position.0;
+        let delta_c = (delta_pos1 - delta_pos2).dot(dir);
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+        let w_sum = w1 + w2;
+
+        if w_sum <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        // Compute Lagrange multiplier update with damping
+        // (Equation 26 of "XPBD: Position-Based Simulation of Compliant Constrained Dynamics")
+        let tilde_compliance = response.compliance / dt.powi(2);
+        let gamma = response.compliance * response.damping / dt;
+        let delta_lagrange =
+            (-magnitude - tilde_compliance * self.limit_lagrange - gamma * delta_c)
+                / ((1.0 + gamma) * w_sum + tilde_compliance);
+        self.limit_lagrange += delta_lagrange;
+
+        // Apply positional correction towards the limits
+        self.apply_positional_correction(body1, body2, delta_lagrange, dir, world_r1, world_r2);
+
+        // Return limit force
+        self.compute_force(self.limit_lagrange, dir, dt)
+    }
+
+    /// Moves the bodies along the free axis towards the motor's target,
+    /// limiting the applied force to the maximum motor force.
+    ///
+    /// Returns the force exerted by the motor.
+    fn apply_motor(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        target: MotorTarget,
+        dt: Scalar,
+    ) -> Vector {
+        if self.max_motor_force <= 0.0 {
+            return Vector::ZERO;
+        }
+
+        let world_r1 = body1.rotation.rotate(self.local_anchor1);
+        let world_r2 = body2.rotation.rotate(self.local_anchor2);
+        let axis1 = body1.rotation.rotate(self.free_axis);
+
+        // The change in translation needed to reach the target during this substep
+        let error = match target {
+            MotorTarget::Velocity(speed) => {
+                let delta_pos1 = body1.current_position() - body1.previous_position.0;
+                let delta_pos2 = body2.current_position() - body2.previous_position.0;
+                speed * dt - (delta_pos2 - delta_pos1).dot(axis1)
+            }
+            MotorTarget::Position(translation) => {
+                let anchor1 = body1.current_position() + world_r1;
+                let anchor2 = body2.current_position() + world_r2;
+                translation - (anchor2 - anchor1).dot(axis1)
+            }
+        };
+
+        if error.abs() <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let dir = axis1 * error.signum();
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+
+        let gradients = [dir, -dir];
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update and limit it by the maximum motor force
+        let delta_lagrange =
+            self.compute_lagrange_update(self.motor_lagrange, error.abs(), &gradients, &w, 0.0, dt);
+        let max_lagrange = self.max_motor_force * dt.powi(2);
+        let motor_lagrange =
+            (self.motor_lagrange + delta_lagrange).clamp(-max_lagrange, max_lagrange);
+        let delta_lagrange = motor_lagrange - self.motor_lagrange;
+        self.motor_lagrange = motor_lagrange;
+
+        // Apply positional correction along the free axis
+        self.apply_positional_correction(body1, body2, delta_lagrange, dir, world_r1, world_r2);
+
+        // Return motor force
+        self.compute_force(self.motor_lagrange, dir, dt)
+    }
+
     /// Sets the joint's free axis. Relative translations are allowed along this free axis.
     pub fn with_free_axis(self, axis: Vector) -> Self {
         Self {
@@ -238,6 +435,47 @@ impl PrismaticJoint {
         }
     }
 
+    /// Sets the [compliance, damping and restitution](JointLimitResponse) of the limits along the free axis,
+    /// so that the joint can ease into its limits or bounce back from them.
+    /// The response only has an effect when limits are set.
+    pub fn with_limit_response(self, response: JointLimitResponse) -> Self {
+        Self {
+            free_axis_limit_response: Some(response),
+            ..self
+        }
+    }
+
+    /// Sets the maximum force that the joint friction can apply to resist relative translation along the free axis.
+    ///
+    /// The friction is applied in the velocity solve, and it holds the bodies in place
+    /// until the force between them exceeds the friction force.
+    pub fn with_friction_force(self, friction_force: Scalar) -> Self {
+        Self {
+            friction_force,
+            ..self
+        }
+    }
+
+    /// Enables the motor with the given target speed in meters per second along the free axis,
+    /// and the maximum force that the motor can apply to reach it.
+    pub fn with_velocity_motor(self, target_speed: Scalar, max_force: Scalar) -> Self {
+        Self {
+            motor_target: Some(MotorTarget::Velocity(target_speed)),
+            max_motor_force: max_force,
+            ..self
+        }
+    }
+
+    /// Enables the motor with the given target translation in meters along the free axis,
+    /// and the maximum force that the motor can apply to reach and hold it.
+    pub fn with_position_motor(self, target_translation: Scalar, max_force: Scalar) -> Self {
+        Self {
+            motor_target: Some(MotorTarget::Position(target_translation)),
+            max_motor_force: max_force,
+            ..self
+        }
+    }
+
     #[cfg(feature = "2d")]
     fn get_delta_q(&self, rot1: &Rotation, rot2: &Rotation) -> Vector3 {
         (*rot2 - *rot1).as_radians() * Vector3::Z
@@ -0,0 +1,264 @@
+//! [`PulleyJoint`] component.
+
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+
+/// A pulley joint connects two bodies with a rope that runs over two fixed pulleys at the `ground_anchor1`
+/// and `ground_anchor2` world positions.
+///
+/// The joint keeps `length1 + ratio * length2` at most the total rope `length`, where `length1` and `length2`
+/// are the distances from the ground anchors to the attachment points on the first and second body.
+/// When one body moves away from its pulley, the other body is pulled towards its own pulley.
+/// A ratio other than 1 works like a block and tackle, where one side of the rope moves faster than the other.
+///
+/// Like a rope, the joint only pulls the bodies and doesn't push them. If the total `length` is not given,
+/// it is computed from the positions of the bodies when the joint is first solved.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// fn setup(mut commands: Commands) {
+///     let elevator = commands.spawn(RigidBody::Dynamic).id();
+///     let counterweight = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // The elevator hangs from a pulley at (-2, 10) and the counterweight from a pulley at (2, 10)
+///     commands.spawn(PulleyJoint::new(elevator, counterweight).with_ground_anchors(
+#[cfg_attr(
+    feature = "2d",
+    doc = "        Vector::new(-2.0, 10.0),\n        Vector::new(2.0, 10.0),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "        Vector::new(-2.0, 10.0, 0.0),\n        Vector::new(2.0, 10.0, 0.0),"
+)]
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct PulleyJoint {
+    /// First entity constrained by the joint.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint.
+    pub entity2: Entity,
+    /// Attachment point on the first body.
+    pub local_anchor1: Vector,
+    /// Attachment point on the second body.
+    pub local_anchor2: Vector,
+    /// The world position of the pulley that the rope of the first body runs over.
+    pub ground_anchor1: Vector,
+    /// The world position of the pulley that the rope of the second body runs over.
+    pub ground_anchor2: Vector,
+    /// The pulley ratio. The rope of the second body counts `ratio` times towards the total length.
+    pub ratio: Scalar,
+    /// The total length of the rope, `length1 + ratio * length2`.
+    /// If `None`, it is computed from the positions of the bodies when the joint is first solved.
+    pub length: Option<Scalar>,
+    /// Linear damping applied by the joint.
+    pub damping_linear: Scalar,
+    /// Angular damping applied by the joint.
+    pub damping_angular: Scalar,
+    /// Lagrange multiplier for the positional correction.
+    pub lagrange: Scalar,
+    /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// The force exerted by the rope on the first body.
+    pub force: Vector,
+}
+
+impl XpbdConstraint<2> for PulleyJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.lagrange = 0.0;
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+
+        let world_r1 = body1.rotation.rotate(self.local_anchor1);
+        let world_r2 = body2.rotation.rotate(self.local_anchor2);
+
+        // Compute the rope segments from the pulleys to the attachment points
+        let delta1 = body1.current_position() + world_r1 - self.ground_anchor1;
+        let delta2 = body2.current_position() + world_r2 - self.ground_anchor2;
+        let (length1, length2) = (delta1.length(), delta2.length());
+
+        let length = *self.length.get_or_insert(length1 + self.ratio * length2);
+        let c = length1 + self.ratio * length2 - length;
+
+        // The rope is slack, so it doesn't pull the bodies
+        if c <= 0.0 || length1 <= Scalar::EPSILON || length2 <= Scalar::EPSILON {
+            self.force = Vector::ZERO;
+            return;
+        }
+
+        let n1 = delta1 / length1;
+        let n2 = delta2 / length2;
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, n1);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, n2);
+        let w_sum = w1 + self.ratio.powi(2) * w2;
+
+        if w_sum <= Scalar::EPSILON {
+            self.force = Vector::ZERO;
+            return;
+        }
+
+        // Compute Lagrange multiplier update
+        let tilde_compliance = self.compliance / dt.powi(2);
+        let delta_lagrange = (-c - tilde_compliance * self.lagrange) / (w_sum + tilde_compliance);
+        self.lagrange += delta_lagrange;
+
+        // Pull both bodies towards their pulleys
+        Self::move_body(body1, world_r1, delta_lagrange * n1);
+        Self::move_body(body2, world_r2, delta_lagrange * self.ratio * n2);
+
+        self.force = self.compute_force(self.lagrange, n1, dt);
+    }
+}
+
+impl Joint for PulleyJoint {
+    fn new(entity1: Entity, entity2: Entity) -> Self {
+        Self {
+            entity1,
+            entity2,
+            local_anchor1: Vector::ZERO,
+            local_anchor2: Vector::ZERO,
+            ground_anchor1: Vector::ZERO,
+            ground_anchor2: Vector::ZERO,
+            ratio: 1.0,
+            length: None,
+            damping_linear: 1.0,
+            damping_angular: 1.0,
+            lagrange: 0.0,
+            compliance: 0.0,
+            force: Vector::ZERO,
+        }
+    }
+
+    fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    fn with_local_anchor_1(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor1: anchor,
+            ..self
+        }
+    }
+
+    fn with_local_anchor_2(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor2: anchor,
+            ..self
+        }
+    }
+
+    fn with_linear_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_linear: damping,
+            ..self
+        }
+    }
+
+    fn with_angular_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_angular: damping,
+            ..self
+        }
+    }
+
+    fn local_anchor_1(&self) -> Vector {
+        self.local_anchor1
+    }
+
+    fn local_anchor_2(&self) -> Vector {
+        self.local_anchor2
+    }
+
+    fn damping_linear(&self) -> Scalar {
+        self.damping_linear
+    }
+
+    fn damping_angular(&self) -> Scalar {
+        self.damping_angular
+    }
+
+    fn total_force(&self) -> Vector {
+        self.force
+    }
+}
+
+impl PulleyJoint {
+    /// Sets the world positions of the pulleys that the ropes of the first and second body run over.
+    pub fn with_ground_anchors(self, ground_anchor1: Vector, ground_anchor2: Vector) -> Self {
+        Self {
+            ground_anchor1,
+            ground_anchor2,
+            ..self
+        }
+    }
+
+    /// Sets the pulley ratio. The rope of the second body counts `ratio` times towards the total length.
+    pub fn with_ratio(self, ratio: Scalar) -> Self {
+        Self { ratio, ..self }
+    }
+
+    /// Sets the total length of the rope, `length1 + ratio * length2`.
+    pub fn with_length(self, length: Scalar) -> Self {
+        Self {
+            length: Some(length),
+            ..self
+        }
+    }
+
+    /// Applies a positional correction `p` at the world-space offset `r` to a dynamic body.
+    fn move_body(body: &mut RigidBodyQueryItem, r: Vector, p: Vector) {
+        if !body.rb.is_dynamic() {
+            return;
+        }
+
+        let inv_mass = body.effective_inv_mass();
+        body.accumulated_translation.0 += p * inv_mass;
+
+        #[cfg(feature = "2d")]
+        if !body.is_rotation_locked() {
+            let inv_inertia = body.effective_world_inv_inertia();
+            let delta_rot =
+                <Self as PositionConstraint>::get_delta_rot(*body.rotation, inv_inertia, r, p);
+            *body.rotation += delta_rot;
+        }
+        #[cfg(feature = "3d")]
+        {
+            let inv_inertia = body.effective_world_inv_inertia();
+            let delta_rot =
+                <Self as PositionConstraint>::get_delta_rot(*body.rotation, inv_inertia, r, p);
+            *body.rotation += delta_rot;
+
+            // Subtracting and adding quaternions can result in unnormalized rotations.
+            body.rotation.0 = body.rotation.0.normalize();
+        }
+    }
+}
+
+impl PositionConstraint for PulleyJoint {}
+
+impl AngularConstraint for PulleyJoint {}
+
+impl MapEntities for PulleyJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity1 = entity_mapper.map_entity(self.entity1);
+        self.entity2 = entity_mapper.map_entity(self.entity2);
+    }
+}
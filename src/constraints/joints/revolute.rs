@@ -9,6 +9,34 @@ use bevy::{
 /// A revolute joint prevents relative movement of the attached bodies, except for rotation around one `aligned_axis`.
 ///
 /// Revolute joints can be useful for things like wheels, fans, revolving doors etc.
+///
+/// The angle limits can have a [`SoftLimitZone`] that slows the joint down before it reaches the limits,
+/// so that doors and levers don't snap into their stops. A [`JointLimitResponse`] can make the limits
+/// themselves compliant and bouncy.
+///
+/// Joint friction can be added using [`RevoluteJoint::with_friction_torque`], so that hinges hold their position
+/// under small loads.
+///
+/// The joint can be driven by a motor with a [target](MotorTarget) angular speed or angle and a maximum torque,
+/// see [`RevoluteJoint::with_velocity_motor`] and [`RevoluteJoint::with_position_motor`]. The motor is solved
+/// together with the rest of the joint, so it doesn't fight the constraint like torques applied manually.
+/// Because the motor torque is limited, the bodies still react physically to impacts and loads.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     let car = commands.spawn(RigidBody::Dynamic).id();
+///     let wheel = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // Spin the wheel at 10 rad/s with a maximum torque of 500 Nm
+///     commands.spawn(RevoluteJoint::new(car, wheel).with_velocity_motor(10.0, 500.0));
+/// }
+/// ```
 #[derive(Component, Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct RevoluteJoint {
@@ -30,6 +58,17 @@ pub struct RevoluteJoint {
     pub aligned_axis: Vector,
     /// The extents of the allowed relative rotation of the bodies around the `aligned_axis`.
     pub angle_limit: Option<AngleLimit>,
+    /// A soft zone inside the angle limits where the relative rotation is slowed down before it reaches the limits.
+    pub angle_limit_soft_zone: Option<SoftLimitZone>,
+    /// How the joint responds when it reaches the angle limits. If `None`, the limits are rigid
+    /// and use the joint's `compliance`.
+    pub angle_limit_response: Option<JointLimitResponse>,
+    /// The maximum torque that the joint friction can apply to resist relative rotation around the `aligned_axis`.
+    pub friction_torque: Scalar,
+    /// The target angular speed or angle of the motor around the `aligned_axis`. The motor is disabled when this is `None`.
+    pub motor_target: Option<MotorTarget>,
+    /// The maximum torque that the motor can apply around the `aligned_axis`.
+    pub max_motor_torque: Scalar,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
@@ -40,6 +79,10 @@ pub struct RevoluteJoint {
     pub align_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the angle limits.
     pub angle_limit_lagrange: Scalar,
+    /// Lagrange multiplier for the angular correction caused by the soft zone of the angle limits.
+    pub soft_zone_lagrange: Scalar,
+    /// Lagrange multiplier for the motor.
+    pub motor_lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
     /// The force exerted by the joint.
@@ -48,6 +91,10 @@ pub struct RevoluteJoint {
     pub align_torque: Torque,
     /// The torque exerted by the joint when limiting the relative rotation of the bodies around the `aligned_axis`.
     pub angle_limit_torque: Torque,
+    /// The torque exerted by the joint in the soft zone of the angle limits.
+    pub soft_zone_torque: Torque,
+    /// The torque exerted by the motor.
+    pub motor_torque: Torque,
 }
 
 impl XpbdConstraint<2> for RevoluteJoint {
@@ -59,6 +106,8 @@ impl XpbdConstraint<2> for RevoluteJoint {
         self.position_lagrange = 0.0;
         self.align_lagrange = 0.0;
         self.angle_limit_lagrange = 0.0;
+        self.soft_zone_lagrange = 0.0;
+        self.motor_lagrange = 0.0;
     }
 
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
@@ -84,6 +133,14 @@ impl XpbdConstraint<2> for RevoluteJoint {
         );
         self.position_lagrange = lagrange;
 
+        // Drive the rotation around the free axis
+        if let Some(target) = self.motor_target {
+            self.motor_torque = self.apply_motor(body1, body2, target, dt);
+        }
+
+        // Slow down the rotation in the soft zone before the angle limits
+        self.soft_zone_torque = self.apply_soft_zone(body1, body2, dt);
+
         // Apply angle limits when rotating around the free axis
         self.angle_limit_torque = self.apply_angle_limits(body1, body2, dt);
     }
@@ -98,11 +155,18 @@ impl Joint for RevoluteJoint {
             local_anchor2: Vector::ZERO,
             aligned_axis: Vector3::Z,
             angle_limit: None,
+            angle_limit_soft_zone: None,
+            angle_limit_response: None,
+            friction_torque: 0.0,
+            motor_target: None,
+            max_motor_torque: 0.0,
             damping_linear: 1.0,
             damping_angular: 1.0,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
             angle_limit_lagrange: 0.0,
+            soft_zone_lagrange: 0.0,
+            motor_lagrange: 0.0,
             compliance: 0.0,
             force: Vector::ZERO,
             #[cfg(feature = "2d")]
@@ -113,6 +177,14 @@ impl Joint for RevoluteJoint {
             angle_limit_torque: 0.0,
             #[cfg(feature = "3d")]
             angle_limit_torque: Vector::ZERO,
+            #[cfg(feature = "2d")]
+            soft_zone_torque: 0.0,
+            #[cfg(feature = "3d")]
+            soft_zone_torque: Vector::ZERO,
+            #[cfg(feature = "2d")]
+            motor_torque: 0.0,
+            #[cfg(feature = "3d")]
+            motor_torque: Vector::ZERO,
         }
     }
 
@@ -163,6 +235,14 @@ impl Joint for RevoluteJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn total_force(&self) -> Vector {
+        self.force
+    }
+
+    fn total_torque(&self) -> Torque {
+        self.align_torque + self.angle_limit_torque + self.soft_zone_torque + self.motor_torque
+    }
 }
 
 impl RevoluteJoint {
@@ -183,12 +263,228 @@ impl RevoluteJoint {
         }
     }
 
+    /// Sets a soft zone inside the angle limits where the relative rotation is slowed down
+    /// before it reaches the limits. The soft zone only has an effect when angle limits are set.
+    pub fn with_angle_limit_soft_zone(self, soft_zone: SoftLimitZone) -> Self {
+        Self {
+            angle_limit_soft_zone: Some(soft_zone),
+            ..self
+        }
+    }
+
+    /// Sets the [compliance, damping and restitution](JointLimitResponse) of the angle limits,
+    /// so that the joint can ease into its limits or bounce back from them.
+    /// The response only has an effect when angle limits are set.
+    pub fn with_angle_limit_response(self, response: JointLimitResponse) -> Self {
+        Self {
+            angle_limit_response: Some(response),
+            ..self
+        }
+    }
+
+    /// Sets the maximum torque that the joint friction can apply to resist relative rotation around the `aligned_axis`.
+    ///
+    /// The friction is applied in the velocity solve, and it holds the bodies in place
+    /// until the torque between them exceeds the friction torque.
+    pub fn with_friction_torque(self, friction_torque: Scalar) -> Self {
+        Self {
+            friction_torque,
+            ..self
+        }
+    }
+
+    /// Enables the motor with the given target angular speed in radians per second around the `aligned_axis`,
+    /// and the maximum torque that the motor can apply to reach it.
+    pub fn with_velocity_motor(self, target_speed: Scalar, max_torque: Scalar) -> Self {
+        Self {
+            motor_target: Some(MotorTarget::Velocity(target_speed)),
+            max_motor_torque: max_torque,
+            ..self
+        }
+    }
+
+    /// Enables the motor with the given target angle in radians around the `aligned_axis`,
+    /// and the maximum torque that the motor can apply to reach and hold it.
+    pub fn with_position_motor(self, target_angle: Scalar, max_torque: Scalar) -> Self {
+        Self {
+            motor_target: Some(MotorTarget::Position(target_angle)),
+            max_motor_torque: max_torque,
+            ..self
+        }
+    }
+
     fn get_delta_q(&self, rot1: &Rotation, rot2: &Rotation) -> Vector3 {
         let a1 = rot1.rotate_vec3(self.aligned_axis);
         let a2 = rot2.rotate_vec3(self.aligned_axis);
         a1.cross(a2)
     }
 
+    /// Computes the relative rotation angle of the given rotations around the `aligned_axis`,
+    /// along with the axis in world space.
+    pub(crate) fn hinge_angle(&self, rot1: &Rotation, rot2: &Rotation) -> (Scalar, Vector3) {
+        #[cfg(feature = "2d")]
+        {
+            (rot2.mul(rot1.inverse()).as_radians(), Vector3::Z)
+        }
+        #[cfg(feature = "3d")]
+        {
+            let n = rot1.rotate_vec3(self.aligned_axis);
+            let reference = self.aligned_axis.any_orthonormal_vector();
+            let n1 = rot1.rotate_vec3(reference);
+            let n2 = rot2.rotate_vec3(reference);
+            (n1.cross(n2).dot(n).atan2(n1.dot(n2)), n)
+        }
+    }
+
+    /// Computes how much the relative rotation angle of the bodies around the `aligned_axis` changed
+    /// during the current substep, given the current angle.
+    fn hinge_angle_delta(
+        &self,
+        body1: &RigidBodyQueryItem,
+        body2: &RigidBodyQueryItem,
+        angle: Scalar,
+    ) -> Scalar {
+        let (previous_angle, _) =
+            self.hinge_angle(&body1.previous_rotation.0, &body2.previous_rotation.0);
+        wrap_angle(angle - previous_angle)
+    }
+
+    /// Rotates the bodies around the `aligned_axis` towards the motor's target,
+    /// limiting the applied torque to the maximum motor torque.
+    ///
+    /// Returns the torque exerted by the motor.
+    fn apply_motor(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        target: MotorTarget,
+        dt: Scalar,
+    ) -> Torque {
+        if self.max_motor_torque <= 0.0 {
+            return Torque::ZERO;
+        }
+
+        let (angle, axis) = self.hinge_angle(&body1.rotation, &body2.rotation);
+
+        // The change in angle needed to reach the target during this substep
+        let error = match target {
+            MotorTarget::Velocity(speed) => {
+                speed * dt - self.hinge_angle_delta(body1, body2, angle)
+            }
+            MotorTarget::Position(target_angle) => wrap_angle(target_angle - angle),
+        };
+
+        if error.abs() <= Scalar::EPSILON {
+            return Torque::ZERO;
+        }
+
+        // Compute generalized inverse masses
+        let w1 = AngularConstraint::compute_generalized_inverse_mass(self, body1, axis);
+        let w2 = AngularConstraint::compute_generalized_inverse_mass(self, body2, axis);
+        let w_sum = w1 + w2;
+
+        if w_sum <= Scalar::EPSILON {
+            return Torque::ZERO;
+        }
+
+        // Compute Lagrange multiplier update and limit it by the maximum motor torque
+        let max_lagrange = self.max_motor_torque * dt.powi(2);
+        let motor_lagrange =
+            (self.motor_lagrange + error / w_sum).clamp(-max_lagrange, max_lagrange);
+        let delta_lagrange = motor_lagrange - self.motor_lagrange;
+        self.motor_lagrange = motor_lagrange;
+
+        // Apply angular correction around the hinge axis
+        self.apply_angular_correction(body1, body2, delta_lagrange, axis);
+
+        self.compute_torque(self.motor_lagrange, axis, dt)
+    }
+
+    /// Applies a compliant and damped correction in the soft zone of the angle limits,
+    /// pushing the relative rotation back towards the inner edge of the zone.
+    fn apply_soft_zone(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Torque {
+        let (Some(angle_limit), Some(soft_zone)) = (self.angle_limit, self.angle_limit_soft_zone)
+        else {
+            return Torque::ZERO;
+        };
+
+        let (angle, axis) = self.hinge_angle(&body1.rotation, &body2.rotation);
+
+        // The soft zones can't overlap
+        let width = soft_zone
+            .width
+            .min(0.5 * (angle_limit.beta - angle_limit.alpha));
+        let c = if angle < angle_limit.alpha + width {
+            angle - (angle_limit.alpha + width)
+        } else if angle > angle_limit.beta - width {
+            angle - (angle_limit.beta - width)
+        } else {
+            return Torque::ZERO;
+        };
+
+        let mut lagrange = self.soft_zone_lagrange;
+        let torque = self.apply_damped_hinge_correction(
+            body1,
+            body2,
+            c,
+            angle,
+            axis,
+            soft_zone.compliance,
+            soft_zone.damping,
+            &mut lagrange,
+            dt,
+        );
+        self.soft_zone_lagrange = lagrange;
+        torque
+    }
+
+    /// Applies a compliant and damped correction `c` to the relative rotation angle of the bodies around the hinge `axis`.
+    ///
+    /// Returns the torque exerted by the correction.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_damped_hinge_correction(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        c: Scalar,
+        angle: Scalar,
+        axis: Vector3,
+        compliance: Scalar,
+        damping: Scalar,
+        lagrange: &mut Scalar,
+        dt: Scalar,
+    ) -> Torque {
+        // How much the angle changed during this substep, used by the damper
+        let delta_angle = self.hinge_angle_delta(body1, body2, angle);
+
+        // Compute generalized inverse masses
+        let w1 = AngularConstraint::compute_generalized_inverse_mass(self, body1, axis);
+        let w2 = AngularConstraint::compute_generalized_inverse_mass(self, body2, axis);
+        let w_sum = w1 + w2;
+
+        if w_sum <= Scalar::EPSILON {
+            return Torque::ZERO;
+        }
+
+        // Compute Lagrange multiplier update with damping
+        // (Equation 26 of "XPBD: Position-Based Simulation of Compliant Constrained Dynamics")
+        let tilde_compliance = compliance / dt.powi(2);
+        let gamma = compliance * damping / dt;
+        let delta_lagrange = (-c - tilde_compliance * *lagrange - gamma * delta_angle)
+            / ((1.0 + gamma) * w_sum + tilde_compliance);
+        *lagrange += delta_lagrange;
+
+        // Apply angular correction around the hinge axis
+        self.apply_angular_correction(body1, body2, delta_lagrange, axis);
+
+        self.compute_torque(*lagrange, axis, dt)
+    }
+
     /// Applies angle limits to limit the relative rotation of the bodies around the `aligned_axis`.
     #[allow(clippy::too_many_arguments)]
     fn apply_angle_limits(
@@ -197,6 +493,32 @@ impl RevoluteJoint {
         body2: &mut RigidBodyQueryItem,
         dt: Scalar,
     ) -> Torque {
+        if let (Some(angle_limit), Some(response)) = (self.angle_limit, self.angle_limit_response) {
+            let (angle, axis) = self.hinge_angle(&body1.rotation, &body2.rotation);
+            let c = if angle < angle_limit.alpha {
+                angle - angle_limit.alpha
+            } else if angle > angle_limit.beta {
+                angle - angle_limit.beta
+            } else {
+                return Torque::ZERO;
+            };
+
+            let mut lagrange = self.angle_limit_lagrange;
+            let torque = self.apply_damped_hinge_correction(
+                body1,
+                body2,
+                c,
+                angle,
+                axis,
+                response.compliance,
+                response.damping,
+                &mut lagrange,
+                dt,
+            );
+            self.angle_limit_lagrange = lagrange;
+            return torque;
+        }
+
         let Some(Some(correction)) = self.angle_limit.map(|angle_limit| {
             #[cfg(feature = "2d")]
             {
@@ -160,6 +160,14 @@ impl Joint for SphericalJoint {
     fn damping_angular(&self) -> Scalar {
         self.damping_angular
     }
+
+    fn total_force(&self) -> Vector {
+        self.force
+    }
+
+    fn total_torque(&self) -> Torque {
+        self.swing_torque + self.twist_torque
+    }
 }
 
 impl SphericalJoint {
@@ -0,0 +1,410 @@
+//! [`SplineDriveJoint`] component.
+
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    math::cubic_splines::{CubicBezier, CubicCurve, CubicGenerator},
+    prelude::*,
+};
+
+/// The point type of the curves followed by [`SplineDriveJoints`](SplineDriveJoint).
+#[cfg(feature = "2d")]
+pub type SplinePoint = Vec2;
+/// The point type of the curves followed by [`SplineDriveJoints`](SplineDriveJoint).
+#[cfg(feature = "3d")]
+pub type SplinePoint = Vec3;
+
+/// A spline drive joint constrains the anchor of the second body to a curve defined in the local space
+/// of the first body, only allowing translation along the curve.
+///
+/// The position of the second body along the curve can be driven by a motor with a target speed and
+/// a maximum force. Because the motor force is limited, the body still reacts physically to impacts and
+/// to forces like gravity, for example slowing down when going up steep slopes.
+///
+/// Spline drive joints can be useful for things like rollercoasters, cable cars and on-rails segments.
+/// The rotation of the body is not constrained by the joint.
+///
+/// The curve can be any [`CubicCurve`], for example one created using a [`CubicBezier`] or a
+/// `CubicCardinalSpline`. By default, the curve is a straight line from the origin to `Vector::X`.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::{math::cubic_splines::CubicCardinalSpline, prelude::*};
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     let track = commands.spawn(RigidBody::Static).id();
+///     let cart = commands.spawn(RigidBody::Dynamic).id();
+///
+///     let curve = CubicCardinalSpline::new_catmull_rom([
+#[cfg_attr(
+    feature = "2d",
+    doc = "        Vec2::new(0.0, 0.0),
+        Vec2::new(10.0, 5.0),
+        Vec2::new(20.0, 0.0),
+        Vec2::new(30.0, 8.0),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "        Vec3::new(0.0, 0.0, 0.0),
+        Vec3::new(10.0, 5.0, 0.0),
+        Vec3::new(20.0, 0.0, 5.0),
+        Vec3::new(30.0, 8.0, 5.0),"
+)]
+///     ])
+///     .to_curve();
+///
+///     // Drive the cart along the track at 5 m/s with a maximum force of 2000 N
+///     commands.spawn(
+///         SplineDriveJoint::new(track, cart)
+///             .with_curve(curve)
+///             .with_motor(5.0, 2000.0),
+///     );
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct SplineDriveJoint {
+    /// First entity constrained by the joint. The curve is defined in the local space of this body.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint. The anchor of this body is constrained to the curve.
+    pub entity2: Entity,
+    /// The origin of the curve on the first body.
+    pub local_anchor1: Vector,
+    /// Attachment point on the second body.
+    pub local_anchor2: Vector,
+    /// The curve that the anchor of the second body follows, relative to the first anchor.
+    pub curve: CubicCurve<SplinePoint>,
+    /// The current position of the second body along the curve as a curve parameter.
+    ///
+    /// The parameter goes from 0 at the start of the curve to the number of curve segments at the end of the curve.
+    pub parameter: Scalar,
+    /// The speed in meters per second along the curve that the motor tries to reach.
+    /// Positive values drive the body towards the end of the curve.
+    pub motor_target_speed: Scalar,
+    /// The maximum force that the motor can apply along the curve. The motor is disabled when this is 0.
+    pub max_motor_force: Scalar,
+    /// Linear damping applied by the joint.
+    pub damping_linear: Scalar,
+    /// Angular damping applied by the joint.
+    pub damping_angular: Scalar,
+    /// Lagrange multiplier for the positional correction.
+    pub position_lagrange: Scalar,
+    /// Lagrange multiplier for the motor.
+    pub motor_lagrange: Scalar,
+    /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// The force exerted by the joint to keep the body on the curve.
+    pub force: Vector,
+    /// The force exerted by the motor along the curve.
+    pub motor_force: Vector,
+}
+
+impl XpbdConstraint<2> for SplineDriveJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.position_lagrange = 0.0;
+        self.motor_lagrange = 0.0;
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+
+        let world_r2 = body2.rotation.rotate(self.local_anchor2);
+        let anchor2 = body2.current_position() + world_r2;
+
+        // Find the point on the curve closest to the anchor of the second body
+        self.update_parameter(body1, anchor2);
+
+        let local_point = self.local_anchor1 + self.curve_position(self.parameter);
+        let world_r1 = body1.rotation.rotate(local_point);
+        let tangent = body1
+            .rotation
+            .rotate(self.curve_velocity(self.parameter))
+            .normalize_or_zero();
+
+        // Keep the anchor on the curve
+        self.force = self.constrain_to_curve(body1, body2, world_r1, world_r2, tangent, dt);
+
+        // Drive the body along the curve
+        if self.max_motor_force > 0.0 && tangent != Vector::ZERO {
+            self.motor_force = self.apply_motor(body1, body2, world_r1, world_r2, tangent, dt);
+        }
+    }
+}
+
+impl Joint for SplineDriveJoint {
+    fn new(entity1: Entity, entity2: Entity) -> Self {
+        Self {
+            entity1,
+            entity2,
+            local_anchor1: Vector::ZERO,
+            local_anchor2: Vector::ZERO,
+            curve: CubicBezier::new([[
+                SplinePoint::ZERO,
+                SplinePoint::X / 3.0,
+                SplinePoint::X * 2.0 / 3.0,
+                SplinePoint::X,
+            ]])
+            .to_curve(),
+            parameter: 0.0,
+            motor_target_speed: 0.0,
+            max_motor_force: 0.0,
+            damping_linear: 1.0,
+            damping_angular: 1.0,
+            position_lagrange: 0.0,
+            motor_lagrange: 0.0,
+            compliance: 0.0,
+            force: Vector::ZERO,
+            motor_force: Vector::ZERO,
+        }
+    }
+
+    fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    fn with_local_anchor_1(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor1: anchor,
+            ..self
+        }
+    }
+
+    fn with_local_anchor_2(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor2: anchor,
+            ..self
+        }
+    }
+
+    fn with_linear_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_linear: damping,
+            ..self
+        }
+    }
+
+    fn with_angular_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_angular: damping,
+            ..self
+        }
+    }
+
+    fn local_anchor_1(&self) -> Vector {
+        self.local_anchor1
+    }
+
+    fn local_anchor_2(&self) -> Vector {
+        self.local_anchor2
+    }
+
+    fn damping_linear(&self) -> Scalar {
+        self.damping_linear
+    }
+
+    fn damping_angular(&self) -> Scalar {
+        self.damping_angular
+    }
+
+    fn total_force(&self) -> Vector {
+        self.force + self.motor_force
+    }
+}
+
+impl SplineDriveJoint {
+    /// Sets the curve that the anchor of the second body follows, relative to the first anchor.
+    pub fn with_curve(self, curve: CubicCurve<SplinePoint>) -> Self {
+        let mut joint = Self { curve, ..self };
+        joint.parameter = joint.parameter.clamp(0.0, joint.max_parameter());
+        joint
+    }
+
+    /// Sets the initial position of the second body along the curve as a curve parameter.
+    ///
+    /// The body is pulled to the corresponding point on the curve.
+    pub fn with_parameter(self, parameter: Scalar) -> Self {
+        Self {
+            parameter: parameter.clamp(0.0, self.max_parameter()),
+            ..self
+        }
+    }
+
+    /// Enables the motor with the given target speed in meters per second along the curve,
+    /// and the maximum force that the motor can apply to reach it.
+    pub fn with_motor(self, target_speed: Scalar, max_force: Scalar) -> Self {
+        Self {
+            motor_target_speed: target_speed,
+            max_motor_force: max_force,
+            ..self
+        }
+    }
+
+    /// Returns the largest curve parameter, which is the number of segments in the curve.
+    pub fn max_parameter(&self) -> Scalar {
+        self.curve.segments().len() as Scalar
+    }
+
+    /// Returns the position on the curve at the given curve parameter, relative to the first anchor.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn curve_position(&self, parameter: Scalar) -> Vector {
+        self.curve.position(parameter as f32).adjust_precision()
+    }
+
+    /// Returns the derivative of the curve at the given curve parameter.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn curve_velocity(&self, parameter: Scalar) -> Vector {
+        self.curve.velocity(parameter as f32).adjust_precision()
+    }
+
+    /// Moves the curve parameter towards the point on the curve closest to the given world-space point
+    /// using a few Newton iterations, starting from the current parameter.
+    fn update_parameter(&mut self, body1: &RigidBodyQueryItem, point: Vector) {
+        let local_point = body1
+            .rotation
+            .inverse()
+            .rotate(point - body1.current_position())
+            - self.local_anchor1;
+        let max_parameter = self.max_parameter();
+
+        for _ in 0..3 {
+            let velocity = self.curve_velocity(self.parameter);
+            let length_squared = velocity.length_squared();
+            if length_squared <= Scalar::EPSILON {
+                break;
+            }
+            let offset = local_point - self.curve_position(self.parameter);
+            self.parameter =
+                (self.parameter + offset.dot(velocity) / length_squared).clamp(0.0, max_parameter);
+        }
+    }
+
+    /// Constrains the anchor of the second body to the curve, allowing translation along the tangent
+    /// except at the ends of the curve.
+    ///
+    /// Returns the force exerted by this constraint.
+    fn constrain_to_curve(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        world_r1: Vector,
+        world_r2: Vector,
+        tangent: Vector,
+        dt: Scalar,
+    ) -> Vector {
+        let target = body1.current_position() + world_r1;
+        let mut delta_x = target - (body2.current_position() + world_r2);
+
+        // Allow movement along the curve when not at one of its ends
+        if self.parameter > 0.0 && self.parameter < self.max_parameter() {
+            delta_x -= tangent * delta_x.dot(tangent);
+        }
+
+        let magnitude = delta_x.length();
+
+        if magnitude <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let dir = delta_x / magnitude;
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+
+        // Constraint gradients and inverse masses
+        let gradients = [dir, -dir];
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update
+        let delta_lagrange = self.compute_lagrange_update(
+            self.position_lagrange,
+            magnitude,
+            &gradients,
+            &w,
+            self.compliance,
+            dt,
+        );
+        self.position_lagrange += delta_lagrange;
+
+        // Apply positional correction to move the anchor onto the curve
+        self.apply_positional_correction(body1, body2, delta_lagrange, dir, world_r1, world_r2);
+
+        // Return constraint force
+        self.compute_force(self.position_lagrange, dir, dt)
+    }
+
+    /// Moves the second body along the curve towards the motor's target speed,
+    /// limiting the applied force to the maximum motor force.
+    ///
+    /// Returns the force exerted by the motor.
+    fn apply_motor(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        world_r1: Vector,
+        world_r2: Vector,
+        tangent: Vector,
+        dt: Scalar,
+    ) -> Vector {
+        let target_distance = self.motor_target_speed * dt;
+
+        // The motor can't drive the body past the ends of the curve
+        if (self.parameter <= 0.0 && target_distance < 0.0)
+            || (self.parameter >= self.max_parameter() && target_distance > 0.0)
+        {
+            return Vector::ZERO;
+        }
+
+        // The relative distance travelled along the curve during this substep
+        let delta_pos1 = body1.current_position() - body1.previous_position.0;
+        let delta_pos2 = body2.current_position() - body2.previous_position.0;
+        let distance = (delta_pos2 - delta_pos1).dot(tangent);
+
+        let error = target_distance - distance;
+
+        if error.abs() <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let dir = tangent * error.signum();
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+
+        let gradients = [dir, -dir];
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update and limit it by the maximum motor force
+        let delta_lagrange =
+            self.compute_lagrange_update(self.motor_lagrange, error.abs(), &gradients, &w, 0.0, dt);
+        let max_lagrange = self.max_motor_force * dt.powi(2);
+        let motor_lagrange =
+            (self.motor_lagrange + delta_lagrange).clamp(-max_lagrange, max_lagrange);
+        let delta_lagrange = motor_lagrange - self.motor_lagrange;
+        self.motor_lagrange = motor_lagrange;
+
+        // Apply positional correction along the curve
+        self.apply_positional_correction(body1, body2, delta_lagrange, dir, world_r1, world_r2);
+
+        // Return motor force
+        self.compute_force(self.motor_lagrange, dir, dt)
+    }
+}
+
+impl PositionConstraint for SplineDriveJoint {}
+
+impl AngularConstraint for SplineDriveJoint {}
+
+impl MapEntities for SplineDriveJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity1 = entity_mapper.map_entity(self.entity1);
+        self.entity2 = entity_mapper.map_entity(self.entity2);
+    }
+}
@@ -0,0 +1,367 @@
+//! [`SuspensionConstraint`] component.
+
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+
+/// A suspension constraint is a spring-damper along an axis between a chassis and a wheel body,
+/// with optional limits for the suspension travel.
+///
+/// The anchor of the wheel body is kept on the suspension axis, which is defined in the local space
+/// of the chassis. Along the axis, the wheel is pulled towards the rest length by a spring with the
+/// given stiffness, and the motion is resisted by a damper. The rotation of the wheel is not constrained,
+/// so it can spin freely.
+///
+/// The constraint is solved in the substepping loop like other joints, so it stays stable with stiff springs
+/// and can be used as a building block for custom vehicle implementations.
+///
+/// By default, the suspension axis points down along the negative Y axis, the rest length is 0,
+/// the stiffness is 1000 N/m and the damping is 100 N·s/m.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// fn setup(mut commands: Commands) {
+///     let chassis = commands.spawn(RigidBody::Dynamic).id();
+///     let wheel = commands.spawn(RigidBody::Dynamic).id();
+///
+///     commands.spawn(
+///         SuspensionConstraint::new(chassis, wheel)
+#[cfg_attr(
+    feature = "2d",
+    doc = "            .with_local_anchor_1(Vector::new(1.0, -0.2))"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "            .with_local_anchor_1(Vector::new(1.0, -0.2, 0.0))"
+)]
+///             .with_rest_length(0.4)
+///             .with_travel_limits(0.2, 0.6)
+///             .with_stiffness(20_000.0)
+///             .with_suspension_damping(1500.0),
+///     );
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuspensionConstraint {
+    /// First entity constrained by the joint, the chassis.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint, the wheel.
+    pub entity2: Entity,
+    /// Attachment point on the first body, the top of the suspension.
+    pub local_anchor1: Vector,
+    /// Attachment point on the second body.
+    pub local_anchor2: Vector,
+    /// The suspension axis in the local space of the first body, pointing from the chassis towards the wheel.
+    pub axis: Vector,
+    /// The distance along the axis between the anchors when the spring is at rest.
+    pub rest_length: Scalar,
+    /// The extents of the allowed distance between the anchors along the axis.
+    pub travel_limits: Option<DistanceLimit>,
+    /// The damping coefficient of the suspension damper, in N·s/m.
+    pub suspension_damping: Scalar,
+    /// Linear damping applied by the joint.
+    pub damping_linear: Scalar,
+    /// Angular damping applied by the joint.
+    pub damping_angular: Scalar,
+    /// Lagrange multiplier for the spring.
+    pub spring_lagrange: Scalar,
+    /// Lagrange multiplier for the positional correction keeping the wheel on the axis and within the travel limits.
+    pub position_lagrange: Scalar,
+    /// The compliance of the spring, the inverse of stiffness, has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// The force exerted by the spring and damper.
+    pub force: Vector,
+    /// The force exerted by the joint to keep the wheel on the axis and within the travel limits.
+    pub limit_force: Vector,
+}
+
+impl XpbdConstraint<2> for SuspensionConstraint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.spring_lagrange = 0.0;
+        self.position_lagrange = 0.0;
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+
+        // Pull the wheel towards the rest length
+        self.force = self.solve_spring(body1, body2, dt);
+
+        // Keep the wheel on the suspension axis and within the travel limits
+        self.limit_force = self.constrain_positions(body1, body2, dt);
+    }
+}
+
+impl Joint for SuspensionConstraint {
+    fn new(entity1: Entity, entity2: Entity) -> Self {
+        Self {
+            entity1,
+            entity2,
+            local_anchor1: Vector::ZERO,
+            local_anchor2: Vector::ZERO,
+            axis: Vector::NEG_Y,
+            rest_length: 0.0,
+            travel_limits: None,
+            suspension_damping: 100.0,
+            damping_linear: 0.0,
+            damping_angular: 0.0,
+            spring_lagrange: 0.0,
+            position_lagrange: 0.0,
+            compliance: 0.001,
+            force: Vector::ZERO,
+            limit_force: Vector::ZERO,
+        }
+    }
+
+    fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    fn with_local_anchor_1(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor1: anchor,
+            ..self
+        }
+    }
+
+    fn with_local_anchor_2(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor2: anchor,
+            ..self
+        }
+    }
+
+    fn with_linear_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_linear: damping,
+            ..self
+        }
+    }
+
+    fn with_angular_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_angular: damping,
+            ..self
+        }
+    }
+
+    fn local_anchor_1(&self) -> Vector {
+        self.local_anchor1
+    }
+
+    fn local_anchor_2(&self) -> Vector {
+        self.local_anchor2
+    }
+
+    fn damping_linear(&self) -> Scalar {
+        self.damping_linear
+    }
+
+    fn damping_angular(&self) -> Scalar {
+        self.damping_angular
+    }
+
+    fn total_force(&self) -> Vector {
+        self.force + self.limit_force
+    }
+}
+
+impl SuspensionConstraint {
+    /// Sets the suspension axis in the local space of the first body, pointing from the chassis towards the wheel.
+    pub fn with_axis(self, axis: Vector) -> Self {
+        Self {
+            axis: axis.normalize_or_zero(),
+            ..self
+        }
+    }
+
+    /// Sets the distance along the axis between the anchors when the spring is at rest.
+    pub fn with_rest_length(self, rest_length: Scalar) -> Self {
+        Self {
+            rest_length,
+            ..self
+        }
+    }
+
+    /// Sets the minimum and maximum distance along the axis between the anchors.
+    pub fn with_travel_limits(self, min: Scalar, max: Scalar) -> Self {
+        Self {
+            travel_limits: Some(DistanceLimit::new(min, max)),
+            ..self
+        }
+    }
+
+    /// Sets the stiffness of the spring in N/m. This is the inverse of the compliance.
+    pub fn with_stiffness(self, stiffness: Scalar) -> Self {
+        Self {
+            compliance: if stiffness > 0.0 {
+                stiffness.recip()
+            } else {
+                Scalar::INFINITY
+            },
+            ..self
+        }
+    }
+
+    /// Sets the damping coefficient of the suspension damper in N·s/m.
+    pub fn with_suspension_damping(self, damping: Scalar) -> Self {
+        Self {
+            suspension_damping: damping,
+            ..self
+        }
+    }
+
+    /// Returns the current distance between the anchors along the suspension axis.
+    pub fn current_length(&self, body1: &RigidBodyQueryItem, body2: &RigidBodyQueryItem) -> Scalar {
+        let world_r1 = body1.rotation.rotate(self.local_anchor1);
+        let world_r2 = body2.rotation.rotate(self.local_anchor2);
+        let axis = body1.rotation.rotate(self.axis);
+        ((body2.current_position() + world_r2) - (body1.current_position() + world_r1)).dot(axis)
+    }
+
+    /// Solves the spring and damper along the suspension axis using a compliant constraint with damping.
+    ///
+    /// Returns the force exerted by the spring and damper.
+    fn solve_spring(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Vector {
+        if !self.compliance.is_finite() {
+            return Vector::ZERO;
+        }
+
+        let world_r1 = body1.rotation.rotate(self.local_anchor1);
+        let world_r2 = body2.rotation.rotate(self.local_anchor2);
+        let axis = body1.rotation.rotate(self.axis);
+
+        // The spring is stretched when the length is above the rest length
+        let c = self.current_length(body1, body2) - self.rest_length;
+
+        // How much the length changed during this substep, used by the damper
+        let delta_pos1 = body1.current_position() - body1.previous_position.0;
+        let delta_pos2 = body2.current_position() - body2.previous_position.0;
+        let delta_length = (delta_pos2 - delta_pos1).dot(axis);
+
+        // The correction is applied along the negative axis to shorten a stretched spring
+        let dir = -axis;
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+        let w_sum = w1 + w2;
+
+        if w_sum <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        // Compute Lagrange multiplier update with damping
+        // (Equation 26 of "XPBD: Position-Based Simulation of Compliant Constrained Dynamics")
+        let tilde_compliance = self.compliance / dt.powi(2);
+        let gamma = self.compliance * self.suspension_damping / dt;
+        let delta_lagrange = (-c - tilde_compliance * self.spring_lagrange - gamma * delta_length)
+            / ((1.0 + gamma) * w_sum + tilde_compliance);
+        self.spring_lagrange += delta_lagrange;
+
+        // Apply positional correction along the suspension axis
+        self.apply_positional_correction(body1, body2, delta_lagrange, dir, world_r1, world_r2);
+
+        // Return the force exerted by the spring and damper
+        self.compute_force(self.spring_lagrange, dir, dt)
+    }
+
+    /// Keeps the anchor of the wheel on the suspension axis and within the travel limits.
+    ///
+    /// Returns the force exerted by this constraint.
+    fn constrain_positions(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Vector {
+        let world_r1 = body1.rotation.rotate(self.local_anchor1);
+        let world_r2 = body2.rotation.rotate(self.local_anchor2);
+        let anchor1 = body1.current_position() + world_r1;
+        let anchor2 = body2.current_position() + world_r2;
+
+        let mut delta_x = Vector::ZERO;
+
+        let axis1 = body1.rotation.rotate(self.axis);
+        if let Some(limits) = self.travel_limits {
+            delta_x += limits.compute_correction_along_axis(anchor1, anchor2, axis1);
+        }
+
+        let zero_distance_limit = DistanceLimit::ZERO;
+
+        #[cfg(feature = "2d")]
+        {
+            let axis2 = Vector::new(axis1.y, -axis1.x);
+            delta_x += zero_distance_limit.compute_correction_along_axis(anchor1, anchor2, axis2);
+        }
+        #[cfg(feature = "3d")]
+        {
+            let axis2 = axis1.any_orthogonal_vector();
+            let axis3 = axis1.cross(axis2);
+
+            delta_x += zero_distance_limit.compute_correction_along_axis(anchor1, anchor2, axis2);
+            delta_x += zero_distance_limit.compute_correction_along_axis(anchor1, anchor2, axis3);
+        }
+
+        let magnitude = delta_x.length();
+
+        if magnitude <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let dir = delta_x / magnitude;
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+
+        // Constraint gradients and inverse masses
+        let gradients = [dir, -dir];
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update
+        let delta_lagrange = self.compute_lagrange_update(
+            self.position_lagrange,
+            magnitude,
+            &gradients,
+            &w,
+            0.0,
+            dt,
+        );
+        self.position_lagrange += delta_lagrange;
+
+        // Apply positional correction to keep the wheel on the axis and within the limits
+        self.apply_positional_correction(body1, body2, delta_lagrange, dir, world_r1, world_r2);
+
+        // Return constraint force
+        self.compute_force(self.position_lagrange, dir, dt)
+    }
+}
+
+impl PositionConstraint for SuspensionConstraint {}
+
+impl AngularConstraint for SuspensionConstraint {}
+
+impl MapEntities for SuspensionConstraint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity1 = entity_mapper.map_entity(self.entity1);
+        self.entity2 = entity_mapper.map_entity(self.entity2);
+    }
+}
@@ -21,6 +21,13 @@
 //!     - [`SphericalJoint`]
 //!     - [`RevoluteJoint`]
 //!     - [`PrismaticJoint`]
+//!     - [`SplineDriveJoint`]
+//!     - [`SuspensionConstraint`]
+//!     - [`GenericJoint`]
+//!     - [`GearJoint`]
+//!     - [`PulleyJoint`]
+//! - [Articulations](MultibodyRoot) with [`MultibodyLink`]s
+#![cfg_attr(feature = "2d", doc = "- [`FaceTarget`]")]
 //!
 //! More constraint types will be added in future releases. If you need more constraints now, consider
 //! [creating your own constraints](#custom-constraints).
@@ -200,14 +207,20 @@
 //! where `q_i` is the [rotation](Rotation) of body `i` and `r_i` is a vector pointing from the body's center of mass to some
 //! attachment position.
 
+#[cfg(feature = "2d")]
+pub mod face_target;
 pub mod joints;
+pub mod multibody;
 pub mod penetration;
 
 mod angular_constraint;
 mod position_constraint;
 
 pub use angular_constraint::AngularConstraint;
+#[cfg(feature = "2d")]
+pub use face_target::*;
 pub use joints::*;
+pub use multibody::{MultibodyLink, MultibodyRoot};
 pub use penetration::*;
 pub use position_constraint::PositionConstraint;
 
@@ -0,0 +1,360 @@
+//! [`MultibodyRoot`] and [`MultibodyLink`] components.
+
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+    utils::HashMap,
+};
+
+/// Marks the root body of an articulation, a tree of bodies connected by [`MultibodyLink`]s
+/// that is solved as one unit, like a ragdoll or a robot arm.
+///
+/// Joints are normally solved one at a time, so long chains of joints converge slowly and stretch
+/// under load unless the [`SubstepCount`] is increased. The links of an articulation are instead solved
+/// together with a direct solver that takes the whole tree into account, keeping even long chains stiff.
+///
+/// The root body can be dynamic, or static or kinematic to attach the articulation to the world.
+/// Each link is attached to its parent at a point, so the links can rotate freely relative to each other.
+/// Regular [joints] can be used alongside the links to limit or drive the rotation.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// fn setup(mut commands: Commands) {
+///     // A chain of 20 links hanging from a static anchor
+///     let mut parent = commands.spawn((RigidBody::Static, MultibodyRoot::default())).id();
+///
+///     for i in 1..=20 {
+///         parent = commands
+///             .spawn((
+///                 RigidBody::Dynamic,
+///                 Position(Vector::NEG_Y * i as Scalar),
+///                 MassPropertiesBundle::new_computed(&Collider::capsule(0.8, 0.1), 1.0),
+///                 MultibodyLink::new(parent)
+///                     .with_local_anchor_1(Vector::NEG_Y * 0.5)
+///                     .with_local_anchor_2(Vector::Y * 0.5),
+///             ))
+///             .id();
+///     }
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct MultibodyRoot {
+    /// The number of times the articulation is solved per substep. Each iteration corrects
+    /// the error left by the linearization of the rotations in the previous one. Defaults to 1.
+    pub iterations: u32,
+}
+
+impl Default for MultibodyRoot {
+    fn default() -> Self {
+        Self { iterations: 1 }
+    }
+}
+
+impl MultibodyRoot {
+    /// Sets the number of times the articulation is solved per substep.
+    pub fn with_iterations(self, iterations: u32) -> Self {
+        Self {
+            iterations: iterations.max(1),
+        }
+    }
+}
+
+/// Attaches a body to its `parent` body in an articulation. The articulation is solved as one unit,
+/// starting from the body with the [`MultibodyRoot`] component at the root of the tree.
+///
+/// The component is added to the child body itself, and the attachment points are given in the local space
+/// of the parent and the child. Links that don't lead to a [`MultibodyRoot`] are ignored.
+///
+/// See [`MultibodyRoot`] for more information.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultibodyLink {
+    /// The parent body in the articulation.
+    pub parent: Entity,
+    /// Attachment point on the parent body.
+    pub local_anchor1: Vector,
+    /// Attachment point on the body of the link.
+    pub local_anchor2: Vector,
+    /// The link's compliance, the inverse of stiffness, has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// Lagrange multiplier for the positional correction.
+    pub lagrange: Vector,
+    /// The force exerted by the parent on the body of the link.
+    pub force: Vector,
+}
+
+impl MultibodyLink {
+    /// Creates a new [`MultibodyLink`] that attaches the body to the given `parent`.
+    pub fn new(parent: Entity) -> Self {
+        Self {
+            parent,
+            local_anchor1: Vector::ZERO,
+            local_anchor2: Vector::ZERO,
+            compliance: 0.0,
+            lagrange: Vector::ZERO,
+            force: Vector::ZERO,
+        }
+    }
+
+    /// Sets the attachment point on the parent body.
+    pub fn with_local_anchor_1(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor1: anchor,
+            ..self
+        }
+    }
+
+    /// Sets the attachment point on the body of the link.
+    pub fn with_local_anchor_2(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor2: anchor,
+            ..self
+        }
+    }
+
+    /// Sets the link's compliance (inverse of stiffness, meters / Newton).
+    pub fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+}
+
+impl MapEntities for MultibodyLink {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.parent = entity_mapper.map_entity(self.parent);
+    }
+}
+
+/// A block of the articulation's constraint matrix, with one row and column per constrained axis.
+#[cfg(feature = "2d")]
+type Block = Matrix2;
+/// A block of the articulation's constraint matrix, with one row and column per constrained axis.
+#[cfg(feature = "3d")]
+type Block = Matrix3;
+
+/// A link of an articulation attached to a body, with the sign of the link's constraint for the body
+/// and the world-space offset of the attachment point.
+type Attachment = (usize, Scalar, Vector);
+
+/// Solves the point constraints of an articulation as one unit.
+///
+/// The `links` must be sorted so that each link comes before its parent link, for example by descending depth.
+/// The linear system `(J M⁻¹ Jᵀ + α̃) Δλ = -C - α̃λ` of the constraints is solved with a sparse block `LDLᵀ`
+/// factorization that eliminates the links from the leaves to the root. The links attached to the same body
+/// are already coupled with each other, so the elimination doesn't create new non-zero blocks
+/// and the cost is linear in the number of links for chains and other trees with a bounded number of children.
+pub(crate) fn solve_articulation(
+    links: &mut [(Entity, MultibodyLink)],
+    bodies: &mut Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    dt: Scalar,
+) {
+    let n = links.len();
+
+    // Compute the attachment points and constraint errors
+    let mut attachments = HashMap::<Entity, Vec<Attachment>>::default();
+    let mut errors = Vec::with_capacity(n);
+
+    for (i, (entity, link)) in links.iter().enumerate() {
+        let Ok(parent) = bodies.get_mut(link.parent) else {
+            errors.push(None);
+            continue;
+        };
+        let world_r1 = parent.rotation.rotate(link.local_anchor1);
+        let point1 = parent.current_position() + world_r1;
+
+        let Ok(child) = bodies.get_mut(*entity) else {
+            errors.push(None);
+            continue;
+        };
+        let world_r2 = child.rotation.rotate(link.local_anchor2);
+        let point2 = child.current_position() + world_r2;
+
+        errors.push(Some(point2 - point1));
+        attachments
+            .entry(link.parent)
+            .or_default()
+            .push((i, -1.0, world_r1));
+        attachments
+            .entry(*entity)
+            .or_default()
+            .push((i, 1.0, world_r2));
+    }
+
+    // Assemble the diagonal and off-diagonal blocks of the system
+    let mut diagonal = links
+        .iter()
+        .map(|(_, link)| Block::IDENTITY * (link.compliance / dt.powi(2)))
+        .collect::<Vec<_>>();
+    let mut off_diagonal = HashMap::<(usize, usize), Block>::default();
+    let mut neighbors = vec![Vec::<usize>::new(); n];
+
+    for (entity, attached) in attachments.iter() {
+        let Ok(body) = bodies.get_mut(*entity) else {
+            continue;
+        };
+        for &(i, sign_i, r_i) in attached {
+            for &(j, sign_j, r_j) in attached {
+                let block = body_block(&body, r_i, r_j) * (sign_i * sign_j);
+                if i == j {
+                    diagonal[i] += block;
+                } else {
+                    *off_diagonal.entry((i, j)).or_insert_with(|| {
+                        neighbors[i].push(j);
+                        Block::ZERO
+                    }) += block;
+                }
+            }
+        }
+    }
+
+    let mut rhs = links
+        .iter()
+        .zip(errors.iter())
+        .map(|((_, link), error)| {
+            error.map_or(Vector::ZERO, |error| {
+                -error - link.lagrange * (link.compliance / dt.powi(2))
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Factorize and forward substitute, eliminating the links from the leaves to the root
+    let mut inverse_diagonal = Vec::with_capacity(n);
+
+    for k in 0..n {
+        let inverse = if errors[k].is_some() && diagonal[k].determinant().abs() > Scalar::EPSILON {
+            diagonal[k].inverse()
+        } else {
+            Block::ZERO
+        };
+        inverse_diagonal.push(inverse);
+
+        let remaining = neighbors[k]
+            .iter()
+            .copied()
+            .filter(|&j| j > k)
+            .collect::<Vec<_>>();
+
+        for &j in &remaining {
+            let factor = off_diagonal[&(j, k)] * inverse;
+            let rhs_k = rhs[k];
+            rhs[j] -= factor * rhs_k;
+
+            for &l in &remaining {
+                let update = factor * off_diagonal[&(k, l)];
+                if j == l {
+                    diagonal[j] -= update;
+                } else {
+                    *off_diagonal.entry((j, l)).or_insert_with(|| {
+                        neighbors[j].push(l);
+                        Block::ZERO
+                    }) -= update;
+                }
+            }
+        }
+    }
+
+    // Back substitute from the root to the leaves
+    let mut delta_lagrange = vec![Vector::ZERO; n];
+
+    for k in (0..n).rev() {
+        let mut sum = rhs[k];
+        for &j in neighbors[k].iter().filter(|&&j| j > k) {
+            sum -= off_diagonal[&(k, j)] * delta_lagrange[j];
+        }
+        delta_lagrange[k] = inverse_diagonal[k] * sum;
+    }
+
+    for ((_, link), delta_lagrange) in links.iter_mut().zip(delta_lagrange.iter()) {
+        link.lagrange += *delta_lagrange;
+    }
+
+    // Apply the positional corrections to the bodies
+    for (entity, attached) in attachments.iter() {
+        let Ok(mut body) = bodies.get_mut(*entity) else {
+            continue;
+        };
+        if !body.rb.is_dynamic() {
+            continue;
+        }
+
+        let mut impulse = Vector::ZERO;
+        let mut angular_impulse = AngularVector::default();
+        for &(i, sign, r) in attached {
+            let p = delta_lagrange[i] * sign;
+            impulse += p;
+            #[cfg(feature = "2d")]
+            {
+                angular_impulse += r.perp_dot(p);
+            }
+            #[cfg(feature = "3d")]
+            {
+                angular_impulse += r.cross(p);
+            }
+        }
+
+        let inv_mass = body.effective_inv_mass();
+        body.accumulated_translation.0 += impulse * inv_mass;
+
+        let inv_inertia = body.effective_world_inv_inertia();
+        #[cfg(feature = "2d")]
+        {
+            *body.rotation += Rotation::from_radians(inv_inertia * angular_impulse);
+        }
+        #[cfg(feature = "3d")]
+        {
+            let delta_rot =
+                Quaternion::from_vec4(0.5 * (inv_inertia * angular_impulse).extend(0.0))
+                    * body.rotation.0;
+            *body.rotation += Rotation(delta_rot);
+
+            // Subtracting and adding quaternions can result in unnormalized rotations.
+            body.rotation.0 = body.rotation.0.normalize();
+        }
+    }
+
+    for ((_, link), error) in links.iter_mut().zip(errors.iter()) {
+        link.force = if error.is_some() {
+            link.lagrange / dt.powi(2)
+        } else {
+            Vector::ZERO
+        };
+    }
+}
+
+/// Computes the block of the inverse effective mass that maps an impulse at the offset `r_j`
+/// to the change in velocity at the offset `r_i` of the body.
+fn body_block(body: &RigidBodyQueryItem, r_i: Vector, r_j: Vector) -> Block {
+    if !body.rb.is_dynamic() {
+        return Block::ZERO;
+    }
+
+    let linear = Block::from_diagonal(body.effective_inv_mass());
+    let inv_inertia = body.effective_world_inv_inertia();
+
+    #[cfg(feature = "2d")]
+    {
+        let (perp_i, perp_j) = (r_i.perp(), r_j.perp());
+        linear + Block::from_cols(perp_i * perp_j.x, perp_i * perp_j.y) * inv_inertia
+    }
+    #[cfg(feature = "3d")]
+    {
+        linear - skew(r_i) * inv_inertia * skew(r_j)
+    }
+}
+
+/// Returns the skew-symmetric cross product matrix of `v`.
+#[cfg(feature = "3d")]
+fn skew(v: Vector) -> Block {
+    Block::from_cols(
+        Vector::new(0.0, v.z, -v.y),
+        Vector::new(-v.z, 0.0, v.x),
+        Vector::new(v.y, -v.x, 0.0),
+    )
+}
@@ -38,6 +38,28 @@ pub struct PenetrationConstraint {
     pub friction: Friction,
     /// The effective [restitution](Restitution) of the contact.
     pub restitution: Restitution,
+    /// The contact points anchored in the local spaces of the bodies for static friction.
+    ///
+    /// Anchors are used for contacts against [kinematic](RigidBody::Kinematic) bodies so that bodies
+    /// resting on moving platforms don't drift. They are stored in [`ContactAnchors`](crate::plugins::solver::ContactAnchors).
+    pub anchor: Option<ContactAnchor>,
+}
+
+/// Contact points anchored in the local spaces of two bodies in contact.
+///
+/// When a contact is anchored, static friction resists the total slip between the anchor points
+/// instead of just the relative movement of the contact points during the current substep.
+/// Because the anchors move along with the bodies, this also keeps track of the motion of moving platforms,
+/// and stacked bodies stay in place instead of slowly drifting.
+///
+/// The anchor is reset once the bodies start sliding relative to each other.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContactAnchor {
+    /// The anchor point on the first body in local coordinates.
+    pub point1: Vector,
+    /// The anchor point on the second body in local coordinates.
+    pub point2: Vector,
 }
 
 impl XpbdConstraint<2> for PenetrationConstraint {
@@ -54,12 +76,8 @@ impl XpbdConstraint<2> for PenetrationConstraint {
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
         let [body1, body2] = bodies;
 
-        let p1 = body1.current_position() + body1.rotation.rotate(self.contact.point1);
-        let p2 = body2.current_position() + body2.rotation.rotate(self.contact.point2);
-        self.contact.penetration = (p1 - p2).dot(self.contact.global_normal1(&body1.rotation));
-
         // If penetration depth is under 0, skip the collision
-        if self.contact.penetration <= Scalar::EPSILON {
+        if !self.update_penetration(body1, body2) {
             return;
         }
 
@@ -97,9 +115,38 @@ impl PenetrationConstraint {
             compliance: 0.0,
             friction: body1.friction.combine(*body2.friction),
             restitution: body1.restitution.combine(*body2.restitution),
+            anchor: None,
         }
     }
 
+    /// Solves overlap between two bodies without static friction.
+    ///
+    /// This is cheaper than a full solve, and it is used for the contacts of bodies with
+    /// a reduced [`PhysicsActivity`] on the substeps between their full solves.
+    pub fn solve_without_friction(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+
+        // If penetration depth is under 0, skip the collision
+        if !self.update_penetration(body1, body2) {
+            return;
+        }
+
+        self.solve_contact(body1, body2, dt);
+    }
+
+    /// Updates the penetration depth of the contact based on the current poses of the bodies,
+    /// returning `true` if the bodies are penetrating.
+    fn update_penetration(
+        &mut self,
+        body1: &RigidBodyQueryItem,
+        body2: &RigidBodyQueryItem,
+    ) -> bool {
+        let p1 = body1.current_position() + body1.rotation.rotate(self.contact.point1);
+        let p2 = body2.current_position() + body2.rotation.rotate(self.contact.point2);
+        self.contact.penetration = (p1 - p2).dot(self.contact.global_normal1(&body1.rotation));
+        self.contact.penetration > Scalar::EPSILON
+    }
+
     /// Solves a non-penetration constraint between two bodies.
     fn solve_contact(
         &mut self,
@@ -151,15 +198,32 @@ impl PenetrationConstraint {
         let r1 = body1.rotation.rotate(self.r1);
         let r2 = body2.rotation.rotate(self.r2);
 
+        // Compute the total slip between the anchor points if the contact is anchored
+        let anchored_slip = self.anchor.and_then(|anchor| {
+            let p1 = body1.current_position() + body1.rotation.rotate(anchor.point1);
+            let p2 = body2.current_position() + body2.rotation.rotate(anchor.point2);
+            let delta_p = p1 - p2;
+            let delta_p_tangent = delta_p - delta_p.dot(normal) * normal;
+            (delta_p_tangent.length() < self.friction.static_coefficient * penetration)
+                .then_some(delta_p_tangent)
+        });
+
+        // The bodies have started sliding, so the anchor is no longer valid
+        if anchored_slip.is_none() {
+            self.anchor = None;
+        }
+
         // Compute relative motion of the contact points and get the tangential component
-        let delta_p1 = body1.current_position() - body1.previous_position.0
-            + body1.rotation.rotate(self.contact.point1)
-            - body1.previous_rotation.rotate(self.contact.point1);
-        let delta_p2 = body2.current_position() - body2.previous_position.0
-            + body2.rotation.rotate(self.contact.point2)
-            - body2.previous_rotation.rotate(self.contact.point2);
-        let delta_p = delta_p1 - delta_p2;
-        let delta_p_tangent = delta_p - delta_p.dot(normal) * normal;
+        let delta_p_tangent = anchored_slip.unwrap_or_else(|| {
+            let delta_p1 = body1.current_position() - body1.previous_position.0
+                + body1.rotation.rotate(self.contact.point1)
+                - body1.previous_rotation.rotate(self.contact.point1);
+            let delta_p2 = body2.current_position() - body2.previous_position.0
+                + body2.rotation.rotate(self.contact.point2)
+                - body2.previous_rotation.rotate(self.contact.point2);
+            let delta_p = delta_p1 - delta_p2;
+            delta_p - delta_p.dot(normal) * normal
+        });
 
         // Compute magnitude of relative tangential movement and get normalized tangent vector
         let sliding_len = delta_p_tangent.length();
@@ -30,28 +30,40 @@ pub trait PositionConstraint: XpbdConstraint<2> {
         let inv_inertia1 = body1.effective_world_inv_inertia();
         let inv_inertia2 = body2.effective_world_inv_inertia();
 
+        // Rotation-locked bodies skip the angular terms entirely.
+        #[cfg(feature = "2d")]
+        let (rotates1, rotates2) = (!body1.is_rotation_locked(), !body2.is_rotation_locked());
+        #[cfg(feature = "3d")]
+        let (rotates1, rotates2) = (true, true);
+
         // Apply positional and rotational updates
         if body1.rb.is_dynamic() && body1.dominance() <= body2.dominance() {
             body1.accumulated_translation.0 += p * inv_mass1;
-            *body1.rotation += Self::get_delta_rot(rot1, inv_inertia1, r1, p);
-
-            #[cfg(feature = "3d")]
-            {
-                // In 3D, subtracting quaternions like above can result in unnormalized rotations,
-                // which causes stability issues (see #235) and panics when trying to rotate unit vectors.
-                // TODO: It would be nice to avoid normalization if possible.
-                //       Maybe the math above can be done in a way that keeps rotations normalized?
-                body1.rotation.0 = body1.rotation.0.normalize();
+
+            if rotates1 {
+                *body1.rotation += Self::get_delta_rot(rot1, inv_inertia1, r1, p);
+
+                #[cfg(feature = "3d")]
+                {
+                    // In 3D, subtracting quaternions like above can result in unnormalized rotations,
+                    // which causes stability issues (see #235) and panics when trying to rotate unit vectors.
+                    // TODO: It would be nice to avoid normalization if possible.
+                    //       Maybe the math above can be done in a way that keeps rotations normalized?
+                    body1.rotation.0 = body1.rotation.0.normalize();
+                }
             }
         }
         if body2.rb.is_dynamic() && body2.dominance() <= body1.dominance() {
             body2.accumulated_translation.0 -= p * inv_mass2;
-            *body2.rotation -= Self::get_delta_rot(rot2, inv_inertia2, r2, p);
 
-            #[cfg(feature = "3d")]
-            {
-                // See comments for `body1` above.
-                body2.rotation.0 = body2.rotation.0.normalize();
+            if rotates2 {
+                *body2.rotation -= Self::get_delta_rot(rot2, inv_inertia2, r2, p);
+
+                #[cfg(feature = "3d")]
+                {
+                    // See comments for `body1` above.
+                    body2.rotation.0 = body2.rotation.0.normalize();
+                }
             }
         }
 
@@ -67,7 +79,10 @@ pub trait PositionConstraint: XpbdConstraint<2> {
         r: Vector,
         n: Vector,
     ) -> Scalar {
-        if body.rb.is_dynamic() {
+        if body.rb.is_dynamic() && body.is_rotation_locked() {
+            // Fast path: rotation-locked bodies only have a linear contribution.
+            body.inverse_mass.0
+        } else if body.rb.is_dynamic() {
             body.inverse_mass.0 + body.inverse_inertia.0 * r.perp_dot(n).powi(2)
         } else {
             // Static and kinematic bodies are a special case, where 0.0 can be thought of as infinite mass.
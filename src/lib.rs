@@ -62,10 +62,13 @@
 )]
 //! | `bevy_scene`           | Enables [`ColliderConstructorHierarchy`] to wait until a [`Scene`] has loaded before processing it.                              | Yes                     |
 //! | `debug-plugin`         | Enables physics debug rendering using the [`PhysicsDebugPlugin`]. The plugin must be added separately.                           | Yes                     |
+//! | `bevy_picking`         | Enables picking colliders with `bevy_picking` using the [`PhysicsPickingPlugin`]. The plugin must be added separately.         | No                      |
+//! | `pbd_fluid`            | Enables particle simulation for fluids and granular materials using the [`FluidPlugin`] and [`GranularPlugin`]. The plugins must be added separately. | No                      |
 //! | `enhanced-determinism` | Enables increased determinism.                                                                                                   | No                      |
 //! | `parallel`             | Enables some extra multithreading, which improves performance for larger simulations but can add some overhead for smaller ones. | Yes                     |
 //! | `simd`                 | Enables [SIMD] optimizations.                                                                                                    | No                      |
 //! | `serialize`            | Enables support for serialization and deserialization using Serde.                                                               | No                      |
+//! | `test-utils`           | Enables the [`test_utils`] module for writing headless physics tests.                                                            | No                      |
 //!
 //! [SIMD]: https://en.wikipedia.org/wiki/Single_instruction,_multiple_data
 //!
@@ -158,8 +161,13 @@
 //!     - [Prismatic joint](PrismaticJoint)
 //!     - [Revolute joint](RevoluteJoint)
 //!     - [Spherical joint](SphericalJoint)
-//!
-//! Joint motors and articulations are not supported yet, but they will be implemented in a future release.
+//!     - [Spline drive joint](SplineDriveJoint)
+//!     - [Suspension constraint](SuspensionConstraint)
+//!     - [Generic joint](GenericJoint)
+//!     - [Gear joint](GearJoint)
+//!     - [Pulley joint](PulleyJoint)
+//! - [Joint motors](MotorTarget)
+//! - [Articulations](MultibodyRoot) for ragdolls and robot arms
 //!
 //! ### Spatial queries
 //!
@@ -190,6 +198,7 @@
 //! - [Configure the schedule used for running physics](PhysicsPlugins#custom-schedule)
 //! - [Pausing, resuming and stepping physics](Physics#pausing-resuming-and-stepping-physics)
 //! - [Usage on servers](#can-the-engine-be-used-on-servers)
+//! - [Running physics in parallel with rendering](#can-physics-run-in-parallel-with-rendering)
 //!
 //! ### Architecture
 //!
@@ -383,6 +392,21 @@
 //! Note that while Bevy XPBD should be locally deterministic, it can produce slightly different results on different
 //! machines.
 //!
+//! ### Can physics run in parallel with rendering?
+//!
+//! Yes, when Bevy's `PipelinedRenderingPlugin` is enabled, which is the case by default on multi-threaded
+//! platforms with `DefaultPlugins`. Rendering then runs in its own sub-app on a separate thread: while frame N
+//! is being rendered, the main app already runs `Update` and the physics step for frame N+1.
+//! The render world receives a copy of the `GlobalTransform`s in the extraction step between the two,
+//! so the data is effectively double-buffered.
+//!
+//! The tradeoff is one frame of latency: what is on screen is always one physics step behind the simulation.
+//! Systems that react to input should therefore avoid waiting on physics results for visual feedback where possible.
+//!
+//! On platforms without pipelined rendering, like single-threaded web builds, physics and rendering run one after
+//! the other. Running the physics step on a separate compute task in parallel with the rest of the main app is not
+//! supported, because the [`PhysicsSchedule`] operates directly on the components of the main world.
+//!
 //! ### Something else?
 //!
 //! Physics engines are very large and Bevy XPBD is young, so stability issues and bugs are to be expected.
@@ -549,30 +573,116 @@ pub extern crate parry3d as parry;
 #[cfg(all(feature = "3d", feature = "parry-f64"))]
 pub extern crate parry3d_f64 as parry;
 
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod commands;
 pub mod components;
 pub mod constraints;
 pub mod math;
 pub mod plugins;
 pub mod resources;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 
 /// Re-exports common components, bundles, resources, plugins and types.
 pub mod prelude {
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::commands::*;
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::character_controller::{
+        CharacterCollision, CharacterCollisionKind, KinematicCharacterController,
+        KinematicCharacterControllerOutput,
+    };
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::collider_morph::ColliderMorph;
     #[cfg(feature = "debug-plugin")]
     pub use crate::plugins::debug::*;
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::destruction::{
+        voronoi_fracture, voronoi_fracture_points, Destructible, DestructibleFractured, Fragment,
+    };
+    #[cfg(all(
+        feature = "pbd_fluid",
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::fluid::{Fluid, FluidConfig, Particle};
+    #[cfg(all(
+        feature = "pbd_fluid",
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::granular::{GranularConfig, GranularMaterial};
+    #[cfg(all(
+        feature = "bevy_picking",
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::picking::{
+        PhysicsPickable, PhysicsPickingFilter, PhysicsPickingSettings,
+    };
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::projectile_penetration::{
+        PenetratingProjectile, PenetrationHit, PenetrationResistance,
+    };
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::validation::{
+        PhysicsValidationConfig, PhysicsValidationIssue, PhysicsValidationIssueKind,
+    };
     pub use crate::{
         components::*,
         constraints::{joints::*, *},
         plugins::{
+            activity_scaling::{ActivityListener, ActivityScalingConfig, PhysicsActivity},
+            bone_collider::BoneCollider,
             collision::{
                 broad_phase::BroadCollisionPairs,
-                contact_reporting::{Collision, CollisionEnded, CollisionStarted},
-                narrow_phase::NarrowPhaseConfig,
+                contact_reporting::{
+                    Collision, CollisionEnded, CollisionStarted, CollisionStartedAt,
+                },
+                narrow_phase::{NarrowPhaseConfig, NarrowPhaseStep},
+                support::{ContactSupport, SupportPolygon},
                 *,
             },
+            divergence::{DivergenceDetector, StepDivergence},
+            editor_manipulation::{EditorManipulation, ManipulatedBody},
+            energy::PhysicsEnergyDiagnostics,
+            force_log::{AppliedForce, ExternalForceApplied, ForceApplier, ForceSource},
+            hit_detection::{HitEvent, Hitbox, Hurtbox},
+            integrator::{PendingImpulses, PosePredictor},
             prepare::{init_transforms, update_mass_properties, PrepareConfig, PreparePlugin},
+            settle::SettleSimulation,
             setup::*,
-            solver::solve_constraint,
+            sleeping::{
+                BodyCameToRest, BodyStartedMoving, IslandId, PhysicsIsland, PhysicsIslands,
+                PhysicsPersistence, Pose,
+            },
+            solver::{
+                solve_constraint, JointSolveOrder, MassRatioDiagnostics, MassRatioStabilization,
+                SolverBodies,
+            },
             spatial_query::*,
+            sync::CorrectionSmoothing,
             *,
         },
         resources::*,
@@ -640,6 +750,48 @@ pub struct SubstepSchedule;
 ///     });
 /// }
 /// ```
+///
+/// ## Contact modification
+///
+/// The schedule runs after the narrow phase and before any contact constraints are generated,
+/// so it can also be used for modifying individual contacts:
+///
+/// - Contacts can be removed with [`Contacts::retain_contacts`].
+/// - Contact normals can be replaced with [`ContactManifold::set_global_normal`],
+/// for example to avoid ghost collisions at internal edges.
+/// - Friction and restitution can be scaled per pair with [`Contacts::friction_scale`]
+/// and [`Contacts::restitution_scale`].
+///
+/// Below is an example of one-way platforms that bodies can jump through from below.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// #[derive(Component)]
+/// struct OneWayPlatform;
+///
+/// fn one_way_platforms(mut collisions: ResMut<Collisions>, platforms: Query<(), With<OneWayPlatform>>) {
+///     for contacts in collisions.iter_mut() {
+///         let platform_is_first = platforms.contains(contacts.entity1);
+///         if !platform_is_first && !platforms.contains(contacts.entity2) {
+///             continue;
+///         }
+///
+///         // Only keep contacts that push the other body up and out of the platform.
+///         // The normals are in the local space of the colliders, so the platform's up direction is `Vector::Y`.
+///         contacts.retain_contacts(|contact| {
+///             let normal = if platform_is_first {
+///                 contact.normal1
+///             } else {
+///                 contact.normal2
+///             };
+///             normal.dot(Vector::Y) > 0.5
+///         });
+///     }
+/// }
+/// ```
 #[derive(Debug, Hash, PartialEq, Eq, Clone, ScheduleLabel)]
 pub struct PostProcessCollisions;
 
@@ -770,7 +922,8 @@ pub enum SubstepSet {
     ///
     /// See [`SolverPlugin`].
     SolveVelocities,
-    /// Contact impulses computed by the solver are stored in contacts in [`Collisions`].
+    /// Contact impulses computed by the solver are stored in contacts in [`Collisions`],
+    /// and joint impulses are accumulated for [breakable joints](BreakableJoint).
     ///
     /// See [`SolverPlugin`].
     StoreImpulses,
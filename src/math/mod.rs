@@ -20,6 +20,13 @@ pub(crate) type Ray = Ray2d;
 #[cfg(feature = "3d")]
 pub(crate) type Ray = Ray3d;
 
+/// The angular vector type chosen based on the dimension, a scalar in 2D and a scaled axis in 3D.
+#[cfg(feature = "2d")]
+pub(crate) type AngularVector = Scalar;
+/// The angular vector type chosen based on the dimension, a scalar in 2D and a scaled axis in 3D.
+#[cfg(feature = "3d")]
+pub(crate) type AngularVector = Vector;
+
 // Note: This is called `Dir` instead of `Direction` because Bevy has a conflicting `Direction` type.
 /// The direction type chosen based on the dimension.
 #[cfg(feature = "2d")]
@@ -21,6 +21,8 @@ pub type Vector2 = Vec2;
 /// The vector type used by Bevy XPBD. This is always a 3D vector regardless of the chosen dimension.
 pub type Vector3 = Vec3;
 
+/// The 2x2 matrix type used by Bevy XPBD.
+pub type Matrix2 = Mat2;
 /// The 3x3 matrix type used by Bevy XPBD.
 pub type Matrix3 = Mat3;
 /// The quaternion type used by Bevy XPBD.
@@ -0,0 +1,214 @@
+//! Scales the simulation quality of [islands](PhysicsIsland) based on their distance to [listeners](ActivityListener).
+//!
+//! See [`PhysicsActivityScalingPlugin`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// An automated level of detail plugin for the simulation.
+///
+/// Each frame, the distance from every [island](PhysicsIsland) to the closest [`ActivityListener`]
+/// is computed, and the bodies in islands that are far away are given a [`PhysicsActivity`] component.
+/// Far-away bodies fall asleep more eagerly, and the static friction of their contacts is only solved on some
+/// of the substeps, which makes them cheaper to simulate at the cost of accuracy. Contact overlap is still
+/// solved on every substep, so far-away bodies don't sink into the ground.
+///
+/// The distances used for the scaling are configured using the [`ActivityScalingConfig`] resource.
+/// If there are no listeners, all bodies are simulated at full quality.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually.
+/// It requires the [`SleepingPlugin`] for computing islands.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             PhysicsActivityScalingPlugin,
+///         ))
+///         .add_systems(Startup, setup)
+///         .run();
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     // Simulate bodies close to the player at full quality
+///     commands.spawn((
+///         RigidBody::Kinematic,
+///         Position::default(),
+///         ActivityListener,
+///     ));
+/// }
+/// ```
+pub struct PhysicsActivityScalingPlugin;
+
+impl Plugin for PhysicsActivityScalingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActivityScalingConfig>()
+            .register_type::<ActivityScalingConfig>()
+            .register_type::<ActivityListener>()
+            .register_type::<PhysicsActivity>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                update_physics_activity
+                    .after(sleeping::send_activity_events)
+                    .in_set(PhysicsStepSet::Sleeping),
+            );
+    }
+}
+
+/// A marker component for entities around which the simulation should run at full quality,
+/// like the player or the camera. Used by the [`PhysicsActivityScalingPlugin`].
+///
+/// The distance to the listener is measured from its [`Position`] if it has one,
+/// and otherwise from its `GlobalTransform`.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct ActivityListener;
+
+/// Configures how the [`PhysicsActivityScalingPlugin`] scales the simulation quality based on distance.
+///
+/// Islands closer than [`near_distance`](Self::near_distance) to a listener are simulated at full quality.
+/// Beyond that, the quality is reduced linearly until [`far_distance`](Self::far_distance),
+/// after which the maximum reduction is used.
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Resource)]
+pub struct ActivityScalingConfig {
+    /// The distance within which islands are simulated at full quality.
+    pub near_distance: Scalar,
+    /// The distance at and beyond which the maximum reduction in quality is used.
+    pub far_distance: Scalar,
+    /// The largest multiplier applied to the [`SleepingThreshold`] for far-away bodies.
+    pub max_sleep_threshold_scale: Scalar,
+    /// The largest interval at which contacts of far-away bodies are fully solved, in substeps.
+    ///
+    /// For example, a value of 4 means that static friction is only solved on every fourth substep,
+    /// and only the contact overlap is solved on the other substeps.
+    /// If the interval is larger than the [`SubstepCount`], contacts are fully solved once per frame.
+    pub max_substep_interval: u32,
+}
+
+impl Default for ActivityScalingConfig {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "2d")]
+            near_distance: 1000.0,
+            #[cfg(feature = "3d")]
+            near_distance: 50.0,
+            #[cfg(feature = "2d")]
+            far_distance: 5000.0,
+            #[cfg(feature = "3d")]
+            far_distance: 250.0,
+            max_sleep_threshold_scale: 4.0,
+            max_substep_interval: 12,
+        }
+    }
+}
+
+/// The reduced simulation quality of a dynamic body, computed by the [`PhysicsActivityScalingPlugin`]
+/// based on the distance from the body's [island](PhysicsIsland) to the closest [`ActivityListener`].
+///
+/// Bodies that are simulated at full quality don't have this component.
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[reflect(Component)]
+pub struct PhysicsActivity {
+    /// The multiplier applied to the [`SleepingThreshold`] of the body.
+    pub sleep_threshold_scale: Scalar,
+    /// The interval at which contacts of the body are fully solved, in substeps.
+    ///
+    /// On the other substeps, only the overlap of the contacts is solved, without static friction.
+    pub substep_interval: u32,
+}
+
+impl Default for PhysicsActivity {
+    fn default() -> Self {
+        Self {
+            sleep_threshold_scale: 1.0,
+            substep_interval: 1,
+        }
+    }
+}
+
+/// Computes the [`PhysicsActivity`] of each dynamic body based on the distance
+/// from its island to the closest [`ActivityListener`].
+fn update_physics_activity(
+    mut commands: Commands,
+    listeners: Query<(Option<&Position>, Option<&GlobalTransform>), With<ActivityListener>>,
+    mut bodies: Query<(&Position, Option<&mut PhysicsActivity>)>,
+    scaled_bodies: Query<Entity, With<PhysicsActivity>>,
+    islands: Res<PhysicsIslands>,
+    config: Res<ActivityScalingConfig>,
+) {
+    let listener_positions = listeners
+        .iter()
+        .filter_map(|(pos, transform)| {
+            pos.map(|pos| pos.0).or_else(|| {
+                #[cfg(feature = "2d")]
+                let translation = transform?.translation().truncate().adjust_precision();
+                #[cfg(feature = "3d")]
+                let translation = transform?.translation().adjust_precision();
+                Some(translation)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let range = (config.far_distance - config.near_distance).max(Scalar::EPSILON);
+
+    for (_, island) in islands.iter() {
+        // The distance from the closest body in the island to the closest listener
+        let distance = if listener_positions.is_empty() {
+            0.0
+        } else {
+            island
+                .bodies
+                .iter()
+                .filter_map(|entity| bodies.get(*entity).ok())
+                .flat_map(|(pos, _)| {
+                    listener_positions
+                        .iter()
+                        .map(move |listener| pos.distance(*listener))
+                })
+                .fold(Scalar::MAX, Scalar::min)
+        };
+
+        let t = ((distance - config.near_distance) / range).clamp(0.0, 1.0);
+        let activity = PhysicsActivity {
+            sleep_threshold_scale: 1.0 + t * (config.max_sleep_threshold_scale - 1.0),
+            substep_interval: 1
+                + (t * config.max_substep_interval.saturating_sub(1) as Scalar).round() as u32,
+        };
+
+        for entity in island.bodies.iter() {
+            let Ok((_, current_activity)) = bodies.get_mut(*entity) else {
+                continue;
+            };
+            match current_activity {
+                Some(mut current) if t > 0.0 => {
+                    current.set_if_neq(activity);
+                }
+                Some(_) => {
+                    commands.entity(*entity).remove::<PhysicsActivity>();
+                }
+                None if t > 0.0 => {
+                    commands.entity(*entity).try_insert(activity);
+                }
+                None => (),
+            }
+        }
+    }
+
+    // Bodies that are no longer in any island, like bodies that became static, use full quality.
+    for entity in &scaled_bodies {
+        if islands.island_id(entity).is_none() {
+            commands.entity(entity).remove::<PhysicsActivity>();
+        }
+    }
+}
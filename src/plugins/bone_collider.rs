@@ -0,0 +1,148 @@
+//! Keeps colliders attached to the bones of animated skeletons, like hitboxes and hurtboxes.
+//!
+//! See [`BoneColliderPlugin`].
+
+use crate::{prelude::*, prepare::PrepareSet};
+use bevy::prelude::*;
+
+/// A plugin that keeps [kinematic](RigidBody::Kinematic) colliders attached to the bones of animated skeletons.
+///
+/// Each frame, the animated global transform of the [bone](BoneCollider::bone) is computed from its hierarchy,
+/// and the velocities of the collider are set so that it reaches the bone during the next physics step.
+/// Because the collider is moved using velocities instead of being teleported, contacts and sensors
+/// see the actual motion of the bone, which is important for fast attacks in action games.
+///
+/// The collider entity should not be a child of the bone. It is typically a [`Sensor`]
+/// used as a hitbox or hurtbox.
+///
+/// Animations should be applied before [`PhysicsSet::Prepare`], which is where the bone transforms are read.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn attach_hitbox(mut commands: Commands, bones: Query<(Entity, &Name)>) {
+///     for (bone, name) in &bones {
+///         if name.as_str() == "RightHand" {
+///             commands.spawn((
+///                 RigidBody::Kinematic,
+#[cfg_attr(feature = "2d", doc = "                Collider::circle(0.1),")]
+#[cfg_attr(feature = "3d", doc = "                Collider::sphere(0.1),")]
+///                 Sensor,
+///                 BoneCollider::new(bone),
+///                 SpatialBundle::default(),
+///             ));
+///         }
+///     }
+/// }
+/// ```
+pub struct BoneColliderPlugin;
+
+impl Plugin for BoneColliderPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<BoneCollider>().add_systems(
+            PostUpdate,
+            follow_bones
+                .in_set(PhysicsSet::Prepare)
+                .after(PrepareSet::InitTransforms),
+        );
+    }
+}
+
+/// Attaches a [kinematic](RigidBody::Kinematic) collider to a bone of an animated skeleton.
+/// Used by the [`BoneColliderPlugin`].
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[reflect(Component)]
+pub struct BoneCollider {
+    /// The bone entity that the collider follows.
+    pub bone: Entity,
+    /// The transform of the collider relative to the bone.
+    pub offset: Transform,
+}
+
+impl BoneCollider {
+    /// Creates a new [`BoneCollider`] that follows the given bone without an offset.
+    pub fn new(bone: Entity) -> Self {
+        Self {
+            bone,
+            offset: Transform::IDENTITY,
+        }
+    }
+
+    /// Sets the transform of the collider relative to the bone.
+    pub fn with_offset(self, offset: Transform) -> Self {
+        Self { offset, ..self }
+    }
+}
+
+/// Sets the velocities of bone colliders so that they reach their bones during the next physics step.
+#[allow(clippy::type_complexity)]
+fn follow_bones(
+    mut colliders: Query<(
+        &BoneCollider,
+        &Position,
+        &Rotation,
+        &mut LinearVelocity,
+        &mut AngularVelocity,
+    )>,
+    hierarchy: Query<(&Transform, Option<&Parent>)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs <= 0.0 {
+        return;
+    }
+
+    for (bone_collider, position, rotation, mut lin_vel, mut ang_vel) in &mut colliders {
+        let Some(bone_transform) = animated_global_transform(bone_collider.bone, &hierarchy) else {
+            continue;
+        };
+        let target = bone_transform * bone_collider.offset;
+
+        #[cfg(feature = "2d")]
+        {
+            let target_position = target.translation.truncate().adjust_precision();
+            let target_rotation = Rotation::from(target);
+            lin_vel.0 = (target_position - position.0) / delta_secs;
+            ang_vel.0 = target_rotation.mul(rotation.inverse()).as_radians() / delta_secs;
+        }
+        #[cfg(feature = "3d")]
+        {
+            let target_position = target.translation.adjust_precision();
+            let target_rotation = Rotation::from(target);
+            lin_vel.0 = (target_position - position.0) / delta_secs;
+
+            // Use the shortest rotation from the current rotation to the target.
+            let mut delta_rotation = target_rotation.0 * rotation.0.inverse();
+            if delta_rotation.w < 0.0 {
+                delta_rotation = -delta_rotation;
+            }
+            let (axis, angle) = delta_rotation.to_axis_angle();
+            ang_vel.0 = axis * angle / delta_secs;
+        }
+    }
+}
+
+/// Computes the global transform of an entity from the local transforms of its hierarchy,
+/// so that animations applied during the current frame are taken into account
+/// before transform propagation.
+fn animated_global_transform(
+    entity: Entity,
+    hierarchy: &Query<(&Transform, Option<&Parent>)>,
+) -> Option<Transform> {
+    let (transform, mut parent) = hierarchy.get(entity).ok()?;
+    let mut global = *transform;
+    while let Some(parent_entity) = parent {
+        let Ok((parent_transform, grandparent)) = hierarchy.get(parent_entity.get()) else {
+            break;
+        };
+        global = *parent_transform * global;
+        parent = grandparent;
+    }
+    Some(global)
+}
@@ -0,0 +1,518 @@
+//! Kinematic character controllers that move and slide along the environment.
+//!
+//! See [`CharacterControllerPlugin`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A plugin for kinematic character controllers that move using [shapecasts](spatial_query#shapecasting)
+/// against the environment.
+///
+/// A [`KinematicCharacterController`] is added to an entity with a [kinematic](RigidBody::Kinematic) rigid body
+/// and a [`Collider`]. Each physics step, the controller moves the collider shape by its
+/// [desired velocity](KinematicCharacterController::velocity) using move-and-slide:
+///
+/// - When the shape hits something, the rest of the motion slides along the surface.
+/// - Slopes steeper than the [maximum slope angle](KinematicCharacterController::max_slope_angle) can't be climbed.
+/// - Obstacles lower than the [step offset](KinematicCharacterController::step_offset) are stepped over.
+/// - The character [snaps to the ground](KinematicCharacterController::snap_to_ground) when walking
+/// down slopes and stairs.
+/// - Characters standing on moving [kinematic](RigidBody::Kinematic) or [dynamic](RigidBody::Dynamic)
+/// bodies are carried along by them, so moving platforms work out of the box.
+///
+/// The resulting motion is applied by setting the [`LinearVelocity`] of the body, so the character moves
+/// through the normal kinematic body pipeline and pushes [dynamic](RigidBody::Dynamic) bodies out of the way.
+/// Dynamic bodies and [sensors](Sensor) don't block the character.
+///
+/// The ground and the walls and ceilings that the character touched are reported
+/// in the [`KinematicCharacterControllerOutput`] component, which is added automatically.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         RigidBody::Kinematic,
+///         Collider::capsule(1.0, 0.4),
+///         KinematicCharacterController::default(),
+///     ));
+/// }
+///
+/// fn movement(
+///     time: Res<Time>,
+///     keyboard_input: Res<ButtonInput<KeyCode>>,
+///     mut characters: Query<(&mut KinematicCharacterController, &KinematicCharacterControllerOutput)>,
+/// ) {
+///     let delta_secs = time.delta_seconds_f64().adjust_precision();
+///     for (mut controller, output) in &mut characters {
+///         let direction = keyboard_input.pressed(KeyCode::KeyD) as i32 - keyboard_input.pressed(KeyCode::KeyA) as i32;
+///         controller.velocity.x = direction as Scalar * 5.0;
+///
+///         // Apply gravity, and jump when on the ground
+///         if output.grounded {
+///             controller.velocity.y = if keyboard_input.just_pressed(KeyCode::Space) { 6.0 } else { 0.0 };
+///         } else {
+///             controller.velocity.y -= 9.81 * delta_secs;
+///         }
+///
+///         // Stop moving up when hitting a ceiling
+///         if output.touching_ceiling() {
+///             controller.velocity.y = controller.velocity.y.min(0.0);
+///         }
+///     }
+/// }
+/// ```
+pub struct CharacterControllerPlugin;
+
+impl Plugin for CharacterControllerPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<KinematicCharacterController>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                move_character_controllers
+                    .after(PhysicsStepSet::BroadPhase)
+                    .after(integrator::apply_impulses)
+                    .before(PhysicsStepSet::Substeps),
+            );
+    }
+}
+
+/// A kinematic character controller that moves a [kinematic](RigidBody::Kinematic) body
+/// with move-and-slide. Used by the [`CharacterControllerPlugin`].
+///
+/// The controller uses the [`Collider`] of the entity for its shapecasts. The results of the latest
+/// move are stored in the [`KinematicCharacterControllerOutput`] component.
+///
+/// See the [`CharacterControllerPlugin`] for an example.
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct KinematicCharacterController {
+    /// The desired velocity of the character, typically set by gameplay code based on input.
+    ///
+    /// Gravity is not applied automatically, so it should be included in the velocity.
+    /// The velocity of a moving platform that the character is standing on is added to this.
+    pub velocity: Vector,
+    /// The up direction of the character, used for detecting the ground and for stepping.
+    ///
+    /// The default is [`Vector::Y`].
+    pub up: Vector,
+    /// The maximum angle in radians between the up direction and the normal of a surface
+    /// for the surface to count as ground that the character can walk on.
+    ///
+    /// The default is 45 degrees.
+    pub max_slope_angle: Scalar,
+    /// The maximum height of obstacles, like stairs, that the character can step over when on the ground.
+    ///
+    /// The default is `0.0`, which disables stepping.
+    pub step_offset: Scalar,
+    /// The maximum distance that the character is moved down to stay on the ground when walking
+    /// down slopes and stairs. Snapping is skipped when the character is moving up, like when jumping.
+    ///
+    /// The default is `0.2`.
+    pub snap_to_ground: Scalar,
+    /// The distance that the character keeps from the environment to avoid getting stuck in it.
+    ///
+    /// The default is `0.01`.
+    pub skin_width: Scalar,
+    /// The maximum number of times that the character slides along surfaces in a single move.
+    ///
+    /// The default is `4`.
+    pub max_slide_iterations: u32,
+}
+
+impl Default for KinematicCharacterController {
+    fn default() -> Self {
+        Self {
+            velocity: Vector::ZERO,
+            up: Vector::Y,
+            max_slope_angle: PI / 4.0,
+            step_offset: 0.0,
+            snap_to_ground: 0.2,
+            skin_width: 0.01,
+            max_slide_iterations: 4,
+        }
+    }
+}
+
+impl KinematicCharacterController {
+    /// Sets the up direction of the character.
+    pub fn with_up(mut self, up: Vector) -> Self {
+        self.up = up;
+        self
+    }
+
+    /// Sets the maximum angle in radians of slopes that the character can walk on.
+    pub fn with_max_slope_angle(mut self, max_slope_angle: Scalar) -> Self {
+        self.max_slope_angle = max_slope_angle;
+        self
+    }
+
+    /// Sets the maximum height of obstacles that the character can step over.
+    pub fn with_step_offset(mut self, step_offset: Scalar) -> Self {
+        self.step_offset = step_offset;
+        self
+    }
+
+    /// Sets the maximum distance that the character is moved down to stay on the ground.
+    pub fn with_snap_to_ground(mut self, snap_to_ground: Scalar) -> Self {
+        self.snap_to_ground = snap_to_ground;
+        self
+    }
+
+    /// Sets the distance that the character keeps from the environment.
+    pub fn with_skin_width(mut self, skin_width: Scalar) -> Self {
+        self.skin_width = skin_width;
+        self
+    }
+
+    /// Returns the kind of a collision with a surface that has the given world-space normal.
+    fn collision_kind(&self, normal: Vector) -> CharacterCollisionKind {
+        let min_cos = self.max_slope_angle.cos();
+        let dot = normal.dot(self.up.normalize_or_zero());
+        if dot >= min_cos - Scalar::EPSILON {
+            CharacterCollisionKind::Ground
+        } else if dot <= -min_cos + Scalar::EPSILON {
+            CharacterCollisionKind::Ceiling
+        } else {
+            CharacterCollisionKind::Wall
+        }
+    }
+}
+
+/// The results of the latest move of a [`KinematicCharacterController`].
+///
+/// This is added automatically by the [`CharacterControllerPlugin`].
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct KinematicCharacterControllerOutput {
+    /// True if the character is standing on ground that isn't too steep.
+    pub grounded: bool,
+    /// The ground that the character is standing on, if any.
+    pub ground: Option<CharacterCollision>,
+    /// The translation that the character wanted to move by during the latest physics step,
+    /// including the motion of the ground.
+    pub desired_translation: Vector,
+    /// The translation that the character actually moved by during the latest physics step.
+    pub effective_translation: Vector,
+    /// The ground, walls and ceilings that the character hit during the latest physics step.
+    pub collisions: Vec<CharacterCollision>,
+}
+
+impl KinematicCharacterControllerOutput {
+    /// Returns true if the character hit a wall during the latest physics step.
+    pub fn touching_wall(&self) -> bool {
+        self.collisions
+            .iter()
+            .any(|collision| collision.kind == CharacterCollisionKind::Wall)
+    }
+
+    /// Returns true if the character hit a ceiling during the latest physics step.
+    pub fn touching_ceiling(&self) -> bool {
+        self.collisions
+            .iter()
+            .any(|collision| collision.kind == CharacterCollisionKind::Ceiling)
+    }
+}
+
+/// A collision of a [`KinematicCharacterController`] with the environment,
+/// stored in [`KinematicCharacterControllerOutput`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CharacterCollision {
+    /// The entity of the collider that was hit.
+    pub entity: Entity,
+    /// The world-space point of contact.
+    pub point: Vector,
+    /// The world-space normal of the surface that was hit, pointing towards the character.
+    pub normal: Vector,
+    /// The kind of the surface that was hit.
+    pub kind: CharacterCollisionKind,
+}
+
+/// The kind of surface in a [`CharacterCollision`], based on the
+/// [maximum slope angle](KinematicCharacterController::max_slope_angle) of the controller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CharacterCollisionKind {
+    /// A surface that the character can stand on.
+    Ground,
+    /// A surface that is too steep to stand on.
+    Wall,
+    /// A surface facing down, like the bottom of a platform.
+    Ceiling,
+}
+
+/// The shapecasts of a single character.
+struct CharacterCaster<'a, 'w, 's> {
+    pipeline: &'a SpatialQueryPipeline,
+    sensors: &'a Query<'w, 's, (), With<Sensor>>,
+    controller: &'a KinematicCharacterController,
+    shape: &'a Collider,
+    rotation: Rotation,
+    filter: SpatialQueryFilter,
+}
+
+impl CharacterCaster<'_, '_, '_> {
+    /// Casts the shape and returns the closest blocking collision and its distance, ignoring sensors.
+    fn cast(
+        &self,
+        origin: Vector,
+        direction: Vector,
+        max_distance: Scalar,
+    ) -> Option<(Scalar, CharacterCollision)> {
+        let direction = Dir::new(direction.f32()).ok()?;
+        #[cfg(feature = "2d")]
+        let shape_rotation = self.rotation.as_radians();
+        #[cfg(feature = "3d")]
+        let shape_rotation = self.rotation.0;
+
+        let mut closest = None;
+        self.pipeline.shape_hits_callback(
+            self.shape,
+            origin,
+            shape_rotation,
+            direction,
+            max_distance,
+            true,
+            self.filter.clone(),
+            |hit| {
+                if self.sensors.contains(hit.entity) {
+                    return true;
+                }
+                let position = origin + direction.adjust_precision() * hit.time_of_impact;
+                let normal = -self.rotation.rotate(hit.normal2);
+                closest = Some((
+                    hit.time_of_impact,
+                    CharacterCollision {
+                        entity: hit.entity,
+                        point: position + self.rotation.rotate(hit.point2),
+                        normal,
+                        kind: self.controller.collision_kind(normal),
+                    },
+                ));
+                false
+            },
+        );
+        closest
+    }
+
+    /// Tries to step over an obstacle by moving up by the step offset, forward by `horizontal`,
+    /// and back down onto walkable ground. Returns the new position and the distance moved forward.
+    fn step_up(
+        &self,
+        position: Vector,
+        up: Vector,
+        horizontal: Vector,
+    ) -> Option<(Vector, Scalar)> {
+        let skin = self.controller.skin_width;
+        let length = horizontal.length();
+        if self.controller.step_offset <= 0.0 || length <= Scalar::EPSILON {
+            return None;
+        }
+        let direction = horizontal / length;
+
+        // Move up
+        let rise = self
+            .cast(position, up, self.controller.step_offset + skin)
+            .map_or(self.controller.step_offset, |(distance, _)| {
+                (distance - skin).max(0.0)
+            });
+        if rise <= skin {
+            return None;
+        }
+        let raised = position + up * rise;
+
+        // Move forward
+        let forward = self
+            .cast(raised, direction, length + skin)
+            .map_or(length, |(distance, _)| (distance - skin).max(0.0));
+        if forward <= Scalar::EPSILON {
+            return None;
+        }
+        let advanced = raised + direction * forward;
+
+        // Move down onto the step
+        let (distance, ground) = self.cast(advanced, -up, rise + skin)?;
+        if ground.kind != CharacterCollisionKind::Ground {
+            return None;
+        }
+        Some((advanced - up * (distance - skin).max(0.0), forward))
+    }
+}
+
+/// Moves [`KinematicCharacterController`]s with move-and-slide and sets their velocities
+/// so that the kinematic body pipeline moves them by the resulting translation.
+#[allow(clippy::type_complexity)]
+fn move_character_controllers(
+    mut commands: Commands,
+    mut characters: Query<
+        (
+            Entity,
+            &KinematicCharacterController,
+            Option<&mut KinematicCharacterControllerOutput>,
+            &Collider,
+            &Position,
+            &Rotation,
+            &mut LinearVelocity,
+            Option<&CollisionLayers>,
+        ),
+        Without<RigidBodyDisabled>,
+    >,
+    bodies: Query<
+        (
+            &Position,
+            &Rotation,
+            &CenterOfMass,
+            &LinearVelocity,
+            &AngularVelocity,
+        ),
+        Without<KinematicCharacterController>,
+    >,
+    collider_parents: Query<&ColliderParent>,
+    sensors: Query<(), With<Sensor>>,
+    pipeline: Res<SpatialQueryPipeline>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs <= 0.0 {
+        return;
+    }
+
+    for (entity, controller, output, collider, position, rotation, mut lin_vel, layers) in
+        &mut characters
+    {
+        let up = controller.up.normalize_or_zero();
+        let skin = controller.skin_width;
+        let previous_ground = output.as_ref().and_then(|output| output.ground);
+
+        // Dynamic bodies are pushed by the character instead of blocking it.
+        let filter = SpatialQueryFilter::from_mask(layers.map_or(LayerMask::ALL, |l| l.filters))
+            .with_excluded_entities([entity])
+            .with_excluded_flags(QueryFilterFlags::DISABLED | QueryFilterFlags::DYNAMIC);
+        let caster = CharacterCaster {
+            pipeline: &pipeline,
+            sensors: &sensors,
+            controller,
+            shape: collider,
+            rotation: *rotation,
+            filter,
+        };
+
+        // Carry the character along with the ground that it is standing on.
+        let ground_velocity = previous_ground
+            .and_then(|ground| {
+                let body = collider_parents
+                    .get(ground.entity)
+                    .map_or(ground.entity, |parent| parent.get());
+                bodies.get(body).ok()
+            })
+            .map_or(
+                Vector::ZERO,
+                |(body_pos, body_rot, center_of_mass, body_lin_vel, body_ang_vel)| {
+                    let offset = position.0 - (body_pos.0 + body_rot.rotate(center_of_mass.0));
+                    #[cfg(feature = "2d")]
+                    let point_velocity = body_lin_vel.0 + body_ang_vel.0 * offset.perp();
+                    #[cfg(feature = "3d")]
+                    let point_velocity = body_lin_vel.0 + body_ang_vel.0.cross(offset);
+                    point_velocity
+                },
+            );
+
+        let desired_translation = (controller.velocity + ground_velocity) * delta_secs;
+        let mut new_position = position.0;
+        let mut remaining = desired_translation;
+        let mut collisions = vec![];
+
+        // Move and slide
+        for _ in 0..controller.max_slide_iterations {
+            let length = remaining.length();
+            if length <= Scalar::EPSILON {
+                break;
+            }
+            let direction = remaining / length;
+
+            let Some((distance, collision)) = caster.cast(new_position, direction, length + skin)
+            else {
+                new_position += remaining;
+                break;
+            };
+
+            let travel = (distance - skin).clamp(0.0, length);
+            new_position += direction * travel;
+            remaining *= 1.0 - travel / length;
+
+            // Step over low obstacles when walking on the ground.
+            if collision.kind == CharacterCollisionKind::Wall && previous_ground.is_some() {
+                let horizontal = remaining - up * remaining.dot(up);
+                if let Some((stepped, forward)) = caster.step_up(new_position, up, horizontal) {
+                    new_position = stepped;
+                    remaining -= horizontal.normalize_or_zero() * forward;
+                    remaining -= up * remaining.dot(up).min(0.0);
+                    continue;
+                }
+            }
+
+            collisions.push(collision);
+
+            // Landing on the ground stops the downward motion.
+            if collision.kind == CharacterCollisionKind::Ground {
+                remaining -= up * remaining.dot(up).min(0.0);
+            }
+
+            // Slide along the surface, without climbing slopes that are too steep.
+            let up_before = remaining.dot(up).max(0.0);
+            remaining -= collision.normal * remaining.dot(collision.normal).min(0.0);
+            if collision.kind != CharacterCollisionKind::Ground {
+                let up_after = remaining.dot(up);
+                if up_after > up_before {
+                    remaining -= up * (up_after - up_before);
+                }
+            }
+        }
+
+        // Detect the ground, and snap to it when walking down slopes and stairs.
+        let snap_distance = if previous_ground.is_some() && controller.velocity.dot(up) <= 0.0 {
+            controller.snap_to_ground.max(0.0)
+        } else {
+            0.0
+        };
+        let ground = caster
+            .cast(new_position, -up, snap_distance + 2.0 * skin)
+            .filter(|(_, ground)| ground.kind == CharacterCollisionKind::Ground)
+            .map(|(distance, ground)| {
+                new_position -= up * (distance - skin).max(0.0);
+                ground
+            });
+        if let Some(ground) = ground {
+            if !collisions.iter().any(|c| c.entity == ground.entity) {
+                collisions.push(ground);
+            }
+        }
+
+        let effective_translation = new_position - position.0;
+        let velocity = effective_translation / delta_secs;
+        // avoid triggering bevy's change detection unnecessarily
+        if lin_vel.0 != velocity {
+            lin_vel.0 = velocity;
+        }
+
+        let new_output = KinematicCharacterControllerOutput {
+            grounded: ground.is_some(),
+            ground,
+            desired_translation,
+            effective_translation,
+            collisions,
+        };
+        if let Some(mut output) = output {
+            *output = new_output;
+        } else {
+            commands.entity(entity).insert(new_output);
+        }
+    }
+}
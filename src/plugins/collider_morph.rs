@@ -0,0 +1,231 @@
+//! Smooth interpolation between two convex collider shapes.
+//!
+//! See [`ColliderMorphPlugin`].
+
+use crate::{prelude::*, prepare::PrepareSet};
+use bevy::prelude::*;
+use parry::shape::{SharedShape, TypedShape};
+
+/// The number of support directions sampled when morphing between convex shapes of different types.
+#[cfg(feature = "2d")]
+const SUPPORT_SAMPLES: usize = 32;
+/// The number of support directions sampled when morphing between convex shapes of different types.
+#[cfg(feature = "3d")]
+const SUPPORT_SAMPLES: usize = 96;
+
+/// A plugin that updates the [`Collider`] of entities with a [`ColliderMorph`]
+/// whenever the [progress](ColliderMorph::progress) of the morph changes.
+///
+/// The [`ColliderAabb`] and mass properties are updated along with the shape,
+/// so a body that grows or changes its shape also gets heavier or lighter smoothly.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+#[cfg_attr(
+    feature = "2d",
+    doc = "    let (small, large) = (Collider::circle(0.5), Collider::rectangle(2.0, 3.0));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    let (small, large) = (Collider::sphere(0.5), Collider::cuboid(2.0, 3.0, 2.0));"
+)]
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         small.clone(),
+///         ColliderMorph::new(small, large),
+///     ));
+/// }
+///
+/// fn grow(mut morphs: Query<&mut ColliderMorph>, time: Res<Time>) {
+///     for mut morph in &mut morphs {
+///         morph.progress = (morph.progress + time.delta_seconds()).min(1.0);
+///     }
+/// }
+/// ```
+pub struct ColliderMorphPlugin;
+
+impl Plugin for ColliderMorphPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            apply_collider_morphs
+                .in_set(PhysicsSet::Prepare)
+                .after(PrepareSet::InitTransforms)
+                .before(PrepareSet::Finalize),
+        );
+    }
+}
+
+/// Interpolates the [`Collider`] of an entity between two convex shapes.
+/// Used by the [`ColliderMorphPlugin`].
+///
+/// Shapes of the same primitive type, like two cuboids or two capsules, are interpolated
+/// parameter-wise. Convex hulls with the same number of vertices are interpolated vertex-wise.
+/// Other convex shapes are interpolated by sampling their support points in a fixed set of directions,
+/// which approximates the result as a convex hull.
+///
+/// The scale of the collider is kept when the shape is updated.
+#[derive(Component, Clone)]
+pub struct ColliderMorph {
+    /// The shape at the start of the morph, when the progress is `0.0`.
+    pub from: Collider,
+    /// The shape at the end of the morph, when the progress is `1.0`.
+    pub to: Collider,
+    /// The progress of the morph in the range `[0.0, 1.0]`.
+    pub progress: Scalar,
+}
+
+impl ColliderMorph {
+    /// Creates a new [`ColliderMorph`] between the given colliders, starting at the `from` shape.
+    pub fn new(from: Collider, to: Collider) -> Self {
+        Self {
+            from,
+            to,
+            progress: 0.0,
+        }
+    }
+
+    /// Sets the progress of the morph.
+    pub fn with_progress(self, progress: Scalar) -> Self {
+        Self { progress, ..self }
+    }
+
+    /// Returns true if both shapes are convex and can be morphed.
+    pub fn is_supported(&self) -> bool {
+        self.from.shape().as_support_map().is_some() && self.to.shape().as_support_map().is_some()
+    }
+
+    /// Computes the interpolated unscaled shape at the given progress in the range `[0.0, 1.0]`.
+    ///
+    /// Returns `None` if either of the shapes is not convex.
+    pub fn shape_at(&self, progress: Scalar) -> Option<SharedShape> {
+        morph_shapes(self.from.shape(), self.to.shape(), progress.clamp(0.0, 1.0))
+    }
+}
+
+/// Updates the colliders of entities whose [`ColliderMorph`] has changed.
+fn apply_collider_morphs(
+    mut query: Query<(&ColliderMorph, &mut Collider), Changed<ColliderMorph>>,
+) {
+    for (morph, mut collider) in &mut query {
+        if let Some(shape) = morph.shape_at(morph.progress) {
+            collider.set_shape(shape);
+        } else {
+            warn!("Collider morphs are only supported between convex shapes");
+        }
+    }
+}
+
+/// Interpolates between two convex shapes.
+fn morph_shapes(from: &SharedShape, to: &SharedShape, t: Scalar) -> Option<SharedShape> {
+    let lerp = |a: Scalar, b: Scalar| a + (b - a) * t;
+
+    match (from.as_typed_shape(), to.as_typed_shape()) {
+        (TypedShape::Ball(a), TypedShape::Ball(b)) => {
+            return Some(SharedShape::ball(lerp(a.radius, b.radius)));
+        }
+        (TypedShape::Cuboid(a), TypedShape::Cuboid(b)) => {
+            let half_extents = Vector::from(a.half_extents).lerp(b.half_extents.into(), t);
+            #[cfg(feature = "2d")]
+            return Some(SharedShape::cuboid(half_extents.x, half_extents.y));
+            #[cfg(feature = "3d")]
+            return Some(SharedShape::cuboid(
+                half_extents.x,
+                half_extents.y,
+                half_extents.z,
+            ));
+        }
+        (TypedShape::Capsule(a), TypedShape::Capsule(b)) => {
+            let start = Vector::from(a.segment.a).lerp(b.segment.a.into(), t);
+            let end = Vector::from(a.segment.b).lerp(b.segment.b.into(), t);
+            return Some(SharedShape::capsule(
+                start.into(),
+                end.into(),
+                lerp(a.radius, b.radius),
+            ));
+        }
+        #[cfg(feature = "3d")]
+        (TypedShape::Cylinder(a), TypedShape::Cylinder(b)) => {
+            return Some(SharedShape::cylinder(
+                lerp(a.half_height, b.half_height),
+                lerp(a.radius, b.radius),
+            ));
+        }
+        #[cfg(feature = "3d")]
+        (TypedShape::Cone(a), TypedShape::Cone(b)) => {
+            return Some(SharedShape::cone(
+                lerp(a.half_height, b.half_height),
+                lerp(a.radius, b.radius),
+            ));
+        }
+        #[cfg(feature = "2d")]
+        (TypedShape::ConvexPolygon(a), TypedShape::ConvexPolygon(b))
+            if a.points().len() == b.points().len() =>
+        {
+            return lerp_points(a.points(), b.points(), t);
+        }
+        #[cfg(feature = "3d")]
+        (TypedShape::ConvexPolyhedron(a), TypedShape::ConvexPolyhedron(b))
+            if a.points().len() == b.points().len() =>
+        {
+            return lerp_points(a.points(), b.points(), t);
+        }
+        _ => {}
+    }
+
+    // Points on the boundary of the interpolated shape can be found by interpolating
+    // the support points of the shapes in the same direction.
+    let (from, to) = (from.as_support_map()?, to.as_support_map()?);
+    let points = support_directions()
+        .map(|direction| {
+            let direction: parry::math::Vector<Scalar> = direction.into();
+            let a = Vector::from(from.local_support_point(&direction));
+            let b = Vector::from(to.local_support_point(&direction));
+            a.lerp(b, t).into()
+        })
+        .collect::<Vec<_>>();
+    SharedShape::convex_hull(&points)
+}
+
+/// Interpolates the vertices of two convex hulls pairwise and computes the hull of the result.
+fn lerp_points(
+    from: &[parry::math::Point<Scalar>],
+    to: &[parry::math::Point<Scalar>],
+    t: Scalar,
+) -> Option<SharedShape> {
+    let points = from
+        .iter()
+        .zip(to)
+        .map(|(a, b)| Vector::from(*a).lerp((*b).into(), t).into())
+        .collect::<Vec<_>>();
+    SharedShape::convex_hull(&points)
+}
+
+/// Returns evenly distributed unit directions for sampling support points.
+#[cfg(feature = "2d")]
+fn support_directions() -> impl Iterator<Item = Vector> {
+    (0..SUPPORT_SAMPLES).map(|i| {
+        let angle = TAU * i as Scalar / SUPPORT_SAMPLES as Scalar;
+        Vector::new(angle.cos(), angle.sin())
+    })
+}
+
+/// Returns evenly distributed unit directions for sampling support points using a Fibonacci sphere.
+#[cfg(feature = "3d")]
+fn support_directions() -> impl Iterator<Item = Vector> {
+    let golden_angle = PI * (3.0 - (5.0 as Scalar).sqrt());
+    (0..SUPPORT_SAMPLES).map(move |i| {
+        let y = 1.0 - 2.0 * (i as Scalar + 0.5) / SUPPORT_SAMPLES as Scalar;
+        let radius = (1.0 - y * y).sqrt();
+        let angle = golden_angle * i as Scalar;
+        Vector::new(radius * angle.cos(), y, radius * angle.sin())
+    })
+}
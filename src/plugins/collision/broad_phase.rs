@@ -7,6 +7,7 @@ use crate::prelude::*;
 use bevy::{
     ecs::entity::{EntityMapper, MapEntities},
     prelude::*,
+    utils::HashMap,
 };
 
 /// Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
@@ -95,7 +96,7 @@ impl MapEntities for AabbIntervals {
 #[allow(clippy::type_complexity)]
 fn update_aabb_intervals(
     aabbs: Query<(
-        &ColliderAabb,
+        Ref<ColliderAabb>,
         Option<&ColliderParent>,
         Option<&CollisionLayers>,
         Ref<Position>,
@@ -115,7 +116,9 @@ fn update_aabb_intervals(
 
                 let is_static =
                     new_parent.is_some_and(|p| rbs.get(p.get()).is_ok_and(RigidBody::is_static));
-                *is_inactive = is_static || (!position.is_changed() && !rotation.is_changed());
+                // Colliders whose shape changed in place are active even if they didn't move.
+                *is_inactive = is_static
+                    || (!position.is_changed() && !rotation.is_changed() && !new_aabb.is_changed());
 
                 true
             } else {
@@ -216,3 +219,103 @@ fn insertion_sort<T>(items: &mut [T], comparison: fn(&T, &T) -> bool) {
         }
     }
 }
+
+#[cfg(feature = "2d")]
+type GridCell = IVec2;
+#[cfg(feature = "3d")]
+type GridCell = IVec3;
+
+/// A uniform grid that hashes points into cells for finding nearby points in constant time,
+/// like the neighbors of particles.
+///
+/// Points are referred to by their indices. A query returns the points in the cell of the queried point
+/// and in the adjacent cells, so the cell size should be at least the largest query radius.
+#[derive(Clone, Debug, Default)]
+pub struct SpatialHashGrid {
+    cell_size: Scalar,
+    cells: HashMap<GridCell, Vec<usize>>,
+}
+
+impl SpatialHashGrid {
+    /// Creates a new empty [`SpatialHashGrid`] with the given cell size.
+    pub fn new(cell_size: Scalar) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::default(),
+        }
+    }
+
+    /// Creates a new [`SpatialHashGrid`] with the given cell size containing the given points.
+    /// The points are referred to by their indices in the slice.
+    pub fn from_points(points: &[Vector], cell_size: Scalar) -> Self {
+        let mut grid = Self::new(cell_size);
+        for (index, point) in points.iter().enumerate() {
+            grid.insert(index, *point);
+        }
+        grid
+    }
+
+    /// Returns the cell size of the grid.
+    pub fn cell_size(&self) -> Scalar {
+        self.cell_size
+    }
+
+    /// Inserts the point with the given index into the grid.
+    pub fn insert(&mut self, index: usize, point: Vector) {
+        self.cells.entry(self.cell(point)).or_default().push(index);
+    }
+
+    /// Removes all points from the grid.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// Returns the indices of the points in the cell of the given point and in the adjacent cells.
+    /// The returned points are not necessarily within the cell size of the given point.
+    pub fn candidates(&self, point: Vector) -> impl Iterator<Item = usize> + '_ {
+        adjacent_cells(self.cell(point))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+    }
+
+    /// Finds the points within `radius` of each of the given points, including the point itself.
+    ///
+    /// The grid should contain the same `points`, and the `radius` should not be larger than the cell size.
+    pub fn neighbor_lists(&self, points: &[Vector], radius: Scalar) -> Vec<Vec<usize>> {
+        let radius_squared = radius * radius;
+        points
+            .iter()
+            .map(|point| {
+                self.candidates(*point)
+                    .filter(|&j| points[j].distance_squared(*point) < radius_squared)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn cell(&self, point: Vector) -> GridCell {
+        #[cfg(feature = "2d")]
+        {
+            (point / self.cell_size).floor().as_ivec2()
+        }
+        #[cfg(feature = "3d")]
+        {
+            (point / self.cell_size).floor().as_ivec3()
+        }
+    }
+}
+
+/// Returns the given cell and the cells around it.
+#[cfg(feature = "2d")]
+fn adjacent_cells(cell: GridCell) -> impl Iterator<Item = GridCell> {
+    (-1..=1).flat_map(move |x| (-1..=1).map(move |y| cell + IVec2::new(x, y)))
+}
+
+/// Returns the given cell and the cells around it.
+#[cfg(feature = "3d")]
+fn adjacent_cells(cell: GridCell) -> impl Iterator<Item = GridCell> {
+    (-1..=1).flat_map(move |x| {
+        (-1..=1).flat_map(move |y| (-1..=1).map(move |z| cell + IVec3::new(x, y, z)))
+    })
+}
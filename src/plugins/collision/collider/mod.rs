@@ -19,8 +19,9 @@ use crate::prelude::*;
 use bevy::{
     ecs::entity::{EntityMapper, MapEntities},
     prelude::*,
-    utils::HashSet,
+    utils::{HashMap, HashSet},
 };
+use std::time::Duration;
 
 /// A trait for creating colliders from other types.
 pub trait IntoCollider<C: AnyCollider> {
@@ -95,6 +96,68 @@ pub trait ScalableCollider: AnyCollider {
     }
 }
 
+/// A trait for collision backends that support ray casts and shape casts in addition to
+/// the AABB and contact manifold computation of [`AnyCollider`].
+///
+/// Together with [`AnyCollider`], this trait defines the per-shape support functions
+/// that a collision backend needs to provide, so that alternative backends like custom SIMD primitives
+/// or a trimmed Parry build can replace the default [`Collider`] without forking the crate.
+/// See [`ColliderBackendPlugin`] for how to use a custom collider backend.
+pub trait CollisionBackend: AnyCollider {
+    /// Casts a ray against the collider with the given position and rotation.
+    ///
+    /// Returns the time of impact and the global surface normal at the point of intersection,
+    /// or `None` if the ray doesn't hit the collider within `max_time_of_impact`.
+    ///
+    /// If `solid` is true, a ray that starts inside the collider hits it at a time of impact of zero.
+    fn cast_ray(
+        &self,
+        position: Vector,
+        rotation: impl Into<Rotation>,
+        ray_origin: Vector,
+        ray_direction: Dir,
+        max_time_of_impact: Scalar,
+        solid: bool,
+    ) -> Option<(Scalar, Vector)>;
+
+    /// Computes when the collider and `other` hit each other for the first time
+    /// when moving with the given linear velocities.
+    ///
+    /// Returns `None` if the colliders don't hit each other within `max_time_of_impact`
+    /// or if the given shapes are not supported.
+    #[allow(clippy::too_many_arguments)]
+    fn cast_shape(
+        &self,
+        position1: Vector,
+        rotation1: impl Into<Rotation>,
+        velocity1: Vector,
+        other: &Self,
+        position2: Vector,
+        rotation2: impl Into<Rotation>,
+        velocity2: Vector,
+        max_time_of_impact: Scalar,
+    ) -> Option<ColliderCastHit>;
+}
+
+/// The result of a [shape cast](CollisionBackend::cast_shape) between two colliders.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColliderCastHit {
+    /// The time at which the colliders come into contact.
+    pub time_of_impact: Scalar,
+    /// The closest point on the first collider, at the time of impact,
+    /// expressed in local space.
+    pub point1: Vector,
+    /// The closest point on the second collider, at the time of impact,
+    /// expressed in local space.
+    pub point2: Vector,
+    /// The outward normal on the first collider, at the time of impact,
+    /// expressed in local space.
+    pub normal1: Vector,
+    /// The outward normal on the second collider, at the time of impact,
+    /// expressed in local space.
+    pub normal2: Vector,
+}
+
 /// A component that stores the `Entity` ID of the [`RigidBody`] that a [`Collider`] is attached to.
 ///
 /// If the collider is a child of a rigid body, this points to the body's `Entity` ID.
@@ -218,6 +281,176 @@ impl From<Transform> for ColliderTransform {
 #[reflect(Component)]
 pub struct Sensor;
 
+/// A component that makes the collisions of a [`Sensor`] collider only be checked every `N` physics steps,
+/// where `N` is the given interval. This is also known as *time slicing*.
+///
+/// Many sensors, like the awareness sensors of AI agents, don't need to be updated every physics step.
+/// Checking them less frequently can significantly reduce the cost of the narrow phase.
+///
+/// Sensors with the same interval are updated in a round-robin fashion based on their entity index,
+/// so that only a fraction of them are updated during each step. Between updates, the previous
+/// collisions of the sensor are kept, and no [collision events](ContactReportingPlugin#collision-events)
+/// are sent for them.
+///
+/// The component has no effect on colliders that are not sensors. An interval of 0 or 1 updates the sensor
+/// every physics step.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // Spawn a sensor that only checks for collisions every 10 physics steps.
+#[cfg_attr(
+    feature = "2d",
+    doc = "    commands.spawn((Collider::circle(5.0), Sensor, SensorUpdateInterval(10)));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    commands.spawn((Collider::sphere(5.0), Sensor, SensorUpdateInterval(10)));"
+)]
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SensorUpdateInterval(pub u32);
+
+impl SensorUpdateInterval {
+    /// Returns true if the collisions of the given sensor entity should be checked during the given physics step.
+    // `u64::is_multiple_of` requires Rust 1.87, which is newer than the supported toolchain.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn is_active(&self, entity: Entity, step: u64) -> bool {
+        self.0 <= 1 || (step + entity.index() as u64) % self.0 as u64 == 0
+    }
+}
+
+/// A component that tracks the entities overlapping a [`Sensor`] collider, along with the time
+/// at which each of them entered the sensor.
+///
+/// This can be used for mechanics like "stand in the zone for 3 seconds" without keeping track of
+/// [collision events](ContactReportingPlugin#collision-events) and timers manually.
+/// Times are measured using the elapsed time of [`Time<Physics>`](Physics).
+///
+/// The component must be added manually, and it is only updated if the [`ContactReportingPlugin`] is enabled.
+/// It can also be used for colliders that are not sensors.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "        Collider::circle(5.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::sphere(5.0),")]
+///         Sensor,
+///         SensorOverlaps::default(),
+///     ));
+/// }
+///
+/// fn capture_zones(query: Query<&SensorOverlaps>, time: Res<Time<Physics>>) {
+///     for overlaps in &query {
+///         for (entity, _) in overlaps.iter() {
+///             if overlaps.stay_duration(entity, time.elapsed()).unwrap().as_secs_f32() > 3.0 {
+///                 println!("{entity:?} captured the zone");
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SensorOverlaps {
+    entry_times: HashMap<Entity, Duration>,
+    hit_count: u64,
+}
+
+impl SensorOverlaps {
+    /// Returns the elapsed time of [`Time<Physics>`](Physics) at which the given entity entered the sensor,
+    /// or `None` if the entity is not overlapping the sensor.
+    pub fn entry_time(&self, entity: Entity) -> Option<Duration> {
+        self.entry_times.get(&entity).copied()
+    }
+
+    /// Returns how long the given entity has been overlapping the sensor at the given elapsed time
+    /// of [`Time<Physics>`](Physics), or `None` if the entity is not overlapping the sensor.
+    pub fn stay_duration(&self, entity: Entity, elapsed: Duration) -> Option<Duration> {
+        self.entry_time(entity)
+            .map(|entry_time| elapsed.saturating_sub(entry_time))
+    }
+
+    /// Returns true if the given entity is overlapping the sensor.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entry_times.contains_key(&entity)
+    }
+
+    /// Returns an iterator over the overlapping entities and their entry times.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Duration)> + '_ {
+        self.entry_times
+            .iter()
+            .map(|(entity, entry_time)| (*entity, *entry_time))
+    }
+
+    /// Returns the number of entities overlapping the sensor.
+    pub fn len(&self) -> usize {
+        self.entry_times.len()
+    }
+
+    /// Returns true if no entities are overlapping the sensor.
+    pub fn is_empty(&self) -> bool {
+        self.entry_times.is_empty()
+    }
+
+    /// Returns the total number of times that entities have entered the sensor.
+    pub fn hit_count(&self) -> u64 {
+        self.hit_count
+    }
+
+    pub(crate) fn enter(&mut self, entity: Entity, time: Duration) {
+        self.entry_times.insert(entity, time);
+        self.hit_count += 1;
+    }
+
+    pub(crate) fn exit(&mut self, entity: Entity) {
+        self.entry_times.remove(&entity);
+    }
+}
+
+impl MapEntities for SensorOverlaps {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entry_times = self
+            .entry_times
+            .drain()
+            .map(|(entity, entry_time)| (entity_mapper.map_entity(entity), entry_time))
+            .collect()
+    }
+}
+
+/// A component that disables a [`Collider`], so that it doesn't collide with anything
+/// and is ignored by [spatial queries](spatial_query).
+///
+/// Spatial queries can include disabled colliders by removing [`QueryFilterFlags::DISABLED`]
+/// from the [`SpatialQueryFilter`].
+///
+/// The collider still contributes to the mass properties of its rigid body.
+/// Existing collisions end when the collider is disabled, and the collider starts colliding again
+/// when the component is removed.
+///
+/// Colliders can also be disabled automatically when their rigid body is disabled.
+/// See [`RigidBodyDisabled`] and [`DisablePropagation`].
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct ColliderDisabled;
+
 /// The Axis-Aligned Bounding Box of a [collider](Collider).
 #[derive(Clone, Copy, Component, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -7,11 +7,20 @@ use bevy::{log, prelude::*};
 use collision::contact_query::UnsupportedShape;
 use itertools::Either;
 use parry::shape::{RoundShape, SharedShape, TypedShape};
+use std::sync::Arc;
 
 #[cfg(feature = "2d")]
 mod primitives2d;
 #[cfg(feature = "3d")]
 mod primitives3d;
+mod sub_colliders;
+mod surface_materials;
+#[cfg(feature = "3d")]
+mod trimesh_mass;
+
+pub(crate) use sub_colliders::update_sub_colliders;
+pub use sub_colliders::{SubColliderId, SubColliders};
+pub use surface_materials::SurfaceMaterials;
 
 #[cfg(feature = "2d")]
 pub(crate) use primitives2d::{EllipseWrapper, RegularPolygonWrapper};
@@ -369,6 +378,34 @@ pub struct Collider {
     scaled_shape: SharedShape,
     /// The global scale used for the collider shape.
     scale: Vector,
+    /// The texture coordinates at the corners of each triangle of a trimesh collider.
+    ///
+    /// Used for computing the texture coordinates at raycast hits.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    uvs: Option<Arc<Vec<[Vec2; 3]>>>,
+    /// The vertex normals at the corners of each triangle of a trimesh collider.
+    ///
+    /// Used for computing the smooth normals at raycast hits.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    vertex_normals: Option<Arc<Vec<[Vector; 3]>>>,
+    /// The configuration of a [chain](Collider::chain) collider.
+    #[cfg(feature = "2d")]
+    chain: Option<ChainConfig>,
+}
+
+/// The configuration of a polyline collider created with [`Collider::chain`] or [`Collider::one_sided_chain`].
+///
+/// The neighbors of each segment act as ghost vertices: contacts are only generated with the segment
+/// that actually faces the other collider, so shapes slide along the chain without hitting internal edges.
+#[cfg(feature = "2d")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChainConfig {
+    /// If true, the last vertex is connected to the first one.
+    pub closed: bool,
+    /// If true, the chain only collides on the right side of each segment,
+    /// which is the outside of a counterclockwise loop.
+    pub one_sided: bool,
 }
 
 impl From<SharedShape> for Collider {
@@ -377,6 +414,10 @@ impl From<SharedShape> for Collider {
             shape: value.clone(),
             scaled_shape: value,
             scale: Vector::ONE,
+            uvs: None,
+            vertex_normals: None,
+            #[cfg(feature = "2d")]
+            chain: None,
         }
     }
 }
@@ -442,6 +483,24 @@ impl AnyCollider for Collider {
     }
 
     fn mass_properties(&self, density: Scalar) -> ColliderMassProperties {
+        // Compute the exact mass properties of the volume enclosed by closed trimeshes.
+        #[cfg(feature = "3d")]
+        if let Some(trimesh) = self.shape_scaled().as_trimesh() {
+            let open_edges = trimesh_mass::open_edge_count(trimesh);
+            if open_edges == 0 {
+                if let Some(props) = trimesh_mass::closed_trimesh_mass_properties(trimesh, density)
+                {
+                    return props;
+                }
+            } else {
+                log::warn!(
+                    "Trimesh collider is not closed ({open_edges} open or non-manifold edges). \
+                    Its mass properties may be inaccurate. Consider using a convex hull \
+                    or a convex decomposition for dynamic bodies."
+                );
+            }
+        }
+
         let props = self.shape_scaled().mass_properties(density);
 
         ColliderMassProperties {
@@ -493,6 +552,62 @@ impl ScalableCollider for Collider {
     }
 }
 
+impl CollisionBackend for Collider {
+    fn cast_ray(
+        &self,
+        position: Vector,
+        rotation: impl Into<Rotation>,
+        ray_origin: Vector,
+        ray_direction: Dir,
+        max_time_of_impact: Scalar,
+        solid: bool,
+    ) -> Option<(Scalar, Vector)> {
+        let ray =
+            parry::query::Ray::new(ray_origin.into(), ray_direction.adjust_precision().into());
+        crate::plugins::spatial_query::heightfield_ray::cast_ray_and_get_normal(
+            self,
+            &make_isometry(position, rotation),
+            &ray,
+            max_time_of_impact,
+            solid,
+        )
+        .map(|hit| (hit.toi, hit.normal.into()))
+    }
+
+    fn cast_shape(
+        &self,
+        position1: Vector,
+        rotation1: impl Into<Rotation>,
+        velocity1: Vector,
+        other: &Self,
+        position2: Vector,
+        rotation2: impl Into<Rotation>,
+        velocity2: Vector,
+        max_time_of_impact: Scalar,
+    ) -> Option<ColliderCastHit> {
+        collision::contact_query::time_of_impact(
+            self,
+            position1,
+            rotation1,
+            velocity1,
+            other,
+            position2,
+            rotation2,
+            velocity2,
+            max_time_of_impact,
+        )
+        .ok()
+        .flatten()
+        .map(|toi| ColliderCastHit {
+            time_of_impact: toi.time_of_impact,
+            point1: toi.point1,
+            point2: toi.point2,
+            normal1: toi.normal1,
+            normal2: toi.normal2,
+        })
+    }
+}
+
 impl Collider {
     /// Returns the raw unscaled shape of the collider.
     pub fn shape(&self) -> &SharedShape {
@@ -505,8 +620,17 @@ impl Collider {
     }
 
     /// Sets the unscaled shape of the collider. The collider's scale will be applied to this shape.
+    ///
+    /// Any stored [trimesh UVs](Collider::with_trimesh_uvs), [vertex normals](Collider::with_trimesh_normals)
+    /// and [chain configuration](Collider::chain) are cleared.
     pub fn set_shape(&mut self, shape: SharedShape) {
         self.shape = shape;
+        self.uvs = None;
+        self.vertex_normals = None;
+        #[cfg(feature = "2d")]
+        {
+            self.chain = None;
+        }
 
         // TODO: The number of subdivisions probably shouldn't be hard-coded
         if let Ok(scaled) = scale_shape(&self.shape, self.scale, 10) {
@@ -548,6 +672,246 @@ impl Collider {
         }
     }
 
+    /// Sets the radius of a ball, capsule, cylinder or cone collider in place.
+    ///
+    /// Unlike replacing the collider, this keeps the scale and other data of the collider intact.
+    /// The [`ColliderAabb`] and mass properties are updated automatically, and existing contacts
+    /// of the collider are kept and refreshed during the next physics step, which makes this suitable
+    /// for growing or shrinking colliders every frame.
+    ///
+    /// Returns `false` and leaves the collider unchanged if the shape doesn't have a radius.
+    pub fn set_radius(&mut self, radius: Scalar) -> bool {
+        self.edit_shape(|shape| {
+            if let Some(ball) = shape.as_ball_mut() {
+                ball.radius = radius;
+                return true;
+            }
+            if let Some(capsule) = shape.as_capsule_mut() {
+                capsule.radius = radius;
+                return true;
+            }
+            #[cfg(feature = "3d")]
+            if let Some(cylinder) = shape.as_cylinder_mut() {
+                cylinder.radius = radius;
+                return true;
+            }
+            #[cfg(feature = "3d")]
+            if let Some(cone) = shape.as_cone_mut() {
+                cone.radius = radius;
+                return true;
+            }
+            false
+        })
+    }
+
+    /// Sets the half height of a capsule, cylinder or cone collider in place.
+    /// The center of the shape stays in place.
+    ///
+    /// See [`Collider::set_radius`] for how the change is applied.
+    ///
+    /// Returns `false` and leaves the collider unchanged if the shape doesn't have a half height.
+    pub fn set_half_height(&mut self, half_height: Scalar) -> bool {
+        self.edit_shape(|shape| {
+            if let Some(capsule) = shape.as_capsule_mut() {
+                let (a, b): (Vector, Vector) = (capsule.segment.a.into(), capsule.segment.b.into());
+                let center = (a + b) * 0.5;
+                let direction = (b - a).try_normalize().unwrap_or(Vector::Y);
+                capsule.segment.a = (center - direction * half_height).into();
+                capsule.segment.b = (center + direction * half_height).into();
+                return true;
+            }
+            #[cfg(feature = "3d")]
+            if let Some(cylinder) = shape.as_cylinder_mut() {
+                cylinder.half_height = half_height;
+                return true;
+            }
+            #[cfg(feature = "3d")]
+            if let Some(cone) = shape.as_cone_mut() {
+                cone.half_height = half_height;
+                return true;
+            }
+            false
+        })
+    }
+
+    /// Sets the half extents of a cuboid collider in place. Rounded cuboids keep their border radius.
+    ///
+    /// See [`Collider::set_radius`] for how the change is applied.
+    ///
+    /// Returns `false` and leaves the collider unchanged if the shape isn't a cuboid.
+    pub fn set_half_extents(&mut self, half_extents: Vector) -> bool {
+        self.edit_shape(|shape| {
+            if let Some(cuboid) = shape.as_cuboid_mut() {
+                cuboid.half_extents = half_extents.into();
+                return true;
+            }
+            if let Some(round_cuboid) = shape.as_round_cuboid_mut() {
+                round_cuboid.inner_shape.half_extents = half_extents.into();
+                return true;
+            }
+            false
+        })
+    }
+
+    /// Edits the unscaled shape in place and updates the scaled shape if the edit succeeds.
+    fn edit_shape(&mut self, edit: impl FnOnce(&mut dyn parry::shape::Shape) -> bool) -> bool {
+        if !edit(self.shape.make_mut()) {
+            return false;
+        }
+
+        // TODO: The number of subdivisions probably shouldn't be hard-coded
+        if let Ok(scaled) = scale_shape(&self.shape, self.scale, 10) {
+            self.scaled_shape = scaled;
+        } else {
+            log::error!("Failed to create convex hull for scaled collider.");
+        }
+        true
+    }
+
+    /// Returns a new collider that is grown by the given `margin` in every direction,
+    /// like a trigger shell around an object.
+    ///
+    /// The result is the exact Minkowski sum of the shape and a ball for most shapes: balls and capsules grow
+    /// their radius, segments become capsules, and polygonal shapes get rounded borders. Trimeshes,
+    /// polylines and heightfields are turned into compounds of rounded triangles or capsules.
+    /// Compound shapes inflate each of their child shapes. Half-spaces and custom shapes are returned unchanged.
+    ///
+    /// The margin is in the local, unscaled space of the collider.
+    pub fn inflated(&self, margin: Scalar) -> Self {
+        let mut collider = Self::from(inflate_shape(&self.shape, margin.max(0.0)));
+        collider.set_scale(self.scale, 10);
+        collider
+    }
+
+    /// Returns a new collider that is shrunk by the given `margin` in every direction,
+    /// for example for checking navigation clearance.
+    ///
+    /// The result is exact for balls, capsules, cuboids and rounded shapes whose border radius
+    /// is at least `margin`, and approximate for cylinders, cones and convex hulls, whose vertices
+    /// are moved towards their center. Shapes without any volume, like trimeshes, polylines, segments
+    /// and triangles, as well as half-spaces and custom shapes, are returned unchanged. Compound shapes
+    /// deflate each of their child shapes. Shapes never shrink below a small minimum size.
+    ///
+    /// The margin is in the local, unscaled space of the collider.
+    pub fn deflated(&self, margin: Scalar) -> Self {
+        let mut collider = Self::from(deflate_shape(&self.shape, margin.max(0.0)));
+        collider.set_scale(self.scale, 10);
+        collider
+    }
+
+    /// Stores texture coordinates for the triangles of a trimesh collider, one UV for each corner of each triangle.
+    ///
+    /// The UVs are used for computing the texture coordinates at [raycast hits](RayHitData::uv),
+    /// for example for decals, bullet holes or painting mechanics.
+    /// The triangles must be in the same order as in the trimesh.
+    pub fn with_trimesh_uvs(mut self, uvs: Vec<[Vec2; 3]>) -> Self {
+        self.uvs = Some(Arc::new(uvs));
+        self
+    }
+
+    /// Returns the texture coordinates of the triangles of a trimesh collider, if they have been stored.
+    pub fn trimesh_uvs(&self) -> Option<&[[Vec2; 3]]> {
+        self.uvs.as_ref().map(|uvs| uvs.as_slice())
+    }
+
+    /// Stores vertex normals for the triangles of a trimesh collider, one normal for each corner of each triangle.
+    ///
+    /// The normals are interpolated for computing the [smooth normals](RayHitData::smooth_normal) at raycast hits,
+    /// so that decals and ricochets match the visual surface instead of the flat triangles.
+    /// The triangles must be in the same order as in the trimesh, and the normals should be in the local,
+    /// unscaled space of the collider.
+    pub fn with_trimesh_normals(mut self, normals: Vec<[Vector; 3]>) -> Self {
+        self.vertex_normals = Some(Arc::new(normals));
+        self
+    }
+
+    /// Returns the vertex normals of the triangles of a trimesh collider, if they have been stored.
+    pub fn trimesh_normals(&self) -> Option<&[[Vector; 3]]> {
+        self.vertex_normals
+            .as_ref()
+            .map(|normals| normals.as_slice())
+    }
+
+    /// Returns the [chain configuration](ChainConfig) of the collider if it was created
+    /// with [`Collider::chain`] or [`Collider::one_sided_chain`].
+    #[cfg(feature = "2d")]
+    pub fn chain_config(&self) -> Option<ChainConfig> {
+        self.chain
+    }
+
+    /// Returns true if the collider is a closed, consistently oriented trimesh,
+    /// meaning that every edge is shared by exactly two triangles with opposite winding.
+    ///
+    /// The mass properties of closed trimeshes are computed exactly from the enclosed volume.
+    /// Open trimeshes don't enclose a volume, so their mass properties are only approximate.
+    #[cfg(feature = "3d")]
+    pub fn is_closed_trimesh(&self) -> bool {
+        self.shape
+            .as_trimesh()
+            .is_some_and(|trimesh| trimesh_mass::open_edge_count(trimesh) == 0)
+    }
+
+    /// Computes the interpolated texture coordinates at the given point on a triangle of a trimesh collider.
+    ///
+    /// The point should be in the local space of the collider. Returns `None` if the collider
+    /// is not a trimesh or if it doesn't have [UVs](Collider::with_trimesh_uvs).
+    pub fn uv_at(&self, triangle: u32, local_point: Vector) -> Option<Vec2> {
+        let uvs = self.uvs.as_ref()?;
+        let (index, [u, v, w]) = self.trimesh_barycentric(triangle, local_point)?;
+        let corners = uvs.get(index)?;
+
+        Some(corners[0] * u as f32 + corners[1] * v as f32 + corners[2] * w as f32)
+    }
+
+    /// Computes the interpolated vertex normal at the given point on a triangle of a trimesh collider.
+    ///
+    /// The point should be in the local space of the collider, and the returned normal is also in local space,
+    /// with the collider's scale taken into account. Hits on back faces return the flipped normal.
+    /// Returns `None` if the collider is not a trimesh or if it doesn't have [vertex normals](Collider::with_trimesh_normals).
+    pub fn smooth_normal_at(&self, triangle: u32, local_point: Vector) -> Option<Vector> {
+        let normals = self.vertex_normals.as_ref()?;
+        let (index, [u, v, w]) = self.trimesh_barycentric(triangle, local_point)?;
+        let corners = normals.get(index)?;
+
+        let mut normal = corners[0] * u + corners[1] * v + corners[2] * w;
+        if index as u32 != triangle {
+            normal = -normal;
+        }
+
+        // Normals are transformed by the inverse of the scale to stay perpendicular to the surface.
+        let normal = (normal / self.scale).normalize_or_zero();
+        (normal != Vector::ZERO).then_some(normal)
+    }
+
+    /// Returns the index of the given trimesh triangle, ignoring the back face offset,
+    /// and the barycentric coordinates of the given local point on the triangle.
+    fn trimesh_barycentric(
+        &self,
+        triangle: u32,
+        local_point: Vector,
+    ) -> Option<(usize, [Scalar; 3])> {
+        let trimesh = self.shape_scaled().as_trimesh()?;
+        let triangle_count = trimesh.indices().len().max(1) as u32;
+
+        // Hits on back faces are offset by the number of triangles.
+        let index = triangle % triangle_count;
+        let triangle = trimesh.triangle(index);
+
+        let (a, b, c): (Vector, Vector, Vector) =
+            (triangle.a.into(), triangle.b.into(), triangle.c.into());
+        let (v0, v1, v2) = (b - a, c - a, local_point - a);
+        let (d00, d01, d11) = (v0.dot(v0), v0.dot(v1), v1.dot(v1));
+        let (d20, d21) = (v2.dot(v0), v2.dot(v1));
+        let denominator = d00 * d11 - d01 * d01;
+        if denominator.abs() <= Scalar::EPSILON {
+            return Some((index as usize, [1.0, 0.0, 0.0]));
+        }
+        let v = (d11 * d20 - d01 * d21) / denominator;
+        let w = (d00 * d21 - d01 * d20) / denominator;
+
+        Some((index as usize, [1.0 - v - w, v, w]))
+    }
+
     /// Projects the given `point` onto `self` transformed by `translation` and `rotation`.
     /// The returned tuple contains the projected point and whether it is inside the collider.
     ///
@@ -817,6 +1181,54 @@ impl Collider {
         SharedShape::polyline(vertices, indices).into()
     }
 
+    /// Creates a collider with a chain shape defined by its vertices, similar to `Box2D`'s chain shape.
+    ///
+    /// A chain is a polyline where the neighbors of each segment act as ghost vertices,
+    /// so shapes like circles roll and slide smoothly along the chain without catching on the internal
+    /// vertices between segments. This is useful for terrain outlines. If `closed` is true,
+    /// the last vertex is connected to the first one.
+    ///
+    /// The chain collides on both sides of its segments. See [`Collider::one_sided_chain`]
+    /// for a chain that only collides on one side.
+    #[cfg(feature = "2d")]
+    pub fn chain(vertices: Vec<Vector>, closed: bool) -> Self {
+        Self::chain_with_config(
+            vertices,
+            ChainConfig {
+                closed,
+                one_sided: false,
+            },
+        )
+    }
+
+    /// Creates a collider with a one-sided chain shape defined by its vertices, similar to `Box2D`'s chain shape.
+    ///
+    /// The chain only collides on the right side of each segment, which is the outside of a counterclockwise loop,
+    /// so shapes can pass through it from the other side. See [`Collider::chain`] for more information.
+    #[cfg(feature = "2d")]
+    pub fn one_sided_chain(vertices: Vec<Vector>, closed: bool) -> Self {
+        Self::chain_with_config(
+            vertices,
+            ChainConfig {
+                closed,
+                one_sided: true,
+            },
+        )
+    }
+
+    #[cfg(feature = "2d")]
+    fn chain_with_config(vertices: Vec<Vector>, config: ChainConfig) -> Self {
+        let vertex_count = vertices.len() as u32;
+        let mut indices: Vec<[u32; 2]> = (1..vertex_count).map(|i| [i - 1, i]).collect();
+        if config.closed && vertex_count > 2 {
+            indices.push([vertex_count - 1, 0]);
+        }
+
+        let mut collider = Self::polyline(vertices, Some(indices));
+        collider.chain = Some(config);
+        collider
+    }
+
     /// Creates a collider with a triangle mesh shape defined by its vertex and index buffers.
     ///
     /// Note that the resulting collider will be hollow and have no interior. This makes it more prone to tunneling and other collision issues.
@@ -898,6 +1310,132 @@ impl Collider {
         SharedShape::convex_hull(&points).map(Into::into)
     }
 
+    /// Creates a collider by extruding a 2D polygon along the Z axis.
+    ///
+    /// The `outline` is a simple polygon in the XY plane, given in either winding order, and the extrusion
+    /// is centered on the XY plane with a total thickness of `depth`. A convex outline produces a single
+    /// convex polyhedron, while a concave outline, like a gear, is decomposed into a compound of
+    /// convex prisms.
+    ///
+    /// Returns `None` if the outline has fewer than three vertices or if it is degenerate.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(feature = "f32")]
+    /// # fn setup(mut commands: Commands) {
+    /// // A ramp from a right triangle
+    /// let ramp = Collider::extrusion(&[Vec2::ZERO, Vec2::new(4.0, 0.0), Vec2::new(0.0, 1.0)], 2.0);
+    /// commands.spawn((RigidBody::Static, ramp.unwrap()));
+    /// # }
+    /// ```
+    #[cfg(feature = "3d")]
+    pub fn extrusion(outline: &[Vector2], depth: Scalar) -> Option<Self> {
+        let half_depth = depth * 0.5;
+        let prism = |polygon: &[Vector2]| {
+            let points = polygon
+                .iter()
+                .flat_map(|p| {
+                    [
+                        Vector::new(p.x, p.y, -half_depth).into(),
+                        Vector::new(p.x, p.y, half_depth).into(),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            SharedShape::convex_hull(&points)
+        };
+
+        if outline.len() < 3 {
+            return None;
+        }
+        if is_convex_polygon(outline) {
+            return prism(outline).map(Into::into);
+        }
+
+        let parts = triangulate_polygon(outline)?
+            .into_iter()
+            .filter_map(|[a, b, c]| prism(&[outline[a], outline[b], outline[c]]))
+            .map(|shape| (parry::math::Isometry::identity(), shape))
+            .collect::<Vec<_>>();
+        (!parts.is_empty()).then(|| SharedShape::compound(parts).into())
+    }
+
+    /// Creates a collider by revolving a 2D profile around the Y axis.
+    ///
+    /// The `profile` is a list of points in the XY plane ordered from bottom to top, where the X coordinate
+    /// is the distance from the Y axis and the Y coordinate is the height. Each circle of the revolution
+    /// is approximated by `segments` points. A profile that produces a convex solid, like a cone or a dome,
+    /// results in a single convex polyhedron, and other profiles, like bottles, are decomposed into
+    /// a compound of convex frustums, one for each edge of the profile.
+    ///
+    /// Returns `None` if the profile has fewer than two points, if `segments` is less than three,
+    /// or if the resulting solid is degenerate.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(feature = "f32")]
+    /// # fn setup(mut commands: Commands) {
+    /// // A bottle with a narrow neck
+    /// let bottle = Collider::revolution(
+    ///     &[
+    ///         Vec2::new(0.5, 0.0),
+    ///         Vec2::new(0.5, 1.0),
+    ///         Vec2::new(0.15, 1.4),
+    ///         Vec2::new(0.15, 1.8),
+    ///     ],
+    ///     16,
+    /// );
+    /// commands.spawn((RigidBody::Dynamic, bottle.unwrap()));
+    /// # }
+    /// ```
+    #[cfg(feature = "3d")]
+    pub fn revolution(profile: &[Vector2], segments: u32) -> Option<Self> {
+        if profile.len() < 2 || segments < 3 {
+            return None;
+        }
+
+        let ring = |point: Vector2| -> Vec<parry::math::Point<Scalar>> {
+            let radius = point.x.max(0.0);
+            if radius <= Scalar::EPSILON {
+                return vec![Vector::new(0.0, point.y, 0.0).into()];
+            }
+            (0..segments)
+                .map(|i| {
+                    let angle = TAU * i as Scalar / segments as Scalar;
+                    Vector::new(radius * angle.cos(), point.y, radius * angle.sin()).into()
+                })
+                .collect()
+        };
+
+        // The solid is convex if the profile, closed by the axis, is a convex polygon.
+        let mut closed_profile = vec![Vector2::new(0.0, profile[0].y)];
+        closed_profile.extend_from_slice(profile);
+        closed_profile.push(Vector2::new(0.0, profile[profile.len() - 1].y));
+        if is_convex_polygon(&closed_profile) {
+            let points = profile.iter().flat_map(|p| ring(*p)).collect::<Vec<_>>();
+            return SharedShape::convex_hull(&points).map(Into::into);
+        }
+
+        let parts = profile
+            .windows(2)
+            .filter(|edge| (edge[1].y - edge[0].y).abs() > Scalar::EPSILON)
+            .filter_map(|edge| {
+                let mut points = ring(edge[0]);
+                points.extend(ring(edge[1]));
+                SharedShape::convex_hull(&points)
+            })
+            .map(|shape| (parry::math::Isometry::identity(), shape))
+            .collect::<Vec<_>>();
+        (!parts.is_empty()).then(|| SharedShape::compound(parts).into())
+    }
+
     /// Creates a collider with a heightfield shape.
     ///
     /// A 2D heightfield is a segment along the `X` axis, subdivided at regular intervals.
@@ -965,6 +1503,104 @@ impl Collider {
         })
     }
 
+    /// Creates a collider with a triangle mesh shape from a `Mesh`, storing the texture coordinates
+    /// of the mesh so that [raycast hits](RayHitData::uv) can report the UVs at the hit point.
+    ///
+    /// Returns `None` if the mesh doesn't have positions, indices or UVs.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    ///     let mesh = Mesh::from(Cuboid::default());
+    ///     commands.spawn((
+    ///         Collider::trimesh_from_mesh_with_uvs(&mesh).unwrap(),
+    ///         PbrBundle {
+    ///             mesh: meshes.add(mesh),
+    ///             ..default()
+    ///         },
+    ///     ));
+    /// }
+    /// ```
+    #[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
+    pub fn trimesh_from_mesh_with_uvs(mesh: &Mesh) -> Option<Self> {
+        let VertexAttributeValues::Float32x2(uvs) = mesh.attribute(Mesh::ATTRIBUTE_UV_0)? else {
+            return None;
+        };
+        let (vertices, indices) = extract_mesh_vertices_indices(mesh)?;
+
+        let triangle_uvs = indices
+            .iter()
+            .map(|triangle| {
+                triangle
+                    .map(|index| Vec2::from(uvs.get(index as usize).copied().unwrap_or_default()))
+            })
+            .collect();
+
+        // Merging duplicate vertices keeps the order of the triangles, so the UVs stay valid.
+        let collider: Collider = SharedShape::trimesh_with_flags(
+            vertices,
+            indices,
+            TrimeshFlags::MERGE_DUPLICATE_VERTICES.into(),
+        )
+        .into();
+        Some(collider.with_trimesh_uvs(triangle_uvs))
+    }
+
+    /// Creates a collider with a triangle mesh shape from a `Mesh`, storing the vertex normals
+    /// of the mesh so that [raycast hits](RayHitData::smooth_normal) can report the smooth normal
+    /// of the visual surface at the hit point.
+    ///
+    /// Returns `None` if the mesh doesn't have positions, indices or normals.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    ///     let mesh = Mesh::from(Sphere::default());
+    ///     commands.spawn((
+    ///         Collider::trimesh_from_mesh_with_normals(&mesh).unwrap(),
+    ///         PbrBundle {
+    ///             mesh: meshes.add(mesh),
+    ///             ..default()
+    ///         },
+    ///     ));
+    /// }
+    /// ```
+    #[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
+    pub fn trimesh_from_mesh_with_normals(mesh: &Mesh) -> Option<Self> {
+        let VertexAttributeValues::Float32x3(normals) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)?
+        else {
+            return None;
+        };
+        let (vertices, indices) = extract_mesh_vertices_indices(mesh)?;
+
+        let triangle_normals = indices
+            .iter()
+            .map(|triangle| {
+                triangle.map(|index| {
+                    let [x, y, z] = normals.get(index as usize).copied().unwrap_or_default();
+                    Vector::new(x as Scalar, y as Scalar, z as Scalar)
+                })
+            })
+            .collect();
+
+        // Merging duplicate vertices keeps the order of the triangles, so the normals stay valid.
+        let collider: Collider = SharedShape::trimesh_with_flags(
+            vertices,
+            indices,
+            TrimeshFlags::MERGE_DUPLICATE_VERTICES.into(),
+        )
+        .into();
+        Some(collider.with_trimesh_normals(triangle_normals))
+    }
+
     /// Creates a collider with a triangle mesh shape from a `Mesh` using the given [`TrimeshFlags`]
     /// for controlling the preprocessing.
     ///
@@ -1259,6 +1895,302 @@ fn extract_mesh_vertices_indices(mesh: &Mesh) -> Option<VerticesIndices> {
     Some((vtx, idx))
 }
 
+/// Returns true if the polygon is convex. Collinear vertices are allowed.
+#[cfg(feature = "3d")]
+fn is_convex_polygon(polygon: &[Vector2]) -> bool {
+    let count = polygon.len();
+    let mut sign = 0.0;
+    for i in 0..count {
+        let (a, b, c) = (
+            polygon[i],
+            polygon[(i + 1) % count],
+            polygon[(i + 2) % count],
+        );
+        let cross = (b - a).perp_dot(c - b);
+        if cross.abs() <= Scalar::EPSILON {
+            continue;
+        }
+        if sign == 0.0 {
+            sign = cross.signum();
+        } else if cross.signum() != sign {
+            return false;
+        }
+    }
+    sign != 0.0
+}
+
+/// Triangulates a simple polygon using ear clipping. Returns `None` if the polygon is degenerate.
+#[cfg(feature = "3d")]
+fn triangulate_polygon(polygon: &[Vector2]) -> Option<Vec<[usize; 3]>> {
+    let signed_area = (0..polygon.len())
+        .map(|i| polygon[i].perp_dot(polygon[(i + 1) % polygon.len()]))
+        .sum::<Scalar>();
+    if signed_area.abs() <= Scalar::EPSILON {
+        return None;
+    }
+
+    // Use counterclockwise winding.
+    let mut remaining: Vec<usize> = if signed_area > 0.0 {
+        (0..polygon.len()).collect()
+    } else {
+        (0..polygon.len()).rev().collect()
+    };
+    let mut triangles = Vec::with_capacity(polygon.len() - 2);
+
+    while remaining.len() > 3 {
+        let count = remaining.len();
+        let ear = (0..count).find(|&i| {
+            let (a, b, c) = (
+                remaining[(i + count - 1) % count],
+                remaining[i],
+                remaining[(i + 1) % count],
+            );
+            let (pa, pb, pc) = (polygon[a], polygon[b], polygon[c]);
+            if (pb - pa).perp_dot(pc - pb) <= 0.0 {
+                return false;
+            }
+            // No other vertex may be inside the ear.
+            remaining.iter().all(|&j| {
+                if j == a || j == b || j == c {
+                    return true;
+                }
+                let p = polygon[j];
+                let inside = (pb - pa).perp_dot(p - pa) >= 0.0
+                    && (pc - pb).perp_dot(p - pb) >= 0.0
+                    && (pa - pc).perp_dot(p - pc) >= 0.0;
+                !inside
+            })
+        })?;
+        triangles.push([
+            remaining[(ear + count - 1) % count],
+            remaining[ear],
+            remaining[(ear + 1) % count],
+        ]);
+        remaining.remove(ear);
+    }
+    triangles.push([remaining[0], remaining[1], remaining[2]]);
+
+    Some(triangles)
+}
+
+/// Grows a shape by the given margin. See [`Collider::inflated`].
+fn inflate_shape(shape: &SharedShape, margin: Scalar) -> SharedShape {
+    if margin <= 0.0 {
+        return shape.clone();
+    }
+
+    let round = |inner_shape: SharedShape, border_radius: Scalar| {
+        if let Some(cuboid) = inner_shape.as_cuboid() {
+            SharedShape::new(RoundShape {
+                inner_shape: *cuboid,
+                border_radius,
+            })
+        } else if let Some(triangle) = inner_shape.as_triangle() {
+            SharedShape::new(RoundShape {
+                inner_shape: *triangle,
+                border_radius,
+            })
+        } else {
+            inner_shape
+        }
+    };
+
+    match shape.as_typed_shape() {
+        TypedShape::Ball(b) => SharedShape::ball(b.radius + margin),
+        TypedShape::Capsule(c) => SharedShape::capsule(c.segment.a, c.segment.b, c.radius + margin),
+        TypedShape::Segment(s) => SharedShape::capsule(s.a, s.b, margin),
+        TypedShape::Cuboid(c) => round(SharedShape::new(*c), margin),
+        TypedShape::RoundCuboid(c) => {
+            round(SharedShape::new(c.inner_shape), c.border_radius + margin)
+        }
+        TypedShape::Triangle(t) => round(SharedShape::new(*t), margin),
+        TypedShape::RoundTriangle(t) => {
+            round(SharedShape::new(t.inner_shape), t.border_radius + margin)
+        }
+        #[cfg(feature = "2d")]
+        TypedShape::ConvexPolygon(cp) => SharedShape::new(RoundShape {
+            inner_shape: cp.clone(),
+            border_radius: margin,
+        }),
+        #[cfg(feature = "2d")]
+        TypedShape::RoundConvexPolygon(cp) => SharedShape::new(RoundShape {
+            inner_shape: cp.inner_shape.clone(),
+            border_radius: cp.border_radius + margin,
+        }),
+        #[cfg(feature = "3d")]
+        TypedShape::ConvexPolyhedron(cp) => SharedShape::new(RoundShape {
+            inner_shape: cp.clone(),
+            border_radius: margin,
+        }),
+        #[cfg(feature = "3d")]
+        TypedShape::RoundConvexPolyhedron(cp) => SharedShape::new(RoundShape {
+            inner_shape: cp.inner_shape.clone(),
+            border_radius: cp.border_radius + margin,
+        }),
+        #[cfg(feature = "3d")]
+        TypedShape::Cylinder(c) => SharedShape::new(RoundShape {
+            inner_shape: *c,
+            border_radius: margin,
+        }),
+        #[cfg(feature = "3d")]
+        TypedShape::RoundCylinder(c) => SharedShape::new(RoundShape {
+            inner_shape: c.inner_shape,
+            border_radius: c.border_radius + margin,
+        }),
+        #[cfg(feature = "3d")]
+        TypedShape::Cone(c) => SharedShape::new(RoundShape {
+            inner_shape: *c,
+            border_radius: margin,
+        }),
+        #[cfg(feature = "3d")]
+        TypedShape::RoundCone(c) => SharedShape::new(RoundShape {
+            inner_shape: c.inner_shape,
+            border_radius: c.border_radius + margin,
+        }),
+        TypedShape::Compound(c) => SharedShape::compound(
+            c.shapes()
+                .iter()
+                .map(|(isometry, shape)| (*isometry, inflate_shape(shape, margin)))
+                .collect(),
+        ),
+        TypedShape::Polyline(p) => SharedShape::compound(
+            p.segments()
+                .map(|s| {
+                    (
+                        parry::math::Isometry::identity(),
+                        SharedShape::capsule(s.a, s.b, margin),
+                    )
+                })
+                .collect(),
+        ),
+        #[cfg(feature = "2d")]
+        TypedShape::HeightField(h) => SharedShape::compound(
+            h.segments()
+                .map(|s| {
+                    (
+                        parry::math::Isometry::identity(),
+                        SharedShape::capsule(s.a, s.b, margin),
+                    )
+                })
+                .collect(),
+        ),
+        #[cfg(feature = "3d")]
+        TypedShape::HeightField(h) => SharedShape::compound(
+            h.triangles()
+                .map(|t| {
+                    (
+                        parry::math::Isometry::identity(),
+                        round(SharedShape::new(t), margin),
+                    )
+                })
+                .collect(),
+        ),
+        TypedShape::TriMesh(t) => SharedShape::compound(
+            t.triangles()
+                .map(|t| {
+                    (
+                        parry::math::Isometry::identity(),
+                        round(SharedShape::new(t), margin),
+                    )
+                })
+                .collect(),
+        ),
+        _ => shape.clone(),
+    }
+}
+
+/// Shrinks a shape by the given margin. See [`Collider::deflated`].
+fn deflate_shape(shape: &SharedShape, margin: Scalar) -> SharedShape {
+    // The minimum size of a deflated shape, to avoid degenerate shapes.
+    const MIN_SIZE: Scalar = 1e-4;
+
+    if margin <= 0.0 {
+        return shape.clone();
+    }
+
+    // Moves the points of a convex shape towards their center by the margin.
+    let shrink_points = |points: &[parry::math::Point<Scalar>]| {
+        let center = points
+            .iter()
+            .fold(Vector::ZERO, |sum, p| sum + Vector::from(*p))
+            / points.len().max(1) as Scalar;
+        points
+            .iter()
+            .map(|p| {
+                let offset = Vector::from(*p) - center;
+                let length = offset.length();
+                let new_length = (length - margin).max(MIN_SIZE);
+                (center + offset.normalize_or_zero() * new_length).into()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Rounded shapes first shrink their border radius, and then their inner shape.
+    let deflate_round = |inner_shape: SharedShape, border_radius: Scalar| {
+        if border_radius >= margin {
+            inflate_shape(&inner_shape, border_radius - margin)
+        } else {
+            deflate_shape(&inner_shape, margin - border_radius)
+        }
+    };
+
+    match shape.as_typed_shape() {
+        TypedShape::Ball(b) => SharedShape::ball((b.radius - margin).max(MIN_SIZE)),
+        TypedShape::Capsule(c) => {
+            SharedShape::capsule(c.segment.a, c.segment.b, (c.radius - margin).max(MIN_SIZE))
+        }
+        TypedShape::Cuboid(c) => SharedShape::new(parry::shape::Cuboid::new(
+            c.half_extents
+                .map(|half_extent| (half_extent - margin).max(MIN_SIZE)),
+        )),
+        TypedShape::RoundCuboid(c) => {
+            deflate_round(SharedShape::new(c.inner_shape), c.border_radius)
+        }
+        TypedShape::RoundTriangle(t) => {
+            deflate_round(SharedShape::new(t.inner_shape), t.border_radius)
+        }
+        #[cfg(feature = "2d")]
+        TypedShape::ConvexPolygon(cp) => {
+            SharedShape::convex_hull(&shrink_points(cp.points())).unwrap_or_else(|| shape.clone())
+        }
+        #[cfg(feature = "2d")]
+        TypedShape::RoundConvexPolygon(cp) => {
+            deflate_round(SharedShape::new(cp.inner_shape.clone()), cp.border_radius)
+        }
+        #[cfg(feature = "3d")]
+        TypedShape::ConvexPolyhedron(cp) => {
+            SharedShape::convex_hull(&shrink_points(cp.points())).unwrap_or_else(|| shape.clone())
+        }
+        #[cfg(feature = "3d")]
+        TypedShape::RoundConvexPolyhedron(cp) => {
+            deflate_round(SharedShape::new(cp.inner_shape.clone()), cp.border_radius)
+        }
+        #[cfg(feature = "3d")]
+        TypedShape::Cylinder(c) => SharedShape::cylinder(
+            (c.half_height - margin).max(MIN_SIZE),
+            (c.radius - margin).max(MIN_SIZE),
+        ),
+        #[cfg(feature = "3d")]
+        TypedShape::RoundCylinder(c) => {
+            deflate_round(SharedShape::new(c.inner_shape), c.border_radius)
+        }
+        #[cfg(feature = "3d")]
+        TypedShape::Cone(c) => SharedShape::cone(
+            (c.half_height - margin).max(MIN_SIZE),
+            (c.radius - margin).max(MIN_SIZE),
+        ),
+        #[cfg(feature = "3d")]
+        TypedShape::RoundCone(c) => deflate_round(SharedShape::new(c.inner_shape), c.border_radius),
+        TypedShape::Compound(c) => SharedShape::compound(
+            c.shapes()
+                .iter()
+                .map(|(isometry, shape)| (*isometry, deflate_shape(shape, margin)))
+                .collect(),
+        ),
+        _ => shape.clone(),
+    }
+}
+
 fn scale_shape(
     shape: &SharedShape,
     scale: Vector,
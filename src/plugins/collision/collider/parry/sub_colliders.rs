@@ -0,0 +1,190 @@
+use crate::{prelude::*, utils::make_isometry};
+use bevy::prelude::*;
+use parry::shape::SharedShape;
+
+/// An identifier for a sub-collider in [`SubColliders`].
+///
+/// Identifiers of removed sub-colliders can be reused by sub-colliders that are inserted later.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubColliderId(pub u32);
+
+/// A set of colliders registered on a single entity without spawning an entity for each of them.
+///
+/// Procedural and voxel worlds often have thousands of static colliders per chunk. Spawning an entity
+/// for each of them adds a lot of entity and archetype overhead, and every collider takes part in the broad phase
+/// separately. With [`SubColliders`], the colliders of a chunk are stored on one entity, and its [`Collider`]
+/// is automatically rebuilt as a [compound](Collider::compound) of the sub-colliders whenever they change.
+/// The broad phase then only sees one collider per chunk, and the compound shape finds the colliding
+/// sub-colliders using its own acceleration structure.
+///
+/// The sub-collider involved in a contact can be found using [`SubColliders::id_for_contact`].
+///
+/// Sub-colliders can't be composite shapes like [trimeshes](Collider::trimesh), [heightfields](Collider::heightfield)
+/// or [compound shapes](Collider::compound). If all sub-colliders are removed, the [`Collider`] is removed as well.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// fn spawn_chunk(mut commands: Commands) {
+///     let mut sub_colliders = SubColliders::default();
+///
+///     // Register a row of blocks without spawning an entity for each block
+///     for x in 0..16 {
+#[cfg_attr(
+    feature = "2d",
+    doc = "        let position = Vector::new(x as Scalar, 0.0);"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "        let position = Vector::new(x as Scalar, 0.0, 0.0);"
+)]
+#[cfg_attr(
+    feature = "2d",
+    doc = "        sub_colliders.insert(position, Rotation::default(), Collider::rectangle(1.0, 1.0));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "        sub_colliders.insert(position, Rotation::default(), Collider::cuboid(1.0, 1.0, 1.0));"
+)]
+///     }
+///
+///     commands.spawn((RigidBody::Static, sub_colliders));
+/// }
+/// ```
+#[derive(Component, Clone, Default)]
+pub struct SubColliders {
+    slots: Vec<Option<(Position, Rotation, Collider)>>,
+    free_slots: Vec<u32>,
+    /// The slots of the sub-shapes of the compound collider built from the sub-colliders.
+    subshape_slots: Vec<u32>,
+}
+
+impl SubColliders {
+    /// Inserts a sub-collider with the given local position and rotation and returns its identifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the collider is a composite shape like a trimesh, heightfield or compound shape.
+    pub fn insert(
+        &mut self,
+        position: impl Into<Position>,
+        rotation: impl Into<Rotation>,
+        collider: impl Into<Collider>,
+    ) -> SubColliderId {
+        let collider = collider.into();
+        assert!(
+            collider.shape().as_composite_shape().is_none(),
+            "sub-colliders can't be composite shapes"
+        );
+
+        let sub_collider = Some((position.into(), rotation.into(), collider));
+        if let Some(slot) = self.free_slots.pop() {
+            self.slots[slot as usize] = sub_collider;
+            SubColliderId(slot)
+        } else {
+            self.slots.push(sub_collider);
+            SubColliderId(self.slots.len() as u32 - 1)
+        }
+    }
+
+    /// Removes the sub-collider with the given identifier and returns its collider.
+    pub fn remove(&mut self, id: SubColliderId) -> Option<Collider> {
+        let (_, _, collider) = self.slots.get_mut(id.0 as usize)?.take()?;
+        self.free_slots.push(id.0);
+        Some(collider)
+    }
+
+    /// Returns the local position, rotation and collider of the sub-collider with the given identifier.
+    pub fn get(&self, id: SubColliderId) -> Option<(&Position, &Rotation, &Collider)> {
+        self.slots
+            .get(id.0 as usize)?
+            .as_ref()
+            .map(|(position, rotation, collider)| (position, rotation, collider))
+    }
+
+    /// Returns an iterator over the identifiers, local positions, rotations and colliders of the sub-colliders.
+    pub fn iter(&self) -> impl Iterator<Item = (SubColliderId, &Position, &Rotation, &Collider)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, sub_collider)| {
+                sub_collider.as_ref().map(|(position, rotation, collider)| {
+                    (SubColliderId(slot as u32), position, rotation, collider)
+                })
+            })
+    }
+
+    /// Returns the number of sub-colliders.
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free_slots.len()
+    }
+
+    /// Returns true if there are no sub-colliders.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the identifier of the sub-collider at the given [contact manifold](ContactManifold).
+    ///
+    /// If `is_first` is true, the entity with the sub-colliders is the first entity of the collision,
+    /// and otherwise it is the second entity.
+    ///
+    /// Returns `None` if the collider has not been rebuilt since the sub-colliders were last modified.
+    pub fn id_for_contact(
+        &self,
+        manifold: &ContactManifold,
+        is_first: bool,
+    ) -> Option<SubColliderId> {
+        let subshape = if is_first {
+            manifold.subshape1
+        } else {
+            manifold.subshape2
+        };
+        self.subshape_slots
+            .get(subshape? as usize)
+            .map(|slot| SubColliderId(*slot))
+    }
+
+    /// Builds a compound shape from the sub-colliders, or returns `None` if there are no sub-colliders.
+    fn build_shape(&mut self) -> Option<SharedShape> {
+        let (slots, shapes): (Vec<_>, Vec<_>) = self
+            .iter()
+            .map(|(id, position, rotation, collider)| {
+                (
+                    id.0,
+                    (
+                        make_isometry(*position, *rotation),
+                        collider.shape_scaled().clone(),
+                    ),
+                )
+            })
+            .unzip();
+        self.subshape_slots = slots;
+        (!shapes.is_empty()).then(|| SharedShape::compound(shapes))
+    }
+}
+
+/// Rebuilds the [`Collider`] of entities whose [`SubColliders`] have changed.
+pub(crate) fn update_sub_colliders(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut SubColliders, Option<&mut Collider>), Changed<SubColliders>>,
+) {
+    for (entity, mut sub_colliders, collider) in &mut query {
+        // Bypass change detection so that the rebuild doesn't trigger another rebuild.
+        let shape = sub_colliders.bypass_change_detection().build_shape();
+        match (shape, collider) {
+            (Some(shape), Some(mut collider)) => collider.set_shape(shape),
+            (Some(shape), None) => {
+                commands.entity(entity).insert(Collider::from(shape));
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<Collider>();
+            }
+            (None, None) => (),
+        }
+    }
+}
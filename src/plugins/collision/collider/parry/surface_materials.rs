@@ -0,0 +1,103 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use parry::shape::TypedShape;
+
+/// Per-triangle surface material indices for [trimesh](Collider::trimesh) and
+/// [heightfield](Collider::heightfield) colliders.
+///
+/// The indices are user-defined and can refer to anything, like footstep sounds or tire grip values
+/// stored in a lookup table. There should be one index per triangle for trimeshes and one index
+/// per cell for heightfields. For other shapes, the first index is used for the whole collider.
+///
+/// The material at a contact point can be looked up using [`SurfaceMaterials::for_contact`],
+/// and the material at a raycast hit using [`SurfaceMaterials::for_ray_hit`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// const GRASS: u32 = 0;
+/// const ROCK: u32 = 1;
+///
+/// fn print_footstep_surfaces(
+///     mut collision_event_reader: EventReader<Collision>,
+///     terrain: Query<(&Collider, &SurfaceMaterials)>,
+/// ) {
+///     for Collision(contacts) in collision_event_reader.read() {
+///         let Ok((collider, materials)) = terrain.get(contacts.entity1) else {
+///             continue;
+///         };
+///         for manifold in contacts.manifolds.iter() {
+///             match materials.for_contact(collider, manifold, true) {
+///                 Some(GRASS) => println!("Stepped on grass"),
+///                 Some(ROCK) => println!("Stepped on rock"),
+///                 _ => (),
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", reflect(Serialize, Deserialize))]
+#[reflect(Component)]
+pub struct SurfaceMaterials(pub Vec<u32>);
+
+impl SurfaceMaterials {
+    /// Creates a new [`SurfaceMaterials`] component from the given material indices.
+    pub fn new(indices: impl Into<Vec<u32>>) -> Self {
+        Self(indices.into())
+    }
+
+    /// Returns the material index of the triangle or cell with the given index.
+    pub fn get(&self, index: u32) -> Option<u32> {
+        self.0.get(index as usize).copied()
+    }
+
+    /// Returns the material index at the given [contact manifold](ContactManifold) of the collider.
+    ///
+    /// If `is_first` is true, the collider is the first entity of the collision,
+    /// and otherwise it is the second entity.
+    pub fn for_contact(
+        &self,
+        collider: &Collider,
+        manifold: &ContactManifold,
+        is_first: bool,
+    ) -> Option<u32> {
+        let subshape = if is_first {
+            manifold.subshape1
+        } else {
+            manifold.subshape2
+        };
+        self.get(Self::element_index(collider, subshape.unwrap_or(0)))
+    }
+
+    /// Returns the material index at the given [raycast hit](RayHitData) on the collider.
+    pub fn for_ray_hit(&self, collider: &Collider, hit: &RayHitData) -> Option<u32> {
+        let triangle = match hit.feature {
+            FeatureId::Face(index) => index,
+            _ => 0,
+        };
+        self.get(Self::element_index(collider, triangle))
+    }
+
+    /// Maps the index of a triangle in a trimesh or heightfield to the index of its material.
+    fn element_index(collider: &Collider, triangle: u32) -> u32 {
+        match collider.shape().as_typed_shape() {
+            // Hits on back faces of trimeshes are offset by the number of triangles.
+            TypedShape::TriMesh(trimesh) => triangle % trimesh.indices().len().max(1) as u32,
+            // In 3D, each heightfield cell consists of two triangles.
+            #[cfg(feature = "3d")]
+            TypedShape::HeightField(heightfield) => {
+                let triangles = (heightfield.nrows() * heightfield.ncols() * 2).max(1) as u32;
+                (triangle % triangles) / 2
+            }
+            #[cfg(feature = "2d")]
+            TypedShape::HeightField(_) => triangle,
+            _ => 0,
+        }
+    }
+}
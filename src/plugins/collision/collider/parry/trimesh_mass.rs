@@ -0,0 +1,107 @@
+//! Mass properties of closed triangle meshes.
+
+use crate::prelude::*;
+use bevy::utils::HashMap;
+use parry::shape::TriMesh;
+
+/// Returns the number of edges of the trimesh that are not shared by exactly two triangles
+/// with opposite winding. A closed, consistently oriented mesh has no such edges.
+///
+/// Vertices with identical positions are treated as the same vertex, so meshes with split vertices
+/// for normals or texture coordinates are handled correctly.
+pub(super) fn open_edge_count(trimesh: &TriMesh) -> usize {
+    let mut canonical_indices = HashMap::<[u64; 3], u32>::default();
+    let vertex_ids = trimesh
+        .vertices()
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let key = [
+                p.x.to_bits() as u64,
+                p.y.to_bits() as u64,
+                p.z.to_bits() as u64,
+            ];
+            *canonical_indices.entry(key).or_insert(i as u32)
+        })
+        .collect::<Vec<_>>();
+
+    // Count the directed edges. Each edge of a closed mesh is traversed once in each direction.
+    let mut edges = HashMap::<(u32, u32), i32>::default();
+    for triangle in trimesh.indices() {
+        for k in 0..3 {
+            let (a, b) = (
+                vertex_ids[triangle[k] as usize],
+                vertex_ids[triangle[(k + 1) % 3] as usize],
+            );
+            if a != b {
+                *edges.entry((a, b)).or_default() += 1;
+            }
+        }
+    }
+
+    edges
+        .iter()
+        .filter(|((a, b), count)| **count != 1 || edges.get(&(*b, *a)) != Some(&1))
+        .count()
+}
+
+/// Computes the exact mass properties of the volume enclosed by a closed trimesh.
+///
+/// The volume is decomposed into tetrahedra between each triangle and a reference point,
+/// and their signed volumes and covariance matrices are summed. The result is independent
+/// of the winding order of the triangles as long as it is consistent.
+///
+/// Returns `None` if the enclosed volume is zero.
+pub(super) fn closed_trimesh_mass_properties(
+    trimesh: &TriMesh,
+    density: Scalar,
+) -> Option<ColliderMassProperties> {
+    let vertices = trimesh.vertices();
+    // Use a point near the mesh as the reference point to reduce numerical error.
+    let reference = Vector::from(trimesh.local_aabb().center());
+
+    let mut volume = 0.0;
+    let mut weighted_center = Vector::ZERO;
+    let mut covariance = Matrix3::ZERO;
+
+    // The canonical covariance of a tetrahedron is scaled by this matrix.
+    let canonical = Matrix3::from_cols(
+        Vector::new(2.0, 1.0, 1.0),
+        Vector::new(1.0, 2.0, 1.0),
+        Vector::new(1.0, 1.0, 2.0),
+    );
+
+    for triangle in trimesh.indices() {
+        let [a, b, c] = triangle.map(|i| Vector::from(vertices[i as usize]) - reference);
+        let determinant = a.dot(b.cross(c));
+        let tetrahedron = Matrix3::from_cols(a, b, c);
+
+        volume += determinant / 6.0;
+        weighted_center += (a + b + c) * determinant / 24.0;
+        covariance += tetrahedron * canonical * tetrahedron.transpose() * (determinant / 120.0);
+    }
+
+    if volume.abs() <= Scalar::EPSILON {
+        return None;
+    }
+
+    // The signs of the volume and covariance depend on the winding order of the triangles.
+    let sign = volume.signum();
+    let mass = volume.abs() * density;
+    let local_center = weighted_center / volume;
+    let covariance = covariance * (sign * density);
+
+    // Move the covariance to the center of mass and convert it to an inertia tensor.
+    let outer = |v: Vector| Matrix3::from_cols(v * v.x, v * v.y, v * v.z);
+    let covariance = covariance - outer(local_center) * mass;
+    let trace = covariance.x_axis.x + covariance.y_axis.y + covariance.z_axis.z;
+    let inertia = Matrix3::from_diagonal(Vector::splat(trace)) - covariance;
+
+    Some(ColliderMassProperties {
+        mass: Mass(mass),
+        inverse_mass: InverseMass(1.0 / mass),
+        inertia: Inertia(inertia),
+        inverse_inertia: InverseInertia(inertia.inverse()),
+        center_of_mass: CenterOfMass(local_center + reference),
+    })
+}
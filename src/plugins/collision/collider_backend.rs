@@ -24,11 +24,24 @@ use bevy::{
 /// - Updates collider mass properties.
 /// - Updates collider scale based on `Transform` scale.
 /// - Propagates child collider positions.
+/// - Cleans up internal data when colliders or rigid bodies are removed.
+///
+/// ## Component removal
+///
+/// Colliders and rigid bodies can be removed independently at runtime:
+///
+/// - When a [`RigidBody`] is removed, its colliders are detached from it and their mass properties are
+/// subtracted from the entity's mass properties. The colliders keep detecting collisions, but they no longer
+/// have a collision response. If the rigid body is added back, the colliders are attached to it again.
+/// - When a collider is removed, its mass properties are subtracted from the rigid body it was attached to,
+/// and internal components like [`ColliderAabb`], [`ColliderParent`] and [`CollidingEntities`] are removed.
+/// Its collisions are ended, so [`CollisionEnded`] events are sent for them.
 ///
 /// ## Custom collision backends
 ///
 /// By default, [`PhysicsPlugins`] adds this plugin for the [`Collider`] component.
 /// You can also create custom collider backends by implementing the [`AnyCollider`] and [`ScalableCollider`] traits.
+/// Backends that also support ray casts and shape casts can implement [`CollisionBackend`].
 ///
 /// To use a custom collider backend, simply add the [`ColliderBackendPlugin`] with your collider type:
 ///
@@ -154,19 +167,45 @@ impl<C: ScalableCollider> Plugin for ColliderBackendPlugin<C> {
         physics_schedule.add_systems((
             update_collider_storage::<C>.before(PhysicsStepSet::BroadPhase),
             handle_collider_storage_removals::<C>.after(PhysicsStepSet::SpatialQuery),
-            handle_rigid_body_removals.after(PhysicsStepSet::SpatialQuery),
+            handle_rigid_body_removals::<C>
+                .after(PhysicsStepSet::SpatialQuery)
+                .before(handle_collider_storage_removals::<C>),
         ));
 
+        physics_schedule.add_systems(
+            handle_collider_removals::<C>
+                .after(PhysicsStepSet::BroadPhase)
+                .after(narrow_phase::reset_collision_states)
+                .before(PhysicsStepSet::Substeps)
+                // Allowing ambiguities is required so that it's possible
+                // to have multiple collision backends at the same time.
+                .ambiguous_with_all(),
+        );
+
         physics_schedule.add_systems(
             wake_on_collider_removed::<C>
                 .in_set(PhysicsStepSet::Sleeping)
-                .after(sleeping::mark_sleeping_bodies)
                 .before(sleeping::wake_on_changed)
                 // Allowing ambiguities is required so that it's possible
                 // to have multiple collision backends at the same time.
                 .ambiguous_with_all(),
         );
 
+        // Keep child collider positions and AABBs up to date for spatial queries while physics is paused
+        // if configured by `PhysicsPausedBehavior`.
+        app.add_systems(
+            self.schedule,
+            (
+                propagate_collider_transforms,
+                update_child_collider_position,
+                update_aabb::<C>,
+            )
+                .chain()
+                .in_set(PhysicsSet::StepSimulation)
+                .run_if(crate::setup::update_spatial_queries_while_paused)
+                .ambiguous_with_all(),
+        );
+
         app.add_systems(
             Update,
             (
@@ -560,21 +599,53 @@ fn update_collider_parents<C: AnyCollider>(
 }
 
 /// Updates colliders when the rigid bodies they were attached to have been removed.
-fn handle_rigid_body_removals(
+///
+/// The colliders are detached from the removed bodies, and their mass properties are subtracted
+/// from the mass properties remaining on the entities.
+#[allow(clippy::type_complexity)]
+pub(crate) fn handle_rigid_body_removals<C: AnyCollider>(
     mut commands: Commands,
-    colliders: Query<(Entity, &ColliderParent), Without<RigidBody>>,
+    mut colliders: Query<
+        (
+            Entity,
+            &ColliderParent,
+            &ColliderTransform,
+            &mut ColliderMassProperties,
+        ),
+        (With<C>, Without<RigidBody>),
+    >,
     bodies: Query<(), With<RigidBody>>,
-    removals: RemovedComponents<RigidBody>,
+    mut mass_props: Query<MassPropertiesQuery, Without<RigidBody>>,
+    mut collider_storage: ResMut<ColliderStorageMap<C>>,
+    mut removals: RemovedComponents<RigidBody>,
 ) {
     // Return if no rigid bodies have been removed
     if removals.is_empty() {
         return;
     }
 
-    for (collider_entity, collider_parent) in &colliders {
+    for (collider_entity, collider_parent, collider_transform, mut collider_mass_properties) in
+        &mut colliders
+    {
         // If the body associated with the collider parent entity doesn't exist,
         // remove ColliderParent and ColliderTransform.
         if !bodies.contains(collider_parent.get()) {
+            // Subtract the collider's mass properties from the mass properties left on the entity.
+            if let Ok(mut mass_properties) = mass_props.get_mut(collider_parent.get()) {
+                mass_properties -= ColliderMassProperties {
+                    center_of_mass: CenterOfMass(
+                        collider_transform
+                            .transform_point(collider_mass_properties.center_of_mass.0),
+                    ),
+                    ..*collider_mass_properties
+                };
+            }
+
+            // The collider no longer contributes to any mass properties. If it is attached to a body again,
+            // its mass properties are recomputed and added to the body.
+            *collider_mass_properties = ColliderMassProperties::ZERO;
+            collider_storage.map.remove(&collider_entity);
+
             commands.entity(collider_entity).remove::<(
                 ColliderParent,
                 ColliderTransform,
@@ -582,6 +653,53 @@ fn handle_rigid_body_removals(
             )>();
         }
     }
+
+    // Bodies that are no longer rigid bodies can't be sleeping.
+    for entity in removals.read() {
+        if let Some(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.remove::<Sleeping>();
+        }
+    }
+}
+
+/// Removes internal collider components from entities whose colliders have been removed,
+/// and ends the collisions of the removed colliders.
+///
+/// The mass properties of the removed colliders are subtracted from their rigid bodies
+/// in `update_collider_mass_properties`.
+fn handle_collider_removals<C: AnyCollider>(
+    mut commands: Commands,
+    colliders: Query<(), With<C>>,
+    mut collisions: ResMut<Collisions>,
+    mut removals: RemovedComponents<C>,
+) {
+    for entity in removals.read() {
+        // Skip despawned entities and entities where the collider has been added back.
+        let Some(mut entity_commands) = commands.get_entity(entity) else {
+            continue;
+        };
+        if colliders.contains(entity) {
+            continue;
+        }
+
+        entity_commands.remove::<(
+            ColliderAabb,
+            ColliderParent,
+            ColliderTransform,
+            PreviousColliderTransform,
+            ColliderMassProperties,
+            CollidingEntities,
+        )>();
+
+        // End the collisions of the removed collider so that they are removed after contact reporting,
+        // even if the other entity is sleeping or static.
+        for contacts in collisions.get_internal_mut().values_mut() {
+            if contacts.entity1 == entity || contacts.entity2 == entity {
+                contacts.during_current_frame = false;
+                contacts.during_current_substep = false;
+            }
+        }
+    }
 }
 
 /// Updates [`ColliderStorageMap`], a resource that stores some collider properties that need
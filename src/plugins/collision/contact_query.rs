@@ -11,6 +11,10 @@
 //! | [`intersection_test`] | Tests whether two [`Collider`]s are intersecting each other.              |
 //! | [`time_of_impact`]    | Computes when two moving [`Collider`]s hit each other for the first time. |
 //!
+//! The queries are plain functions that take colliders and their poses directly, so they can also be used
+//! outside of the ECS, for example in tooling, tests and offline baking. [`contact_manifolds`] computes
+//! the same manifolds that the [narrow phase](crate::plugins::collision::narrow_phase) uses.
+//!
 //! For geometric queries that query the entire world for intersections, like raycasting, shapecasting
 //! and point projection, see [spatial queries](spatial_query).
 
@@ -209,6 +213,8 @@ pub fn contact_manifolds(
                         0,
                     )],
                     index: 0,
+                    subshape1: None,
+                    subshape2: None,
                 }];
             }
         }
@@ -216,7 +222,16 @@ pub fn contact_manifolds(
 
     let mut manifold_index = 0;
 
-    manifolds
+    // Sub-shape indices are only meaningful for composite shapes like trimeshes and compound shapes,
+    // and for heightfields.
+    let is_composite = |collider: &Collider| {
+        let shape = collider.shape_scaled();
+        shape.as_composite_shape().is_some() || shape.as_heightfield().is_some()
+    };
+    let is_composite1 = is_composite(collider1);
+    let is_composite2 = is_composite(collider2);
+
+    let mut manifolds: Vec<ContactManifold> = manifolds
         .iter()
         .filter_map(|manifold| {
             let subpos1 = manifold.subshape_pos1.unwrap_or_default();
@@ -244,8 +259,10 @@ pub fn contact_manifolds(
                     .contacts()
                     .iter()
                     .enumerate()
-                    .map(|(contact_index, contact)| {
-                        ContactData::new(
+                    .map(|(contact_index, contact)| ContactData {
+                        feature1: contact.fid1.unpack().into(),
+                        feature2: contact.fid2.unpack().into(),
+                        ..ContactData::new(
                             subpos1.transform_point(&contact.local_p1).into(),
                             subpos2.transform_point(&contact.local_p2).into(),
                             normal1,
@@ -256,13 +273,471 @@ pub fn contact_manifolds(
                     })
                     .collect(),
                 index: manifold_index,
+                subshape1: is_composite1.then_some(manifold.subshape1),
+                subshape2: is_composite2.then_some(manifold.subshape2),
             };
 
             manifold_index += 1;
 
             Some(manifold)
         })
-        .collect()
+        .collect();
+
+    #[cfg(feature = "3d")]
+    if collider1.shape_scaled().as_trimesh().is_some()
+        && collider2.shape_scaled().as_capsule().is_some()
+    {
+        add_capsule_trimesh_contacts(
+            collider1,
+            collider2,
+            &mut manifolds,
+            true,
+            &isometry12,
+            prediction_distance,
+        );
+    } else if collider2.shape_scaled().as_trimesh().is_some()
+        && collider1.shape_scaled().as_capsule().is_some()
+    {
+        add_capsule_trimesh_contacts(
+            collider2,
+            collider1,
+            &mut manifolds,
+            false,
+            &isometry12.inverse(),
+            prediction_distance,
+        );
+    }
+
+    #[cfg(feature = "3d")]
+    {
+        let is_cylinder_contact_shape = |collider: &Collider| {
+            let shape = collider.shape_scaled();
+            shape.as_cylinder().is_some()
+                || shape.as_cuboid().is_some()
+                || shape.as_halfspace().is_some()
+        };
+        let has_cylinder = collider1.shape_scaled().as_cylinder().is_some()
+            || collider2.shape_scaled().as_cylinder().is_some();
+        if has_cylinder
+            && is_cylinder_contact_shape(collider1)
+            && is_cylinder_contact_shape(collider2)
+        {
+            let isometry21 = isometry12.inverse();
+            for manifold in manifolds.iter_mut() {
+                let _ = add_cylinder_rim_contacts(
+                    collider1,
+                    collider2,
+                    manifold,
+                    true,
+                    &isometry12,
+                    prediction_distance,
+                ) || add_cylinder_rim_contacts(
+                    collider2,
+                    collider1,
+                    manifold,
+                    false,
+                    &isometry21,
+                    prediction_distance,
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "2d")]
+    if collider1.chain_config().is_some() || collider2.chain_config().is_some() {
+        let rotation12 = Rotation::from_radians(isometry12.rotation.angle());
+        if let Some(config) = collider1.chain_config() {
+            apply_chain_ghost_vertices(collider1, config, &mut manifolds, true, rotation12);
+        }
+        if let Some(config) = collider2.chain_config() {
+            apply_chain_ghost_vertices(collider2, config, &mut manifolds, false, rotation12);
+        }
+        for (index, manifold) in manifolds.iter_mut().enumerate() {
+            manifold.index = index;
+        }
+    }
+
+    manifolds
+}
+
+/// Replaces single-point contacts between a capsule and the triangles of a trimesh with two-point contacts
+/// when the capsule is lying on a triangle.
+///
+/// Parry computes a single contact point for a capsule and a triangle, which makes capsules
+/// resting on trimesh floors wobble around the contact. Here, both end points of the capsule's
+/// segment are projected onto the triangle, and a contact is added for each of them if it is within
+/// the triangle and the prediction distance.
+///
+/// `capsule_to_trimesh` transforms points from the local space of the capsule to the local space of the trimesh.
+#[cfg(feature = "3d")]
+fn add_capsule_trimesh_contacts(
+    trimesh_collider: &Collider,
+    capsule_collider: &Collider,
+    manifolds: &mut [ContactManifold],
+    trimesh_is_first: bool,
+    capsule_to_trimesh: &parry::math::Isometry<Scalar>,
+    prediction_distance: Scalar,
+) {
+    let (Some(trimesh), Some(capsule)) = (
+        trimesh_collider.shape_scaled().as_trimesh(),
+        capsule_collider.shape_scaled().as_capsule(),
+    ) else {
+        return;
+    };
+
+    let endpoints: [Vector; 2] = [
+        (capsule_to_trimesh * capsule.segment.a).into(),
+        (capsule_to_trimesh * capsule.segment.b).into(),
+    ];
+
+    for manifold in manifolds.iter_mut() {
+        let subshape = if trimesh_is_first {
+            manifold.subshape1
+        } else {
+            manifold.subshape2
+        };
+        let Some(triangle_index) = subshape else {
+            continue;
+        };
+        if manifold.contacts.len() != 1 || triangle_index as usize >= trimesh.indices().len() {
+            continue;
+        }
+
+        let triangle = trimesh.triangle(triangle_index);
+        let (a, b, c): (Vector, Vector, Vector) =
+            (triangle.a.into(), triangle.b.into(), triangle.c.into());
+
+        // The normal pointing from the trimesh towards the capsule, in the local space of the trimesh.
+        let normal = if trimesh_is_first {
+            manifold.normal1
+        } else {
+            manifold.normal2
+        };
+
+        // Only use the triangle if the contact is on its face.
+        let face_normal = (b - a).cross(c - a).normalize_or_zero();
+        if face_normal.dot(normal).abs() < 1.0 - 1e-3 {
+            continue;
+        }
+
+        let mut contacts = vec![];
+        for (endpoint_index, endpoint) in endpoints.iter().enumerate() {
+            let distance = (*endpoint - a).dot(normal) - capsule.radius;
+            if distance > prediction_distance {
+                continue;
+            }
+
+            let point_on_triangle = *endpoint - normal * (*endpoint - a).dot(normal);
+            if !triangle_contains_point(a, b, c, face_normal, point_on_triangle) {
+                continue;
+            }
+            let point_on_capsule: Vector = capsule_to_trimesh
+                .inverse_transform_point(&(*endpoint - normal * capsule.radius).into())
+                .into();
+
+            let (point1, point2) = if trimesh_is_first {
+                (point_on_triangle, point_on_capsule)
+            } else {
+                (point_on_capsule, point_on_triangle)
+            };
+            let (feature1, feature2) = if trimesh_is_first {
+                (
+                    FeatureId::Face(triangle_index),
+                    FeatureId::Vertex(endpoint_index as u32),
+                )
+            } else {
+                (
+                    FeatureId::Vertex(endpoint_index as u32),
+                    FeatureId::Face(triangle_index),
+                )
+            };
+            contacts.push(ContactData {
+                feature1,
+                feature2,
+                ..ContactData::new(
+                    point1,
+                    point2,
+                    manifold.normal1,
+                    manifold.normal2,
+                    -distance,
+                    endpoint_index,
+                )
+            });
+        }
+
+        if contacts.len() == 2 {
+            manifold.contacts = contacts;
+        }
+    }
+}
+
+/// Returns true if the point, which lies in the plane of the triangle, is inside the triangle.
+#[cfg(feature = "3d")]
+fn triangle_contains_point(a: Vector, b: Vector, c: Vector, normal: Vector, point: Vector) -> bool {
+    let edge_test = |from: Vector, to: Vector| (to - from).cross(point - from).dot(normal) >= 0.0;
+    edge_test(a, b) && edge_test(b, c) && edge_test(c, a)
+}
+
+/// Replaces the contacts between a cylinder and a flat face of a plane, box or cylinder with contacts
+/// on the rim of the cylinder when the cylinder is resting on its cap or on its side.
+///
+/// Parry computes cylinder contacts using support mapping, which yields a single contact point
+/// for a cylinder resting on a face. This makes stacked cylinders and cylinders lying on their side wobble.
+/// Here, points on the rim of the cylinder are projected onto the contact plane, and a contact is added
+/// for each of them that is on the face of the other shape and within the prediction distance.
+///
+/// Returns true if the contacts were replaced.
+///
+/// `other_to_cylinder` transforms points from the local space of the other shape to the local space of the cylinder.
+#[cfg(feature = "3d")]
+fn add_cylinder_rim_contacts(
+    cylinder_collider: &Collider,
+    other_collider: &Collider,
+    manifold: &mut ContactManifold,
+    cylinder_is_first: bool,
+    other_to_cylinder: &parry::math::Isometry<Scalar>,
+    prediction_distance: Scalar,
+) -> bool {
+    // The tolerance for the alignment of the contact normal with the axis or the side of the cylinder.
+    const ALIGNMENT_TOLERANCE: Scalar = 0.02;
+    // The maximum distance of a projected rim point from the surface of the other shape.
+    const SURFACE_TOLERANCE: Scalar = 1e-3;
+
+    let Some(cylinder) = cylinder_collider.shape_scaled().as_cylinder() else {
+        return false;
+    };
+    let Some(contact) = manifold.contacts.first() else {
+        return false;
+    };
+    let other_shape = other_collider.shape_scaled();
+
+    // The normal pointing from the cylinder towards the other shape, and the contact point
+    // on the other shape, both in the local space of the cylinder.
+    let (normal, other_point) = if cylinder_is_first {
+        (
+            manifold.normal1,
+            Vector::from(other_to_cylinder * parry::math::Point::from(contact.point2)),
+        )
+    } else {
+        (
+            manifold.normal2,
+            Vector::from(other_to_cylinder * parry::math::Point::from(contact.point1)),
+        )
+    };
+
+    let (radius, half_height) = (cylinder.radius, cylinder.half_height);
+    let rim_points: Vec<Vector> = if normal.y.abs() >= 1.0 - ALIGNMENT_TOLERANCE {
+        // Resting on a cap.
+        let y = half_height * normal.y.signum();
+        vec![
+            Vector::new(radius, y, 0.0),
+            Vector::new(0.0, y, radius),
+            Vector::new(-radius, y, 0.0),
+            Vector::new(0.0, y, -radius),
+        ]
+    } else if normal.y.abs() <= ALIGNMENT_TOLERANCE {
+        // Resting on the side.
+        let radial = Vector::new(normal.x, 0.0, normal.z).normalize_or_zero() * radius;
+        vec![
+            radial + Vector::Y * half_height,
+            radial - Vector::Y * half_height,
+        ]
+    } else {
+        return false;
+    };
+
+    let mut contacts = vec![];
+    for rim_point in rim_points {
+        let distance = (other_point - rim_point).dot(normal);
+        if distance > prediction_distance {
+            continue;
+        }
+
+        // Project the rim point onto the contact plane and make sure that it is on the other shape.
+        let plane_point = rim_point + normal * distance;
+        let local_plane_point = other_to_cylinder.inverse_transform_point(&plane_point.into());
+        let projection = other_shape.project_local_point(&local_plane_point, true);
+        if !projection.is_inside
+            && (projection.point - local_plane_point).norm() > SURFACE_TOLERANCE
+        {
+            continue;
+        }
+
+        let (point1, point2) = if cylinder_is_first {
+            (rim_point, local_plane_point.into())
+        } else {
+            (local_plane_point.into(), rim_point)
+        };
+        contacts.push(ContactData::new(
+            point1,
+            point2,
+            manifold.normal1,
+            manifold.normal2,
+            -distance,
+            contacts.len(),
+        ));
+    }
+
+    if contacts.len() >= 2 {
+        manifold.contacts = contacts;
+        true
+    } else {
+        false
+    }
+}
+
+/// Removes or corrects contacts with a [chain](Collider::chain) collider that are caused by internal vertices
+/// or by the back side of a one-sided chain.
+///
+/// Each segment of the chain uses its neighbors as ghost vertices. A contact at a vertex is only kept
+/// if the vertex is convex and the contact normal is between the normals of the two adjacent segments.
+/// At concave and flat vertices, the normal of the segment is used instead, so shapes don't catch
+/// on internal edges. Contacts at convex vertices are kept by the segment that ends at the vertex,
+/// so that they aren't duplicated.
+///
+/// `rotation12` is the rotation of the second collider relative to the first one.
+#[cfg(feature = "2d")]
+fn apply_chain_ghost_vertices(
+    chain: &Collider,
+    config: ChainConfig,
+    manifolds: &mut Vec<ContactManifold>,
+    chain_is_first: bool,
+    rotation12: Rotation,
+) {
+    let Some(polyline) = chain.shape_scaled().as_polyline() else {
+        return;
+    };
+    let segment_count = polyline.num_segments() as u32;
+    if segment_count == 0 {
+        return;
+    }
+
+    let segment_direction = |index: u32| {
+        let segment = polyline.segment(index);
+        Vector::from(segment.b) - Vector::from(segment.a)
+    };
+    // The normal on the right side of the segment.
+    let right_normal =
+        |direction: Vector| Vector::new(direction.y, -direction.x).normalize_or_zero();
+    let previous = |index: u32| match index {
+        0 if config.closed => Some(segment_count - 1),
+        0 => None,
+        _ => Some(index - 1),
+    };
+    let next = |index: u32| match index + 1 {
+        next if next < segment_count => Some(next),
+        _ if config.closed => Some(0),
+        _ => None,
+    };
+
+    // The normal tolerance for treating a contact as a face contact.
+    const FACE_TOLERANCE: Scalar = 1e-3;
+
+    manifolds.retain_mut(|manifold| {
+        let (subshape, normal) = if chain_is_first {
+            (manifold.subshape1, manifold.normal1)
+        } else {
+            (manifold.subshape2, manifold.normal2)
+        };
+        let Some(index) = subshape else {
+            return true;
+        };
+        let Some(contact) = manifold.contacts.first() else {
+            return true;
+        };
+        let point = if chain_is_first {
+            contact.point1
+        } else {
+            contact.point2
+        };
+
+        let direction = segment_direction(index);
+        let length_squared = direction.length_squared();
+        if length_squared <= Scalar::EPSILON {
+            return true;
+        }
+
+        // Flip the normals if the contact is on the left side of a two-sided chain.
+        let face_normal = right_normal(direction);
+        let side = if normal.dot(face_normal) >= 0.0 {
+            1.0
+        } else if config.one_sided {
+            return false;
+        } else {
+            -1.0
+        };
+        let face_normal = face_normal * side;
+
+        if normal.dot(face_normal) >= 1.0 - FACE_TOLERANCE {
+            return true;
+        }
+
+        // The contact is at a vertex. Find the neighbor that shares the vertex.
+        let segment = polyline.segment(index);
+        let t = (point - Vector::from(segment.a)).dot(direction) / length_squared;
+        let at_end = t > 0.5;
+        let Some(neighbor) = (if at_end { next(index) } else { previous(index) }) else {
+            // The ends of an open chain are treated as rounded vertices.
+            return true;
+        };
+        let neighbor_direction = segment_direction(neighbor);
+        let neighbor_normal = right_normal(neighbor_direction) * side;
+
+        let turn = if at_end {
+            direction.perp_dot(neighbor_direction)
+        } else {
+            neighbor_direction.perp_dot(direction)
+        };
+        let is_convex = side * turn > Scalar::EPSILON;
+
+        if is_convex {
+            let (from, to) = if at_end {
+                (face_normal, neighbor_normal)
+            } else {
+                (neighbor_normal, face_normal)
+            };
+            let in_cone = from.perp_dot(normal) * side >= 0.0 && normal.perp_dot(to) * side >= 0.0;
+            if in_cone {
+                // The segment that ends at the vertex owns the vertex contact.
+                return at_end;
+            }
+            if normal.dot(neighbor_normal) > normal.dot(face_normal) {
+                // The contact belongs to the neighbor.
+                return false;
+            }
+        }
+
+        // Use the normal of the segment, as if the neighbor continued the segment.
+        let other_normal = if chain_is_first {
+            rotation12.inverse().rotate(-face_normal)
+        } else {
+            rotation12.rotate(-face_normal)
+        };
+        let (normal1, normal2) = if chain_is_first {
+            (face_normal, other_normal)
+        } else {
+            (other_normal, face_normal)
+        };
+        manifold.normal1 = normal1;
+        manifold.normal2 = normal2;
+        for contact in manifold.contacts.iter_mut() {
+            contact.normal1 = normal1;
+            contact.normal2 = normal2;
+        }
+        true
+    });
+}
+
+impl From<parry::shape::FeatureId> for FeatureId {
+    fn from(value: parry::shape::FeatureId) -> Self {
+        match value {
+            parry::shape::FeatureId::Vertex(index) => Self::Vertex(index),
+            #[cfg(feature = "3d")]
+            parry::shape::FeatureId::Edge(index) => Self::Edge(index),
+            parry::shape::FeatureId::Face(index) => Self::Face(index),
+            parry::shape::FeatureId::Unknown => Self::Unknown,
+        }
+    }
 }
 
 /// Information about the closest points between two [`Collider`]s.
@@ -3,6 +3,7 @@
 //! See [`ContactReportingPlugin`].
 
 use crate::prelude::*;
+use std::time::Duration;
 
 /// Sends collision events and updates [`CollidingEntities`].
 ///
@@ -13,8 +14,16 @@ use crate::prelude::*;
 ///
 /// - [`Collision`]
 /// - [`CollisionStarted`]
+/// - [`CollisionStartedAt`]
 /// - [`CollisionEnded`]
 ///
+/// All of the events carry the [`ContactPairId`] of the collision pair, so gameplay state keyed by the pair
+/// can be looked up and cleaned up from any of them without relying on entity identifiers or on the order
+/// of the events. The events of each type are sent
+/// in the order of their pair identifiers, which is the order in which the pairs started colliding.
+/// This keeps the event order consistent between runs even if the internal storage of [`Collisions`]
+/// is reordered.
+///
 /// You can listen to them with normal event readers:
 ///
 /// ```no_run
@@ -45,13 +54,16 @@ impl Plugin for ContactReportingPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<Collision>()
             .add_event::<CollisionStarted>()
+            .add_event::<CollisionStartedAt>()
             .add_event::<CollisionEnded>();
 
         let physics_schedule = app
             .get_schedule_mut(PhysicsSchedule)
             .expect("add PhysicsSchedule first");
 
-        physics_schedule.add_systems(report_contacts.in_set(PhysicsStepSet::ReportContacts));
+        physics_schedule.add_systems(
+            (report_contacts, update_sensor_overlaps).in_set(PhysicsStepSet::ReportContacts),
+        );
     }
 }
 
@@ -89,6 +101,8 @@ pub struct Collision(pub Contacts);
 /// A [collision event](ContactReportingPlugin#collision-events)
 /// that is sent when two entities start colliding.
 ///
+/// The event contains the two entities and the [`ContactPairId`] of the collision pair.
+///
 /// ## Example
 ///
 /// ```no_run
@@ -104,7 +118,7 @@ pub struct Collision(pub Contacts);
 /// }
 ///
 /// fn print_started_collisions(mut collision_event_reader: EventReader<CollisionStarted>) {
-///     for CollisionStarted(entity1, entity2) in collision_event_reader.read() {
+///     for CollisionStarted(entity1, entity2, _pair_id) in collision_event_reader.read() {
 ///         println!(
 ///             "Entities {:?} and {:?} started colliding",
 ///             entity1,
@@ -115,11 +129,80 @@ pub struct Collision(pub Contacts);
 /// ```
 #[derive(Event, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-pub struct CollisionStarted(pub Entity, pub Entity);
+pub struct CollisionStarted(pub Entity, pub Entity, pub ContactPairId);
+
+/// A [collision event](ContactReportingPlugin#collision-events)
+/// that is sent when two entities start colliding, with the substep and poses at which the contact was first detected.
+///
+/// Collision events are only sent once per physics step, so the colliders have usually moved past
+/// the point of impact by the time the event is read. This event can be used for spawning effects
+/// like sparks and decals at the true impact pose instead of the pose at the end of the step.
+/// [`CollisionStartedAt::time`] returns the elapsed physics time at which the contact was detected.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         .add_systems(Update, print_impacts)
+///         .run();
+/// }
+///
+/// fn print_impacts(mut collision_event_reader: EventReader<CollisionStartedAt>) {
+///     for event in collision_event_reader.read() {
+///         println!(
+///             "Entity {:?} hit {:?} at {:?}, {:?} into the simulation",
+///             event.entity1,
+///             event.entity2,
+///             event.pose1.position,
+///             event.time(),
+///         );
+///     }
+/// }
+/// ```
+#[derive(Event, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollisionStartedAt {
+    /// The first entity of the collision pair.
+    pub entity1: Entity,
+    /// The second entity of the collision pair.
+    pub entity2: Entity,
+    /// The identifier of the collision pair.
+    pub pair_id: ContactPairId,
+    /// The index of the substep at which the contact was detected, in the range `0..substep_count`.
+    pub substep: u32,
+    /// The number of substeps in the physics step.
+    pub substep_count: u32,
+    /// The pose of the first entity's collider at the end of the substep during which the contact was detected.
+    pub pose1: Pose,
+    /// The pose of the second entity's collider at the end of the substep during which the contact was detected.
+    pub pose2: Pose,
+    /// The elapsed physics time at the end of the physics step.
+    pub step_elapsed: Duration,
+    /// The duration of the physics step.
+    pub step_delta: Duration,
+}
+
+impl CollisionStartedAt {
+    /// Returns the elapsed physics time at the end of the substep during which the contact was detected.
+    pub fn time(&self) -> Duration {
+        let substep_count = self.substep_count.max(1);
+        let substep_end = (self.substep + 1).min(substep_count);
+        self.step_elapsed - self.step_delta + self.step_delta * substep_end / substep_count
+    }
+}
 
 /// A [collision event](ContactReportingPlugin#collision-events)
 /// that is sent when two entities stop colliding.
 ///
+/// The event contains the two entities and the [`ContactPairId`] that the collision pair had,
+/// which can be used for cleaning up gameplay state that is keyed by the pair identifier.
+///
 /// ## Example
 ///
 /// ```no_run
@@ -135,7 +218,7 @@ pub struct CollisionStarted(pub Entity, pub Entity);
 /// }
 ///
 /// fn print_ended_collisions(mut collision_event_reader: EventReader<CollisionEnded>) {
-///     for CollisionEnded(entity1, entity2) in collision_event_reader.read() {
+///     for CollisionEnded(entity1, entity2, _pair_id) in collision_event_reader.read() {
 ///         println!(
 ///             "Entities {:?} and {:?} stopped colliding",
 ///             entity1,
@@ -146,7 +229,7 @@ pub struct CollisionStarted(pub Entity, pub Entity);
 /// ```
 #[derive(Event, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-pub struct CollisionEnded(pub Entity, pub Entity);
+pub struct CollisionEnded(pub Entity, pub Entity, pub ContactPairId);
 
 /// Sends collision events and updates [`CollidingEntities`].
 pub fn report_contacts(
@@ -154,15 +237,40 @@ pub fn report_contacts(
     collisions: Res<Collisions>,
     mut collision_ev_writer: EventWriter<Collision>,
     mut collision_started_ev_writer: EventWriter<CollisionStarted>,
+    mut collision_started_at_ev_writer: EventWriter<CollisionStartedAt>,
     mut collision_ended_ev_writer: EventWriter<CollisionEnded>,
+    substep_count: Res<SubstepCount>,
+    time: Res<Time<Physics>>,
 ) {
-    for ((entity1, entity2), contacts) in collisions.get_internal().iter() {
+    // Send events in the order of the pair identifiers so that the order is stable.
+    let mut sorted_collisions = collisions.get_internal().iter().collect::<Vec<_>>();
+    sorted_collisions.sort_unstable_by_key(|(_, contacts)| contacts.pair_id);
+
+    for ((entity1, entity2), contacts) in sorted_collisions {
         if contacts.during_current_frame {
             collision_ev_writer.send(Collision(contacts.clone()));
 
             // Collision started
             if !contacts.during_previous_frame {
-                collision_started_ev_writer.send(CollisionStarted(*entity1, *entity2));
+                collision_started_ev_writer.send(CollisionStarted(
+                    *entity1,
+                    *entity2,
+                    contacts.pair_id,
+                ));
+
+                if let Some(start) = contacts.start {
+                    collision_started_at_ev_writer.send(CollisionStartedAt {
+                        entity1: *entity1,
+                        entity2: *entity2,
+                        pair_id: contacts.pair_id,
+                        substep: start.substep,
+                        substep_count: substep_count.0,
+                        pose1: start.pose1,
+                        pose2: start.pose2,
+                        step_elapsed: time.elapsed(),
+                        step_delta: time.delta(),
+                    });
+                }
 
                 if let Ok(mut colliding_entities1) = colliders.get_mut(*entity1) {
                     colliding_entities1.insert(*entity2);
@@ -175,7 +283,7 @@ pub fn report_contacts(
 
         // Collision ended
         if !contacts.during_current_frame && contacts.during_previous_frame {
-            collision_ended_ev_writer.send(CollisionEnded(*entity1, *entity2));
+            collision_ended_ev_writer.send(CollisionEnded(*entity1, *entity2, contacts.pair_id));
 
             if let Ok(mut colliding_entities1) = colliders.get_mut(*entity1) {
                 colliding_entities1.remove(entity2);
@@ -186,3 +294,32 @@ pub fn report_contacts(
         }
     }
 }
+
+/// Updates the [`SensorOverlaps`] of colliders that started or stopped colliding.
+fn update_sensor_overlaps(
+    mut overlaps: Query<&mut SensorOverlaps>,
+    collisions: Res<Collisions>,
+    time: Res<Time<Physics>>,
+) {
+    if overlaps.is_empty() {
+        return;
+    }
+
+    for ((entity1, entity2), contacts) in collisions.get_internal().iter() {
+        let started = contacts.during_current_frame && !contacts.during_previous_frame;
+        let ended = !contacts.during_current_frame && contacts.during_previous_frame;
+        if !started && !ended {
+            continue;
+        }
+
+        for (sensor, other) in [(*entity1, *entity2), (*entity2, *entity1)] {
+            if let Ok(mut overlaps) = overlaps.get_mut(sensor) {
+                if started {
+                    overlaps.enter(other, time.elapsed());
+                } else {
+                    overlaps.exit(other);
+                }
+            }
+        }
+    }
+}
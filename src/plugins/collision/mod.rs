@@ -22,6 +22,7 @@ pub mod collider_backend;
 pub mod contact_query;
 pub mod contact_reporting;
 pub mod narrow_phase;
+pub mod support;
 
 use crate::prelude::*;
 use bevy::prelude::*;
@@ -68,6 +69,15 @@ use indexmap::IndexMap;
 /// Collision filtering and removal should be done in the [`PostProcessCollisions`] schedule.
 /// Otherwise, the physics solver will use the old contact data.
 ///
+/// ### Modifying contacts
+///
+/// Individual contacts can be removed with [`Contacts::retain_contacts`], and their normals can be changed
+/// with [`ContactManifold::set_global_normal`]. The friction and restitution of a collision pair can be scaled
+/// using [`Contacts::friction_scale`] and [`Contacts::restitution_scale`].
+///
+/// This is useful for things like one-way platforms and removing ghost collisions.
+/// See [`PostProcessCollisions`] for an example.
+///
 /// ### Adding new collisions
 ///
 /// The following methods can be used for adding new collisions:
@@ -78,6 +88,15 @@ use indexmap::IndexMap;
 /// The most convenient place for adding new collisions is in the [`PostProcessCollisions`] schedule.
 /// Otherwise, the physics solver might not have access to them in time.
 ///
+/// ### Pair identifiers
+///
+/// Each collision pair is assigned a unique [`ContactPairId`] when it is first inserted.
+/// The identifier stays the same for as long as the entities are in contact, so it can be used
+/// as a key for gameplay state associated with the contact pair, like damage over time or sound effects.
+///
+/// Small amounts of per-pair state can also be stored directly in [`Contacts::user_data`],
+/// which persists for as long as the entities are in contact.
+///
 /// ## Implementation details
 ///
 /// Internally, the collisions are stored in an `IndexMap` that contains collisions from both the current frame
@@ -86,7 +105,11 @@ use indexmap::IndexMap;
 /// However, the public methods only use the current frame's collisions. To access the internal data structure,
 /// you can use [`get_internal`](Self::get_internal) or [`get_internal_mut`](Self::get_internal_mut).
 #[derive(Resource, Clone, Debug, Default, PartialEq)]
-pub struct Collisions(IndexMap<(Entity, Entity), Contacts, fxhash::FxBuildHasher>);
+pub struct Collisions(
+    IndexMap<(Entity, Entity), Contacts, fxhash::FxBuildHasher>,
+    /// The next [`ContactPairId`] to assign to a new collision pair.
+    u64,
+);
 
 impl Collisions {
     /// Returns a reference to the internal `IndexMap`.
@@ -191,10 +214,21 @@ impl Collisions {
     /// If a collision entry with the same entities already exists, it will be overwritten,
     /// and the old value will be returned. Otherwise, `None` is returned.
     ///
+    /// The [`ContactPairId`] of an existing collision entry is preserved.
+    /// New collision pairs are assigned a new unique identifier.
+    ///
     /// **Note**: Manually inserting collisions can be error prone and should generally be avoided.
     /// If you simply want to modify existing collisions, consider using methods like [`get_mut`](Self::get_mut)
     /// or [`iter_mut`](Self::iter_mut).
-    pub fn insert_collision_pair(&mut self, contacts: Contacts) -> Option<Contacts> {
+    pub fn insert_collision_pair(&mut self, mut contacts: Contacts) -> Option<Contacts> {
+        contacts.pair_id = match self.0.get(&(contacts.entity1, contacts.entity2)) {
+            Some(previous) => previous.pair_id,
+            None => {
+                self.1 += 1;
+                ContactPairId(self.1)
+            }
+        };
+
         // TODO: We might want to order the data by Entity ID so that entity1, point1 etc. are for the "smaller"
         // entity ID. This requires changes elsewhere as well though.
         self.0
@@ -252,6 +286,17 @@ impl Collisions {
 #[derive(Resource, Clone, Debug, Default, Deref, DerefMut, PartialEq)]
 pub(super) struct PreviousCollisions(Collisions);
 
+/// A unique identifier for a collision pair in [`Collisions`].
+///
+/// The identifier is assigned when two entities start colliding, and it stays the same
+/// for as long as they remain in contact. Identifiers are never reused, and new pairs are assigned
+/// identifiers in increasing order, so they also define the order in which pairs started colliding.
+///
+/// The identifier is stored in [`Contacts`] and in all of the [collision events](ContactReportingPlugin#collision-events).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContactPairId(pub u64);
+
 /// All contacts between two colliders.
 ///
 /// The contacts are stored in contact manifolds.
@@ -260,6 +305,25 @@ pub(super) struct PreviousCollisions(Collisions);
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Contacts {
+    /// A unique identifier for the collision pair that stays the same while the entities are in contact.
+    ///
+    /// The identifier is assigned by [`Collisions`] when the pair is first inserted.
+    pub pair_id: ContactPairId,
+    /// Arbitrary user data associated with the collision pair, like accumulated damage or an index
+    /// into gameplay data. It is zero for new pairs and persists for as long as the entities are in contact,
+    /// so it can be modified in the [`PostProcessCollisions`] schedule and read from [`Collision`] events.
+    pub user_data: u64,
+    /// A multiplier for the combined [`Friction`] coefficients used when solving this contact pair.
+    ///
+    /// This is reset to `1.0` by the narrow phase every substep, so it can be modified
+    /// in the [`PostProcessCollisions`] schedule, for example to make a surface slippery
+    /// only for some bodies.
+    pub friction_scale: Scalar,
+    /// A multiplier for the combined [`Restitution`] coefficient used when solving this contact pair.
+    ///
+    /// This is reset to `1.0` by the narrow phase every substep, so it can be modified
+    /// in the [`PostProcessCollisions`] schedule.
+    pub restitution_scale: Scalar,
     /// First entity in the contact.
     pub entity1: Entity,
     /// Second entity in the contact.
@@ -274,6 +338,11 @@ pub struct Contacts {
     pub during_current_substep: bool,
     /// True if the bodies were in contact during the previous frame.
     pub during_previous_frame: bool,
+    /// The substep and the poses of the colliders at which the contact was first detected during the current frame.
+    ///
+    /// For collisions that started during the current frame, this can be used for spawning effects like sparks
+    /// and decals at the true impact pose instead of the pose at the end of the physics step.
+    pub start: Option<ContactStart>,
     /// The total normal impulse applied to the first body in a collision.
     ///
     /// To get the corresponding force, divide the impulse by `Time<Substeps>`.
@@ -302,6 +371,41 @@ impl Contacts {
     pub fn total_tangent_force(&self, delta_time: Scalar) -> Scalar {
         self.total_tangent_impulse / delta_time
     }
+
+    /// Retains only the contact points for which the predicate returns `true`.
+    ///
+    /// The predicate can also modify the contacts, for example to change their normals.
+    /// Manifolds that end up empty are kept so that manifold indices remain stable,
+    /// but they don't generate any constraints.
+    ///
+    /// Contacts should be modified in the [`PostProcessCollisions`] schedule.
+    /// Otherwise, the physics solver will use the old contact data.
+    pub fn retain_contacts(&mut self, mut f: impl FnMut(&mut ContactData) -> bool) {
+        for manifold in self.manifolds.iter_mut() {
+            manifold.contacts.retain_mut(&mut f);
+        }
+    }
+
+    /// Returns `true` if the pair has no contact points that would generate constraints.
+    pub fn is_empty(&self) -> bool {
+        self.manifolds
+            .iter()
+            .all(|manifold| manifold.contacts.is_empty())
+    }
+}
+
+/// The substep and the poses of two colliders at which their contact was first detected during a physics step.
+///
+/// Stored in [`Contacts::start`] and sent in [`CollisionStartedAt`] events.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContactStart {
+    /// The index of the substep at which the contact was detected, in the range `0..SubstepCount`.
+    pub substep: u32,
+    /// The pose of the first collider at the end of the substep during which the contact was detected.
+    pub pose1: Pose,
+    /// The pose of the second collider at the end of the substep during which the contact was detected.
+    pub pose2: Pose,
 }
 
 /// A contact manifold between two colliders, containing a set of contact points.
@@ -319,6 +423,16 @@ pub struct ContactManifold {
     pub normal2: Vector,
     /// The index of the manifold in the collision.
     pub index: usize,
+    /// The index of the sub-shape of the first collider that the manifold belongs to,
+    /// like the index of a triangle in a trimesh or a child shape in a compound shape.
+    ///
+    /// This is `None` if the first collider is not a composite shape.
+    pub subshape1: Option<u32>,
+    /// The index of the sub-shape of the second collider that the manifold belongs to,
+    /// like the index of a triangle in a trimesh or a child shape in a compound shape.
+    ///
+    /// This is `None` if the second collider is not a composite shape.
+    pub subshape2: Option<u32>,
 }
 
 impl ContactManifold {
@@ -331,6 +445,156 @@ impl ContactManifold {
     pub fn global_normal2(&self, rotation: &Rotation) -> Vector {
         rotation.rotate(self.normal2)
     }
+
+    /// Sets the normal of the manifold and all of its contacts from a world-space normal
+    /// pointing towards the exterior of the first entity.
+    ///
+    /// `rotation1` and `rotation2` are the [`Rotation`]s of the first and second collider.
+    /// This can be used for removing ghost collisions at internal edges by replacing the contact normal
+    /// with the normal of the surface.
+    pub fn set_global_normal(
+        &mut self,
+        normal: Vector,
+        rotation1: &Rotation,
+        rotation2: &Rotation,
+    ) {
+        self.normal1 = rotation1.inverse().rotate(normal);
+        self.normal2 = rotation2.inverse().rotate(-normal);
+        for contact in self.contacts.iter_mut() {
+            contact.normal1 = self.normal1;
+            contact.normal2 = self.normal2;
+        }
+    }
+
+    /// Returns the sum of the magnitudes of the normal impulses of the contacts in this manifold.
+    ///
+    /// See the caveats of [`ContactData::normal_impulse`].
+    pub fn total_normal_impulse(&self) -> Scalar {
+        self.contacts
+            .iter()
+            .map(|contact| contact.normal_impulse.abs())
+            .sum()
+    }
+
+    /// Returns an approximation of the area of the contact patch, estimated from the spread
+    /// of the contact points on the contact plane.
+    ///
+    /// In 2D, this is the length of the contact patch along the contact surface.
+    /// Manifolds with a single contact point don't have a measurable spread, so their area is zero.
+    pub fn patch_area(&self) -> Scalar {
+        let normal = self.normal1.normalize_or_zero();
+
+        #[cfg(feature = "2d")]
+        {
+            let tangent = normal.perp();
+            let (min, max) =
+                self.contacts
+                    .iter()
+                    .fold((Scalar::MAX, Scalar::MIN), |(min, max), contact| {
+                        let offset = contact.point1.dot(tangent);
+                        (min.min(offset), max.max(offset))
+                    });
+            (max - min).max(0.0)
+        }
+        #[cfg(feature = "3d")]
+        {
+            let (u, v) = normal.any_orthonormal_pair();
+            let points = self
+                .contacts
+                .iter()
+                .map(|contact| Vector2::new(contact.point1.dot(u), contact.point1.dot(v)))
+                .collect();
+            convex_hull_area(points)
+        }
+    }
+
+    /// Returns the approximate average pressure over the contact patch, computed from the
+    /// [total normal impulse](Self::total_normal_impulse) and the given substep duration in seconds,
+    /// which can be read from `Time<Substeps>`.
+    ///
+    /// The [patch area](Self::patch_area) is clamped to at least `min_area`, so that contacts
+    /// with a single point don't have an infinite pressure. In 2D, the pressure is a force per unit length.
+    pub fn patch_pressure(&self, substep_delta_secs: Scalar, min_area: Scalar) -> Scalar {
+        let area = self.patch_area().max(min_area);
+        if substep_delta_secs <= Scalar::EPSILON || area <= Scalar::EPSILON {
+            return 0.0;
+        }
+        self.total_normal_impulse() / substep_delta_secs / area
+    }
+}
+
+/// Computes the area of the convex hull of the given points.
+#[cfg(feature = "3d")]
+fn convex_hull_area(points: Vec<Vector2>) -> Scalar {
+    let hull = convex_hull_indices(&points);
+    if hull.len() < 3 {
+        return 0.0;
+    }
+
+    // Shoelace formula
+    let mut area = 0.0;
+    for i in 0..hull.len() {
+        area += points[hull[i]].perp_dot(points[hull[(i + 1) % hull.len()]]);
+    }
+    0.5 * area.abs()
+}
+
+/// Computes the convex hull of the given points using the monotone chain algorithm.
+///
+/// Returns the indices of the hull vertices in counterclockwise order.
+#[cfg(feature = "3d")]
+pub(crate) fn convex_hull_indices(points: &[Vector2]) -> Vec<usize> {
+    let mut order = (0..points.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (points[a], points[b]);
+        a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y))
+    });
+
+    if order.len() < 3 {
+        order.dedup_by(|a, b| points[*a] == points[*b]);
+        return order;
+    }
+
+    let mut hull: Vec<usize> = Vec::with_capacity(order.len() + 1);
+    for pass in 0..2 {
+        let start = hull.len();
+        for &index in order.iter() {
+            let point = points[index];
+            while hull.len() >= start + 2 {
+                let (a, b) = (points[hull[hull.len() - 2]], points[hull[hull.len() - 1]]);
+                if (b - a).perp_dot(point - a) > 0.0 {
+                    break;
+                }
+                hull.pop();
+            }
+            hull.push(index);
+        }
+        hull.pop();
+        if pass == 0 {
+            order.reverse();
+        }
+    }
+    hull
+}
+
+/// Identifies the geometric feature of a shape, like a face or a vertex, that a contact point is on.
+///
+/// The indices are specific to the shape. For example, for a cuboid, the index identifies
+/// one of its faces or vertices, and for a trimesh, the sub-shape index of the
+/// [`ContactManifold`] identifies the triangle.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeatureId {
+    /// A vertex with the given index.
+    Vertex(u32),
+    /// An edge with the given index.
+    #[cfg(feature = "3d")]
+    Edge(u32),
+    /// A face with the given index.
+    Face(u32),
+    /// The feature is unknown, for example because the contact was computed by a custom collider.
+    #[default]
+    Unknown,
 }
 
 /// Data related to a single contact between two bodies.
@@ -438,6 +702,16 @@ pub struct ContactData {
     pub tangent_impulse: Scalar,
     /// The index of the contact in a contact manifold if it is in one.
     pub index: usize,
+    /// The geometric feature of the first collider that the contact point is on.
+    ///
+    /// For composite shapes like trimeshes, the feature is relative to the sub-shape
+    /// given by [`ContactManifold::subshape1`].
+    pub feature1: FeatureId,
+    /// The geometric feature of the second collider that the contact point is on.
+    ///
+    /// For composite shapes like trimeshes, the feature is relative to the sub-shape
+    /// given by [`ContactManifold::subshape2`].
+    pub feature2: FeatureId,
 }
 
 impl ContactData {
@@ -461,6 +735,8 @@ impl ContactData {
             normal_impulse: 0.0,
             tangent_impulse: 0.0,
             index,
+            feature1: FeatureId::Unknown,
+            feature2: FeatureId::Unknown,
         }
     }
 
@@ -39,6 +39,8 @@ impl<C: AnyCollider> Plugin for NarrowPhasePlugin<C> {
 
         app.init_resource::<NarrowPhaseInitialized>()
             .init_resource::<NarrowPhaseConfig>()
+            .init_resource::<NarrowPhaseStep>()
+            .init_resource::<RetainedPairs>()
             .init_resource::<Collisions>()
             .register_type::<NarrowPhaseConfig>();
 
@@ -63,6 +65,8 @@ impl<C: AnyCollider> Plugin for NarrowPhasePlugin<C> {
         if !is_first_instance {
             physics_schedule.add_systems(
                 (
+                    advance_narrow_phase_step.before(PhysicsStepSet::BroadPhase),
+                    retain_pairs_of_removed_ids.before(PhysicsStepSet::BroadPhase),
                     // Reset collision states before the substepping loop.
                     reset_collision_states
                         .after(PhysicsStepSet::BroadPhase)
@@ -89,6 +93,33 @@ impl<C: AnyCollider> Plugin for NarrowPhasePlugin<C> {
             );
         }
 
+        // Record the solved poses of new contacts. Only one narrow phase instance should do this.
+        if !is_first_instance {
+            substep_schedule
+                .add_systems(update_contact_start_poses.after(SubstepSet::ApplyTranslation));
+        }
+
+        // Re-associate the state of retained pairs with new pairs. Only one narrow phase instance should do this.
+        if !is_first_instance {
+            substep_schedule.add_systems(
+                restore_retained_pairs
+                    .run_if(|retained: Res<RetainedPairs>| !retained.pairs.is_empty())
+                    .in_set(SubstepSet::NarrowPhase)
+                    .after(NarrowPhaseSet::CollectCollisions)
+                    .before(NarrowPhaseSet::Last),
+            );
+        }
+
+        // Weld contact normals at tile seams. Only one narrow phase instance should do this.
+        #[cfg(feature = "2d")]
+        if !is_first_instance {
+            substep_schedule.add_systems(
+                weld_tile_seams
+                    .run_if(|config: Res<NarrowPhaseConfig>| config.weld_tile_seams)
+                    .in_set(NarrowPhaseSet::Last),
+            );
+        }
+
         // Collect contacts into `Collisions`.
         substep_schedule.add_systems(
             (
@@ -105,6 +136,12 @@ impl<C: AnyCollider> Plugin for NarrowPhasePlugin<C> {
 #[derive(Resource, Default)]
 struct NarrowPhaseInitialized;
 
+/// The number of physics steps run so far. Used for time slicing sensors with [`SensorUpdateInterval`].
+///
+/// The step is advanced once per physics step, before the [broad phase](PhysicsStepSet::BroadPhase).
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NarrowPhaseStep(pub u64);
+
 /// A resource for configuring the [narrow phase](NarrowPhasePlugin).
 #[derive(Resource, Reflect, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -116,6 +153,16 @@ pub struct NarrowPhaseConfig {
     /// include pairs of entities that *might* be in contact after constraint solving or
     /// other positional changes.
     pub prediction_distance: Scalar,
+    /// If true, the normals of contacts against adjacent static colliders are welded together
+    /// when the contacts lie on the same surface. This prevents bodies like capsule characters
+    /// from getting snagged on the seams between identical tiles.
+    ///
+    /// Contacts are considered to lie on the same surface if their distance from
+    /// the surface is within the [`prediction_distance`](Self::prediction_distance).
+    ///
+    /// Disabled by default.
+    #[cfg(feature = "2d")]
+    pub weld_tile_seams: bool,
 }
 
 impl Default for NarrowPhaseConfig {
@@ -125,6 +172,8 @@ impl Default for NarrowPhaseConfig {
             prediction_distance: 1.0,
             #[cfg(feature = "3d")]
             prediction_distance: 0.01,
+            #[cfg(feature = "2d")]
+            weld_tile_seams: false,
         }
     }
 }
@@ -144,15 +193,21 @@ pub enum NarrowPhaseSet {
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 pub fn collect_collisions<C: AnyCollider>(
-    query: Query<(
-        Ref<Position>,
-        Option<&AccumulatedTranslation>,
-        Ref<Rotation>,
-        &C,
-    )>,
+    query: Query<
+        (
+            Ref<Position>,
+            Option<&AccumulatedTranslation>,
+            Ref<Rotation>,
+            &C,
+        ),
+        Without<ColliderDisabled>,
+    >,
     broad_collision_pairs: Res<BroadCollisionPairs>,
     mut collisions: ResMut<Collisions>,
     narrow_phase_config: Res<NarrowPhaseConfig>,
+    sensor_intervals: Query<&SensorUpdateInterval, With<Sensor>>,
+    step: Res<NarrowPhaseStep>,
+    substep: Res<SubstepIndex>,
 ) {
     if query.is_empty() {
         return;
@@ -172,9 +227,17 @@ pub fn collect_collisions<C: AnyCollider>(
             false
         }
     });
-    let broad_collision_pairs = stationary_collisions
+
+    // Time-sliced sensors are only checked during some physics steps.
+    let is_skipped = |entity: Entity| {
+        sensor_intervals
+            .get(entity)
+            .is_ok_and(|interval| !interval.is_active(entity, step.0))
+    };
+    let (skipped_pairs, broad_collision_pairs): (Vec<_>, Vec<_>) = stationary_collisions
         .chain(broad_collision_pairs.0.iter())
-        .collect::<Vec<_>>();
+        .partition(|(entity1, entity2)| is_skipped(*entity1) || is_skipped(*entity2));
+    let skipped_pairs = skipped_pairs.into_iter().copied().collect::<Vec<_>>();
 
     #[cfg(feature = "parallel")]
     {
@@ -191,6 +254,7 @@ pub fn collect_collisions<C: AnyCollider>(
                         &query,
                         &collisions,
                         &narrow_phase_config,
+                        substep.0,
                         |contacts| {
                             new_collisions.push(contacts);
                         },
@@ -213,6 +277,7 @@ pub fn collect_collisions<C: AnyCollider>(
                 &query,
                 &collisions,
                 &narrow_phase_config,
+                substep.0,
                 |contacts| {
                     new_collisions.push(contacts);
                 },
@@ -221,6 +286,13 @@ pub fn collect_collisions<C: AnyCollider>(
 
         collisions.extend(new_collisions);
     }
+
+    // Keep the existing collisions of skipped time-sliced sensors.
+    for (entity1, entity2) in skipped_pairs {
+        if let Some(contacts) = collisions.get_internal_mut().get_mut(&(entity1, entity2)) {
+            contacts.during_current_frame = true;
+        }
+    }
 }
 
 /// Helper method that calculates the intersection between two colliders to determine if they are in contact.
@@ -228,18 +300,31 @@ pub fn collect_collisions<C: AnyCollider>(
 fn process_collision_pair<C: AnyCollider, F>(
     entity1: Entity,
     entity2: Entity,
-    bodies: &Query<(
-        Ref<Position>,
-        Option<&AccumulatedTranslation>,
-        Ref<Rotation>,
-        &C,
-    )>,
+    bodies: &Query<
+        (
+            Ref<Position>,
+            Option<&AccumulatedTranslation>,
+            Ref<Rotation>,
+            &C,
+        ),
+        Without<ColliderDisabled>,
+    >,
     collisions: &ResMut<Collisions>,
     narrow_phase_config: &Res<NarrowPhaseConfig>,
+    substep: u32,
     mut handle_collision: F,
 ) where
     F: FnMut(Contacts),
 {
+    // Keep the entity order of an existing collision pair. The broad phase can report the pair
+    // in the opposite order once the colliders have moved past each other, which would otherwise
+    // start a new collision with a new pair identifier.
+    let (entity1, entity2) = if collisions.get_internal().contains_key(&(entity2, entity1)) {
+        (entity2, entity1)
+    } else {
+        (entity1, entity2)
+    };
+
     if let Ok([bundle1, bundle2]) = bodies.get_many([entity1, entity2]) {
         let (position1, accumulated_translation1, rotation1, collider1) = bundle1;
         let (position2, accumulated_translation2, rotation2, collider2) = bundle2;
@@ -249,12 +334,33 @@ fn process_collision_pair<C: AnyCollider, F>(
 
         let previous_contact = collisions.get_internal().get(&(entity1, entity2));
 
+        // Keep the start of contacts that were already detected during the current frame.
+        let start = previous_contact
+            .filter(|c| c.during_current_frame)
+            .and_then(|c| c.start)
+            .unwrap_or(ContactStart {
+                substep,
+                pose1: Pose {
+                    position: position1,
+                    rotation: *rotation1,
+                },
+                pose2: Pose {
+                    position: position2,
+                    rotation: *rotation2,
+                },
+            });
+
         let contacts = Contacts {
+            pair_id: previous_contact.map_or(ContactPairId::default(), |c| c.pair_id),
+            user_data: previous_contact.map_or(0, |c| c.user_data),
+            friction_scale: 1.0,
+            restitution_scale: 1.0,
             entity1,
             entity2,
             during_current_frame: true,
             during_current_substep: true,
             during_previous_frame: previous_contact.map_or(false, |c| c.during_previous_frame),
+            start: Some(start),
             manifolds: collider1.contact_manifolds(
                 collider2,
                 position1,
@@ -273,6 +379,244 @@ fn process_collision_pair<C: AnyCollider, F>(
     }
 }
 
+/// A contact manifold against a static collider, used for welding tile seams.
+#[cfg(feature = "2d")]
+struct SeamSurface {
+    pair: (Entity, Entity),
+    manifold_index: usize,
+    /// True if the body touching the surface is the first entity of the pair.
+    is_first: bool,
+    /// The world-space normal of the static surface, pointing towards the body.
+    normal: Vector,
+    /// The world-space contact point on the static surface.
+    point: Vector,
+}
+
+/// Welds the normals of contacts at the seams between adjacent static colliders like tiles.
+///
+/// When a body touches several static colliders, a contact that lies on the surface of another contact
+/// but has a different normal is most likely caused by an internal corner at a seam. Its normal is replaced
+/// with the normal of the surface so that the body slides across the seam without getting snagged.
+///
+/// Enabled with [`NarrowPhaseConfig::weld_tile_seams`].
+#[cfg(feature = "2d")]
+fn weld_tile_seams(
+    mut collisions: ResMut<Collisions>,
+    colliders: Query<(
+        &Position,
+        Option<&AccumulatedTranslation>,
+        &Rotation,
+        Option<&ColliderParent>,
+    )>,
+    bodies: Query<&RigidBody>,
+    narrow_phase_config: Res<NarrowPhaseConfig>,
+) {
+    let tolerance = narrow_phase_config.prediction_distance;
+
+    let transform = |entity: Entity| {
+        colliders
+            .get(entity)
+            .ok()
+            .map(|(position, accumulated_translation, rotation, _)| {
+                let position = position.0 + accumulated_translation.map_or(Vector::ZERO, |t| t.0);
+                (position, *rotation)
+            })
+    };
+    let is_static = |entity: Entity| {
+        let body = colliders
+            .get(entity)
+            .ok()
+            .and_then(|(.., parent)| parent)
+            .map_or(entity, |parent| parent.get());
+        bodies.get(body).is_ok_and(|rb| rb.is_static())
+    };
+
+    // Collect the static surfaces that each non-static collider is touching.
+    let mut surfaces = bevy::utils::HashMap::<Entity, Vec<SeamSurface>>::default();
+    for (&(entity1, entity2), contacts) in collisions
+        .get_internal()
+        .iter()
+        .filter(|(_, contacts)| contacts.during_current_substep)
+    {
+        let (Some((position1, rotation1)), Some((position2, rotation2))) =
+            (transform(entity1), transform(entity2))
+        else {
+            continue;
+        };
+        let (static1, static2) = (is_static(entity1), is_static(entity2));
+
+        for (is_first, entity) in [(true, entity1), (false, entity2)] {
+            if (is_first && (static1 || !static2)) || (!is_first && (static2 || !static1)) {
+                continue;
+            }
+            for (manifold_index, manifold) in contacts.manifolds.iter().enumerate() {
+                let Some(contact) = manifold
+                    .contacts
+                    .iter()
+                    .max_by(|a, b| a.penetration.total_cmp(&b.penetration))
+                else {
+                    continue;
+                };
+                let (normal, point) = if is_first {
+                    (
+                        -manifold.global_normal1(&rotation1),
+                        position2 + rotation2.rotate(contact.point2),
+                    )
+                } else {
+                    (
+                        -manifold.global_normal2(&rotation2),
+                        position1 + rotation1.rotate(contact.point1),
+                    )
+                };
+                surfaces.entry(entity).or_default().push(SeamSurface {
+                    pair: (entity1, entity2),
+                    manifold_index,
+                    is_first,
+                    normal,
+                    point,
+                });
+            }
+        }
+    }
+
+    // Find contacts that lie on the surface of another contact, but have a different normal.
+    let mut welds = vec![];
+    for surfaces in surfaces.values().filter(|surfaces| surfaces.len() > 1) {
+        for seam in surfaces.iter() {
+            let surface = surfaces.iter().find(|surface| {
+                let alignment = surface.normal.dot(seam.normal);
+                alignment < 1.0 - 1e-4
+                    && alignment > -Scalar::EPSILON
+                    && (seam.point - surface.point).dot(surface.normal).abs() <= tolerance
+                    && (surface.point - seam.point).dot(seam.normal).abs() > tolerance
+            });
+            if let Some(surface) = surface {
+                welds.push((
+                    seam.pair,
+                    seam.manifold_index,
+                    seam.is_first,
+                    surface.normal,
+                ));
+            }
+        }
+    }
+
+    // Replace the normals of the seam contacts with the normals of the surfaces.
+    for ((entity1, entity2), manifold_index, is_first, surface_normal) in welds {
+        let (Some((position1, rotation1)), Some((position2, rotation2))) =
+            (transform(entity1), transform(entity2))
+        else {
+            continue;
+        };
+        let Some(manifold) = collisions
+            .get_internal_mut()
+            .get_mut(&(entity1, entity2))
+            .and_then(|contacts| contacts.manifolds.get_mut(manifold_index))
+        else {
+            continue;
+        };
+
+        // The first normal points from the first entity towards the second entity.
+        let global_normal1 = if is_first {
+            -surface_normal
+        } else {
+            surface_normal
+        };
+        let normal1 = rotation1.inverse().rotate(global_normal1);
+        let normal2 = rotation2.inverse().rotate(-global_normal1);
+
+        manifold.normal1 = normal1;
+        manifold.normal2 = normal2;
+        for contact in manifold.contacts.iter_mut() {
+            let point1 = position1 + rotation1.rotate(contact.point1);
+            let point2 = position2 + rotation2.rotate(contact.point2);
+            contact.normal1 = normal1;
+            contact.normal2 = normal2;
+            contact.penetration = (point1 - point2).dot(global_normal1);
+        }
+    }
+}
+
+/// Advances [`NarrowPhaseStep`] once per physics step.
+fn advance_narrow_phase_step(mut step: ResMut<NarrowPhaseStep>) {
+    step.0 += 1;
+}
+
+/// The number of physics steps for which the state of a contact pair is retained
+/// after an entity with a [`PhysicsId`] is despawned.
+const PAIR_RETENTION_STEPS: u64 = 60;
+
+/// The [`PhysicsId`]s of entities, and the state of contact pairs retained after entities
+/// with a [`PhysicsId`] were despawned.
+#[derive(Resource, Default)]
+struct RetainedPairs {
+    ids: bevy::utils::HashMap<Entity, PhysicsId>,
+    /// The user data of retained pairs and the step at which they were retained.
+    pairs: bevy::utils::HashMap<(PhysicsId, PhysicsId), (u64, u64)>,
+}
+
+/// Keeps track of [`PhysicsId`]s and retains the state of the contact pairs of entities whose [`PhysicsId`]
+/// was removed, so that it can be re-associated if an entity with the same identifier is respawned.
+fn retain_pairs_of_removed_ids(
+    ids: Query<(Entity, &PhysicsId), Changed<PhysicsId>>,
+    mut removed_ids: RemovedComponents<PhysicsId>,
+    mut retained: ResMut<RetainedPairs>,
+    collisions: Res<Collisions>,
+    step: Res<NarrowPhaseStep>,
+) {
+    let RetainedPairs { ids: id_map, pairs } = &mut *retained;
+
+    for entity in removed_ids.read() {
+        let Some(id) = id_map.remove(&entity) else {
+            continue;
+        };
+        for contacts in collisions.get_internal().values() {
+            let other = if contacts.entity1 == entity {
+                contacts.entity2
+            } else if contacts.entity2 == entity {
+                contacts.entity1
+            } else {
+                continue;
+            };
+            if let Some(other_id) = id_map.get(&other) {
+                pairs.insert(ordered_pair(id, *other_id), (contacts.user_data, step.0));
+            }
+        }
+    }
+
+    for (entity, id) in &ids {
+        id_map.insert(entity, *id);
+    }
+
+    pairs.retain(|_, (_, retained_at)| step.0 - *retained_at <= PAIR_RETENTION_STEPS);
+}
+
+/// Re-associates the state of retained contact pairs with new pairs between entities with the same [`PhysicsId`]s.
+fn restore_retained_pairs(mut collisions: ResMut<Collisions>, mut retained: ResMut<RetainedPairs>) {
+    let RetainedPairs { ids, pairs } = &mut *retained;
+
+    for contacts in collisions.get_internal_mut().values_mut() {
+        if contacts.during_previous_frame {
+            continue;
+        }
+        let (Some(id1), Some(id2)) = (ids.get(&contacts.entity1), ids.get(&contacts.entity2))
+        else {
+            continue;
+        };
+        if let Some((user_data, _)) = pairs.remove(&ordered_pair(*id1, *id2)) {
+            contacts.user_data = user_data;
+        }
+    }
+}
+
+fn ordered_pair(id1: PhysicsId, id2: PhysicsId) -> (PhysicsId, PhysicsId) {
+    if id1 <= id2 {
+        (id1, id2)
+    } else {
+        (id2, id1)
+    }
+}
+
 fn remove_ended_collisions(mut collisions: ResMut<Collisions>) {
     collisions.retain(|contacts| contacts.during_current_frame);
 }
@@ -310,6 +654,65 @@ pub fn reset_collision_states(
     }
 }
 
+/// Updates the poses of the [`ContactStart`]s of contacts that were detected during the current substep
+/// to the poses of the colliders at the end of the substep.
+///
+/// Contacts are detected using the predicted poses of the colliders, which can already be overlapping.
+/// Using the poses after the constraints have been solved reports the pose of the impact instead.
+#[allow(clippy::type_complexity)]
+pub fn update_contact_start_poses(
+    mut collisions: ResMut<Collisions>,
+    colliders: Query<(
+        &Position,
+        &Rotation,
+        Option<&ColliderParent>,
+        Option<&ColliderTransform>,
+    )>,
+    bodies: Query<(&Position, &Rotation), With<RigidBody>>,
+    substep: Res<SubstepIndex>,
+) {
+    // Child colliders are only moved with their body before the narrow phase,
+    // so their poses are computed from the pose of the body.
+    let collider_pose = |entity: Entity| {
+        let (position, rotation, parent, collider_transform) = colliders.get(entity).ok()?;
+        let parent_pose = parent
+            .filter(|parent| parent.get() != entity)
+            .and_then(|parent| bodies.get(parent.get()).ok());
+        let (Some((parent_pos, parent_rot)), Some(collider_transform)) =
+            (parent_pose, collider_transform)
+        else {
+            return Some(Pose {
+                position: position.0,
+                rotation: *rotation,
+            });
+        };
+
+        #[cfg(feature = "2d")]
+        let rotation = *parent_rot + collider_transform.rotation;
+        #[cfg(feature = "3d")]
+        let rotation = Rotation((parent_rot.0 * collider_transform.rotation.0).normalize());
+        Some(Pose {
+            position: parent_pos.0 + parent_rot.rotate(collider_transform.translation),
+            rotation,
+        })
+    };
+
+    for contacts in collisions.get_internal_mut().values_mut() {
+        let Some(start) = contacts.start.as_mut() else {
+            continue;
+        };
+        if !contacts.during_current_substep || start.substep != substep.0 {
+            continue;
+        }
+        if let Some(pose) = collider_pose(contacts.entity1) {
+            start.pose1 = pose;
+        }
+        if let Some(pose) = collider_pose(contacts.entity2) {
+            start.pose2 = pose;
+        }
+    }
+}
+
 /// Reset `during_current_substep` for each collision in [`Collisions`].
 pub fn reset_substep_collision_states(mut collisions: ResMut<Collisions>) {
     for contacts in collisions.get_internal_mut().values_mut() {
@@ -0,0 +1,245 @@
+//! Support polygons and centers of pressure computed from contacts.
+//!
+//! See [`ContactSupport`].
+
+use crate::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// The region supporting a body against a given `up` direction, computed from the body's current contacts
+/// by [`ContactSupport`].
+///
+/// A body resting on its supports is stable as long as its center of mass, projected along `up`,
+/// lies inside the support polygon. The [`stability_margin`](Self::stability_margin) tells how far
+/// the center of mass can move before the body starts tipping over.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SupportPolygon {
+    /// The world-space contact points that form the boundary of the support polygon.
+    ///
+    /// In 3D, the vertices are ordered counterclockwise around `up`.
+    /// In 2D, the polygon is a segment and the vertices are its endpoints.
+    pub vertices: Vec<Vector>,
+    /// The up direction that the support polygon was computed for.
+    pub up: Vector,
+    /// The world-space center of pressure, the average of the supporting contact points
+    /// weighted by their normal impulses.
+    ///
+    /// If the contacts don't have normal impulses yet, the points are weighted equally.
+    pub center_of_pressure: Vector,
+    /// The sum of the normal impulses of the supporting contacts during the latest substep.
+    pub total_normal_impulse: Scalar,
+}
+
+impl SupportPolygon {
+    /// Returns the signed distance from the given world-space point, projected along `up`,
+    /// to the boundary of the support polygon. The distance is positive inside of the polygon.
+    ///
+    /// For a point like the center of mass of the body, this is how far the point can move
+    /// before the body starts tipping over.
+    pub fn stability_margin(&self, point: Vector) -> Scalar {
+        #[cfg(feature = "2d")]
+        {
+            let tangent = self.up.perp();
+            let offset = point.dot(tangent);
+            let (min, max) =
+                self.vertices
+                    .iter()
+                    .fold((Scalar::MAX, Scalar::MIN), |(min, max), vertex| {
+                        let offset = vertex.dot(tangent);
+                        (min.min(offset), max.max(offset))
+                    });
+            (offset - min).min(max - offset)
+        }
+        #[cfg(feature = "3d")]
+        {
+            let project = |p: Vector| p - self.up * p.dot(self.up);
+            let point = project(point);
+
+            match self.vertices.len() {
+                0 => Scalar::MIN,
+                1 => -point.distance(project(self.vertices[0])),
+                2 => {
+                    let (a, b) = (project(self.vertices[0]), project(self.vertices[1]));
+                    let ab = b - a;
+                    let t = ((point - a).dot(ab) / ab.length_squared().max(Scalar::EPSILON))
+                        .clamp(0.0, 1.0);
+                    -point.distance(a + ab * t)
+                }
+                count => (0..count)
+                    .map(|i| {
+                        let a = project(self.vertices[i]);
+                        let b = project(self.vertices[(i + 1) % count]);
+                        let inward = self.up.cross(b - a).normalize_or_zero();
+                        (point - a).dot(inward)
+                    })
+                    .fold(Scalar::MAX, Scalar::min),
+            }
+        }
+    }
+
+    /// Returns true if the given world-space point, projected along `up`, is inside of the support polygon.
+    pub fn contains(&self, point: Vector) -> bool {
+        self.stability_margin(point) >= 0.0
+    }
+}
+
+/// A [`SystemParam`] for computing the [support polygon](SupportPolygon) and center of pressure
+/// of a body from its current contacts in [`Collisions`].
+///
+/// This is useful for balancing characters and stacking puzzles that need to reason about tipping
+/// without manually aggregating contact manifolds.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// #[derive(Component)]
+/// struct Crate;
+///
+/// fn detect_tipping(
+///     crates: Query<(Entity, &Position, &Rotation, &CenterOfMass), With<Crate>>,
+///     support: ContactSupport,
+/// ) {
+///     for (entity, position, rotation, center_of_mass) in &crates {
+///         let Some(polygon) = support.support_polygon(entity, Vector::Y) else {
+///             continue;
+///         };
+///         let world_com = position.0 + rotation.rotate(center_of_mass.0);
+///         if !polygon.contains(world_com) {
+///             println!("{:?} is tipping over", entity);
+///         }
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct ContactSupport<'w, 's> {
+    collisions: Res<'w, Collisions>,
+    colliders: Query<
+        'w,
+        's,
+        (
+            &'static Position,
+            &'static Rotation,
+            Option<&'static ColliderParent>,
+        ),
+    >,
+}
+
+impl ContactSupport<'_, '_> {
+    /// Computes the support polygon of the given body against the given `up` direction,
+    /// like the opposite of gravity.
+    ///
+    /// Contacts of all colliders attached to the body are taken into account, but only contacts
+    /// that push the body along `up` support it. Returns `None` if the body has no supporting contacts.
+    pub fn support_polygon(&self, body: Entity, up: Vector) -> Option<SupportPolygon> {
+        let up = up.normalize_or_zero();
+        if up == Vector::ZERO {
+            return None;
+        }
+
+        let is_body_collider = |entity: Entity| {
+            entity == body
+                || self
+                    .colliders
+                    .get(entity)
+                    .is_ok_and(|(_, _, parent)| parent.is_some_and(|p| p.get() == body))
+        };
+
+        // The supporting contact points and their normal impulses
+        let mut points: Vec<(Vector, Scalar)> = vec![];
+
+        for contacts in self.collisions.iter() {
+            if !contacts.during_current_frame {
+                continue;
+            }
+            let is_first = is_body_collider(contacts.entity1);
+            if !is_first && !is_body_collider(contacts.entity2) {
+                continue;
+            }
+
+            let collider = if is_first {
+                contacts.entity1
+            } else {
+                contacts.entity2
+            };
+            let Ok((position, rotation, _)) = self.colliders.get(collider) else {
+                continue;
+            };
+
+            for manifold in contacts.manifolds.iter() {
+                // The normal of the supporting surface, pointing towards the body
+                let normal = if is_first {
+                    -manifold.global_normal1(rotation)
+                } else {
+                    -manifold.global_normal2(rotation)
+                };
+                if normal.dot(up) <= 0.0 {
+                    continue;
+                }
+
+                for contact in manifold.contacts.iter() {
+                    let point = if is_first {
+                        contact.global_point1(position, rotation)
+                    } else {
+                        contact.global_point2(position, rotation)
+                    };
+                    points.push((point, contact.normal_impulse.abs()));
+                }
+            }
+        }
+
+        if points.is_empty() {
+            return None;
+        }
+
+        let total_normal_impulse: Scalar = points.iter().map(|(_, impulse)| impulse).sum();
+        let center_of_pressure = if total_normal_impulse > Scalar::EPSILON {
+            points
+                .iter()
+                .map(|(point, impulse)| *point * *impulse)
+                .sum::<Vector>()
+                / total_normal_impulse
+        } else {
+            points.iter().map(|(point, _)| *point).sum::<Vector>() / points.len() as Scalar
+        };
+
+        #[cfg(feature = "2d")]
+        let vertices = {
+            let tangent = up.perp();
+            let min = points
+                .iter()
+                .map(|(point, _)| *point)
+                .min_by(|a, b| a.dot(tangent).total_cmp(&b.dot(tangent)));
+            let max = points
+                .iter()
+                .map(|(point, _)| *point)
+                .max_by(|a, b| a.dot(tangent).total_cmp(&b.dot(tangent)));
+            let mut vertices = min.into_iter().chain(max).collect::<Vec<_>>();
+            vertices.dedup();
+            vertices
+        };
+        #[cfg(feature = "3d")]
+        let vertices = {
+            let (u, v) = up.any_orthonormal_pair();
+            let projected = points
+                .iter()
+                .map(|(point, _)| Vector2::new(point.dot(u), point.dot(v)))
+                .collect::<Vec<_>>();
+
+            // `u` and `v` form a right-handed basis with `up`, so the hull is counterclockwise around `up`
+            crate::plugins::collision::convex_hull_indices(&projected)
+                .into_iter()
+                .map(|index| points[index].0)
+                .collect()
+        };
+
+        Some(SupportPolygon {
+            vertices,
+            up,
+            center_of_pressure,
+            total_normal_impulse,
+        })
+    }
+}
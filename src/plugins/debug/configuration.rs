@@ -68,6 +68,9 @@ pub struct PhysicsGizmos {
     pub joint_anchor_color: Option<Color>,
     /// The color of the lines drawn between joint anchors, indicating the separation.
     pub joint_separation_color: Option<Color>,
+    /// The color of the arrows drawn for [anchor errors](JointAnchorError) of joints whose anchors
+    /// didn't coincide when the joint was created.
+    pub joint_anchor_error_color: Option<Color>,
     /// The color used for the rays in [raycasts](spatial_query#raycasting).
     pub raycast_color: Option<Color>,
     /// The color used for the hit points in [raycasts](spatial_query#raycasting).
@@ -102,6 +105,7 @@ impl Default for PhysicsGizmos {
             contact_normal_scale: ContactGizmoScale::default(),
             joint_anchor_color: Some(Color::PINK),
             joint_separation_color: Some(Color::RED),
+            joint_anchor_error_color: Some(Color::YELLOW),
             raycast_color: Some(Color::RED),
             raycast_point_color: Some(Color::YELLOW),
             raycast_normal_color: Some(Color::PINK),
@@ -146,6 +150,7 @@ impl PhysicsGizmos {
             contact_normal_scale: ContactGizmoScale::default(),
             joint_anchor_color: Some(Color::PINK),
             joint_separation_color: Some(Color::RED),
+            joint_anchor_error_color: Some(Color::YELLOW),
             raycast_color: Some(Color::RED),
             raycast_point_color: Some(Color::YELLOW),
             raycast_normal_color: Some(Color::PINK),
@@ -171,6 +176,7 @@ impl PhysicsGizmos {
             contact_normal_scale: ContactGizmoScale::default(),
             joint_anchor_color: None,
             joint_separation_color: None,
+            joint_anchor_error_color: None,
             raycast_color: None,
             raycast_point_color: None,
             raycast_normal_color: None,
@@ -288,6 +294,12 @@ impl PhysicsGizmos {
         }
     }
 
+    /// Sets the color used for debug rendering [joint anchor errors](JointAnchorError).
+    pub fn with_joint_anchor_error_color(mut self, color: Color) -> Self {
+        self.joint_anchor_error_color = Some(color);
+        self
+    }
+
     /// Sets the colors used for debug rendering raycasts.
     pub fn with_raycast_colors(
         mut self,
@@ -356,6 +368,7 @@ impl PhysicsGizmos {
     pub fn without_joints(mut self) -> Self {
         self.joint_anchor_color = None;
         self.joint_separation_color = None;
+        self.joint_anchor_error_color = None;
         self
     }
 
@@ -23,7 +23,7 @@ use bevy::{ecs::query::Has, prelude::*, utils::intern::Interned};
 /// - [Collider] wireframes
 /// - Using different colors for [sleeping](Sleeping) bodies
 /// - [Contacts]
-/// - [Joints](joints)
+/// - [Joints](joints), including [anchor errors](JointAnchorError) of joints whose anchors don't coincide
 /// - [`RayCaster`]
 /// - [`ShapeCaster`]
 /// - Changing the visibility of entities to only show debug rendering
@@ -107,6 +107,17 @@ impl Plugin for PhysicsDebugPlugin {
 
         app.register_type::<PhysicsGizmos>()
             .register_type::<DebugRender>()
+            .register_type::<JointAnchorError>()
+            .add_systems(
+                self.schedule,
+                (
+                    validate_joint_anchors::<FixedJoint>,
+                    validate_joint_anchors::<RevoluteJoint>,
+                    validate_joint_anchors::<SphericalJoint>,
+                )
+                    .after(PhysicsSet::Prepare)
+                    .before(PhysicsSet::StepSimulation),
+            )
             .add_systems(
                 self.schedule,
                 (
@@ -124,6 +135,14 @@ impl Plugin for PhysicsDebugPlugin {
                     debug_render_joints::<DistanceJoint>,
                     debug_render_joints::<RevoluteJoint>,
                     debug_render_joints::<SphericalJoint>,
+                    debug_render_joints::<SplineDriveJoint>,
+                    debug_render_joints::<SuspensionConstraint>,
+                    debug_render_joints::<GenericJoint>,
+                    debug_render_joints::<GearJoint>,
+                    debug_render_joints::<PulleyJoint>,
+                    debug_render_joint_anchor_errors::<FixedJoint>,
+                    debug_render_joint_anchor_errors::<RevoluteJoint>,
+                    debug_render_joint_anchor_errors::<SphericalJoint>,
                     debug_render_raycasts,
                     #[cfg(all(
                         feature = "default-collider",
@@ -438,6 +457,67 @@ fn debug_render_joints<T: Joint>(
     }
 }
 
+/// The anchor separation of joints that is tolerated before a [`JointAnchorError`] is reported.
+const JOINT_ANCHOR_ERROR_TOLERANCE: Scalar = 1e-3;
+
+/// The offset between the world-space anchors of a joint at the time the joint was created,
+/// pointing from the anchor on the first body to the anchor on the second body.
+///
+/// For [fixed](FixedJoint), [revolute](RevoluteJoint) and [spherical](SphericalJoint) joints, the anchors should
+/// coincide when the bodies are at rest. Otherwise, the joint pulls the bodies together on the first
+/// step, which causes a jolt at startup. When the [`PhysicsDebugPlugin`] is enabled, this component is added to
+/// such joints, a warning with the measured offset is logged once, and the offset is drawn as an arrow
+/// using the [`PhysicsGizmos::joint_anchor_error_color`].
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq)]
+#[reflect(Component)]
+pub struct JointAnchorError(pub Vector);
+
+/// Measures the anchor separation of newly created joints and reports a [`JointAnchorError`]
+/// if the anchors don't coincide.
+fn validate_joint_anchors<T: Joint>(
+    mut commands: Commands,
+    bodies: Query<(&Position, &Rotation)>,
+    joints: Query<(Entity, &T), Added<T>>,
+) {
+    for (entity, joint) in &joints {
+        let Ok([(pos1, rot1), (pos2, rot2)]) = bodies.get_many(joint.entities()) else {
+            continue;
+        };
+
+        let anchor1 = pos1.0 + rot1.rotate(joint.local_anchor_1());
+        let anchor2 = pos2.0 + rot2.rotate(joint.local_anchor_2());
+        let offset = anchor2 - anchor1;
+        let error = offset.length();
+
+        if error > JOINT_ANCHOR_ERROR_TOLERANCE {
+            warn!(
+                "the anchors of joint {entity:?} are {error} units apart at rest (offset {offset}), \
+                which causes a jolt when the simulation starts. Check the local anchors of the joint."
+            );
+            commands.entity(entity).insert(JointAnchorError(offset));
+        }
+    }
+}
+
+fn debug_render_joint_anchor_errors<T: Joint>(
+    bodies: Query<(&Position, &Rotation)>,
+    joints: Query<(&T, &JointAnchorError)>,
+    mut gizmos: Gizmos<PhysicsGizmos>,
+    store: Res<GizmoConfigStore>,
+) {
+    let config = store.config::<PhysicsGizmos>().1;
+    let Some(color) = config.joint_anchor_error_color else {
+        return;
+    };
+
+    for (joint, error) in &joints {
+        if let Ok((pos1, rot1)) = bodies.get(joint.entities()[0]) {
+            let anchor1 = pos1.0 + rot1.rotate(joint.local_anchor_1());
+            gizmos.draw_arrow(anchor1, anchor1 + error.0, 0.1 * error.0.length(), color);
+        }
+    }
+}
+
 fn debug_render_raycasts(
     query: Query<(&RayCaster, &RayHits)>,
     mut gizmos: Gizmos<PhysicsGizmos>,
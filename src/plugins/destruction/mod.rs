@@ -0,0 +1,246 @@
+//! Breaks [`Destructible`] bodies into precomputed fragments when they are hit hard enough.
+//!
+//! See [`DestructionPlugin`].
+//!
+//! Fragments can be authored by hand or computed at runtime with [`voronoi_fracture`].
+
+mod voronoi;
+pub use voronoi::*;
+
+use crate::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+
+/// Breaks [`Destructible`] bodies into precomputed fragments when they are hit hard enough.
+///
+/// Each physics frame, the total contact force of every collision involving a [`Destructible`]
+/// body is compared against its [`force_threshold`](Destructible::force_threshold). If the threshold
+/// is exceeded, the body is despawned and replaced by a new dynamic rigid body for each of its
+/// [fragments](Fragment). The fragments inherit the velocity of the original body at their location,
+/// including the contribution of its angular velocity.
+///
+/// A [`DestructibleFractured`] event is sent for each fractured body. It can be used for spawning
+/// meshes, particles or sounds for the new fragments.
+///
+/// The destruction system runs in [`PhysicsStepSet::ReportContacts`].
+pub struct DestructionPlugin;
+
+impl Plugin for DestructionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DestructibleFractured>();
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule.add_systems(fracture_destructibles.in_set(PhysicsStepSet::ReportContacts));
+    }
+}
+
+/// A component that makes a [rigid body](RigidBody) break into precomputed [fragments](Fragment)
+/// when the total contact force of a collision exceeds the [`force_threshold`](Self::force_threshold).
+///
+/// Fracturing is handled by the [`DestructionPlugin`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// # #[cfg(feature = "f32")]
+/// fn setup(mut commands: Commands) {
+///     // Spawn a box that breaks into two halves when hit hard enough
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(2.0, 1.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(2.0, 1.0, 1.0),")]
+///         Destructible::new(
+///             500.0,
+///             vec![
+#[cfg_attr(
+    feature = "2d",
+    doc = "                Fragment::new(Collider::rectangle(1.0, 1.0)).with_position(Vec2::new(-0.5, 0.0)),"
+)]
+#[cfg_attr(
+    feature = "2d",
+    doc = "                Fragment::new(Collider::rectangle(1.0, 1.0)).with_position(Vec2::new(0.5, 0.0)),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "                Fragment::new(Collider::cuboid(1.0, 1.0, 1.0)).with_position(Vec3::new(-0.5, 0.0, 0.0)),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "                Fragment::new(Collider::cuboid(1.0, 1.0, 1.0)).with_position(Vec3::new(0.5, 0.0, 0.0)),"
+)]
+///             ],
+///         ),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Component, Debug)]
+pub struct Destructible {
+    /// The total contact force required for breaking the body.
+    ///
+    /// The force is computed from the total normal impulse of a single collision
+    /// divided by the physics timestep.
+    pub force_threshold: Scalar,
+    /// The fragments that the body is replaced with when it breaks.
+    pub fragments: Vec<Fragment>,
+}
+
+impl Destructible {
+    /// Creates a new [`Destructible`] that breaks into the given `fragments`
+    /// when a contact force exceeds `force_threshold`.
+    pub fn new(force_threshold: Scalar, fragments: Vec<Fragment>) -> Self {
+        Self {
+            force_threshold,
+            fragments,
+        }
+    }
+}
+
+/// A precomputed piece of a [`Destructible`] body.
+///
+/// The position and rotation are relative to the body that the fragment belongs to.
+#[derive(Clone, Debug)]
+pub struct Fragment {
+    /// The collider of the fragment.
+    pub collider: Collider,
+    /// The position of the fragment in the local space of the original body.
+    pub position: Vector,
+    /// The rotation of the fragment relative to the original body.
+    pub rotation: Rotation,
+}
+
+impl Fragment {
+    /// Creates a new [`Fragment`] with the given collider at the origin of the original body.
+    pub fn new(collider: Collider) -> Self {
+        Self {
+            collider,
+            position: Vector::ZERO,
+            rotation: Rotation::default(),
+        }
+    }
+
+    /// Sets the position of the fragment in the local space of the original body.
+    pub fn with_position(self, position: Vector) -> Self {
+        Self { position, ..self }
+    }
+
+    /// Sets the rotation of the fragment relative to the original body.
+    pub fn with_rotation(self, rotation: Rotation) -> Self {
+        Self { rotation, ..self }
+    }
+}
+
+/// An event that is sent when a [`Destructible`] body breaks into [fragments](Fragment).
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct DestructibleFractured {
+    /// The entity of the body that was broken. It has been despawned.
+    pub entity: Entity,
+    /// The entities of the spawned fragments, in the same order as in [`Destructible::fragments`].
+    pub fragments: Vec<Entity>,
+}
+
+type DestructibleQueryComponents = (
+    &'static Destructible,
+    &'static Position,
+    &'static Rotation,
+    &'static LinearVelocity,
+    &'static AngularVelocity,
+    Option<&'static CenterOfMass>,
+    Option<&'static CollisionLayers>,
+    Option<&'static ColliderDensity>,
+);
+
+/// Replaces [`Destructible`] bodies with their [fragments](Fragment) when
+/// the contact force of a collision exceeds the body's force threshold.
+fn fracture_destructibles(
+    mut commands: Commands,
+    collisions: Res<Collisions>,
+    collider_parents: Query<&ColliderParent>,
+    destructibles: Query<DestructibleQueryComponents>,
+    time: Res<Time>,
+    mut fracture_ev_writer: EventWriter<DestructibleFractured>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    if delta_secs == 0.0 {
+        return;
+    }
+
+    // Find the largest contact force for each destructible body.
+    let mut max_forces = HashMap::<Entity, Scalar>::default();
+
+    for contacts in collisions.iter() {
+        let force = contacts.total_normal_impulse / delta_secs;
+
+        for collider in [contacts.entity1, contacts.entity2] {
+            let body = collider_parents.get(collider).map_or(collider, |p| p.get());
+
+            if destructibles.contains(body) {
+                let max_force = max_forces.entry(body).or_insert(0.0);
+                *max_force = max_force.max(force);
+            }
+        }
+    }
+
+    for (entity, force) in max_forces {
+        let Ok((destructible, pos, rot, lin_vel, ang_vel, center_of_mass, layers, density)) =
+            destructibles.get(entity)
+        else {
+            continue;
+        };
+
+        if force < destructible.force_threshold {
+            continue;
+        }
+
+        let world_com = pos.0 + rot.rotate(center_of_mass.map_or(Vector::ZERO, |com| com.0));
+
+        let fragments = destructible
+            .fragments
+            .iter()
+            .map(|fragment| {
+                let fragment_pos = pos.0 + rot.rotate(fragment.position);
+
+                #[cfg(feature = "2d")]
+                let fragment_rot = rot.mul(fragment.rotation);
+                #[cfg(feature = "3d")]
+                let fragment_rot = Rotation(rot.0 * fragment.rotation.0);
+
+                // Inherit the velocity of the original body at the fragment's position.
+                let r = fragment_pos - world_com;
+                #[cfg(feature = "2d")]
+                let fragment_lin_vel = lin_vel.0 + ang_vel.0 * r.perp();
+                #[cfg(feature = "3d")]
+                let fragment_lin_vel = lin_vel.0 + ang_vel.0.cross(r);
+
+                let mut fragment_commands = commands.spawn((
+                    RigidBody::Dynamic,
+                    fragment.collider.clone(),
+                    Position(fragment_pos),
+                    fragment_rot,
+                    LinearVelocity(fragment_lin_vel),
+                    *ang_vel,
+                    TransformBundle::default(),
+                ));
+
+                if let Some(layers) = layers {
+                    fragment_commands.insert(*layers);
+                }
+                if let Some(density) = density {
+                    fragment_commands.insert(*density);
+                }
+
+                fragment_commands.id()
+            })
+            .collect();
+
+        commands.entity(entity).despawn_recursive();
+
+        fracture_ev_writer.send(DestructibleFractured { entity, fragments });
+    }
+}
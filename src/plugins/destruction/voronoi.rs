@@ -0,0 +1,257 @@
+//! Runtime Voronoi fracturing of convex shapes.
+
+use super::Fragment;
+use crate::prelude::*;
+#[cfg(feature = "3d")]
+use bevy::utils::HashSet;
+use parry::{math::Point, shape::TypedShape};
+
+/// Fractures a convex [`Collider`] into `fragment_count` Voronoi cells around the given `impact_point`.
+///
+/// The Voronoi sites are scattered randomly inside the collider's bounds, biased towards the impact point,
+/// so fragments near the impact are smaller than fragments further away. The given `seed` makes the result
+/// deterministic.
+///
+/// Each fragment is a [`Collider::convex_hull`] centered at its own center of mass, and its
+/// [position](Fragment::position) is relative to the original collider. The fragments fill the original shape,
+/// so with the same [`ColliderDensity`], the mass is distributed between them based on their sizes.
+///
+/// The impact point should be given in the local space of the collider. Concave shapes are fractured
+/// based on their convex hull, and shapes that are not supported return an empty vector.
+///
+/// The result can be used as the fragments of a [`Destructible`](super::Destructible).
+pub fn voronoi_fracture(
+    collider: &Collider,
+    impact_point: Vector,
+    fragment_count: usize,
+    seed: u64,
+) -> Vec<Fragment> {
+    match collider_points(collider) {
+        Some(points) => voronoi_fracture_points(&points, impact_point, fragment_count, seed),
+        None => vec![],
+    }
+}
+
+/// Fractures the convex hull of the given `points` into `fragment_count` Voronoi cells
+/// around the given `impact_point`.
+///
+/// See [`voronoi_fracture`] for more information.
+pub fn voronoi_fracture_points(
+    points: &[Vector],
+    impact_point: Vector,
+    fragment_count: usize,
+    seed: u64,
+) -> Vec<Fragment> {
+    let Some(hull) = ConvexHull::new(points) else {
+        return vec![];
+    };
+
+    if fragment_count <= 1 {
+        return Fragment::from_points(hull.points).into_iter().collect();
+    }
+
+    let (min, max) = hull.points.iter().fold(
+        (Vector::splat(Scalar::MAX), Vector::splat(Scalar::MIN)),
+        |(min, max), point| (min.min(*point), max.max(*point)),
+    );
+
+    // Scatter the Voronoi sites, biased towards the impact point.
+    let mut rng = XorShift64::new(seed);
+    let sites = (0..fragment_count)
+        .map(|_| {
+            #[cfg(feature = "2d")]
+            let random_point = min + (max - min) * Vector::new(rng.next(), rng.next());
+            #[cfg(feature = "3d")]
+            let random_point = min + (max - min) * Vector::new(rng.next(), rng.next(), rng.next());
+            let t = rng.next();
+            impact_point.lerp(random_point, t.sqrt())
+        })
+        .collect::<Vec<_>>();
+
+    sites
+        .iter()
+        .enumerate()
+        .filter_map(|(i, site)| {
+            let mut cell = hull.clone();
+
+            // Clip the hull with the bisector planes between this site and all other sites.
+            for (j, other_site) in sites.iter().enumerate() {
+                if i == j || site == other_site {
+                    continue;
+                }
+                let normal = *other_site - *site;
+                let offset = normal.dot((*site + *other_site) * 0.5);
+                cell = cell.clip(normal, offset)?;
+            }
+
+            Fragment::from_points(cell.points)
+        })
+        .collect()
+}
+
+impl Fragment {
+    /// Creates a fragment from the convex hull of the given points,
+    /// centering the collider at its center of mass.
+    fn from_points(points: Vec<Vector>) -> Option<Self> {
+        let center = Collider::convex_hull(points.clone())?
+            .mass_properties(1.0)
+            .center_of_mass
+            .0;
+        let collider = Collider::convex_hull(points.iter().map(|p| *p - center).collect())?;
+        Some(Fragment::new(collider).with_position(center))
+    }
+}
+
+/// Returns the points of the convex hull of the given collider, or `None` if the shape is not supported.
+fn collider_points(collider: &Collider) -> Option<Vec<Vector>> {
+    let points: Vec<Point<Scalar>> = match collider.shape_scaled().as_typed_shape() {
+        #[cfg(feature = "2d")]
+        TypedShape::Ball(s) => s.to_polyline(32),
+        #[cfg(feature = "2d")]
+        TypedShape::Cuboid(s) => s.to_polyline(),
+        #[cfg(feature = "2d")]
+        TypedShape::Capsule(s) => s.to_polyline(16),
+        #[cfg(feature = "2d")]
+        TypedShape::ConvexPolygon(s) => s.points().to_vec(),
+        #[cfg(feature = "3d")]
+        TypedShape::Ball(s) => s.to_trimesh(16, 16).0,
+        #[cfg(feature = "3d")]
+        TypedShape::Cuboid(s) => s.to_trimesh().0,
+        #[cfg(feature = "3d")]
+        TypedShape::Capsule(s) => s.to_trimesh(16, 16).0,
+        #[cfg(feature = "3d")]
+        TypedShape::Cylinder(s) => s.to_trimesh(16).0,
+        #[cfg(feature = "3d")]
+        TypedShape::Cone(s) => s.to_trimesh(16).0,
+        #[cfg(feature = "3d")]
+        TypedShape::ConvexPolyhedron(s) => s.points().to_vec(),
+        TypedShape::Triangle(s) => vec![s.a, s.b, s.c],
+        TypedShape::TriMesh(s) => s.vertices().to_vec(),
+        _ => return None,
+    };
+    Some(points.into_iter().map(Into::into).collect())
+}
+
+/// A convex hull represented by its vertices and edges.
+#[derive(Clone, Debug)]
+struct ConvexHull {
+    points: Vec<Vector>,
+    edges: Vec<[usize; 2]>,
+}
+
+impl ConvexHull {
+    /// Computes the convex hull of the given points.
+    /// Returns `None` if the hull is degenerate.
+    fn new(points: &[Vector]) -> Option<Self> {
+        let points = points
+            .iter()
+            .map(|p| (*p).into())
+            .collect::<Vec<Point<Scalar>>>();
+
+        #[cfg(feature = "2d")]
+        {
+            let hull = parry::transformation::convex_hull(&points);
+            if hull.len() < 3 {
+                return None;
+            }
+            let edges = (0..hull.len()).map(|i| [i, (i + 1) % hull.len()]).collect();
+            Some(Self {
+                points: hull.into_iter().map(Into::into).collect(),
+                edges,
+            })
+        }
+
+        #[cfg(feature = "3d")]
+        {
+            let (vertices, indices) = parry::transformation::convex_hull(&points);
+            if vertices.len() < 4 {
+                return None;
+            }
+            let edges = indices
+                .iter()
+                .flat_map(|[a, b, c]| [[*a, *b], [*b, *c], [*c, *a]])
+                .map(|[a, b]| [a.min(b) as usize, a.max(b) as usize])
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect();
+            Some(Self {
+                points: vertices.into_iter().map(Into::into).collect(),
+                edges,
+            })
+        }
+    }
+
+    /// Clips the hull with a plane, keeping the part where `normal.dot(point) <= offset`.
+    /// Returns `None` if the remaining hull is degenerate.
+    fn clip(&self, normal: Vector, offset: Scalar) -> Option<Self> {
+        let distances = self
+            .points
+            .iter()
+            .map(|p| normal.dot(*p) - offset)
+            .collect::<Vec<_>>();
+
+        if distances.iter().all(|d| *d <= 0.0) {
+            return Some(self.clone());
+        }
+
+        let mut points = self
+            .points
+            .iter()
+            .zip(&distances)
+            .filter(|(_, d)| **d <= 0.0)
+            .map(|(p, _)| *p)
+            .collect::<Vec<_>>();
+
+        // Add the points where the edges cross the plane.
+        for [a, b] in &self.edges {
+            let (d1, d2) = (distances[*a], distances[*b]);
+            if (d1 <= 0.0) != (d2 <= 0.0) {
+                let t = d1 / (d1 - d2);
+                points.push(self.points[*a].lerp(self.points[*b], t));
+            }
+        }
+
+        Self::new(&points)
+    }
+}
+
+/// A small deterministic pseudo-random number generator.
+struct XorShift64(u64);
+
+impl XorShift64 {
+    fn new(seed: u64) -> Self {
+        // The state must be non-zero.
+        Self((seed ^ 0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    /// Returns a pseudo-random number in the range `[0, 1)`.
+    fn next(&mut self) -> Scalar {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as Scalar / (1u64 << 53) as Scalar
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn voronoi_fracture_preserves_mass() {
+        #[cfg(feature = "2d")]
+        let collider = Collider::rectangle(2.0, 1.0);
+        #[cfg(feature = "3d")]
+        let collider = Collider::cuboid(2.0, 1.0, 1.0);
+
+        let fragments = voronoi_fracture(&collider, Vector::X, 8, 42);
+        assert!(fragments.len() > 1);
+
+        let total_mass: Scalar = fragments
+            .iter()
+            .map(|fragment| fragment.collider.mass_properties(1.0).mass.0)
+            .sum();
+        assert_relative_eq!(total_mass, 2.0, epsilon = 1e-3);
+    }
+}
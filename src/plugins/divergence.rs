@@ -0,0 +1,187 @@
+//! Compares two configurations of the same scene step by step.
+//!
+//! See [`DivergenceDetector`].
+
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::HashMap};
+
+use crate::prelude::*;
+
+/// A development tool that steps two apps with different configurations of the same scene side by side
+/// and measures how far their simulations diverge at each step.
+///
+/// This can be used to evaluate the impact of changes to the solver configuration or [`SubstepCount`],
+/// or to find the step where a feature breaks determinism.
+///
+/// Bodies are matched between the two apps using their [`PhysicsId`]s, so each body that should be compared
+/// must have the same [`PhysicsId`] in both apps. Bodies without a matching body in the other app
+/// are counted in [`StepDivergence::unmatched_bodies`].
+///
+/// Both apps are advanced by the same amount of time on each step, regardless of real time.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn create_app(substeps: u32) -> App {
+///     let mut app = App::new();
+///     app.add_plugins((MinimalPlugins, PhysicsPlugins::default()))
+///         .insert_resource(SubstepCount(substeps))
+///         .add_systems(Startup, setup);
+///     app
+/// }
+///
+/// fn setup(mut commands: Commands) {
+#[cfg_attr(
+    feature = "2d",
+    doc = "    commands.spawn((RigidBody::Dynamic, Collider::circle(0.5), PhysicsId(0)));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    commands.spawn((RigidBody::Dynamic, Collider::sphere(0.5), PhysicsId(0)));"
+)]
+/// }
+///
+/// fn main() {
+///     let mut detector = DivergenceDetector::new(create_app(12), create_app(4));
+///
+///     for divergence in detector.run(600) {
+///         println!(
+///             "step {}: position RMS {}, velocity RMS {}",
+///             divergence.step, divergence.position_rms, divergence.linear_velocity_rms,
+///         );
+///     }
+/// }
+/// ```
+pub struct DivergenceDetector {
+    apps: [App; 2],
+    delta: Duration,
+    step: usize,
+}
+
+impl DivergenceDetector {
+    /// Creates a [`DivergenceDetector`] that compares the simulations of the given apps.
+    ///
+    /// By default, the apps are advanced by 1/60 seconds on each step.
+    pub fn new(app_a: App, app_b: App) -> Self {
+        Self {
+            apps: [app_a, app_b],
+            delta: Duration::from_secs_f64(1.0 / 60.0),
+            step: 0,
+        }
+    }
+
+    /// Sets the amount of time that the apps are advanced by on each step.
+    pub fn with_delta(self, delta: Duration) -> Self {
+        Self { delta, ..self }
+    }
+
+    /// Returns the two apps that are compared.
+    pub fn apps(&self) -> [&App; 2] {
+        [&self.apps[0], &self.apps[1]]
+    }
+
+    /// Returns mutable references to the two apps that are compared.
+    pub fn apps_mut(&mut self) -> [&mut App; 2] {
+        let [app_a, app_b] = &mut self.apps;
+        [app_a, app_b]
+    }
+
+    /// Updates both apps once and returns the divergence between their simulations after the update.
+    pub fn step(&mut self) -> StepDivergence {
+        for app in self.apps.iter_mut() {
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(self.delta));
+            app.update();
+        }
+        self.step += 1;
+        self.measure()
+    }
+
+    /// Updates both apps the given number of times and returns the divergence after each update.
+    pub fn run(&mut self, steps: usize) -> Vec<StepDivergence> {
+        (0..steps).map(|_| self.step()).collect()
+    }
+
+    /// Measures the divergence between the bodies of the two apps.
+    fn measure(&mut self) -> StepDivergence {
+        let [app_a, app_b] = &mut self.apps;
+
+        let mut query_b = app_b
+            .world
+            .query::<(&PhysicsId, &Position, &LinearVelocity, &AngularVelocity)>();
+        let mut bodies_b: HashMap<PhysicsId, (Vector, Vector, AngularVelocity)> = query_b
+            .iter(&app_b.world)
+            .map(|(id, pos, lin_vel, ang_vel)| (*id, (pos.0, lin_vel.0, *ang_vel)))
+            .collect();
+
+        let mut divergence = StepDivergence {
+            step: self.step,
+            ..default()
+        };
+
+        let mut query_a = app_a
+            .world
+            .query::<(&PhysicsId, &Position, &LinearVelocity, &AngularVelocity)>();
+        for (id, pos, lin_vel, ang_vel) in query_a.iter(&app_a.world) {
+            let Some((pos_b, lin_vel_b, ang_vel_b)) = bodies_b.remove(id) else {
+                divergence.unmatched_bodies += 1;
+                continue;
+            };
+
+            divergence.matched_bodies += 1;
+            divergence.position_rms += (pos.0 - pos_b).length_squared();
+            divergence.linear_velocity_rms += (lin_vel.0 - lin_vel_b).length_squared();
+            #[cfg(feature = "2d")]
+            {
+                divergence.angular_velocity_rms += (ang_vel.0 - ang_vel_b.0).powi(2);
+            }
+            #[cfg(feature = "3d")]
+            {
+                divergence.angular_velocity_rms += (ang_vel.0 - ang_vel_b.0).length_squared();
+            }
+        }
+        divergence.unmatched_bodies += bodies_b.len();
+
+        // Turn the sums of squared differences into root mean squares
+        if divergence.matched_bodies > 0 {
+            let count = divergence.matched_bodies as Scalar;
+            divergence.position_rms = (divergence.position_rms / count).sqrt();
+            divergence.linear_velocity_rms = (divergence.linear_velocity_rms / count).sqrt();
+            divergence.angular_velocity_rms = (divergence.angular_velocity_rms / count).sqrt();
+        }
+
+        divergence
+    }
+}
+
+/// The divergence between the simulations of two apps after a step of a [`DivergenceDetector`].
+///
+/// The differences are root mean squares over the bodies that were matched by their [`PhysicsId`]s.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StepDivergence {
+    /// The number of steps taken by the [`DivergenceDetector`], starting from 1.
+    pub step: usize,
+    /// The root mean square of the distances between the [positions](Position) of matched bodies.
+    pub position_rms: Scalar,
+    /// The root mean square of the differences between the [linear velocities](LinearVelocity) of matched bodies.
+    pub linear_velocity_rms: Scalar,
+    /// The root mean square of the differences between the [angular velocities](AngularVelocity) of matched bodies.
+    pub angular_velocity_rms: Scalar,
+    /// The number of bodies that were found in both apps.
+    pub matched_bodies: usize,
+    /// The number of bodies that were only found in one of the apps.
+    pub unmatched_bodies: usize,
+}
+
+impl StepDivergence {
+    /// Returns true if any of the root mean squares is larger than the given tolerance.
+    pub fn exceeds(&self, tolerance: Scalar) -> bool {
+        self.position_rms > tolerance
+            || self.linear_velocity_rms > tolerance
+            || self.angular_velocity_rms > tolerance
+    }
+}
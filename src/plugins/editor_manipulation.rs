@@ -0,0 +1,151 @@
+//! Lets editors and transform gizmos move rigid bodies without fighting the solver.
+//!
+//! See [`EditorManipulationPlugin`].
+
+use crate::{prelude::*, prepare::PrepareSet};
+use bevy::prelude::*;
+
+/// A plugin for cooperating with editors and transform gizmos that move rigid bodies by dragging them.
+///
+/// While a body has the [`EditorManipulation`] component, it is temporarily made [kinematic](RigidBody::Kinematic),
+/// its velocities are kept at zero, and its cached contacts are cleared. This way, the body follows
+/// its `Transform` exactly without being pushed around by the solver or pushing other bodies
+/// with a large velocity. When the component is removed, the original [`RigidBody`] type is restored,
+/// and the body and the bodies it was touching are woken up.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// #[derive(Event)]
+/// struct GizmoDragStarted(Entity);
+///
+/// #[derive(Event)]
+/// struct GizmoDragEnded(Entity);
+///
+/// fn handle_gizmo_drags(
+///     mut commands: Commands,
+///     mut started: EventReader<GizmoDragStarted>,
+///     mut ended: EventReader<GizmoDragEnded>,
+/// ) {
+///     for GizmoDragStarted(entity) in started.read() {
+///         commands.entity(*entity).insert(EditorManipulation);
+///     }
+///     for GizmoDragEnded(entity) in ended.read() {
+///         commands.entity(*entity).remove::<EditorManipulation>();
+///     }
+/// }
+/// ```
+pub struct EditorManipulationPlugin;
+
+impl Plugin for EditorManipulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<EditorManipulation>()
+            .register_type::<ManipulatedBody>();
+
+        app.add_systems(
+            PostUpdate,
+            (
+                begin_manipulation,
+                end_manipulation,
+                hold_manipulated_bodies,
+            )
+                .chain()
+                .in_set(PrepareSet::PreInit),
+        );
+    }
+}
+
+/// A marker component for rigid bodies that are being moved by an editor or a transform gizmo.
+///
+/// Used by the [`EditorManipulationPlugin`]. Insert the component when dragging begins
+/// and remove it when dragging ends.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct EditorManipulation;
+
+/// Stores the [`RigidBody`] type of a body while it is being [manipulated](EditorManipulation),
+/// so that it can be restored afterwards.
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[reflect(Component)]
+pub struct ManipulatedBody(pub RigidBody);
+
+/// Makes bodies that have started being manipulated kinematic and clears their velocities and contacts.
+fn begin_manipulation(
+    mut commands: Commands,
+    mut bodies: Query<
+        (
+            Entity,
+            &mut RigidBody,
+            Option<&mut LinearVelocity>,
+            Option<&mut AngularVelocity>,
+        ),
+        (Added<EditorManipulation>, Without<ManipulatedBody>),
+    >,
+    mut collisions: ResMut<Collisions>,
+) {
+    for (entity, mut rb, lin_vel, ang_vel) in &mut bodies {
+        commands.entity(entity).insert(ManipulatedBody(*rb));
+        *rb = RigidBody::Kinematic;
+        if let Some(mut lin_vel) = lin_vel {
+            lin_vel.0 = Vector::ZERO;
+        }
+        if let Some(mut ang_vel) = ang_vel {
+            *ang_vel = AngularVelocity::ZERO;
+        }
+        clear_contacts(&mut commands, &mut collisions, entity);
+    }
+}
+
+/// Restores the original [`RigidBody`] type of bodies that are no longer being manipulated.
+fn end_manipulation(
+    mut commands: Commands,
+    mut removed: RemovedComponents<EditorManipulation>,
+    mut bodies: Query<(&mut RigidBody, &ManipulatedBody), Without<EditorManipulation>>,
+    mut collisions: ResMut<Collisions>,
+) {
+    for entity in removed.read() {
+        let Ok((mut rb, manipulated)) = bodies.get_mut(entity) else {
+            continue;
+        };
+        *rb = manipulated.0;
+        commands.entity(entity).remove::<ManipulatedBody>();
+        clear_contacts(&mut commands, &mut collisions, entity);
+    }
+}
+
+/// Keeps the velocities of manipulated bodies at zero, so that they only move through their `Transform`.
+fn hold_manipulated_bodies(
+    mut bodies: Query<
+        (Option<&mut LinearVelocity>, Option<&mut AngularVelocity>),
+        With<EditorManipulation>,
+    >,
+) {
+    for (lin_vel, ang_vel) in &mut bodies {
+        if let Some(mut lin_vel) = lin_vel {
+            if lin_vel.0 != Vector::ZERO {
+                lin_vel.0 = Vector::ZERO;
+            }
+        }
+        if let Some(mut ang_vel) = ang_vel {
+            if *ang_vel != AngularVelocity::ZERO {
+                *ang_vel = AngularVelocity::ZERO;
+            }
+        }
+    }
+}
+
+/// Removes the cached contacts of the entity and wakes up the entity and the bodies it was touching.
+fn clear_contacts(commands: &mut Commands, collisions: &mut Collisions, entity: Entity) {
+    for contacts in collisions.collisions_with_entity(entity) {
+        commands.entity(contacts.entity1).remove::<Sleeping>();
+        commands.entity(contacts.entity2).remove::<Sleeping>();
+    }
+    collisions.remove_collisions_with_entity(entity);
+    commands.entity(entity).remove::<Sleeping>();
+}
@@ -0,0 +1,156 @@
+//! Tracks the total energy of the simulation and how fast it drifts.
+//!
+//! See [`PhysicsEnergyDiagnosticsPlugin`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A plugin that sums up the kinetic and gravitational potential energy of all dynamic bodies
+/// after each physics step and stores them in the [`PhysicsEnergyDiagnostics`] resource.
+///
+/// The rate at which the total energy changes can be used to detect configurations that inject energy
+/// into the simulation, like bad combinations of restitution and compliance, before things visibly explode.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             PhysicsEnergyDiagnosticsPlugin,
+///         ))
+///         .add_systems(Update, warn_about_energy_gain)
+///         .run();
+/// }
+///
+/// fn warn_about_energy_gain(energy: Res<PhysicsEnergyDiagnostics>) {
+///     if energy.drift_rate > 100.0 {
+///         warn!("The simulation is gaining {} J/s", energy.drift_rate);
+///     }
+/// }
+/// ```
+pub struct PhysicsEnergyDiagnosticsPlugin;
+
+impl Plugin for PhysicsEnergyDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsEnergyDiagnostics>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                update_energy_diagnostics
+                    .after(PhysicsStepSet::Substeps)
+                    .before(PhysicsStepSet::Sleeping),
+            );
+    }
+}
+
+/// Energy diagnostics of the simulation, updated after each physics step by the [`PhysicsEnergyDiagnosticsPlugin`].
+///
+/// The potential energy is the gravitational potential energy relative to the origin, so only changes
+/// in the total energy are meaningful. In a closed system without damping or friction, the total energy
+/// should stay roughly constant. Friction, damping and inelastic collisions dissipate energy and cause
+/// a negative drift, while a consistently positive drift means that the simulation is gaining energy.
+///
+/// Spawning, despawning and teleporting bodies also changes the total energy.
+/// Use [`PhysicsEnergyDiagnostics::reset`] to measure the drift from a new starting point.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct PhysicsEnergyDiagnostics {
+    /// The total linear and rotational kinetic energy of the dynamic bodies.
+    pub kinetic_energy: Scalar,
+    /// The total gravitational potential energy of the dynamic bodies, relative to the origin.
+    pub potential_energy: Scalar,
+    /// The rate of change of the total energy during the latest physics step, in joules per second.
+    pub drift_rate: Scalar,
+    /// The rate of change of the total energy since the first step or the latest [reset](Self::reset),
+    /// in joules per second.
+    pub average_drift_rate: Scalar,
+    /// The largest [`drift_rate`](Self::drift_rate) since the first step or the latest [reset](Self::reset).
+    pub max_drift_rate: Scalar,
+    initial_energy: Option<Scalar>,
+    previous_energy: Option<Scalar>,
+    elapsed: Scalar,
+}
+
+impl PhysicsEnergyDiagnostics {
+    /// Returns the total energy, the sum of the kinetic and potential energy.
+    pub fn total_energy(&self) -> Scalar {
+        self.kinetic_energy + self.potential_energy
+    }
+
+    /// Returns the change in total energy since the first step or the latest [reset](Self::reset).
+    pub fn total_drift(&self) -> Scalar {
+        self.initial_energy
+            .map_or(0.0, |initial| self.total_energy() - initial)
+    }
+
+    /// Resets the drift tracking so that drift is measured from the next physics step.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+type EnergyBodyComponents = (
+    &'static RigidBody,
+    &'static Position,
+    &'static Rotation,
+    &'static LinearVelocity,
+    &'static AngularVelocity,
+    &'static Mass,
+    &'static Inertia,
+    Option<&'static GravityScale>,
+);
+
+/// Sums up the energy of the dynamic bodies and updates the [`PhysicsEnergyDiagnostics`].
+fn update_energy_diagnostics(
+    bodies: Query<EnergyBodyComponents, Without<RigidBodyDisabled>>,
+    gravity: Res<Gravity>,
+    mut diagnostics: ResMut<PhysicsEnergyDiagnostics>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    let mut kinetic_energy = 0.0;
+    let mut potential_energy = 0.0;
+
+    for (rb, pos, rot, lin_vel, ang_vel, mass, inertia, gravity_scale) in &bodies {
+        if !rb.is_dynamic() {
+            continue;
+        }
+
+        #[cfg(feature = "2d")]
+        let rotational_energy = 0.5 * inertia.rotated(rot).0 * ang_vel.0 * ang_vel.0;
+        #[cfg(feature = "3d")]
+        let rotational_energy = 0.5 * ang_vel.dot(inertia.rotated(rot).0 * ang_vel.0);
+
+        kinetic_energy += 0.5 * mass.0 * lin_vel.length_squared() + rotational_energy;
+
+        let gravity_scale = gravity_scale.map_or(1.0, |scale| scale.0);
+        potential_energy -= mass.0 * gravity_scale * gravity.0.dot(pos.0);
+    }
+
+    let diagnostics = &mut *diagnostics;
+    diagnostics.kinetic_energy = kinetic_energy;
+    diagnostics.potential_energy = potential_energy;
+
+    let total_energy = diagnostics.total_energy();
+    let initial_energy = *diagnostics.initial_energy.get_or_insert(total_energy);
+
+    if let Some(previous_energy) = diagnostics.previous_energy {
+        if delta_secs > 0.0 {
+            diagnostics.elapsed += delta_secs;
+            diagnostics.drift_rate = (total_energy - previous_energy) / delta_secs;
+            diagnostics.average_drift_rate = (total_energy - initial_energy) / diagnostics.elapsed;
+            diagnostics.max_drift_rate = diagnostics.max_drift_rate.max(diagnostics.drift_rate);
+        }
+    }
+    diagnostics.previous_energy = Some(total_energy);
+}
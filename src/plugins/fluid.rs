@@ -0,0 +1,506 @@
+//! Particle-based fluid simulation using Position Based Fluids.
+//!
+//! See [`FluidPlugin`].
+
+use crate::{
+    plugins::{
+        collision::{broad_phase::SpatialHashGrid, collider_backend::handle_rigid_body_removals},
+        integrator::clear_forces_and_impulses,
+    },
+    prelude::*,
+    utils::make_isometry,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// The strength of the artificial pressure that prevents particles from clumping together.
+const TENSILE_STRENGTH: Scalar = 0.1;
+/// The distance at which the artificial pressure is evaluated, relative to the smoothing radius.
+const TENSILE_DISTANCE: Scalar = 0.2;
+
+/// A plugin that simulates [fluids](Fluid) made of particles using Position Based Fluids.
+///
+/// Each particle is moved by gravity, and its position is corrected so that the density around it
+/// stays close to the [rest density](FluidConfig::rest_density) of the fluid. This makes the fluid
+/// nearly incompressible, so it flows, splashes and fills containers like water.
+///
+/// Particles collide with [`Collider`]s as balls with the [particle radius](FluidConfig::particle_radius).
+/// The impulses of the collisions are applied to [dynamic](RigidBody::Dynamic) bodies using [`ExternalImpulse`],
+/// so floating objects are pushed up by the fluid and moving bodies push the fluid around.
+/// Colliders are found using the [`SpatialQueryPipeline`], and [sensors](Sensor) are ignored.
+///
+/// Particles don't collide with particles of other fluids.
+///
+/// This plugin requires the `pbd_fluid` feature. It is not included in [`PhysicsPlugins`] and must be added manually.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), FluidPlugin))
+///         .add_systems(Startup, setup)
+///         .run();
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     // A block of water
+#[cfg_attr(
+    feature = "2d",
+    doc = "    let water = Fluid::default().with_block(Vector::new(-1.0, 0.0), Vector::new(1.0, 2.0));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    let water = Fluid::default().with_block(Vector::new(-1.0, 0.0, -1.0), Vector::new(1.0, 2.0, 1.0));"
+)]
+///     commands.spawn(water);
+/// }
+/// ```
+pub struct FluidPlugin;
+
+impl Plugin for FluidPlugin {
+    fn build(&self, app: &mut App) {
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                simulate_fluids
+                    .after(PhysicsStepSet::SpatialQuery)
+                    // The impulses applied to bodies are cleared before the next step otherwise
+                    .after(clear_forces_and_impulses)
+                    // Bodies that are being removed are ignored
+                    .ambiguous_with(handle_rigid_body_removals::<Collider>),
+            );
+    }
+}
+
+/// Configures the simulation of a [`Fluid`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FluidConfig {
+    /// The radius of the particles. Particles are spaced by their diameter when the fluid is at rest.
+    ///
+    /// Default: `0.1`
+    pub particle_radius: Scalar,
+    /// The distance within which particles affect each other. Typically about four times the particle radius.
+    ///
+    /// Default: `0.4`
+    pub smoothing_radius: Scalar,
+    /// The density of the fluid at rest. The mass of each particle is derived from this and the particle radius.
+    ///
+    /// Default: `1000.0`, the density of water
+    pub rest_density: Scalar,
+    /// The number of density constraint iterations per physics step.
+    /// More iterations make the fluid less compressible.
+    ///
+    /// Default: `4`
+    pub iterations: u32,
+    /// A regularization term for the density constraints. Larger values make the fluid softer but more stable.
+    ///
+    /// Default: `0.1`
+    pub relaxation: Scalar,
+    /// How strongly the velocities of nearby particles are smoothed towards each other, in the range `[0.0, 1.0]`.
+    ///
+    /// Default: `0.01`
+    pub viscosity: Scalar,
+    /// How much of the tangential velocity of particles relative to colliders is removed on contact,
+    /// in the range `[0.0, 1.0]`.
+    ///
+    /// Default: `0.1`
+    pub friction: Scalar,
+    /// Determines which colliders the particles collide with.
+    pub query_filter: SpatialQueryFilter,
+}
+
+impl Default for FluidConfig {
+    fn default() -> Self {
+        Self {
+            particle_radius: 0.1,
+            smoothing_radius: 0.4,
+            rest_density: 1000.0,
+            iterations: 4,
+            relaxation: 0.1,
+            viscosity: 0.01,
+            friction: 0.1,
+            query_filter: SpatialQueryFilter::default(),
+        }
+    }
+}
+
+impl FluidConfig {
+    /// Returns the mass of a single particle.
+    pub fn particle_mass(&self) -> Scalar {
+        let spacing = 2.0 * self.particle_radius;
+        #[cfg(feature = "2d")]
+        {
+            self.rest_density * spacing.powi(2)
+        }
+        #[cfg(feature = "3d")]
+        {
+            self.rest_density * spacing.powi(3)
+        }
+    }
+}
+
+/// A particle of a [`Fluid`] or a [`GranularMaterial`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Particle {
+    /// The world-space position of the particle.
+    pub position: Vector,
+    /// The world-space velocity of the particle.
+    pub velocity: Vector,
+}
+
+/// A body of fluid made of particles, like a pool of water. Simulated by the [`FluidPlugin`].
+///
+/// The particles are stored in world space, independently of the transform of the entity.
+#[derive(Component, Clone, Debug, Default)]
+pub struct Fluid {
+    /// The configuration of the fluid simulation.
+    pub config: FluidConfig,
+    /// The particles of the fluid.
+    pub particles: Vec<Particle>,
+}
+
+impl Fluid {
+    /// Creates a new [`Fluid`] without particles using the given configuration.
+    pub fn new(config: FluidConfig) -> Self {
+        Self {
+            config,
+            particles: vec![],
+        }
+    }
+
+    /// Adds a particle with the given world-space position and velocity.
+    pub fn add_particle(&mut self, position: Vector, velocity: Vector) {
+        self.particles.push(Particle { position, velocity });
+    }
+
+    /// Fills the box between the world-space points `min` and `max` with particles at rest,
+    /// spaced by the particle diameter.
+    pub fn fill_block(&mut self, min: Vector, max: Vector) {
+        let points = block_points(min, max, self.config.particle_radius);
+        self.particles.extend(points.map(|position| Particle {
+            position,
+            velocity: Vector::ZERO,
+        }));
+    }
+
+    /// Fills the box between the world-space points `min` and `max` with particles at rest,
+    /// spaced by the particle diameter.
+    pub fn with_block(mut self, min: Vector, max: Vector) -> Self {
+        self.fill_block(min, max);
+        self
+    }
+}
+
+/// Returns points that fill the box between `min` and `max`, spaced by the diameter of particles with the given radius.
+pub(crate) fn block_points(
+    min: Vector,
+    max: Vector,
+    particle_radius: Scalar,
+) -> impl Iterator<Item = Vector> {
+    let spacing = 2.0 * particle_radius;
+    let counts = ((max - min) / spacing).floor().max(Vector::ZERO);
+    let offset = min + Vector::splat(particle_radius);
+
+    #[cfg(feature = "2d")]
+    {
+        (0..counts.x as u32).flat_map(move |x| {
+            (0..counts.y as u32)
+                .map(move |y| offset + Vector::new(x as Scalar, y as Scalar) * spacing)
+        })
+    }
+    #[cfg(feature = "3d")]
+    {
+        (0..counts.x as u32).flat_map(move |x| {
+            (0..counts.y as u32).flat_map(move |y| {
+                (0..counts.z as u32).map(move |z| {
+                    offset + Vector::new(x as Scalar, y as Scalar, z as Scalar) * spacing
+                })
+            })
+        })
+    }
+}
+
+/// Steps the simulation of all [fluids](Fluid) and applies the impulses of particle collisions to rigid bodies.
+fn simulate_fluids(
+    mut fluids: Query<&mut Fluid>,
+    mut collisions: ParticleCollisions,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs <= 0.0 {
+        return;
+    }
+
+    for mut fluid in &mut fluids {
+        let Fluid { config, particles } = &mut *fluid;
+        let count = particles.len();
+        let h = config.smoothing_radius;
+        let mass = config.particle_mass();
+        let volume = mass / config.rest_density;
+        let tensile_reference = poly6((TENSILE_DISTANCE * h).powi(2), h);
+
+        // Apply gravity and predict the new positions.
+        for particle in particles.iter_mut() {
+            particle.velocity += gravity.0 * delta_secs;
+        }
+        let mut predicted = particles
+            .iter()
+            .map(|particle| particle.position + particle.velocity * delta_secs)
+            .collect::<Vec<_>>();
+
+        let neighbors = SpatialHashGrid::from_points(&predicted, h).neighbor_lists(&predicted, h);
+        let mut lambdas = vec![0.0; count];
+        let mut contacts: Vec<Vec<ParticleContact>> = vec![vec![]; count];
+
+        for _ in 0..config.iterations {
+            // Compute the density constraint multipliers.
+            for i in 0..count {
+                let mut density = 0.0;
+                let mut gradient_i = Vector::ZERO;
+                let mut gradient_sum = 0.0;
+
+                for &j in &neighbors[i] {
+                    let offset = predicted[i] - predicted[j];
+                    density += mass * poly6(offset.length_squared(), h);
+                    if i != j {
+                        let gradient = spiky_gradient(offset, h) * volume;
+                        gradient_i += gradient;
+                        gradient_sum += gradient.length_squared();
+                    }
+                }
+                gradient_sum += gradient_i.length_squared();
+
+                // Only correct compression so that particles at the free surface don't clump together.
+                let constraint = (density / config.rest_density - 1.0).max(0.0);
+                lambdas[i] = -constraint / (gradient_sum + config.relaxation);
+            }
+
+            // Compute and apply the position corrections.
+            let corrections = (0..count)
+                .map(|i| {
+                    neighbors[i]
+                        .iter()
+                        .filter(|&&j| i != j)
+                        .map(|&j| {
+                            let offset = predicted[i] - predicted[j];
+                            let tensile = -TENSILE_STRENGTH
+                                * (poly6(offset.length_squared(), h) / tensile_reference).powi(4);
+                            spiky_gradient(offset, h) * (lambdas[i] + lambdas[j] + tensile)
+                        })
+                        .sum::<Vector>()
+                        * volume
+                })
+                .collect::<Vec<_>>();
+
+            for (i, correction) in corrections.into_iter().enumerate() {
+                predicted[i] += correction;
+                collisions.collide(
+                    &mut predicted[i],
+                    config.particle_radius,
+                    &config.query_filter,
+                    &mut contacts[i],
+                );
+            }
+        }
+
+        // Update the velocities and positions.
+        for (particle, position) in particles.iter_mut().zip(&predicted) {
+            particle.velocity = (*position - particle.position) / delta_secs;
+            particle.position = *position;
+        }
+
+        // Smooth the velocities using XSPH viscosity.
+        let velocities = particles.iter().map(|p| p.velocity).collect::<Vec<_>>();
+        for (i, particle) in particles.iter_mut().enumerate() {
+            let smoothing = neighbors[i]
+                .iter()
+                .filter(|&&j| i != j)
+                .map(|&j| {
+                    let offset = predicted[i] - predicted[j];
+                    (velocities[j] - velocities[i]) * poly6(offset.length_squared(), h) * volume
+                })
+                .sum::<Vector>();
+            particle.velocity += smoothing * config.viscosity;
+        }
+
+        let friction = config.friction.clamp(0.0, 1.0);
+        collisions.exchange_impulses(
+            particles,
+            &contacts,
+            config.particle_radius,
+            mass,
+            delta_secs,
+            |tangential_velocity, _| -tangential_velocity * friction,
+        );
+    }
+}
+
+/// A contact between a particle and a collider.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ParticleContact {
+    /// The collider entity.
+    pub entity: Entity,
+    /// The total distance that the particle was pushed out of the collider during the physics step.
+    pub push: Vector,
+}
+
+/// Collides particles with [`Collider`]s and exchanges impulses between particles and rigid bodies.
+#[derive(SystemParam)]
+pub(crate) struct ParticleCollisions<'w, 's> {
+    pipeline: Res<'w, SpatialQueryPipeline>,
+    colliders: Query<'w, 's, (Option<&'static ColliderParent>, Has<Sensor>)>,
+    bodies: Query<
+        'w,
+        's,
+        (
+            &'static RigidBody,
+            &'static Position,
+            &'static Rotation,
+            &'static CenterOfMass,
+            &'static LinearVelocity,
+            &'static AngularVelocity,
+            &'static mut ExternalImpulse,
+        ),
+    >,
+}
+
+impl ParticleCollisions<'_, '_> {
+    /// Pushes a particle out of the colliders it overlaps and accumulates the push
+    /// of each collider into its contact in `contacts`.
+    pub fn collide(
+        &self,
+        position: &mut Vector,
+        radius: Scalar,
+        query_filter: &SpatialQueryFilter,
+        contacts: &mut Vec<ParticleContact>,
+    ) {
+        let ball = parry::shape::Ball::new(radius);
+        let aabb = ColliderAabb::new(*position, Vector::splat(radius));
+
+        self.pipeline
+            .aabb_intersections_with_aabb_callback(aabb, |entity| {
+                let Some((isometry, collider, layers, flags)) =
+                    self.pipeline.colliders.get(&entity)
+                else {
+                    return true;
+                };
+                let is_sensor = self
+                    .colliders
+                    .get(entity)
+                    .map_or(true, |(_, sensor)| sensor);
+                if is_sensor || !query_filter.test_collider(entity, *layers, *flags) {
+                    return true;
+                }
+
+                let particle_isometry = make_isometry(*position, Rotation::default());
+                if let Ok(Some(hit)) = parry::query::contact(
+                    &particle_isometry,
+                    &ball,
+                    isometry,
+                    collider.shape_scaled().as_ref(),
+                    0.0,
+                ) {
+                    if hit.dist < 0.0 {
+                        // The normal points from the particle into the collider.
+                        let push = Vector::from(*hit.normal1) * hit.dist;
+                        *position += push;
+                        match contacts.iter_mut().find(|contact| contact.entity == entity) {
+                            Some(contact) => contact.push += push,
+                            None => contacts.push(ParticleContact { entity, push }),
+                        }
+                    }
+                }
+                true
+            });
+    }
+
+    /// Applies friction to the particles that are in contact with colliders, and applies the opposite
+    /// of the momentum change of the particles to [dynamic](RigidBody::Dynamic) bodies using [`ExternalImpulse`].
+    ///
+    /// Each collider that a particle is in contact with only receives the impulse of its own push.
+    ///
+    /// The `friction` function computes the velocity change of a particle from its tangential velocity
+    /// relative to the body and the magnitude of its velocity change along the contact normal.
+    pub fn exchange_impulses(
+        &mut self,
+        particles: &mut [Particle],
+        contacts: &[Vec<ParticleContact>],
+        radius: Scalar,
+        mass: Scalar,
+        delta_secs: Scalar,
+        friction: impl Fn(Vector, Scalar) -> Vector,
+    ) {
+        for (particle, contacts) in particles.iter_mut().zip(contacts) {
+            for &ParticleContact { entity, push } in contacts {
+                let Some(normal) = push.try_normalize() else {
+                    continue;
+                };
+
+                let body_entity = self
+                    .colliders
+                    .get(entity)
+                    .ok()
+                    .and_then(|(parent, _)| parent.map(|p| p.get()))
+                    .unwrap_or(entity);
+                let Ok((rb, position, rotation, center_of_mass, lin_vel, ang_vel, mut impulse)) =
+                    self.bodies.get_mut(body_entity)
+                else {
+                    continue;
+                };
+
+                let point = particle.position - normal * radius;
+                let world_com = position.0 + rotation.rotate(center_of_mass.0);
+                let offset = point - world_com;
+                #[cfg(feature = "2d")]
+                let body_velocity =
+                    lin_vel.0 + Vector::new(-ang_vel.0 * offset.y, ang_vel.0 * offset.x);
+                #[cfg(feature = "3d")]
+                let body_velocity = lin_vel.0 + ang_vel.0.cross(offset);
+
+                // Apply friction to the velocity relative to the body.
+                let relative_velocity = particle.velocity - body_velocity;
+                let tangential_velocity =
+                    relative_velocity - normal * relative_velocity.dot(normal);
+                let friction = friction(tangential_velocity, push.length() / delta_secs);
+                particle.velocity += friction;
+
+                if rb.is_dynamic() {
+                    // The body receives the opposite of the momentum change of the particle.
+                    let particle_impulse = mass * (push / delta_secs + friction);
+                    impulse.apply_impulse_at_point(-particle_impulse, point, world_com);
+                }
+            }
+        }
+    }
+}
+
+/// The poly6 smoothing kernel used for density estimation, evaluated at the squared distance `r_squared`.
+fn poly6(r_squared: Scalar, h: Scalar) -> Scalar {
+    let h_squared = h * h;
+    if r_squared >= h_squared {
+        return 0.0;
+    }
+    #[cfg(feature = "2d")]
+    let factor = 4.0 / (PI * h.powi(8));
+    #[cfg(feature = "3d")]
+    let factor = 315.0 / (64.0 * PI * h.powi(9));
+    factor * (h_squared - r_squared).powi(3)
+}
+
+/// The gradient of the spiky smoothing kernel used for the density constraint gradients.
+fn spiky_gradient(offset: Vector, h: Scalar) -> Vector {
+    let r = offset.length();
+    if r >= h || r <= Scalar::EPSILON {
+        return Vector::ZERO;
+    }
+    #[cfg(feature = "2d")]
+    let factor = -30.0 / (PI * h.powi(5));
+    #[cfg(feature = "3d")]
+    let factor = -45.0 / (PI * h.powi(6));
+    offset / r * factor * (h - r).powi(2)
+}
@@ -0,0 +1,336 @@
+//! Logging of force and impulse applications with their sources.
+//!
+//! See [`ForceLogPlugin`].
+
+use crate::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// A plugin that logs force and impulse applications made through [`ForceApplier`]
+/// as [`ExternalForceApplied`] events.
+///
+/// Each application is tagged with a [`ForceSource`], so when a body behaves unexpectedly,
+/// like flying away, the events show which gameplay systems pushed it and how hard.
+///
+/// Without this plugin, [`ForceApplier`] applies forces without logging them.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// #[derive(Component)]
+/// struct Explosion;
+///
+/// fn explode(explosions: Query<Entity, With<Explosion>>, mut applier: ForceApplier) {
+///     for entity in &explosions {
+///         applier.apply_impulse(entity, Vector::Y * 100.0, ForceSource("explosion"));
+///     }
+/// }
+///
+/// fn print_forces(mut events: EventReader<ExternalForceApplied>) {
+///     for event in events.read() {
+///         println!("{:?} was pushed by {}: {:?}", event.entity, event.source.0, event.force);
+///     }
+/// }
+/// ```
+pub struct ForceLogPlugin;
+
+impl Plugin for ForceLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ExternalForceApplied>();
+    }
+}
+
+/// A tag that identifies where a force or impulse came from, like `"explosion"` or `"player_jump"`.
+/// Used by [`ForceApplier`] and [`ExternalForceApplied`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ForceSource(pub &'static str);
+
+impl ForceSource {
+    /// A source for applications whose origin is not known.
+    pub const UNKNOWN: Self = Self("unknown");
+}
+
+impl From<&'static str> for ForceSource {
+    fn from(value: &'static str) -> Self {
+        Self(value)
+    }
+}
+
+/// A force, torque or impulse applied to a body through [`ForceApplier`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AppliedForce {
+    /// A force added to [`ExternalForce`], optionally at a world-space point.
+    Force {
+        /// The world-space force.
+        force: Vector,
+        /// The world-space point where the force was applied, or `None` for the center of mass.
+        point: Option<Vector>,
+    },
+    /// A torque added to [`ExternalTorque`].
+    Torque(Torque),
+    /// An impulse added to [`ExternalImpulse`], optionally at a world-space point.
+    Impulse {
+        /// The world-space impulse.
+        impulse: Vector,
+        /// The world-space point where the impulse was applied, or `None` for the center of mass.
+        point: Option<Vector>,
+    },
+    /// An angular impulse added to [`ExternalAngularImpulse`].
+    AngularImpulse(Torque),
+}
+
+/// An event that is sent by [`ForceApplier`] for each force or impulse application
+/// when the [`ForceLogPlugin`] is enabled.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct ExternalForceApplied {
+    /// The body that the force was applied to.
+    pub entity: Entity,
+    /// The source of the force.
+    pub source: ForceSource,
+    /// The applied force.
+    pub force: AppliedForce,
+}
+
+type ForceApplierComponents = (
+    &'static RigidBody,
+    Option<&'static mut ExternalForce>,
+    Option<&'static mut ExternalTorque>,
+    Option<&'static mut ExternalImpulse>,
+    Option<&'static mut ExternalAngularImpulse>,
+    Option<&'static Position>,
+    Option<&'static Rotation>,
+    Option<&'static CenterOfMass>,
+    Option<&'static GlobalTransform>,
+);
+
+/// A [`SystemParam`] for applying forces and impulses to bodies with a [`ForceSource`] tag.
+///
+/// The forces are added to the [`ExternalForce`], [`ExternalTorque`], [`ExternalImpulse`] and
+/// [`ExternalAngularImpulse`] components of the bodies, just like when modifying the components directly.
+/// If a body doesn't have the component yet, for example because it was spawned this frame,
+/// the component is inserted using `Commands`.
+/// If the [`ForceLogPlugin`] is enabled, an [`ExternalForceApplied`] event is also sent for each application.
+///
+/// Each method returns `false` if the entity is not a [rigid body](RigidBody).
+///
+/// See the [`ForceLogPlugin`] for an example.
+#[derive(SystemParam)]
+pub struct ForceApplier<'w, 's> {
+    commands: Commands<'w, 's>,
+    bodies: Query<'w, 's, ForceApplierComponents>,
+    events: Option<ResMut<'w, Events<ExternalForceApplied>>>,
+}
+
+impl ForceApplier<'_, '_> {
+    /// Adds a world-space `force` to the [`ExternalForce`] of the given body.
+    pub fn apply_force(&mut self, entity: Entity, force: Vector, source: ForceSource) -> bool {
+        let Ok((_, external_force, ..)) = self.bodies.get_mut(entity) else {
+            return false;
+        };
+        if let Some(mut external_force) = external_force {
+            external_force.apply_force(force);
+        } else {
+            self.commands.add(move |world: &mut World| {
+                apply_or_insert::<ExternalForce>(world, entity, |f| {
+                    f.apply_force(force);
+                });
+            });
+        }
+        self.log(entity, source, AppliedForce::Force { force, point: None });
+        true
+    }
+
+    /// Adds a world-space `force` at a world-space `point` to the [`ExternalForce`] of the given body,
+    /// which also causes a torque to be applied.
+    pub fn apply_force_at_point(
+        &mut self,
+        entity: Entity,
+        force: Vector,
+        point: Vector,
+        source: ForceSource,
+    ) -> bool {
+        let Ok((_, external_force, _, _, _, position, rotation, center_of_mass, transform)) =
+            self.bodies.get_mut(entity)
+        else {
+            return false;
+        };
+        let world_com = world_center_of_mass(position, rotation, center_of_mass, transform);
+        if let Some(mut external_force) = external_force {
+            external_force.apply_force_at_point(force, point, world_com);
+        } else {
+            self.commands.add(move |world: &mut World| {
+                apply_or_insert::<ExternalForce>(world, entity, |f| {
+                    f.apply_force_at_point(force, point, world_com);
+                });
+            });
+        }
+        self.log(
+            entity,
+            source,
+            AppliedForce::Force {
+                force,
+                point: Some(point),
+            },
+        );
+        true
+    }
+
+    /// Adds a `torque` to the [`ExternalTorque`] of the given body.
+    pub fn apply_torque(&mut self, entity: Entity, torque: Torque, source: ForceSource) -> bool {
+        let Ok((_, _, external_torque, ..)) = self.bodies.get_mut(entity) else {
+            return false;
+        };
+        if let Some(mut external_torque) = external_torque {
+            external_torque.apply_torque(torque);
+        } else {
+            self.commands.add(move |world: &mut World| {
+                apply_or_insert::<ExternalTorque>(world, entity, |t| {
+                    t.apply_torque(torque);
+                });
+            });
+        }
+        self.log(entity, source, AppliedForce::Torque(torque));
+        true
+    }
+
+    /// Adds a world-space `impulse` to the [`ExternalImpulse`] of the given body.
+    pub fn apply_impulse(&mut self, entity: Entity, impulse: Vector, source: ForceSource) -> bool {
+        let Ok((_, _, _, external_impulse, ..)) = self.bodies.get_mut(entity) else {
+            return false;
+        };
+        if let Some(mut external_impulse) = external_impulse {
+            external_impulse.apply_impulse(impulse);
+        } else {
+            self.commands.add(move |world: &mut World| {
+                apply_or_insert::<ExternalImpulse>(world, entity, |i| {
+                    i.apply_impulse(impulse);
+                });
+            });
+        }
+        self.log(
+            entity,
+            source,
+            AppliedForce::Impulse {
+                impulse,
+                point: None,
+            },
+        );
+        true
+    }
+
+    /// Adds a world-space `impulse` at a world-space `point` to the [`ExternalImpulse`] of the given body,
+    /// which also causes an angular impulse to be applied.
+    pub fn apply_impulse_at_point(
+        &mut self,
+        entity: Entity,
+        impulse: Vector,
+        point: Vector,
+        source: ForceSource,
+    ) -> bool {
+        let Ok((_, _, _, external_impulse, _, position, rotation, center_of_mass, transform)) =
+            self.bodies.get_mut(entity)
+        else {
+            return false;
+        };
+        let world_com = world_center_of_mass(position, rotation, center_of_mass, transform);
+        if let Some(mut external_impulse) = external_impulse {
+            external_impulse.apply_impulse_at_point(impulse, point, world_com);
+        } else {
+            self.commands.add(move |world: &mut World| {
+                apply_or_insert::<ExternalImpulse>(world, entity, |i| {
+                    i.apply_impulse_at_point(impulse, point, world_com);
+                });
+            });
+        }
+        self.log(
+            entity,
+            source,
+            AppliedForce::Impulse {
+                impulse,
+                point: Some(point),
+            },
+        );
+        true
+    }
+
+    /// Adds an angular `impulse` to the [`ExternalAngularImpulse`] of the given body.
+    pub fn apply_angular_impulse(
+        &mut self,
+        entity: Entity,
+        impulse: Torque,
+        source: ForceSource,
+    ) -> bool {
+        let Ok((_, _, _, _, external_angular_impulse, ..)) = self.bodies.get_mut(entity) else {
+            return false;
+        };
+        if let Some(mut external_angular_impulse) = external_angular_impulse {
+            external_angular_impulse.apply_impulse(impulse);
+        } else {
+            self.commands.add(move |world: &mut World| {
+                apply_or_insert::<ExternalAngularImpulse>(world, entity, |i| {
+                    i.apply_impulse(impulse);
+                });
+            });
+        }
+        self.log(entity, source, AppliedForce::AngularImpulse(impulse));
+        true
+    }
+
+    fn log(&mut self, entity: Entity, source: ForceSource, force: AppliedForce) {
+        if let Some(events) = self.events.as_mut() {
+            events.send(ExternalForceApplied {
+                entity,
+                source,
+                force,
+            });
+        }
+    }
+}
+
+/// Returns the world-space center of mass of a body. Bodies that haven't been initialized yet
+/// don't have a [`Position`], so their `GlobalTransform` is used instead.
+fn world_center_of_mass(
+    position: Option<&Position>,
+    rotation: Option<&Rotation>,
+    center_of_mass: Option<&CenterOfMass>,
+    transform: Option<&GlobalTransform>,
+) -> Vector {
+    match position {
+        Some(position) => {
+            let local_com = center_of_mass.map_or(Vector::ZERO, |com| com.0);
+            position.0 + rotation.map_or(local_com, |rotation| rotation.rotate(local_com))
+        }
+        #[cfg(feature = "2d")]
+        None => transform.map_or(Vector::ZERO, |transform| {
+            transform.translation().truncate().adjust_precision()
+        }),
+        #[cfg(feature = "3d")]
+        None => transform.map_or(Vector::ZERO, |transform| {
+            transform.translation().adjust_precision()
+        }),
+    }
+}
+
+/// Modifies the component `C` of the entity, inserting a default component first if it doesn't exist.
+/// Used for applying forces to bodies that don't have the force components yet.
+fn apply_or_insert<C: Component + Default>(
+    world: &mut World,
+    entity: Entity,
+    apply: impl FnOnce(&mut C),
+) {
+    let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    if let Some(mut component) = entity_mut.get_mut::<C>() {
+        apply(&mut component);
+    } else {
+        let mut component = C::default();
+        apply(&mut component);
+        entity_mut.insert(component);
+    }
+}
@@ -0,0 +1,286 @@
+//! Granular materials like sand and gravel made of particles.
+//!
+//! See [`GranularPlugin`].
+
+use crate::{
+    plugins::{
+        collision::{broad_phase::SpatialHashGrid, collider_backend::handle_rigid_body_removals},
+        fluid::{block_points, ParticleCollisions, ParticleContact},
+        integrator::clear_forces_and_impulses,
+    },
+    prelude::*,
+};
+use bevy::prelude::*;
+
+/// A plugin that simulates [granular materials](GranularMaterial) like sand and gravel.
+///
+/// Granular materials are made of ball-shaped particles that don't overlap each other and resist sliding
+/// against each other with Coulomb friction, so they can form piles that stay at rest with a natural slope.
+///
+/// Like [fluids](Fluid), particles collide with [`Collider`]s, and the impulses of the collisions
+/// are applied to [dynamic](RigidBody::Dynamic) bodies using [`ExternalImpulse`]. This lets bodies
+/// plow through piles of sand while being slowed down by it.
+///
+/// Particles don't collide with particles of other granular materials.
+///
+/// This plugin requires the `pbd_fluid` feature. It is not included in [`PhysicsPlugins`] and must be added manually.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default(), GranularPlugin))
+///         .add_systems(Startup, setup)
+///         .run();
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     // A block of sand that collapses into a pile
+#[cfg_attr(
+    feature = "2d",
+    doc = "    let sand = GranularMaterial::default().with_block(Vector::new(-0.5, 0.0), Vector::new(0.5, 2.0));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    let sand = GranularMaterial::default().with_block(Vector::new(-0.5, 0.0, -0.5), Vector::new(0.5, 2.0, 0.5));"
+)]
+///     commands.spawn(sand);
+/// }
+/// ```
+pub struct GranularPlugin;
+
+impl Plugin for GranularPlugin {
+    fn build(&self, app: &mut App) {
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                simulate_granular_materials
+                    .after(PhysicsStepSet::SpatialQuery)
+                    // The impulses applied to bodies are cleared before the next step otherwise
+                    .after(clear_forces_and_impulses)
+                    // Bodies that are being removed are ignored
+                    .ambiguous_with(handle_rigid_body_removals::<Collider>),
+            );
+    }
+}
+
+/// Configures the simulation of a [`GranularMaterial`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GranularConfig {
+    /// The radius of the particles.
+    ///
+    /// Default: `0.05`
+    pub particle_radius: Scalar,
+    /// The density of the particles. The mass of each particle is derived from this and the particle radius.
+    ///
+    /// Default: `1600.0`, roughly the density of dry sand
+    pub density: Scalar,
+    /// The number of contact iterations per physics step. More iterations make piles more stable.
+    ///
+    /// Default: `4`
+    pub iterations: u32,
+    /// The static friction coefficient between particles. Higher values make steeper piles.
+    ///
+    /// Default: `0.6`
+    pub static_friction: Scalar,
+    /// The dynamic friction coefficient between particles.
+    ///
+    /// Default: `0.4`
+    pub dynamic_friction: Scalar,
+    /// The friction coefficient between particles and colliders.
+    ///
+    /// Default: `0.5`
+    pub collider_friction: Scalar,
+    /// Particles slower than this speed are stopped, which helps piles come to rest.
+    ///
+    /// Default: `0.05`
+    pub rest_speed: Scalar,
+    /// Determines which colliders the particles collide with.
+    pub query_filter: SpatialQueryFilter,
+}
+
+impl Default for GranularConfig {
+    fn default() -> Self {
+        Self {
+            particle_radius: 0.05,
+            density: 1600.0,
+            iterations: 4,
+            static_friction: 0.6,
+            dynamic_friction: 0.4,
+            collider_friction: 0.5,
+            rest_speed: 0.05,
+            query_filter: SpatialQueryFilter::default(),
+        }
+    }
+}
+
+impl GranularConfig {
+    /// Returns the mass of a single particle.
+    pub fn particle_mass(&self) -> Scalar {
+        #[cfg(feature = "2d")]
+        {
+            self.density * PI * self.particle_radius.powi(2)
+        }
+        #[cfg(feature = "3d")]
+        {
+            self.density * 4.0 / 3.0 * PI * self.particle_radius.powi(3)
+        }
+    }
+}
+
+/// A granular material made of particles, like a pile of sand. Simulated by the [`GranularPlugin`].
+///
+/// The particles are stored in world space, independently of the transform of the entity.
+#[derive(Component, Clone, Debug, Default)]
+pub struct GranularMaterial {
+    /// The configuration of the simulation.
+    pub config: GranularConfig,
+    /// The particles of the material.
+    pub particles: Vec<Particle>,
+}
+
+impl GranularMaterial {
+    /// Creates a new [`GranularMaterial`] without particles using the given configuration.
+    pub fn new(config: GranularConfig) -> Self {
+        Self {
+            config,
+            particles: vec![],
+        }
+    }
+
+    /// Adds a particle with the given world-space position and velocity.
+    pub fn add_particle(&mut self, position: Vector, velocity: Vector) {
+        self.particles.push(Particle { position, velocity });
+    }
+
+    /// Fills the box between the world-space points `min` and `max` with particles at rest,
+    /// spaced by the particle diameter.
+    pub fn fill_block(&mut self, min: Vector, max: Vector) {
+        let points = block_points(min, max, self.config.particle_radius);
+        self.particles.extend(points.map(|position| Particle {
+            position,
+            velocity: Vector::ZERO,
+        }));
+    }
+
+    /// Fills the box between the world-space points `min` and `max` with particles at rest,
+    /// spaced by the particle diameter.
+    pub fn with_block(mut self, min: Vector, max: Vector) -> Self {
+        self.fill_block(min, max);
+        self
+    }
+}
+
+/// Steps the simulation of all [granular materials](GranularMaterial) and applies the impulses
+/// of particle collisions to rigid bodies.
+fn simulate_granular_materials(
+    mut materials: Query<&mut GranularMaterial>,
+    mut collisions: ParticleCollisions,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs <= 0.0 {
+        return;
+    }
+
+    for mut material in &mut materials {
+        let GranularMaterial { config, particles } = &mut *material;
+        let count = particles.len();
+        let diameter = 2.0 * config.particle_radius;
+
+        // Apply gravity and predict the new positions.
+        for particle in particles.iter_mut() {
+            particle.velocity += gravity.0 * delta_secs;
+        }
+        let mut predicted = particles
+            .iter()
+            .map(|particle| particle.position + particle.velocity * delta_secs)
+            .collect::<Vec<_>>();
+
+        // Particles don't move far during the iterations,
+        // so the neighbors are found once per step with some margin.
+        let search_radius = 1.5 * diameter;
+        let neighbors = SpatialHashGrid::from_points(&predicted, search_radius)
+            .neighbor_lists(&predicted, search_radius);
+        let mut contacts: Vec<Vec<ParticleContact>> = vec![vec![]; count];
+
+        for _ in 0..config.iterations {
+            for i in 0..count {
+                for &j in neighbors[i].iter().filter(|&&j| j > i) {
+                    let offset = predicted[i] - predicted[j];
+                    let distance = offset.length();
+                    if distance >= diameter || distance <= Scalar::EPSILON {
+                        continue;
+                    }
+
+                    // Separate the particles.
+                    let normal = offset / distance;
+                    let penetration = diameter - distance;
+                    predicted[i] += normal * penetration * 0.5;
+                    predicted[j] -= normal * penetration * 0.5;
+
+                    // Apply positional Coulomb friction to the relative tangential motion during the step.
+                    let relative_motion = (predicted[i] - particles[i].position)
+                        - (predicted[j] - particles[j].position);
+                    let tangential = relative_motion - normal * relative_motion.dot(normal);
+                    let tangential_length = tangential.length();
+                    let correction = if tangential_length < config.static_friction * penetration {
+                        tangential
+                    } else if tangential_length > Scalar::EPSILON {
+                        tangential
+                            * (config.dynamic_friction * penetration / tangential_length).min(1.0)
+                    } else {
+                        Vector::ZERO
+                    };
+                    predicted[i] -= correction * 0.5;
+                    predicted[j] += correction * 0.5;
+                }
+            }
+
+            for (position, contact) in predicted.iter_mut().zip(contacts.iter_mut()) {
+                collisions.collide(
+                    position,
+                    config.particle_radius,
+                    &config.query_filter,
+                    contact,
+                );
+            }
+        }
+
+        // Update the velocities and positions.
+        for (particle, position) in particles.iter_mut().zip(&predicted) {
+            particle.velocity = (*position - particle.position) / delta_secs;
+            particle.position = *position;
+        }
+
+        let friction = config.collider_friction;
+        collisions.exchange_impulses(
+            particles,
+            &contacts,
+            config.particle_radius,
+            config.particle_mass(),
+            delta_secs,
+            |tangential_velocity, normal_velocity_change| {
+                // Coulomb friction limits the tangential velocity change by the normal velocity change.
+                let speed = tangential_velocity.length();
+                if speed <= Scalar::EPSILON {
+                    return Vector::ZERO;
+                }
+                -tangential_velocity * (friction * normal_velocity_change / speed).min(1.0)
+            },
+        );
+
+        // Stop slow particles so that piles come to rest.
+        for particle in particles.iter_mut() {
+            if particle.velocity.length_squared() < config.rest_speed.powi(2) {
+                particle.velocity = Vector::ZERO;
+            }
+        }
+    }
+}
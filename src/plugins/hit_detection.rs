@@ -0,0 +1,290 @@
+//! Hitbox and hurtbox matching for combat in action games.
+//!
+//! See [`HitDetectionPlugin`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A plugin that matches overlapping [hitboxes](Hitbox) and [hurtboxes](Hurtbox) and sends [`HitEvent`]s.
+///
+/// Hitboxes and hurtboxes are typically [`Sensor`] colliders, for example attached to the weapons
+/// and bodies of characters using the [`BoneColliderPlugin`]. A hitbox only hits while it is
+/// [active](Hitbox::activate), and each activation of a hitbox is treated as a single attack:
+/// a target is hit at most once per activation, even if several of its hurtboxes overlap the hitbox
+/// or the overlap lasts for multiple frames. If several hurtboxes of a target are hit during the same
+/// physics step, the hit with the deepest overlap is reported.
+///
+/// Hits between a hitbox and a hurtbox with the same owner, or with the same team, are ignored.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// fn start_attack(mut hitboxes: Query<&mut Hitbox>) {
+///     for mut hitbox in &mut hitboxes {
+///         hitbox.activate();
+///     }
+/// }
+///
+/// fn apply_damage(mut hits: EventReader<HitEvent>, mut targets: Query<&mut Health>) {
+///     for hit in hits.read() {
+///         if let Ok(mut health) = targets.get_mut(hit.target) {
+///             health.0 -= 10.0;
+///         }
+///     }
+/// }
+/// ```
+pub struct HitDetectionPlugin;
+
+impl Plugin for HitDetectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Hitbox>()
+            .register_type::<Hurtbox>()
+            .add_event::<HitEvent>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(detect_hits.in_set(PhysicsStepSet::ReportContacts));
+    }
+}
+
+/// A collider that hits [hurtboxes](Hurtbox) while it is active, like the blade of a sword.
+/// Used by the [`HitDetectionPlugin`].
+///
+/// Hitboxes are inactive by default. Each call to [`Hitbox::activate`] starts a new attack,
+/// during which each target can be hit once.
+#[derive(Reflect, Clone, Component, Debug, Default, PartialEq)]
+#[reflect(Component)]
+pub struct Hitbox {
+    /// The entity that owns the hitbox, like the character swinging the sword.
+    /// The hitbox never hits hurtboxes with the same owner.
+    pub owner: Option<Entity>,
+    /// The team of the hitbox. The hitbox never hits hurtboxes of the same team.
+    pub team: Option<u32>,
+    active: bool,
+    activation: u32,
+    #[reflect(ignore)]
+    hit_targets: Vec<Entity>,
+}
+
+impl Hitbox {
+    /// Creates a new inactive hitbox without an owner or a team.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the owner of the hitbox.
+    pub fn with_owner(self, owner: Entity) -> Self {
+        Self {
+            owner: Some(owner),
+            ..self
+        }
+    }
+
+    /// Sets the team of the hitbox.
+    pub fn with_team(self, team: u32) -> Self {
+        Self {
+            team: Some(team),
+            ..self
+        }
+    }
+
+    /// Starts a new attack. Targets that were hit during previous attacks can be hit again.
+    pub fn activate(&mut self) {
+        self.active = true;
+        self.activation = self.activation.wrapping_add(1);
+        self.hit_targets.clear();
+    }
+
+    /// Ends the current attack. The hitbox doesn't hit anything until it is activated again.
+    pub fn deactivate(&mut self) {
+        self.active = false;
+    }
+
+    /// Returns true if the hitbox is active.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Returns the number of times the hitbox has been activated.
+    /// Used for identifying the attack that a [`HitEvent`] belongs to.
+    pub fn activation(&self) -> u32 {
+        self.activation
+    }
+
+    /// Returns the targets that have been hit during the current attack.
+    pub fn hit_targets(&self) -> &[Entity] {
+        &self.hit_targets
+    }
+
+    /// Returns true if the hitbox can hit the given hurtbox based on their owners and teams.
+    pub fn can_hit(&self, hurtbox: &Hurtbox, hurtbox_entity: Entity) -> bool {
+        let target = hurtbox.owner.unwrap_or(hurtbox_entity);
+        let same_owner = self.owner == Some(target);
+        let same_team = self.team.is_some() && self.team == hurtbox.team;
+        !same_owner && !same_team
+    }
+}
+
+/// A collider that can be hit by [hitboxes](Hitbox), like the body of a character.
+/// Used by the [`HitDetectionPlugin`].
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct Hurtbox {
+    /// The entity that owns the hurtbox, like the character it belongs to.
+    /// Hurtboxes with the same owner are treated as a single target.
+    pub owner: Option<Entity>,
+    /// The team of the hurtbox. Hitboxes of the same team never hit the hurtbox.
+    pub team: Option<u32>,
+}
+
+impl Hurtbox {
+    /// Creates a new hurtbox without an owner or a team.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the owner of the hurtbox.
+    pub fn with_owner(self, owner: Entity) -> Self {
+        Self {
+            owner: Some(owner),
+            ..self
+        }
+    }
+
+    /// Sets the team of the hurtbox.
+    pub fn with_team(self, team: u32) -> Self {
+        Self {
+            team: Some(team),
+            ..self
+        }
+    }
+}
+
+/// An event that is sent by the [`HitDetectionPlugin`] when a [`Hitbox`] hits a [`Hurtbox`].
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct HitEvent {
+    /// The hitbox entity.
+    pub hitbox: Entity,
+    /// The hurtbox entity that was hit.
+    pub hurtbox: Entity,
+    /// The [owner](Hitbox::owner) of the hitbox.
+    pub attacker: Option<Entity>,
+    /// The [owner](Hurtbox::owner) of the hurtbox, or the hurtbox itself if it has no owner.
+    pub target: Entity,
+    /// The [activation](Hitbox::activation) of the hitbox that the hit belongs to.
+    pub activation: u32,
+    /// The penetration depth of the overlap.
+    pub depth: Scalar,
+    /// The world-space direction from the hitbox into the hurtbox.
+    pub direction: Vector,
+    /// The linear velocity of the hitbox's body relative to the hurtbox's body.
+    pub relative_velocity: Vector,
+}
+
+/// Finds overlapping hitboxes and hurtboxes and sends a [`HitEvent`] for the deepest hit
+/// on each new target of an active hitbox.
+fn detect_hits(
+    mut hitboxes: Query<&mut Hitbox>,
+    hurtboxes: Query<&Hurtbox>,
+    rotations: Query<&Rotation>,
+    collider_parents: Query<&ColliderParent>,
+    velocities: Query<&LinearVelocity>,
+    collisions: Res<Collisions>,
+    mut hit_events: EventWriter<HitEvent>,
+) {
+    let mut hits: Vec<HitEvent> = vec![];
+
+    for contacts in collisions.iter() {
+        if !contacts.during_current_frame {
+            continue;
+        }
+
+        for flipped in [false, true] {
+            let (hitbox_entity, hurtbox_entity) = if flipped {
+                (contacts.entity2, contacts.entity1)
+            } else {
+                (contacts.entity1, contacts.entity2)
+            };
+            let (Ok(hitbox), Ok(hurtbox)) =
+                (hitboxes.get(hitbox_entity), hurtboxes.get(hurtbox_entity))
+            else {
+                continue;
+            };
+
+            let target = hurtbox.owner.unwrap_or(hurtbox_entity);
+            if !hitbox.active
+                || !hitbox.can_hit(hurtbox, hurtbox_entity)
+                || hitbox.hit_targets.contains(&target)
+            {
+                continue;
+            }
+
+            // Find the deepest contact and its normal pointing from the hitbox into the hurtbox.
+            let hitbox_rotation = rotations.get(hitbox_entity).copied().unwrap_or_default();
+            let Some((depth, direction)) = contacts
+                .manifolds
+                .iter()
+                .flat_map(|manifold| {
+                    let direction = if flipped {
+                        manifold.global_normal2(&hitbox_rotation)
+                    } else {
+                        manifold.global_normal1(&hitbox_rotation)
+                    };
+                    manifold
+                        .contacts
+                        .iter()
+                        .map(move |contact| (contact.penetration, direction))
+                })
+                .max_by(|(a, _), (b, _)| a.total_cmp(b))
+            else {
+                continue;
+            };
+
+            let velocity = |entity: Entity| {
+                let body = collider_parents
+                    .get(entity)
+                    .map_or(entity, |parent| parent.get());
+                velocities
+                    .get(body)
+                    .map_or(Vector::ZERO, |lin_vel| lin_vel.0)
+            };
+
+            let hit = HitEvent {
+                hitbox: hitbox_entity,
+                hurtbox: hurtbox_entity,
+                attacker: hitbox.owner,
+                target,
+                activation: hitbox.activation,
+                depth,
+                direction,
+                relative_velocity: velocity(hitbox_entity) - velocity(hurtbox_entity),
+            };
+
+            // Only keep the deepest hit on each target.
+            match hits
+                .iter_mut()
+                .find(|other| other.hitbox == hitbox_entity && other.target == target)
+            {
+                Some(other) if other.depth < depth => *other = hit,
+                Some(_) => {}
+                None => hits.push(hit),
+            }
+        }
+    }
+
+    for hit in hits {
+        if let Ok(mut hitbox) = hitboxes.get_mut(hit.hitbox) {
+            hitbox.hit_targets.push(hit.target);
+        }
+        hit_events.send(hit);
+    }
+}
@@ -2,8 +2,10 @@
 //!
 //! See [`IntegratorPlugin`].
 
+use std::sync::{Arc, Mutex, PoisonError};
+
 use crate::prelude::*;
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 
 /// Integrates Newton's 2nd law of motion, applying forces and moving entities according to their velocities.
 ///
@@ -13,17 +15,33 @@ use bevy::prelude::*;
 /// The integration scheme used is very closely related to implicit Euler integration.
 ///
 /// The integration systems run in [`SubstepSet::Integrate`].
+///
+/// Impulses queued in [`PendingImpulses`] are applied before the substepping loop, together with
+/// [`ExternalImpulse`] and [`ExternalAngularImpulse`].
+///
+/// The poses that the integrator will produce can be previewed with the [`PosePredictor`] system parameter.
 pub struct IntegratorPlugin;
 
 impl Plugin for IntegratorPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<PendingImpulses>();
+
         app.get_schedule_mut(SubstepSchedule)
             .expect("add SubstepSchedule first")
-            .add_systems((integrate_pos, integrate_rot).in_set(SubstepSet::Integrate));
+            .add_systems(
+                ((integrate_pos, integrate_rot), transport_relative_bodies)
+                    .chain()
+                    .in_set(SubstepSet::Integrate),
+            );
+        #[cfg(feature = "2d")]
+        app.get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first")
+            .add_systems(accumulate_angles.in_set(SubstepSet::UpdateVelocities));
         app.get_schedule_mut(PhysicsSchedule)
             .expect("add PhysicsSchedule first")
             .add_systems(
-                apply_impulses
+                (apply_pending_impulses, apply_impulses)
+                    .chain()
                     .after(PhysicsStepSet::BroadPhase)
                     .before(PhysicsStepSet::Substeps),
             )
@@ -39,6 +57,7 @@ type PosIntegrationComponents = (
     &'static mut LinearVelocity,
     Option<&'static LinearDamping>,
     Option<&'static GravityScale>,
+    Option<&'static WindInfluence>,
     &'static ExternalForce,
     &'static Mass,
     &'static InverseMass,
@@ -46,13 +65,16 @@ type PosIntegrationComponents = (
 );
 
 /// Explicitly integrates the positions and linear velocities of bodies taking only external forces
-/// like gravity into account. This acts as a prediction for the next positions of the bodies.
+/// like gravity and wind into account. This acts as a prediction for the next positions of the bodies.
 fn integrate_pos(
-    mut bodies: Query<PosIntegrationComponents, Without<Sleeping>>,
+    mut bodies: Query<PosIntegrationComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
     gravity: Res<Gravity>,
+    ambient_forces: Res<PhysicsAmbientForces>,
     time: Res<Time>,
+    physics_time: Res<Time<Physics>>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
+    let wind = ambient_forces.wind_at(physics_time.elapsed_seconds_f64() as Scalar);
 
     for (
         rb,
@@ -62,6 +84,7 @@ fn integrate_pos(
         mut lin_vel,
         lin_damping,
         gravity_scale,
+        wind_influence,
         external_force,
         mass,
         inv_mass,
@@ -89,7 +112,9 @@ fn integrate_pos(
             // Apply forces
             let gravitation_force =
                 effective_mass * gravity.0 * gravity_scale.map_or(1.0, |scale| scale.0);
-            let external_forces = gravitation_force + external_force.force();
+            let wind_force =
+                wind_influence.map_or(Vector::ZERO, |influence| influence.0 * (wind - lin_vel.0));
+            let external_forces = gravitation_force + wind_force + external_force.force();
             let delta_lin_vel = delta_secs * external_forces * effective_inv_mass;
             // avoid triggering bevy's change detection unnecessarily
             if delta_lin_vel != Vector::ZERO {
@@ -102,6 +127,104 @@ fn integrate_pos(
     }
 }
 
+/// Returns `rotation.rotate(vec) - vec`.
+///
+/// Unlike subtracting the vectors, this doesn't lose precision when the rotation is small,
+/// which is the case for the rotation of a reference frame during a substep.
+fn rotation_displacement(rotation: Rotation, vec: Vector) -> Vector {
+    #[cfg(feature = "2d")]
+    {
+        let (cos, sin) = (rotation.cos(), rotation.sin());
+        // cos - 1 = -sin² / (1 + cos)
+        let cos_minus_one = if cos > 0.0 {
+            -sin * sin / (1.0 + cos)
+        } else {
+            cos - 1.0
+        };
+        Vector::new(
+            vec.x * cos_minus_one - vec.y * sin,
+            vec.x * sin + vec.y * cos_minus_one,
+        )
+    }
+    #[cfg(feature = "3d")]
+    {
+        let axis = rotation.0.xyz();
+        let t = 2.0 * axis.cross(vec);
+        rotation.0.w * t + axis.cross(t)
+    }
+}
+
+type RelativeBodyComponents = (
+    &'static RelativeTo,
+    &'static RigidBody,
+    &'static Position,
+    &'static PreviousRotation,
+    &'static CenterOfMass,
+    &'static mut AccumulatedTranslation,
+    Option<&'static LockedAxes>,
+);
+
+type ReferenceFrameComponents = (
+    &'static Position,
+    &'static Rotation,
+    &'static PreviousRotation,
+    &'static CenterOfMass,
+    &'static AccumulatedTranslation,
+);
+
+/// Carries bodies with the [`RelativeTo`] component along with the rigid motion of their reference bodies.
+///
+/// The integrated translation of the body is replaced by the translation of the reference frame at the body's
+/// location and the translation caused by the body's velocity relative to the reference frame.
+///
+/// The velocity of the body is computed from its change in position, so it is the average velocity over
+/// the previous substep. The relative velocity is found by subtracting the motion of the reference frame
+/// over that same substep, and it is then rotated along with the reference frame. Comparing against the velocity
+/// of the frame at the body's current location instead would make bodies resting on rotating frames drift outwards.
+fn transport_relative_bodies(
+    mut bodies: Query<RelativeBodyComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    frames: Query<ReferenceFrameComponents, Without<RelativeTo>>,
+) {
+    for (relative_to, rb, pos, prev_rot, center_of_mass, mut translation, locked_axes) in
+        &mut bodies
+    {
+        if !rb.is_dynamic() {
+            continue;
+        }
+
+        let Ok((frame_pos, frame_rot, frame_prev_rot, frame_center_of_mass, frame_translation)) =
+            frames.get(relative_to.0)
+        else {
+            continue;
+        };
+
+        // The centers of mass at the start of the substep
+        let center = pos.0 + prev_rot.rotate(center_of_mass.0);
+        let frame_center = frame_pos.0 + frame_prev_rot.rotate(frame_center_of_mass.0);
+        let offset = center - frame_center;
+
+        // The rotation of the reference frame during this substep.
+        // The frame is assumed to have rotated the same amount during the previous substep.
+        #[cfg(feature = "2d")]
+        let delta_rot = frame_rot.mul(frame_prev_rot.inverse());
+        #[cfg(feature = "3d")]
+        let delta_rot = Rotation(frame_rot.0 * frame_prev_rot.0 .0.inverse());
+
+        let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
+        let frame_motion = frame_translation.0 + rotation_displacement(delta_rot, offset);
+        let previous_frame_motion =
+            frame_translation.0 - rotation_displacement(delta_rot.inverse(), offset);
+        let relative_motion =
+            delta_rot.rotate(translation.0 - locked_axes.apply_to_vec(previous_frame_motion));
+        let new_translation = frame_motion + relative_motion;
+
+        // avoid triggering bevy's change detection unnecessarily
+        if new_translation != translation.0 {
+            translation.0 = new_translation;
+        }
+    }
+}
+
 type RotIntegrationComponents = (
     &'static RigidBody,
     &'static mut Rotation,
@@ -118,7 +241,11 @@ type RotIntegrationComponents = (
 /// Explicitly integrates the rotations and angular velocities of bodies taking only external torque into account.
 /// This acts as a prediction for the next rotations of the bodies.
 #[cfg(feature = "2d")]
-fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>, time: Res<Time>) {
+fn integrate_rot(
+    mut bodies: Query<RotIntegrationComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    rotation_integration: Res<RotationIntegration>,
+    time: Res<Time>,
+) {
     let delta_secs = time.delta_seconds_adjusted();
 
     for (
@@ -164,17 +291,45 @@ fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>,
             }
         }
         // avoid triggering bevy's change detection unnecessarily
-        let delta = locked_axes.apply_to_angular_velocity(delta_secs * ang_vel.0);
-        if delta != 0.0 {
-            *rot += Rotation::from_radians(delta);
+        if let Some(new_rot) = integrated_rotation(
+            *rot,
+            ang_vel.0,
+            locked_axes,
+            &rotation_integration,
+            delta_secs,
+        ) {
+            *rot = new_rot;
         }
     }
 }
 
+/// Returns the rotation after rotating by the given angular velocity for `delta_secs`,
+/// or `None` if the rotation doesn't change.
+#[cfg(feature = "2d")]
+fn integrated_rotation(
+    rot: Rotation,
+    ang_vel: Scalar,
+    locked_axes: LockedAxes,
+    rotation_integration: &RotationIntegration,
+    delta_secs: Scalar,
+) -> Option<Rotation> {
+    let delta = locked_axes
+        .apply_to_angular_velocity(delta_secs * ang_vel)
+        .clamp(
+            -rotation_integration.max_delta_rotation,
+            rotation_integration.max_delta_rotation,
+        );
+    (delta != 0.0).then(|| rot + Rotation::from_radians(delta))
+}
+
 /// Explicitly integrates the rotations and angular velocities of bodies taking only external torque into account.
 /// This acts as a prediction for the next rotations of the bodies.
 #[cfg(feature = "3d")]
-fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>, time: Res<Time>) {
+fn integrate_rot(
+    mut bodies: Query<RotIntegrationComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    rotation_integration: Res<RotationIntegration>,
+    time: Res<Time>,
+) {
     let delta_secs = time.delta_seconds_adjusted();
 
     for (
@@ -222,18 +377,528 @@ fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>,
             }
         }
 
-        let q = Quaternion::from_vec4(ang_vel.0.extend(0.0)) * rot.0;
-        let effective_dq = locked_axes
-            .apply_to_angular_velocity(delta_secs * 0.5 * q.xyz())
-            .extend(delta_secs * 0.5 * q.w);
         // avoid triggering bevy's change detection unnecessarily
-        let delta = Quaternion::from_vec4(effective_dq);
-        if delta != Quaternion::from_xyzw(0.0, 0.0, 0.0, 0.0) {
-            rot.0 = (rot.0 + delta).normalize();
+        if let Some(new_rot) = integrated_rotation(
+            *rot,
+            ang_vel.0,
+            locked_axes,
+            &rotation_integration,
+            delta_secs,
+        ) {
+            *rot = new_rot;
         }
     }
 }
 
+/// Returns the rotation after rotating by the given angular velocity for `delta_secs`,
+/// or `None` if the rotation doesn't change.
+#[cfg(feature = "3d")]
+fn integrated_rotation(
+    rot: Rotation,
+    ang_vel: Vector,
+    locked_axes: LockedAxes,
+    rotation_integration: &RotationIntegration,
+    delta_secs: Scalar,
+) -> Option<Rotation> {
+    let effective_ang_vel = locked_axes.apply_to_angular_velocity(ang_vel);
+    let angle = effective_ang_vel.length() * delta_secs;
+    let is_clamped = angle > rotation_integration.max_delta_rotation;
+
+    if rotation_integration.exponential_map || is_clamped {
+        // Limit the rotation angle of the substep. The velocity itself is kept
+        // so that the clamped rotation isn't lost when velocities are updated.
+        let scale = if is_clamped {
+            rotation_integration.max_delta_rotation / angle
+        } else {
+            1.0
+        };
+        let scaled_axis = effective_ang_vel * delta_secs * scale;
+        if scaled_axis == Vector::ZERO {
+            return None;
+        }
+        if rotation_integration.exponential_map {
+            return Some(Rotation(
+                (Quaternion::from_scaled_axis(scaled_axis) * rot.0).normalize(),
+            ));
+        }
+        let q = Quaternion::from_vec4(scaled_axis.extend(0.0)) * rot.0;
+        return Some(Rotation((rot.0 + q * 0.5).normalize()));
+    }
+
+    let q = Quaternion::from_vec4(ang_vel.extend(0.0)) * rot.0;
+    let effective_dq = locked_axes
+        .apply_to_angular_velocity(delta_secs * 0.5 * q.xyz())
+        .extend(delta_secs * 0.5 * q.w);
+    let delta = Quaternion::from_vec4(effective_dq);
+    (delta != Quaternion::from_xyzw(0.0, 0.0, 0.0, 0.0))
+        .then(|| Rotation((rot.0 + delta).normalize()))
+}
+
+/// A [`SystemParam`] for predicting the future poses of rigid bodies without stepping the simulation.
+///
+/// The prediction integrates the current velocities of a body together with [`Gravity`],
+/// [`GravityScale`], [damping](LinearDamping) and [`LockedAxes`] using the same semi-implicit Euler
+/// steps as the integrator, with the current substep size. This makes trajectory previews, like the arc
+/// of a grenade, match the path that the body actually takes as long as nothing else affects it.
+///
+/// Collisions, joints, external forces and wind are not taken into account.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// #[derive(Component)]
+/// struct Grenade;
+///
+/// fn draw_grenade_arcs(mut gizmos: Gizmos, predictor: PosePredictor, grenades: Query<Entity, With<Grenade>>) {
+///     for entity in &grenades {
+///         let points = (0..=20).filter_map(|i| predictor.predict_pose(entity, i as Scalar * 0.05));
+#[cfg_attr(
+    feature = "2d",
+    doc = "        gizmos.linestrip_2d(points.map(|pose| pose.position.f32()), Color::WHITE);"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "        gizmos.linestrip(points.map(|pose| pose.position.f32()), Color::WHITE);"
+)]
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct PosePredictor<'w, 's> {
+    bodies: Query<
+        'w,
+        's,
+        (
+            &'static RigidBody,
+            &'static Position,
+            &'static Rotation,
+            &'static LinearVelocity,
+            &'static AngularVelocity,
+            Option<&'static LinearDamping>,
+            Option<&'static AngularDamping>,
+            Option<&'static GravityScale>,
+            Option<&'static LockedAxes>,
+            Has<Sleeping>,
+            Has<RigidBodyDisabled>,
+        ),
+    >,
+    gravity: Res<'w, Gravity>,
+    rotation_integration: Res<'w, RotationIntegration>,
+    substep_time: Res<'w, Time<Substeps>>,
+    substep_count: Res<'w, SubstepCount>,
+}
+
+impl PosePredictor<'_, '_> {
+    /// Predicts the pose of the given rigid body after `delta_secs` seconds of simulation.
+    ///
+    /// Static, [sleeping](Sleeping) and [disabled](RigidBodyDisabled) bodies keep their current pose.
+    /// Returns `None` if the entity is not a rigid body.
+    pub fn predict_pose(&self, entity: Entity, delta_secs: Scalar) -> Option<Pose> {
+        let (
+            rb,
+            pos,
+            rot,
+            lin_vel,
+            ang_vel,
+            lin_damping,
+            ang_damping,
+            gravity_scale,
+            locked_axes,
+            is_sleeping,
+            is_disabled,
+        ) = self.bodies.get(entity).ok()?;
+
+        let mut pose = Pose {
+            position: pos.0,
+            rotation: *rot,
+        };
+        if rb.is_static() || is_sleeping || is_disabled || delta_secs <= 0.0 {
+            return Some(pose);
+        }
+
+        // Use the substep size of the simulation, so that the result matches the integrator.
+        // Before the first physics step, a 60 Hz step is assumed.
+        let substep_secs = match self.substep_time.delta_seconds_f64().adjust_precision() {
+            secs if secs > 0.0 => secs,
+            _ => 1.0 / (60.0 * self.substep_count.0.max(1) as Scalar),
+        };
+        let substeps = (delta_secs / substep_secs).round().max(1.0) as u32;
+        let substep_secs = delta_secs / substeps as Scalar;
+
+        let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
+        let gravity = self.gravity.0 * gravity_scale.map_or(1.0, |scale| scale.0);
+        let mut lin_vel = lin_vel.0;
+        let mut ang_vel = ang_vel.0;
+
+        for _ in 0..substeps {
+            if rb.is_dynamic() {
+                if let Some(damping) = lin_damping {
+                    lin_vel *= 1.0 / (1.0 + substep_secs * damping.0);
+                }
+                if let Some(damping) = ang_damping {
+                    ang_vel *= 1.0 / (1.0 + substep_secs * damping.0);
+                }
+                lin_vel += locked_axes.apply_to_vec(substep_secs * gravity);
+            }
+            pose.position += locked_axes.apply_to_vec(substep_secs * lin_vel);
+            if let Some(rotation) = integrated_rotation(
+                pose.rotation,
+                ang_vel,
+                locked_axes,
+                &self.rotation_integration,
+                substep_secs,
+            ) {
+                pose.rotation = rotation;
+            }
+        }
+
+        Some(pose)
+    }
+}
+
+/// Adds the rotation of bodies during the substep to their [`AccumulatedAngle`].
+#[cfg(feature = "2d")]
+fn accumulate_angles(
+    mut bodies: Query<
+        (&Rotation, &PreviousRotation, &mut AccumulatedAngle),
+        (Without<Sleeping>, Without<RigidBodyDisabled>),
+    >,
+) {
+    for (rot, prev_rot, mut angle) in &mut bodies {
+        let delta = (*rot - prev_rot.0).as_radians();
+        // Avoid triggering Bevy's change detection unnecessarily
+        if delta != 0.0 {
+            angle.0 += delta;
+        }
+    }
+}
+
+/// A thread-safe queue of impulses and forces that are applied to bodies at a well-defined point
+/// of the simulation: after the [broad phase](PhysicsStepSet::BroadPhase) and before the
+/// [substepping loop](PhysicsStepSet::Substeps), at the same time as [`ExternalImpulse`]s.
+///
+/// Impulses can be queued from systems running in any schedule, like `Update`, and even from async tasks
+/// by cloning the resource, since clones share the same queue. Queuing only requires shared access
+/// to the resource, so systems that queue impulses can run in parallel.
+///
+/// Forces are applied for the duration of a single physics step by converting them into impulses.
+///
+/// Impulses and forces have a smaller effect on heavier bodies. To change the velocity of a body by
+/// a fixed amount regardless of its mass, like "add 5 m/s upwards" for a jump, use
+/// [`apply_velocity_change`](Self::apply_velocity_change) or [`accelerate`](Self::accelerate) instead.
+/// They multiply by the mass or angular inertia of the body internally, so bodies with different masses
+/// get the same change in velocity.
+///
+/// Impulses applied to [sleeping](Sleeping) bodies wake them up, and impulses for entities that
+/// aren't dynamic rigid bodies are ignored.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::{prelude::*, tasks::AsyncComputeTaskPool};
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn jump(pending_impulses: Res<PendingImpulses>, query: Query<Entity, With<Player>>) {
+///     for entity in &query {
+///         // Change the velocity by 5 m/s upwards regardless of the mass of the player.
+///         pending_impulses.apply_velocity_change(entity, Vector::Y * 5.0);
+///     }
+/// }
+///
+/// fn explode_later(pending_impulses: Res<PendingImpulses>, query: Query<Entity, With<Player>>) {
+///     let pending_impulses = pending_impulses.clone();
+///     let entities: Vec<Entity> = query.iter().collect();
+///     AsyncComputeTaskPool::get()
+///         .spawn(async move {
+///             for entity in entities {
+///                 pending_impulses.apply_impulse(entity, Vector::X * 10.0);
+///             }
+///         })
+///         .detach();
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct PendingImpulses(Arc<Mutex<Vec<PendingImpulse>>>);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PendingImpulse {
+    Impulse {
+        entity: Entity,
+        impulse: Vector,
+        point: Option<Vector>,
+    },
+    AngularImpulse {
+        entity: Entity,
+        impulse: Torque,
+    },
+    Force {
+        entity: Entity,
+        force: Vector,
+        point: Option<Vector>,
+    },
+    Torque {
+        entity: Entity,
+        torque: Torque,
+    },
+    VelocityChange {
+        entity: Entity,
+        velocity_change: Vector,
+    },
+    AngularVelocityChange {
+        entity: Entity,
+        velocity_change: Torque,
+    },
+    Acceleration {
+        entity: Entity,
+        acceleration: Vector,
+    },
+    AngularAcceleration {
+        entity: Entity,
+        acceleration: Torque,
+    },
+}
+
+impl PendingImpulses {
+    /// Queues an `impulse` at the center of mass of the given body.
+    pub fn apply_impulse(&self, entity: Entity, impulse: Vector) {
+        self.push(PendingImpulse::Impulse {
+            entity,
+            impulse,
+            point: None,
+        });
+    }
+
+    /// Queues an `impulse` at the given `point` in world space.
+    /// If the point is not at the center of mass, the impulse also rotates the body.
+    pub fn apply_impulse_at_point(&self, entity: Entity, impulse: Vector, point: Vector) {
+        self.push(PendingImpulse::Impulse {
+            entity,
+            impulse,
+            point: Some(point),
+        });
+    }
+
+    /// Queues an angular `impulse` for the given body.
+    pub fn apply_angular_impulse(&self, entity: Entity, impulse: Torque) {
+        self.push(PendingImpulse::AngularImpulse { entity, impulse });
+    }
+
+    /// Queues a `force` at the center of mass of the given body for the duration of one physics step.
+    pub fn apply_force(&self, entity: Entity, force: Vector) {
+        self.push(PendingImpulse::Force {
+            entity,
+            force,
+            point: None,
+        });
+    }
+
+    /// Queues a `force` at the given `point` in world space for the duration of one physics step.
+    /// If the point is not at the center of mass, the force also rotates the body.
+    pub fn apply_force_at_point(&self, entity: Entity, force: Vector, point: Vector) {
+        self.push(PendingImpulse::Force {
+            entity,
+            force,
+            point: Some(point),
+        });
+    }
+
+    /// Queues a `torque` for the given body for the duration of one physics step.
+    pub fn apply_torque(&self, entity: Entity, torque: Torque) {
+        self.push(PendingImpulse::Torque { entity, torque });
+    }
+
+    /// Queues a change of the linear velocity of the given body by `velocity_change`, regardless of its mass.
+    ///
+    /// This is an impulse that is multiplied by the mass of the body.
+    pub fn apply_velocity_change(&self, entity: Entity, velocity_change: Vector) {
+        self.push(PendingImpulse::VelocityChange {
+            entity,
+            velocity_change,
+        });
+    }
+
+    /// Queues a change of the angular velocity of the given body by `velocity_change`,
+    /// regardless of its angular inertia.
+    ///
+    /// This is an angular impulse that is multiplied by the world-space angular inertia of the body.
+    pub fn apply_angular_velocity_change(&self, entity: Entity, velocity_change: Torque) {
+        self.push(PendingImpulse::AngularVelocityChange {
+            entity,
+            velocity_change,
+        });
+    }
+
+    /// Queues a linear `acceleration` of the given body for the duration of one physics step,
+    /// regardless of its mass.
+    ///
+    /// This is a force that is multiplied by the mass of the body.
+    pub fn accelerate(&self, entity: Entity, acceleration: Vector) {
+        self.push(PendingImpulse::Acceleration {
+            entity,
+            acceleration,
+        });
+    }
+
+    /// Queues an angular `acceleration` of the given body for the duration of one physics step,
+    /// regardless of its angular inertia.
+    ///
+    /// This is a torque that is multiplied by the world-space angular inertia of the body.
+    pub fn accelerate_angular(&self, entity: Entity, acceleration: Torque) {
+        self.push(PendingImpulse::AngularAcceleration {
+            entity,
+            acceleration,
+        });
+    }
+
+    /// Returns the number of queued impulses and forces.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns true if no impulses or forces are queued.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Removes all queued impulses and forces without applying them.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn push(&self, impulse: PendingImpulse) {
+        self.lock().push(impulse);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<PendingImpulse>> {
+        // The queue can't be left in an invalid state, so a poisoned lock can be used normally.
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+type PendingImpulseQueryComponents = (
+    &'static RigidBody,
+    &'static mut LinearVelocity,
+    &'static mut AngularVelocity,
+    &'static Position,
+    &'static Rotation,
+    &'static CenterOfMass,
+    &'static Mass,
+    &'static Inertia,
+    &'static InverseMass,
+    &'static InverseInertia,
+    Option<&'static LockedAxes>,
+);
+
+/// Drains the [`PendingImpulses`] and applies them to the velocities of bodies.
+fn apply_pending_impulses(
+    pending_impulses: Res<PendingImpulses>,
+    mut bodies: Query<PendingImpulseQueryComponents>,
+    time: Res<Time>,
+) {
+    let pending = std::mem::take(&mut *pending_impulses.lock());
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for pending_impulse in pending {
+        // The last element is true if the impulse is a velocity change that is scaled by the mass.
+        let (entity, impulse, point, angular_impulse, mass_scaled) = match pending_impulse {
+            PendingImpulse::Impulse {
+                entity,
+                impulse,
+                point,
+            } => (entity, impulse, point, Torque::default(), false),
+            PendingImpulse::AngularImpulse { entity, impulse } => {
+                (entity, Vector::ZERO, None, impulse, false)
+            }
+            PendingImpulse::Force {
+                entity,
+                force,
+                point,
+            } => (entity, force * delta_secs, point, Torque::default(), false),
+            PendingImpulse::Torque { entity, torque } => {
+                (entity, Vector::ZERO, None, torque * delta_secs, false)
+            }
+            PendingImpulse::VelocityChange {
+                entity,
+                velocity_change,
+            } => (entity, velocity_change, None, Torque::default(), true),
+            PendingImpulse::AngularVelocityChange {
+                entity,
+                velocity_change,
+            } => (entity, Vector::ZERO, None, velocity_change, true),
+            PendingImpulse::Acceleration {
+                entity,
+                acceleration,
+            } => (
+                entity,
+                acceleration * delta_secs,
+                None,
+                Torque::default(),
+                true,
+            ),
+            PendingImpulse::AngularAcceleration {
+                entity,
+                acceleration,
+            } => (entity, Vector::ZERO, None, acceleration * delta_secs, true),
+        };
+
+        let Ok((
+            rb,
+            mut lin_vel,
+            mut ang_vel,
+            position,
+            rotation,
+            center_of_mass,
+            mass,
+            inertia,
+            inv_mass,
+            inv_inertia,
+            locked_axes,
+        )) = bodies.get_mut(entity)
+        else {
+            continue;
+        };
+        if !rb.is_dynamic() {
+            continue;
+        }
+
+        let (impulse, mut angular_impulse) = if mass_scaled {
+            (
+                impulse * mass.0,
+                inertia.rotated(rotation).0 * angular_impulse,
+            )
+        } else {
+            (impulse, angular_impulse)
+        };
+        if let Some(point) = point {
+            let world_com = position.0 + rotation.rotate(center_of_mass.0);
+            #[cfg(feature = "2d")]
+            {
+                angular_impulse += (point - world_com).perp_dot(impulse);
+            }
+            #[cfg(feature = "3d")]
+            {
+                angular_impulse += (point - world_com).cross(impulse);
+            }
+        }
+
+        let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
+        let effective_inv_mass = locked_axes.apply_to_vec(Vector::splat(inv_mass.0));
+        let effective_inv_inertia = locked_axes.apply_to_rotation(inv_inertia.rotated(rotation).0);
+
+        // Changing the velocities also wakes up sleeping bodies
+        lin_vel.0 += impulse * effective_inv_mass;
+        ang_vel.0 += effective_inv_inertia * angular_impulse;
+    }
+}
+
 type ImpulseQueryComponents = (
     &'static RigidBody,
     &'static mut ExternalImpulse,
@@ -246,7 +911,7 @@ type ImpulseQueryComponents = (
     Option<&'static LockedAxes>,
 );
 
-fn apply_impulses(mut bodies: Query<ImpulseQueryComponents, Without<Sleeping>>) {
+pub(crate) fn apply_impulses(mut bodies: Query<ImpulseQueryComponents, Without<Sleeping>>) {
     for (
         rb,
         impulse,
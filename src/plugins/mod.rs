@@ -20,31 +20,131 @@
 //! - [`PhysicsSchedule`] and [`PhysicsStepSet`]
 //! - [`SubstepSchedule`] and [`SubstepSet`]
 
+pub mod activity_scaling;
+pub mod bone_collider;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod character_controller;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod collider_morph;
 pub mod collision;
 #[cfg(feature = "debug-plugin")]
 pub mod debug;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod destruction;
+pub mod divergence;
+pub mod editor_manipulation;
+pub mod energy;
+#[cfg(all(
+    feature = "pbd_fluid",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod fluid;
+pub mod force_log;
+#[cfg(all(
+    feature = "pbd_fluid",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod granular;
+pub mod hit_detection;
 pub mod integrator;
+#[cfg(all(
+    feature = "bevy_picking",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod picking;
 pub mod prepare;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod projectile_penetration;
+pub mod settle;
 pub mod setup;
 pub mod sleeping;
 pub mod solver;
 pub mod spatial_query;
 pub mod sync;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod validation;
 
+pub use activity_scaling::PhysicsActivityScalingPlugin;
 use bevy::utils::intern::Interned;
+pub use bone_collider::BoneColliderPlugin;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use character_controller::CharacterControllerPlugin;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use collider_morph::ColliderMorphPlugin;
 pub use collision::{
     broad_phase::BroadPhasePlugin, collider_backend::*, contact_reporting::ContactReportingPlugin,
     narrow_phase::NarrowPhasePlugin,
 };
 #[cfg(feature = "debug-plugin")]
 pub use debug::PhysicsDebugPlugin;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use destruction::DestructionPlugin;
+pub use editor_manipulation::EditorManipulationPlugin;
+pub use energy::PhysicsEnergyDiagnosticsPlugin;
+#[cfg(all(
+    feature = "pbd_fluid",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use fluid::FluidPlugin;
+pub use force_log::ForceLogPlugin;
+#[cfg(all(
+    feature = "pbd_fluid",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use granular::GranularPlugin;
+pub use hit_detection::HitDetectionPlugin;
 pub use integrator::IntegratorPlugin;
+#[cfg(all(
+    feature = "bevy_picking",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use picking::PhysicsPickingPlugin;
 pub use prepare::PreparePlugin;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use projectile_penetration::ProjectilePenetrationPlugin;
 pub use setup::PhysicsSetupPlugin;
 pub use sleeping::SleepingPlugin;
 pub use solver::SolverPlugin;
 pub use spatial_query::SpatialQueryPlugin;
 pub use sync::SyncPlugin;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use validation::PhysicsValidationPlugin;
 
 #[allow(unused_imports)]
 use crate::prelude::*; // For doc comments
@@ -67,6 +167,8 @@ use bevy::prelude::*;
 /// - [`SolverPlugin`]: Solves positional and angular [constraints], updates velocities and solves velocity constraints
 /// (dynamic [friction](Friction) and [restitution](Restitution)).
 /// - [`SleepingPlugin`]: Controls when bodies should be deactivated and marked as [`Sleeping`] to improve performance.
+/// - [`DestructionPlugin`]: Breaks [`Destructible`] bodies into precomputed fragments
+/// on strong impacts (only with the default [`Collider`]).
 /// - [`SpatialQueryPlugin`]: Handles spatial queries like [raycasting](RayCaster) and shapecasting.
 /// - [`SyncPlugin`]: Keeps [`Position`] and [`Rotation`] in sync with `Transform`.
 /// - `PhysicsDebugPlugin`: Renders physics objects and events like [AABBs](ColliderAabb) and [contacts](Collision)
@@ -200,13 +302,21 @@ impl PluginGroup for PhysicsPlugins {
             .add(ColliderBackendPlugin::<Collider>::new(self.schedule))
             .add(NarrowPhasePlugin::<Collider>::default());
 
-        builder
+        let builder = builder
             .add(BroadPhasePlugin)
             .add(ContactReportingPlugin)
             .add(IntegratorPlugin)
             .add(SolverPlugin)
             .add(SleepingPlugin)
             .add(SpatialQueryPlugin::new(self.schedule))
-            .add(SyncPlugin::new(self.schedule))
+            .add(SyncPlugin::new(self.schedule));
+
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        let builder = builder.add(DestructionPlugin);
+
+        builder
     }
 }
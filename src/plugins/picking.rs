@@ -0,0 +1,172 @@
+//! A picking backend for `bevy_picking` that uses [spatial queries](spatial_query) against colliders.
+//!
+//! See [`PhysicsPickingPlugin`].
+
+use crate::prelude::*;
+use bevy::{ecs::query::Has, prelude::*, render::view::RenderLayers};
+use bevy_picking_core::backend::prelude::*;
+
+/// A picking backend for `bevy_picking` that finds the colliders under each pointer
+/// using the [`SpatialQueryPipeline`].
+///
+/// In 3D, the picking ray of each pointer and camera is cast against colliders, and the closest hit is reported.
+/// In 2D, all colliders that contain the point under the pointer are reported.
+///
+/// Which colliders can be picked can be configured using [`PhysicsPickingSettings`], [`PhysicsPickingFilter`]
+/// on cameras, and the [`PhysicsPickable`] marker component. Colliders that have `Pickable::IGNORE`
+/// or don't share any `RenderLayers` with the camera are never picked.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually
+/// along with the picking plugins.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             PhysicsPickingPlugin,
+///         ))
+///         // Only pick colliders on the first layer
+///         .insert_resource(PhysicsPickingSettings {
+///             filter: SpatialQueryFilter::from_mask(0b0001),
+///             ..default()
+///         })
+///         .run();
+/// }
+/// ```
+pub struct PhysicsPickingPlugin;
+
+impl Plugin for PhysicsPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsPickingSettings>()
+            .register_type::<PhysicsPickingSettings>()
+            .register_type::<PhysicsPickable>()
+            .add_systems(PreUpdate, update_hits.in_set(PickSet::Backend));
+    }
+}
+
+/// Settings for the [`PhysicsPickingPlugin`].
+#[derive(Resource, Reflect, Clone, Default)]
+#[reflect(Resource)]
+pub struct PhysicsPickingSettings {
+    /// If true, only cameras and colliders with the [`PhysicsPickable`] component are considered for picking.
+    pub require_markers: bool,
+    /// The filter used for picking with cameras that don't have a [`PhysicsPickingFilter`].
+    #[reflect(ignore)]
+    pub filter: SpatialQueryFilter,
+}
+
+/// A marker component for cameras and colliders that should be considered for picking
+/// when [`PhysicsPickingSettings::require_markers`] is true.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct PhysicsPickable;
+
+/// A [`SpatialQueryFilter`] for picking with a specific camera.
+/// Overrides the filter in [`PhysicsPickingSettings`].
+#[derive(Component, Clone, Default)]
+pub struct PhysicsPickingFilter(pub SpatialQueryFilter);
+
+/// Casts the picking rays of all pointers against colliders and sends the hits as [`PointerHits`].
+#[allow(clippy::too_many_arguments)]
+fn update_hits(
+    settings: Res<PhysicsPickingSettings>,
+    ray_map: Res<RayMap>,
+    cameras: Query<(
+        &Camera,
+        Option<&PhysicsPickingFilter>,
+        Has<PhysicsPickable>,
+        Option<&RenderLayers>,
+    )>,
+    pickables: Query<&Pickable>,
+    markers: Query<(), With<PhysicsPickable>>,
+    render_layers: Query<&RenderLayers>,
+    #[cfg(feature = "2d")] transforms: Query<&GlobalTransform>,
+    pipeline: Option<Res<SpatialQueryPipeline>>,
+    mut output: EventWriter<PointerHits>,
+) {
+    let Some(pipeline) = pipeline else {
+        return;
+    };
+
+    for (&ray_id, &ray) in ray_map.map().iter() {
+        let Ok((camera, filter, has_marker, camera_layers)) = cameras.get(ray_id.camera) else {
+            continue;
+        };
+        if settings.require_markers && !has_marker {
+            continue;
+        }
+
+        let filter = filter.map_or(&settings.filter, |filter| &filter.0);
+        let camera_layers = camera_layers.copied().unwrap_or_default();
+        let is_pickable = |entity: Entity| {
+            let has_marker = !settings.require_markers || markers.contains(entity);
+            // Entities without render layers are on the default layer.
+            let entity_layers = render_layers.get(entity).copied().unwrap_or_default();
+            let is_ignored = pickables
+                .get(entity)
+                .is_ok_and(|pickable| *pickable == Pickable::IGNORE);
+            has_marker && camera_layers.intersects(&entity_layers) && !is_ignored
+        };
+
+        #[cfg(feature = "3d")]
+        let picks = pipeline
+            .cast_ray_predicate(
+                ray.origin.adjust_precision(),
+                ray.direction,
+                Scalar::MAX,
+                true,
+                filter.clone(),
+                &is_pickable,
+            )
+            .map(|hit| {
+                let position = ray.origin + *ray.direction * hit.time_of_impact as f32;
+                let data = HitData::new(
+                    ray_id.camera,
+                    hit.time_of_impact as f32,
+                    Some(position),
+                    Some(hit.normal.f32()),
+                );
+                (hit.entity, data)
+            })
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        #[cfg(feature = "2d")]
+        let picks = {
+            let mut picks = vec![];
+            pipeline.point_intersections_callback(
+                ray.origin.truncate().adjust_precision(),
+                filter.clone(),
+                |entity| {
+                    if is_pickable(entity) {
+                        // Sort colliders by their distance along the picking ray.
+                        let z = transforms
+                            .get(entity)
+                            .map_or(0.0, |transform| transform.translation().z);
+                        let data = HitData::new(
+                            ray_id.camera,
+                            ray.origin.z - z,
+                            Some(ray.origin.truncate().extend(z)),
+                            None,
+                        );
+                        picks.push((entity, data));
+                    }
+                    true
+                },
+            );
+            picks
+        };
+
+        if !picks.is_empty() {
+            output.send(PointerHits::new(ray_id.pointer, picks, camera.order as f32));
+        }
+    }
+}
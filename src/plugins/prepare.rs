@@ -15,6 +15,7 @@ use bevy::{ecs::query::QueryFilter, prelude::*, utils::intern::Interned};
 /// - Adds missing mass properties for entities with a [`RigidBody`] component
 /// - Updates mass properties
 /// - Clamps restitution coefficients between 0 and 1
+/// - Disables the colliders and joints of disabled rigid bodies based on [`DisablePropagation`]
 ///
 /// The [`Transform`] component will be initialized based on [`Position`] or [`Rotation`]
 /// and vice versa. You can configure this synchronization using the [`PrepareConfig`] resource.
@@ -96,7 +97,9 @@ impl Plugin for PreparePlugin {
         );
 
         app.init_resource::<PrepareConfig>()
-            .register_type::<PrepareConfig>();
+            .register_type::<PrepareConfig>()
+            .init_resource::<DisablePropagation>()
+            .register_type::<DisablePropagation>();
 
         // Note: Collider logic is handled by the `ColliderBackendPlugin`
         app.add_systems(
@@ -117,8 +120,29 @@ impl Plugin for PreparePlugin {
         .add_systems(
             self.schedule,
             init_rigid_bodies.in_set(PrepareSet::InitRigidBodies),
-        )
-        .add_systems(
+        );
+
+        #[cfg(feature = "2d")]
+        app.add_systems(
+            self.schedule,
+            crate::components::update_z_layers.in_set(PrepareSet::PreInit),
+        );
+
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        app.add_systems(
+            self.schedule,
+            (
+                crate::components::validate_bodies,
+                crate::plugins::collision::update_sub_colliders,
+            )
+                .chain()
+                .in_set(PrepareSet::PreInit),
+        );
+
+        app.add_systems(
             self.schedule,
             init_mass_properties.in_set(PrepareSet::InitMassProperties),
         )
@@ -132,6 +156,19 @@ impl Plugin for PreparePlugin {
                 update_mass_properties,
                 clamp_collider_density,
                 clamp_restitution,
+                (
+                    propagate_disabled_to_colliders,
+                    propagate_disabled_to_joints::<FixedJoint>,
+                    propagate_disabled_to_joints::<RevoluteJoint>,
+                    propagate_disabled_to_joints::<SphericalJoint>,
+                    propagate_disabled_to_joints::<PrismaticJoint>,
+                    propagate_disabled_to_joints::<DistanceJoint>,
+                    propagate_disabled_to_joints::<SplineDriveJoint>,
+                    propagate_disabled_to_joints::<SuspensionConstraint>,
+                    propagate_disabled_to_joints::<GenericJoint>,
+                    propagate_disabled_to_joints::<GearJoint>,
+                    propagate_disabled_to_joints::<PulleyJoint>,
+                ),
                 // All the components we added above must exist before we can simulate the bodies.
                 apply_deferred,
             )
@@ -513,6 +550,80 @@ fn clamp_collider_density(mut query: Query<&mut ColliderDensity, Changed<Collide
     }
 }
 
+/// A marker component for colliders and joints that were disabled because their rigid body was disabled.
+/// See [`DisablePropagation`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct DisabledByBody;
+
+/// A marker component for entities whose physics was disabled with
+/// [`disable_physics_recursive`](PhysicsEntityCommandsExt::disable_physics_recursive).
+///
+/// Stores which of the disabling components were added by the command.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct DisabledRecursively {
+    pub(crate) body: bool,
+    pub(crate) collider: bool,
+}
+
+/// Disables the colliders of bodies with [`RigidBodyDisabled`] and enables them again
+/// when the body is enabled, based on [`DisablePropagation`].
+fn propagate_disabled_to_colliders(
+    mut commands: Commands,
+    colliders: Query<(
+        Entity,
+        &ColliderParent,
+        Has<ColliderDisabled>,
+        Has<DisabledByBody>,
+    )>,
+    disabled_bodies: Query<(), With<RigidBodyDisabled>>,
+    propagation: Res<DisablePropagation>,
+) {
+    for (entity, collider_parent, is_disabled, is_disabled_by_body) in &colliders {
+        let body_disabled = disabled_bodies.contains(collider_parent.get());
+
+        if propagation.colliders && body_disabled && !is_disabled {
+            commands
+                .entity(entity)
+                .insert((ColliderDisabled, DisabledByBody));
+        } else if is_disabled_by_body && !(propagation.colliders && body_disabled) {
+            commands
+                .entity(entity)
+                .remove::<(ColliderDisabled, DisabledByBody)>();
+        }
+    }
+}
+
+/// Disables joints attached to bodies with [`RigidBodyDisabled`] and enables them again
+/// when the bodies are enabled, based on [`DisablePropagation`].
+///
+/// Joints attached to bodies disabled with
+/// [`disable_physics_recursive`](PhysicsEntityCommandsExt::disable_physics_recursive)
+/// are always disabled.
+fn propagate_disabled_to_joints<J: Joint>(
+    mut commands: Commands,
+    joints: Query<(Entity, &J, Has<JointDisabled>, Has<DisabledByBody>)>,
+    disabled_bodies: Query<Has<DisabledRecursively>, With<RigidBodyDisabled>>,
+    propagation: Res<DisablePropagation>,
+) {
+    for (entity, joint, is_disabled, is_disabled_by_body) in &joints {
+        let disable = joint
+            .entities()
+            .iter()
+            .filter_map(|body| disabled_bodies.get(*body).ok())
+            .any(|disabled_recursively| propagation.joints || disabled_recursively);
+
+        if disable && !is_disabled {
+            commands
+                .entity(entity)
+                .insert((JointDisabled, DisabledByBody));
+        } else if is_disabled_by_body && !disable {
+            commands
+                .entity(entity)
+                .remove::<(JointDisabled, DisabledByBody)>();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,234 @@
+//! Projectiles that pass through colliders while losing energy.
+//!
+//! See [`ProjectilePenetrationPlugin`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A plugin for projectiles that penetrate colliders, like bullets passing through walls.
+///
+/// When a [`PenetratingProjectile`] is added to an entity, a ray is cast from the [`Position`]
+/// of the entity in the [direction](PenetratingProjectile::direction) of the projectile.
+/// The cast continues through the colliders that it hits, and for each of them, the projectile loses
+/// energy based on the distance it travels inside the collider and the [`PenetrationResistance`]
+/// of the collider. When the projectile runs out of energy, it stops inside the collider.
+///
+/// A [`PenetrationHit`] event is sent for each penetrated collider in the order that they were hit.
+/// To fire the projectile again, insert the [`PenetratingProjectile`] component again.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // A thin wooden wall that bullets can pass through
+///     commands.spawn((
+///         RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(0.1, 2.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(0.1, 2.0, 2.0),")]
+///         PenetrationResistance(500.0),
+///     ));
+///
+///     // Fire a bullet
+///     commands.spawn((
+///         Position::default(),
+#[cfg_attr(
+    feature = "2d",
+    doc = "        PenetratingProjectile::new(Direction2d::X, 100.0, 1000.0),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "        PenetratingProjectile::new(Direction3d::X, 100.0, 1000.0),"
+)]
+///     ));
+/// }
+///
+/// fn print_hits(mut hits: EventReader<PenetrationHit>) {
+///     for hit in hits.read() {
+///         println!("Penetrated {:?} with {} energy left", hit.entity, hit.remaining_energy);
+///     }
+/// }
+/// ```
+pub struct ProjectilePenetrationPlugin;
+
+impl Plugin for ProjectilePenetrationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PenetrationResistance>()
+            .add_event::<PenetrationHit>()
+            .add_systems(
+                PostUpdate,
+                cast_penetrating_projectiles.after(PhysicsSet::Sync),
+            );
+    }
+}
+
+/// How much energy a [`PenetratingProjectile`] loses per unit of distance travelled inside a collider.
+/// Used by the [`ProjectilePenetrationPlugin`].
+///
+/// Colliders without this component stop projectiles completely.
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct PenetrationResistance(pub Scalar);
+
+/// A projectile that penetrates colliders until it runs out of energy.
+/// Used by the [`ProjectilePenetrationPlugin`].
+///
+/// The projectile is fired from the [`Position`] of the entity when the component is added.
+#[derive(Component, Clone)]
+pub struct PenetratingProjectile {
+    /// The direction that the projectile travels in.
+    pub direction: Dir,
+    /// The maximum distance that the projectile travels.
+    pub max_distance: Scalar,
+    /// The initial energy of the projectile.
+    pub energy: Scalar,
+    /// Determines which colliders the projectile can hit.
+    pub query_filter: SpatialQueryFilter,
+}
+
+impl PenetratingProjectile {
+    /// Creates a new [`PenetratingProjectile`] with the given direction, maximum distance and energy.
+    pub fn new(direction: Dir, max_distance: Scalar, energy: Scalar) -> Self {
+        Self {
+            direction,
+            max_distance,
+            energy,
+            query_filter: SpatialQueryFilter::default(),
+        }
+    }
+
+    /// Sets the [`SpatialQueryFilter`] of the projectile.
+    pub fn with_query_filter(self, query_filter: SpatialQueryFilter) -> Self {
+        Self {
+            query_filter,
+            ..self
+        }
+    }
+}
+
+/// An event that is sent by the [`ProjectilePenetrationPlugin`] for each collider hit by a [`PenetratingProjectile`].
+///
+/// The events of a projectile are sent in the order that the colliders were hit.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct PenetrationHit {
+    /// The projectile entity.
+    pub projectile: Entity,
+    /// The collider entity that was hit.
+    pub entity: Entity,
+    /// The world-space point where the projectile entered the collider.
+    pub entry_point: Vector,
+    /// The world-space surface normal at the entry point.
+    pub entry_normal: Vector,
+    /// The world-space point where the projectile exited the collider,
+    /// or `None` if the projectile stopped inside the collider.
+    pub exit_point: Option<Vector>,
+    /// The energy of the projectile after penetrating the collider.
+    pub remaining_energy: Scalar,
+}
+
+impl PenetrationHit {
+    /// Returns true if the projectile stopped inside the collider.
+    pub fn stopped(&self) -> bool {
+        self.exit_point.is_none()
+    }
+}
+
+/// Casts new penetrating projectiles and sends a [`PenetrationHit`] for each penetrated collider.
+fn cast_penetrating_projectiles(
+    projectiles: Query<(Entity, &Position, &PenetratingProjectile), Added<PenetratingProjectile>>,
+    resistances: Query<&PenetrationResistance>,
+    pipeline: Res<SpatialQueryPipeline>,
+    mut hit_events: EventWriter<PenetrationHit>,
+) {
+    for (entity, position, projectile) in &projectiles {
+        let hits = penetration_hits(&pipeline, entity, position.0, projectile, |collider| {
+            resistances.get(collider).ok().map(|r| r.0)
+        });
+        hit_events.send_batch(hits);
+    }
+}
+
+/// Computes the colliders penetrated by a projectile in the order that they are hit.
+///
+/// The distance travelled inside a collider is measured between the first entry point
+/// and the last exit point along the ray.
+fn penetration_hits(
+    pipeline: &SpatialQueryPipeline,
+    projectile_entity: Entity,
+    origin: Vector,
+    projectile: &PenetratingProjectile,
+    resistance: impl Fn(Entity) -> Option<Scalar>,
+) -> Vec<PenetrationHit> {
+    let direction = projectile.direction.adjust_precision();
+    let max_distance = projectile.max_distance;
+
+    let mut ray_hits = vec![];
+    pipeline.ray_hits_callback(
+        origin,
+        projectile.direction,
+        max_distance,
+        true,
+        projectile.query_filter.clone(),
+        |hit| {
+            ray_hits.push(hit);
+            true
+        },
+    );
+    ray_hits.sort_by(|a, b| a.time_of_impact.total_cmp(&b.time_of_impact));
+
+    let mut energy = projectile.energy;
+    let mut hits = vec![];
+
+    for ray_hit in ray_hits {
+        let Some((isometry, collider, _, _)) = pipeline.colliders.get(&ray_hit.entity) else {
+            continue;
+        };
+
+        let entry_distance = ray_hit.time_of_impact;
+
+        // Cast a ray backwards from the end of the projectile's path to find the exit point.
+        let end = origin + direction * max_distance;
+        let exit_distance = collider
+            .shape_scaled()
+            .cast_ray_and_get_normal(
+                isometry,
+                &parry::query::Ray::new(end.into(), (-direction).into()),
+                max_distance - entry_distance,
+                true,
+            )
+            .map(|back_hit| back_hit.toi)
+            .filter(|back_distance| *back_distance > 0.0)
+            .map(|back_distance| max_distance - back_distance);
+
+        let thickness = exit_distance.unwrap_or(max_distance) - entry_distance;
+        let resistance = resistance(ray_hit.entity);
+        let loss = resistance.map_or(Scalar::INFINITY, |resistance| resistance * thickness);
+
+        let mut hit = PenetrationHit {
+            projectile: projectile_entity,
+            entity: ray_hit.entity,
+            entry_point: origin + direction * entry_distance,
+            entry_normal: ray_hit.normal,
+            exit_point: exit_distance.map(|distance| origin + direction * distance),
+            remaining_energy: energy - loss,
+        };
+
+        if exit_distance.is_none() || loss >= energy {
+            hit.exit_point = None;
+            hit.remaining_energy = 0.0;
+            hits.push(hit);
+            break;
+        }
+
+        energy = hit.remaining_energy;
+        hits.push(hit);
+    }
+
+    hits
+}
@@ -0,0 +1,165 @@
+//! Settles bodies in an isolated simulation to produce naturally resting poses.
+//!
+//! See [`SettleSimulation`].
+
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+use crate::prelude::*;
+
+/// A procedural placement helper that drops bodies to the ground and simulates them until they come to rest
+/// in an isolated, headless app, producing naturally resting poses for props during level generation.
+///
+/// The simulation runs as fast as possible, independently of real time and of any other physics world.
+/// Sleeping is configured aggressively so that the simulation can finish early once all dynamic bodies
+/// have come to rest. The sleeping configuration and other resources can be changed using
+/// [`SettleSimulation::insert_resource`].
+///
+/// Static bodies like the ground and dynamic props are added with [`SettleSimulation::spawn`], and
+/// [`SettleSimulation::settle`] returns the final transforms of the dynamic bodies.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// let mut simulation = SettleSimulation::new();
+///
+/// // The ground
+/// simulation.spawn((
+///     RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "    Collider::rectangle(20.0, 1.0),")]
+#[cfg_attr(feature = "3d", doc = "    Collider::cuboid(20.0, 1.0, 20.0),")]
+///     Position(Vector::NEG_Y * 0.5),
+/// ));
+///
+/// // Props dropped from random-ish heights
+/// for i in 0..5 {
+///     simulation.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::rectangle(1.0, 1.0),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::cuboid(1.0, 1.0, 1.0),")]
+#[cfg_attr(
+    feature = "2d",
+    doc = "        Position(Vector::new(i as Scalar * 1.5, 1.0 + i as Scalar * 0.3)),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "        Position(Vector::new(i as Scalar * 1.5, 1.0 + i as Scalar * 0.3, 0.0)),"
+)]
+///     ));
+/// }
+///
+/// // Simulate for at most 10 seconds and use the resting transforms for the props
+/// for (prop, transform) in simulation.settle(600) {
+///     println!("{prop:?} came to rest at {}", transform.translation);
+/// }
+/// ```
+pub struct SettleSimulation {
+    app: App,
+    bodies: Vec<Entity>,
+    steps: usize,
+}
+
+impl Default for SettleSimulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SettleSimulation {
+    /// Creates a new [`SettleSimulation`] that advances the simulation by 1/60 seconds on each step.
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            PhysicsPlugins::default(),
+            bevy::asset::AssetPlugin::default(),
+            #[cfg(feature = "bevy_scene")]
+            bevy::scene::ScenePlugin,
+        ))
+        // Collider constructors need the mesh assets
+        .init_resource::<Assets<Mesh>>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        // Put bodies to sleep aggressively so that the simulation can finish early
+        .insert_resource(SleepingThreshold::Velocity {
+            linear: 0.2,
+            angular: 0.4,
+        })
+        .insert_resource(DeactivationTime(0.2));
+        Self {
+            app,
+            bodies: vec![],
+            steps: 0,
+        }
+    }
+
+    /// Inserts a resource, for example to configure [`Gravity`], [`SubstepCount`] or the sleeping configuration.
+    pub fn insert_resource<R: Resource>(&mut self, resource: R) -> &mut Self {
+        self.app.insert_resource(resource);
+        self
+    }
+
+    /// Spawns a body with the given bundle into the simulation and returns its entity in the simulation.
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        let entity = self.app.world.spawn(bundle).id();
+        self.bodies.push(entity);
+        entity
+    }
+
+    /// Returns the number of steps that have been simulated.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Returns true if all dynamic bodies in the simulation are [sleeping](Sleeping).
+    pub fn is_settled(&mut self) -> bool {
+        let mut query = self
+            .app
+            .world
+            .query::<(&RigidBody, Has<Sleeping>, Has<RigidBodyDisabled>)>();
+        query
+            .iter(&self.app.world)
+            .all(|(rb, is_sleeping, is_disabled)| !rb.is_dynamic() || is_sleeping || is_disabled)
+    }
+
+    /// Simulates until all dynamic bodies have come to rest or `max_steps` steps have been taken,
+    /// and returns the final transforms of the dynamic bodies in the order that they were spawned.
+    pub fn settle(&mut self, max_steps: usize) -> Vec<(Entity, Transform)> {
+        for _ in 0..max_steps {
+            self.app.update();
+            self.steps += 1;
+
+            if self.is_settled() {
+                break;
+            }
+        }
+
+        self.bodies
+            .iter()
+            .filter_map(|entity| {
+                let entity_ref = self.app.world.get_entity(*entity)?;
+                if !entity_ref.get::<RigidBody>()?.is_dynamic() {
+                    return None;
+                }
+                Some((*entity, *entity_ref.get::<Transform>()?))
+            })
+            .collect()
+    }
+
+    /// Returns a reference to the underlying `App`.
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    /// Returns a mutable reference to the underlying `App`.
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+}
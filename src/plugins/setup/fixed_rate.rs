@@ -0,0 +1,228 @@
+//! Schedules that are run at their own fixed rates alongside the physics simulation.
+
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::intern::Interned, utils::HashMap};
+
+use crate::prelude::*;
+
+/// The maximum amount of time that can be accumulated for a [fixed-rate schedule](FixedRateSchedulePlugin)
+/// during a single frame. Prevents "death spirals" where each frame takes longer and longer to simulate.
+const MAX_OVERSTEP_PER_FRAME: Duration = Duration::from_millis(250);
+
+/// A plugin that runs a schedule at its own fixed rate, independently of the timestep of [`Time<Physics>`](Physics).
+///
+/// This allows different parts of a simulation to be updated at different rates in the same app,
+/// for example vehicle suspension and drivetrain systems at 120 Hz while the rest of the
+/// [`PhysicsSchedule`] steps at 30 Hz. Each schedule is keyed by its label, and its rate can be changed
+/// at runtime using the [`FixedRateSchedules`] resource.
+///
+/// Like the physics clock, the time of each schedule follows `Time<Real>`, is scaled by the
+/// [relative speed](PhysicsTime::relative_speed) of [`Time<Physics>`](Physics), and stops when physics is paused.
+/// The schedule is run as many times per frame as needed to catch up to real time,
+/// and the generic `Time` resource is set to the clock of the schedule while it runs.
+///
+/// By default, the schedule is run in `PostUpdate` after the [`PhysicsSchedule`]. Multiple instances of this plugin
+/// can be added for different schedules.
+///
+/// Note that the schedules share the world with the physics simulation. To step a separate set of bodies
+/// at a different rate, add a separate physics world with the [`PhysicsWorldPlugin`].
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// struct VehicleSchedule;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             FixedRateSchedulePlugin::new(VehicleSchedule, 120.0),
+///         ))
+///         // Step the rest of the simulation at 30 Hz
+///         .insert_resource(Time::new_with(Physics::fixed_hz(30.0)))
+///         .add_systems(VehicleSchedule, update_suspension)
+///         .run();
+/// }
+///
+/// fn update_suspension(time: Res<Time>) {
+///     // `time.delta()` is 1/120 seconds here
+/// }
+/// ```
+pub struct FixedRateSchedulePlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+    run_in: Interned<dyn ScheduleLabel>,
+    delta: Duration,
+}
+
+impl FixedRateSchedulePlugin {
+    /// Creates a [`FixedRateSchedulePlugin`] that runs the given schedule at a fixed rate in Hertz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero, negative or not finite.
+    pub fn new(schedule: impl ScheduleLabel, hz: f64) -> Self {
+        assert!(hz > 0.0 && hz.is_finite(), "Hz must be positive and finite");
+        Self {
+            schedule: schedule.intern(),
+            run_in: PostUpdate.intern(),
+            delta: Duration::from_secs_f64(1.0 / hz),
+        }
+    }
+
+    /// Sets the schedule that the fixed-rate schedule is run in.
+    /// It should be the same schedule that runs the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn run_in(self, schedule: impl ScheduleLabel) -> Self {
+        Self {
+            run_in: schedule.intern(),
+            ..self
+        }
+    }
+}
+
+impl Plugin for FixedRateSchedulePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FixedRateSchedules>();
+        app.world
+            .resource_mut::<FixedRateSchedules>()
+            .schedules
+            .insert(self.schedule, FixedRate::new(self.delta));
+
+        app.init_schedule(self.schedule);
+
+        let schedule = self.schedule;
+        app.add_systems(
+            self.run_in,
+            (move |world: &mut World| run_fixed_rate_schedule(world, schedule))
+                .in_set(PhysicsSet::StepSimulation)
+                .after(super::run_physics_schedule),
+        );
+    }
+
+    fn is_unique(&self) -> bool {
+        false
+    }
+}
+
+/// The timing state of a schedule run by a [`FixedRateSchedulePlugin`].
+#[derive(Clone, Copy, Debug)]
+pub struct FixedRate {
+    delta: Duration,
+    overstep: Duration,
+    clock: Time,
+}
+
+impl FixedRate {
+    fn new(delta: Duration) -> Self {
+        Self {
+            delta,
+            overstep: Duration::ZERO,
+            clock: Time::default(),
+        }
+    }
+
+    /// Returns the amount of time that the schedule is advanced by each time it runs.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Sets the amount of time that the schedule is advanced by each time it runs.
+    /// A zero `delta` stops the schedule from running.
+    pub fn set_delta(&mut self, delta: Duration) {
+        self.delta = delta;
+    }
+
+    /// Sets the rate at which the schedule is run in Hertz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero, negative or not finite. Use [`FixedRate::set_delta`]
+    /// with a zero delta to stop the schedule from running.
+    pub fn set_hz(&mut self, hz: f64) {
+        assert!(hz > 0.0 && hz.is_finite(), "Hz must be positive and finite");
+        self.delta = Duration::from_secs_f64(1.0 / hz);
+    }
+
+    /// Returns the accumulated time that has not been consumed by runs of the schedule yet.
+    pub fn overstep(&self) -> Duration {
+        self.overstep
+    }
+
+    /// Returns the clock of the schedule. It is set as the generic `Time` resource while the schedule runs.
+    pub fn clock(&self) -> &Time {
+        &self.clock
+    }
+}
+
+/// The [fixed rates](FixedRate) of the schedules run by [`FixedRateSchedulePlugin`]s, keyed by schedule label.
+#[derive(Resource, Debug, Default)]
+pub struct FixedRateSchedules {
+    schedules: HashMap<Interned<dyn ScheduleLabel>, FixedRate>,
+}
+
+impl FixedRateSchedules {
+    /// Returns the [`FixedRate`] of the given schedule, if it is run by a [`FixedRateSchedulePlugin`].
+    pub fn get(&self, schedule: impl ScheduleLabel) -> Option<&FixedRate> {
+        self.schedules.get(&schedule.intern())
+    }
+
+    /// Returns a mutable reference to the [`FixedRate`] of the given schedule,
+    /// if it is run by a [`FixedRateSchedulePlugin`].
+    pub fn get_mut(&mut self, schedule: impl ScheduleLabel) -> Option<&mut FixedRate> {
+        self.schedules.get_mut(&schedule.intern())
+    }
+}
+
+/// Runs a schedule as many times as its [`FixedRate`] requires to catch up to the physics clock.
+fn run_fixed_rate_schedule(world: &mut World, schedule: Interned<dyn ScheduleLabel>) {
+    let physics_clock = world.resource::<Time<Physics>>();
+    let elapsed = if physics_clock.is_paused() {
+        Duration::ZERO
+    } else {
+        let real_delta = world.resource::<Time<Real>>().delta();
+        real_delta
+            .mul_f64(physics_clock.relative_speed_f64())
+            .min(MAX_OVERSTEP_PER_FRAME)
+    };
+
+    let mut schedules = world.resource_mut::<FixedRateSchedules>();
+    let Some(rate) = schedules.schedules.get_mut(&schedule) else {
+        return;
+    };
+    rate.overstep += elapsed;
+    if rate.delta.is_zero() {
+        return;
+    }
+
+    let old_clock = world.resource::<Time>().as_generic();
+
+    loop {
+        let mut schedules = world.resource_mut::<FixedRateSchedules>();
+        let Some(rate) = schedules.schedules.get_mut(&schedule) else {
+            break;
+        };
+        if rate.delta.is_zero() || rate.overstep < rate.delta {
+            break;
+        }
+        rate.overstep -= rate.delta;
+        rate.clock.advance_by(rate.delta);
+        let clock = rate.clock;
+
+        *world.resource_mut::<Time>() = clock;
+        trace!("running fixed-rate schedule {schedule:?}");
+        let _ = world.try_run_schedule(schedule);
+    }
+
+    // Set generic `Time` resource back to the clock that was active before the schedule.
+    *world.resource_mut::<Time>() = old_clock;
+}
@@ -2,10 +2,14 @@
 //!
 //! See [`PhysicsSetupPlugin`].
 
+mod fixed_rate;
+mod physics_world;
 mod time;
 
 use std::time::Duration;
 
+pub use fixed_rate::*;
+pub use physics_world::*;
 pub use time::*;
 
 use super::sync::PreviousGlobalTransform;
@@ -65,20 +69,33 @@ impl Plugin for PhysicsSetupPlugin {
         app.init_resource::<Time<Physics>>()
             .insert_resource(Time::new_with(Substeps))
             .init_resource::<SubstepCount>()
+            .init_resource::<SubstepIndex>()
             .init_resource::<BroadCollisionPairs>()
             .init_resource::<SleepingThreshold>()
+            .init_resource::<PhysicsLengthUnit>()
             .init_resource::<DeactivationTime>()
             .init_resource::<Gravity>()
+            .init_resource::<PhysicsAmbientForces>()
+            .init_resource::<PhysicsPausedBehavior>()
+            .init_resource::<RotationIntegration>()
             .register_type::<Time<Physics>>()
             .register_type::<Time<Substeps>>()
             .register_type::<SubstepCount>()
+            .register_type::<SubstepIndex>()
             .register_type::<BroadCollisionPairs>()
             .register_type::<SleepingThreshold>()
+            .register_type::<PhysicsLengthUnit>()
             .register_type::<DeactivationTime>()
             .register_type::<Gravity>()
+            .register_type::<PhysicsAmbientForces>()
+            .register_type::<PhysicsPausedBehavior>()
+            .register_type::<RotationIntegration>()
             .register_type::<RigidBody>()
+            .register_type::<KinematicCollideMode>()
             .register_type::<Sleeping>()
             .register_type::<SleepingDisabled>()
+            .register_type::<SleepGroup>()
+            .register_type::<RigidBodyDisabled>()
             .register_type::<TimeSleeping>()
             .register_type::<Position>()
             .register_type::<Rotation>()
@@ -99,6 +116,8 @@ impl Plugin for PhysicsSetupPlugin {
             .register_type::<ExternalImpulse>()
             .register_type::<ExternalAngularImpulse>()
             .register_type::<GravityScale>()
+            .register_type::<WindInfluence>()
+            .register_type::<PhysicsId>()
             .register_type::<Mass>()
             .register_type::<InverseMass>()
             .register_type::<Inertia>()
@@ -109,15 +128,39 @@ impl Plugin for PhysicsSetupPlugin {
             .register_type::<LockedAxes>()
             .register_type::<ColliderParent>()
             .register_type::<Dominance>()
+            .register_type::<RelativeTo>()
             .register_type::<CollisionLayers>()
             .register_type::<CollidingEntities>()
             .register_type::<CoefficientCombine>()
             .register_type::<Sensor>()
+            .register_type::<SensorUpdateInterval>()
+            .register_type::<SensorOverlaps>()
+            .register_type::<ColliderDisabled>()
             .register_type::<ColliderTransform>()
             .register_type::<PreviousColliderTransform>()
             .register_type::<ColliderConstructor>()
             .register_type::<ColliderConstructorHierarchy>()
-            .register_type::<ColliderConstructorHierarchyConfig>();
+            .register_type::<ColliderConstructorHierarchyConfig>()
+            .register_type::<JointDisabled>()
+            .register_type::<BreakableJoint>()
+            .register_type::<JointIterations>()
+            .register_type::<MultibodyRoot>()
+            .register_type::<OrphanedJointPolicy>();
+
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        app.register_type::<MergedBody>()
+            .register_type::<SurfaceMaterials>()
+            .register_type::<ValidatedBody>();
+
+        #[cfg(feature = "2d")]
+        app.register_type::<ZLayer>()
+            .register_type::<ZLayerAdjacency>()
+            .register_type::<AccumulatedAngle>()
+            .register_type::<FaceTarget>()
+            .register_type::<AimTarget>();
 
         // Configure higher level system sets for the given schedule
         let schedule = self.schedule;
@@ -289,6 +332,24 @@ fn run_physics_schedule(world: &mut World, mut is_first_run: Local<IsFirstRun>)
     is_first_run.0 = false;
 }
 
+/// A run condition that is true if physics is paused and spatial queries
+/// should still be updated according to [`PhysicsPausedBehavior`].
+pub(crate) fn update_spatial_queries_while_paused(
+    time: Res<Time<Physics>>,
+    behavior: Res<PhysicsPausedBehavior>,
+) -> bool {
+    time.is_paused() && behavior.update_spatial_queries
+}
+
+/// A run condition that is true if physics is running, or if it is paused
+/// and transforms should still be synchronized according to [`PhysicsPausedBehavior`].
+pub(crate) fn sync_transforms_while_paused(
+    time: Res<Time<Physics>>,
+    behavior: Res<PhysicsPausedBehavior>,
+) -> bool {
+    !time.is_paused() || behavior.sync_transforms
+}
+
 /// Runs the [`SubstepSchedule`].
 fn run_substep_schedule(world: &mut World) {
     let delta = world.resource::<Time<Physics>>().delta();
@@ -301,6 +362,7 @@ fn run_substep_schedule(world: &mut World) {
     let _ = world.try_schedule_scope(SubstepSchedule, |world, schedule| {
         for i in 0..substeps {
             trace!("running SubstepSchedule: {i}");
+            world.resource_mut::<SubstepIndex>().0 = i;
             *world.resource_mut::<Time>() = world.resource::<Time<Substeps>>().as_generic();
             schedule.run(world);
         }
@@ -0,0 +1,161 @@
+//! Separate physics worlds that are stepped at their own fixed rates.
+
+use bevy::{
+    app::{AppLabel, InternedAppLabel, SubApp},
+    prelude::*,
+    utils::HashMap,
+};
+
+use crate::prelude::*;
+
+/// A plugin that adds a separate physics world with its own instance of the [`PhysicsPlugins`],
+/// stepped at its own fixed rate.
+///
+/// This allows different parts of a game to be simulated at different rates,
+/// for example vehicles at 120 Hz and background debris at 30 Hz. Each world is a sub-app keyed by
+/// its `AppLabel`, with its own [`PhysicsSchedule`] runner and [`Time<Physics>`](Physics) clock.
+/// Like the main physics world, the worlds are stepped every frame as many times as needed to catch up to real time.
+///
+/// Bodies in different worlds don't interact with each other. Bodies are spawned into a world by accessing
+/// its `App` with `App::sub_app_mut`, and data like transforms can be moved between the main world and
+/// the physics world using an extract function given to [`PhysicsWorldPlugin::with_extract`].
+/// The extract function is run every frame before the physics world is updated.
+///
+/// The labels and initial rates of the worlds are stored in the [`PhysicsWorlds`] resource of the main app.
+/// The rate of a world can be changed at runtime using the [`Time<Physics>`](Physics) resource of the world.
+///
+/// Each world is a separate `App` with its own [`PhysicsPlugins`], `TimePlugin` and `TransformPlugin`,
+/// so it has its own copy of all physics resources and schedules, which are run every frame after the main app
+/// is updated. The task pools are shared with the main app.
+/// Bodies that don't need a different rate should be kept in the main physics world.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually.
+/// Multiple instances of this plugin can be added for different worlds.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::{app::AppLabel, prelude::*};
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// #[derive(AppLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// struct DebrisWorld;
+///
+/// #[derive(Component)]
+/// struct Debris;
+///
+/// fn main() {
+///     let mut app = App::new();
+///
+///     app.add_plugins((
+///         DefaultPlugins,
+///         // Simulate the main physics world at 120 Hz...
+///         PhysicsPlugins::default(),
+///         // ...and background debris at 30 Hz
+///         PhysicsWorldPlugin::new(DebrisWorld, 30.0).with_extract(sync_debris),
+///     ))
+///     .insert_resource(Time::new_with(Physics::fixed_hz(120.0)));
+///
+///     app.sub_app_mut(DebrisWorld).world.spawn((
+///         Debris,
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "        Collider::circle(0.5),")]
+#[cfg_attr(feature = "3d", doc = "        Collider::sphere(0.5),")]
+///         TransformBundle::default(),
+///     ));
+///
+///     app.run();
+/// }
+///
+/// // Copy the transforms of the debris to the main world, for example for rendering
+/// fn sync_debris(main_world: &mut World, debris_world: &mut World) {
+///     // ...
+/// }
+/// ```
+pub struct PhysicsWorldPlugin {
+    label: InternedAppLabel,
+    hz: f64,
+    extract: fn(&mut World, &mut World),
+}
+
+impl PhysicsWorldPlugin {
+    /// Creates a [`PhysicsWorldPlugin`] that adds a physics world with the given label,
+    /// stepped at a fixed rate in Hertz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero, negative or not finite.
+    pub fn new(label: impl AppLabel, hz: f64) -> Self {
+        assert!(hz > 0.0 && hz.is_finite(), "Hz must be positive and finite");
+        Self {
+            label: label.intern(),
+            hz,
+            extract: |_, _| {},
+        }
+    }
+
+    /// Sets the function that moves data between the main world and the physics world.
+    ///
+    /// The function is given the main world and the physics world, in that order,
+    /// and it is run every frame before the physics world is updated.
+    pub fn with_extract(self, extract: fn(&mut World, &mut World)) -> Self {
+        Self { extract, ..self }
+    }
+}
+
+impl Plugin for PhysicsWorldPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsWorlds>();
+        app.world
+            .resource_mut::<PhysicsWorlds>()
+            .rates
+            .insert(self.label, self.hz);
+
+        // Only add what the physics plugins need. The task pools are shared with the main app,
+        // and the sub-app is updated by the runner of the main app.
+        let mut world_app = App::new();
+        world_app
+            .add_plugins((
+                bevy::time::TimePlugin,
+                TransformPlugin,
+                PhysicsPlugins::default(),
+            ))
+            // Collider constructors read the mesh assets and scene instances,
+            // but they aren't loaded in the physics world
+            .init_resource::<Assets<Mesh>>()
+            .insert_resource(Time::new_with(Physics::fixed_hz(self.hz)));
+        #[cfg(feature = "bevy_scene")]
+        world_app.init_resource::<bevy::scene::SceneSpawner>();
+
+        let extract = self.extract;
+        app.insert_sub_app(
+            self.label,
+            SubApp::new(world_app, move |main_world, world_app| {
+                extract(main_world, &mut world_app.world);
+            }),
+        );
+    }
+
+    fn is_unique(&self) -> bool {
+        false
+    }
+}
+
+/// The labels and initial fixed rates in Hertz of the physics worlds added by [`PhysicsWorldPlugin`]s.
+#[derive(Resource, Debug, Default)]
+pub struct PhysicsWorlds {
+    rates: HashMap<InternedAppLabel, f64>,
+}
+
+impl PhysicsWorlds {
+    /// Returns the fixed rate in Hertz that the physics world with the given label was created with.
+    pub fn initial_hz(&self, label: impl AppLabel) -> Option<f64> {
+        self.rates.get(&label.intern()).copied()
+    }
+
+    /// Returns an iterator over the labels of the physics worlds.
+    pub fn labels(&self) -> impl Iterator<Item = InternedAppLabel> + '_ {
+        self.rates.keys().copied()
+    }
+}
@@ -3,11 +3,15 @@
 //! See [`SleepingPlugin`].
 
 use crate::prelude::*;
-use bevy::prelude::*;
+use bevy::{
+    ecs::query::Has,
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
 
 /// Controls when bodies should be deactivated and marked as [`Sleeping`] to improve performance.
 ///
-/// Bodies are marked as [`Sleeping`] when their linear and angular velocities are below the [`SleepingThreshold`]
+/// Bodies are marked as [`Sleeping`] when their velocities or kinetic energy are below the [`SleepingThreshold`]
 /// for a duration indicated by [`DeactivationTime`].
 ///
 /// Bodies are woken up when an active body or constraint interacts with them, or when gravity changes,
@@ -15,19 +19,47 @@ use bevy::prelude::*;
 ///
 /// This plugin does *not* handle constraints waking up bodies. That is done by the [solver].
 ///
+/// The plugin also computes simulation islands, groups of dynamic bodies connected by contacts and [joints],
+/// and stores them in the [`PhysicsIslands`] resource.
+///
+/// When bodies fall asleep or are woken up, [`BodyCameToRest`] and [`BodyStartedMoving`] events are sent.
+/// Bodies with [`SleepingDisabled`] never come to rest.
+///
+/// Bodies that are connected by [joints] or share a [`SleepGroup`] fall asleep and wake up together,
+/// so that jointed structures never end up partially asleep.
+///
+/// The poses of sleeping bodies can be saved and restored without waking them up using [`PhysicsPersistence`].
+///
 /// The sleeping systems run in [`PhysicsStepSet::Sleeping`].
 pub struct SleepingPlugin;
 
 impl Plugin for SleepingPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsIslands>()
+            .add_event::<BodyStartedMoving>()
+            .add_event::<BodyCameToRest>();
+
         app.get_schedule_mut(PhysicsSchedule)
             .expect("add PhysicsSchedule first")
             .add_systems(wake_on_collision_ended.in_set(PhysicsStepSet::ReportContacts))
             .add_systems(
                 (
-                    mark_sleeping_bodies,
+                    // Wake up bodies before marking new ones as sleeping so that the motion
+                    // of bodies during this step doesn't wake them up right after they fall asleep
                     wake_on_changed,
-                    wake_all_sleeping_bodies.run_if(resource_changed::<Gravity>),
+                    mark_sleeping_bodies,
+                    wake_all_sleeping_bodies.run_if(
+                        resource_changed::<Gravity>
+                            .or_else(resource_changed::<PhysicsAmbientForces>),
+                    ),
+                    // Apply the sleep transitions so that whole sleep sets can be woken up
+                    apply_deferred,
+                    wake_sleep_sets,
+                    update_physics_islands,
+                    // Apply the sleep transitions so that activity events can be sent for them
+                    apply_deferred,
+                    send_activity_events,
+                    clear_restored_asleep,
                 )
                     .chain()
                     .in_set(PhysicsStepSet::Sleeping),
@@ -35,53 +67,304 @@ impl Plugin for SleepingPlugin {
     }
 }
 
+/// An event that is sent when a [sleeping](Sleeping) body is woken up and starts moving.
+///
+/// This can be used for reacting to bodies becoming active without checking every body each frame.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BodyStartedMoving(pub Entity);
+
+/// An event that is sent when a body comes to rest and falls [asleep](Sleeping).
+///
+/// This can be used for effects like dust when an object settles, or for only saving objects
+/// that have settled, without checking every body each frame.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BodyCameToRest(pub Entity);
+
+/// Sends [`BodyStartedMoving`] and [`BodyCameToRest`] events for bodies that were woken up or fell asleep.
+pub(crate) fn send_activity_events(
+    mut woken_bodies: RemovedComponents<Sleeping>,
+    bodies: Query<Has<Sleeping>, With<RigidBody>>,
+    resting_bodies: Query<Entity, (Added<Sleeping>, With<RigidBody>, Without<RestoredAsleep>)>,
+    mut started_moving: EventWriter<BodyStartedMoving>,
+    mut came_to_rest: EventWriter<BodyCameToRest>,
+) {
+    // Bodies that were despawned or put back to sleep are not moving.
+    started_moving.send_batch(
+        woken_bodies
+            .read()
+            .filter(|entity| bodies.get(*entity).is_ok_and(|is_sleeping| !is_sleeping))
+            .map(BodyStartedMoving),
+    );
+    came_to_rest.send_batch(resting_bodies.iter().map(BodyCameToRest));
+}
+
+/// The position and rotation of a body, as extracted by [`PhysicsPersistence::extract_settled`]
+/// or stored in a [`ContactStart`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pose {
+    /// The position of the body.
+    pub position: Vector,
+    /// The rotation of the body.
+    pub rotation: Rotation,
+}
+
+/// Save-game friendly extraction and restoration of bodies that have settled and fallen [asleep](Sleeping).
+///
+/// Saving only the poses of settled bodies keeps save files small, and restoring them with
+/// [`PhysicsPersistence::restore_settled`] keeps them asleep instead of waking up every body on load.
+///
+/// Bodies are identified by their [`Entity`]. When entities are recreated on load,
+/// the entities should be remapped before restoring, for example by storing a [`PhysicsId`] alongside each pose.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn save_and_load(world: &mut World) {
+///     // Get the poses of all sleeping bodies
+///     let poses = PhysicsPersistence::extract_settled(world);
+///
+///     // ...write the poses to a save file, and later read them back...
+///
+///     // Restore the poses, keeping the bodies asleep
+///     PhysicsPersistence::restore_settled(world, poses);
+/// }
+/// ```
+pub struct PhysicsPersistence;
+
+impl PhysicsPersistence {
+    /// Returns the [`Pose`]s of all [sleeping](Sleeping) rigid bodies.
+    pub fn extract_settled(world: &mut World) -> Vec<(Entity, Pose)> {
+        let mut query = world
+            .query_filtered::<(Entity, &Position, &Rotation), (With<RigidBody>, With<Sleeping>)>();
+        query
+            .iter(world)
+            .map(|(entity, position, rotation)| {
+                (
+                    entity,
+                    Pose {
+                        position: position.0,
+                        rotation: *rotation,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Moves the given bodies to their [`Pose`]s and puts them to [sleep](Sleeping) without waking them up
+    /// on the next physics step, and without waking up other bodies.
+    ///
+    /// The velocities of the bodies are reset to zero. Entities that don't exist are skipped.
+    ///
+    /// No [`BodyCameToRest`] events are sent for the restored bodies.
+    pub fn restore_settled(world: &mut World, poses: impl IntoIterator<Item = (Entity, Pose)>) {
+        for (entity, pose) in poses {
+            let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+                continue;
+            };
+            entity_mut.insert((
+                Position(pose.position),
+                pose.rotation,
+                LinearVelocity::default(),
+                AngularVelocity::default(),
+                TimeSleeping::default(),
+                Sleeping,
+                RestoredAsleep,
+            ));
+        }
+    }
+}
+
+/// A marker component for bodies that were restored by [`PhysicsPersistence::restore_settled`].
+///
+/// The changes made when restoring the bodies don't wake them up. The marker is removed
+/// at the end of the next physics step.
+#[derive(Component)]
+pub struct RestoredAsleep;
+
+/// Removes the [`RestoredAsleep`] marker after the restored bodies have been kept asleep for a physics step.
+fn clear_restored_asleep(mut commands: Commands, bodies: Query<Entity, With<RestoredAsleep>>) {
+    for entity in &bodies {
+        commands.entity(entity).remove::<RestoredAsleep>();
+    }
+}
+
 type SleepingQueryComponents = (
     Entity,
     &'static RigidBody,
     &'static mut LinearVelocity,
     &'static mut AngularVelocity,
     &'static mut TimeSleeping,
+    Option<&'static PhysicsActivity>,
+    &'static Rotation,
+    &'static Mass,
+    &'static Inertia,
 );
 
-/// Adds the [`Sleeping`] component to bodies whose linear and anigular velocities have been
+/// Adds the [`Sleeping`] component to bodies whose velocities or kinetic energy have been
 /// under the [`SleepingThreshold`] for a duration indicated by [`DeactivationTime`].
+///
+/// Bodies that are connected by joints or share a [`SleepGroup`] are only marked as sleeping
+/// once all of them are ready to sleep.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn mark_sleeping_bodies(
     mut commands: Commands,
-    mut bodies: Query<SleepingQueryComponents, (Without<Sleeping>, Without<SleepingDisabled>)>,
+    mut bodies: Query<
+        SleepingQueryComponents,
+        (
+            Without<Sleeping>,
+            Without<SleepingDisabled>,
+            Without<RigidBodyDisabled>,
+        ),
+    >,
+    sleep_set_bodies: Query<SleepSetBodyComponents>,
+    joints: IslandJointQueries,
     deactivation_time: Res<DeactivationTime>,
     sleep_threshold: Res<SleepingThreshold>,
+    length_unit: Res<PhysicsLengthUnit>,
     dt: Res<Time>,
 ) {
-    for (entity, rb, mut lin_vel, mut ang_vel, mut time_sleeping) in &mut bodies {
+    let sleep_sets = compute_sleep_sets(&sleep_set_bodies, &joints);
+
+    // Sleep sets with a body that can't sleep yet are kept awake.
+    let mut awake_sets = sleep_set_bodies
+        .iter()
+        .filter(|(_, rb, _, is_sleeping, sleeping_disabled, is_disabled)| {
+            rb.is_dynamic() && !is_sleeping && *sleeping_disabled && !is_disabled
+        })
+        .filter_map(|(entity, ..)| sleep_sets.get(&entity).copied())
+        .collect::<HashSet<_>>();
+    let mut ready_bodies = vec![];
+
+    for (entity, rb, lin_vel, ang_vel, mut time_sleeping, activity, rotation, mass, inertia) in
+        &mut bodies
+    {
         // Only dynamic bodies can sleep.
         if !rb.is_dynamic() {
             continue;
         }
 
-        let lin_vel_sq = lin_vel.length_squared();
+        // Far-away bodies can have scaled thresholds so that they fall asleep more eagerly.
+        let scale = activity.map_or(1.0, |activity| activity.sleep_threshold_scale);
 
-        #[cfg(feature = "2d")]
-        let ang_vel_sq = ang_vel.0.powi(2);
-        #[cfg(feature = "3d")]
-        let ang_vel_sq = ang_vel.0.dot(ang_vel.0);
+        let is_still = match *sleep_threshold {
+            SleepingThreshold::Velocity { linear, angular } => {
+                let lin_vel_sq = lin_vel.length_squared();
+
+                #[cfg(feature = "2d")]
+                let ang_vel_sq = ang_vel.0.powi(2);
+                #[cfg(feature = "3d")]
+                let ang_vel_sq = ang_vel.0.dot(ang_vel.0);
+
+                let lin_threshold = linear * scale;
+                let ang_threshold = angular * scale;
+
+                // Negative thresholds indicate that sleeping is disabled.
+                let lin_sleeping_threshold_sq = lin_threshold * lin_threshold.abs();
+                let ang_sleeping_threshold_sq = ang_threshold * ang_threshold.abs();
 
-        // Negative thresholds indicate that sleeping is disabled.
-        let lin_sleeping_threshold_sq = sleep_threshold.linear * sleep_threshold.linear.abs();
-        let ang_sleeping_threshold_sq = sleep_threshold.angular * sleep_threshold.angular.abs();
+                lin_vel_sq < lin_sleeping_threshold_sq && ang_vel_sq < ang_sleeping_threshold_sq
+            }
+            SleepingThreshold::Energy(threshold) => {
+                // The energy is proportional to the squared velocity, so the threshold is scaled quadratically.
+                // Negative thresholds indicate that sleeping is disabled, as the energy can't be negative.
+                let energy = specific_kinetic_energy(&lin_vel, &ang_vel, rotation, mass, inertia)
+                    / length_unit.0.powi(2);
+                energy < threshold * scale.powi(2)
+            }
+        };
 
-        // If linear and angular velocity are below the sleeping threshold,
-        // add delta time to the time sleeping, i.e. the time that the body has remained still.
-        if lin_vel_sq < lin_sleeping_threshold_sq && ang_vel_sq < ang_sleeping_threshold_sq {
+        // If the body is still enough, add delta time to the time sleeping,
+        // i.e. the time that the body has remained still.
+        if is_still {
             time_sleeping.0 += dt.delta_seconds_adjusted();
         } else {
             time_sleeping.0 = 0.0;
         }
 
-        // If the body has been still for long enough, set it to sleep and reset velocities.
         if time_sleeping.0 > deactivation_time.0 {
+            ready_bodies.push(entity);
+        } else if let Some(set) = sleep_sets.get(&entity) {
+            awake_sets.insert(*set);
+        }
+    }
+
+    for entity in ready_bodies {
+        if sleep_sets
+            .get(&entity)
+            .is_some_and(|set| awake_sets.contains(set))
+        {
+            continue;
+        }
+
+        // The body and its sleep set have been still for long enough, set it to sleep and reset velocities.
+        // The reset bypasses change detection so that `wake_on_changed` doesn't wake the body on the next step.
+        if let Ok((_, _, mut lin_vel, mut ang_vel, ..)) = bodies.get_mut(entity) {
             commands.entity(entity).try_insert(Sleeping);
-            *lin_vel = LinearVelocity::ZERO;
-            *ang_vel = AngularVelocity::ZERO;
+            *lin_vel.bypass_change_detection() = LinearVelocity::ZERO;
+            *ang_vel.bypass_change_detection() = AngularVelocity::ZERO;
+        }
+    }
+}
+
+/// Returns the kinetic energy of a body divided by its mass, in J/kg (m²/s²) when using meters as units.
+///
+/// The rotational part is `0.5 * ω · I ω / m`, where `I / m` is proportional to the squared size of the body,
+/// so large bodies need to rotate slower than small bodies to be considered still.
+fn specific_kinetic_energy(
+    lin_vel: &LinearVelocity,
+    ang_vel: &AngularVelocity,
+    rotation: &Rotation,
+    mass: &Mass,
+    inertia: &Inertia,
+) -> Scalar {
+    let linear = 0.5 * lin_vel.length_squared();
+
+    if mass.0 <= Scalar::EPSILON || !mass.0.is_finite() {
+        return linear;
+    }
+
+    #[cfg(feature = "2d")]
+    let angular = 0.5 * inertia.rotated(rotation).0 * ang_vel.0.powi(2);
+    #[cfg(feature = "3d")]
+    let angular = 0.5 * ang_vel.0.dot(inertia.rotated(rotation).0 * ang_vel.0);
+
+    linear + angular / mass.0
+}
+
+/// Wakes up the sleeping bodies of sleep sets that have an awake body,
+/// so that bodies connected by joints or sharing a [`SleepGroup`] wake up together.
+fn wake_sleep_sets(
+    mut commands: Commands,
+    sleep_set_bodies: Query<SleepSetBodyComponents>,
+    mut time_sleeping: Query<&mut TimeSleeping, With<Sleeping>>,
+    joints: IslandJointQueries,
+) {
+    let sleep_sets = compute_sleep_sets(&sleep_set_bodies, &joints);
+
+    let awake_sets = sleep_set_bodies
+        .iter()
+        .filter(|(_, rb, _, is_sleeping, _, is_disabled)| {
+            rb.is_dynamic() && !is_sleeping && !is_disabled
+        })
+        .filter_map(|(entity, ..)| sleep_sets.get(&entity).copied())
+        .collect::<HashSet<_>>();
+
+    if awake_sets.is_empty() {
+        return;
+    }
+
+    for (entity, set) in sleep_sets.iter() {
+        if !awake_sets.contains(set) {
+            continue;
+        }
+        if let Ok(mut time_sleeping) = time_sleeping.get_mut(*entity) {
+            commands.entity(*entity).remove::<Sleeping>();
+            time_sleeping.0 = 0.0;
         }
     }
 }
@@ -96,6 +379,7 @@ type WokeUpFilter = Or<(
     Changed<ExternalImpulse>,
     Changed<ExternalAngularImpulse>,
     Changed<GravityScale>,
+    Changed<WindInfluence>,
 )>;
 
 /// Removes the [`Sleeping`] component from sleeping bodies when properties like
@@ -103,7 +387,10 @@ type WokeUpFilter = Or<(
 #[allow(clippy::type_complexity)]
 pub fn wake_on_changed(
     mut commands: Commands,
-    mut bodies: Query<(Entity, &mut TimeSleeping), (With<Sleeping>, WokeUpFilter)>,
+    mut bodies: Query<
+        (Entity, &mut TimeSleeping),
+        (With<Sleeping>, Without<RestoredAsleep>, WokeUpFilter),
+    >,
 ) {
     for (entity, mut time_sleeping) in &mut bodies {
         commands.entity(entity).remove::<Sleeping>();
@@ -112,7 +399,7 @@ pub fn wake_on_changed(
 }
 
 /// Removes the [`Sleeping`] component from all sleeping bodies.
-/// Triggered automatically when [`Gravity`] is changed.
+/// Triggered automatically when [`Gravity`] or [`PhysicsAmbientForces`] is changed.
 fn wake_all_sleeping_bodies(
     mut commands: Commands,
     mut bodies: Query<(Entity, &mut TimeSleeping), With<Sleeping>>,
@@ -161,3 +448,308 @@ fn wake_on_collision_ended(
         }
     }
 }
+
+/// A unique identifier for a [`PhysicsIsland`].
+///
+/// Islands are recomputed every physics frame, so identifiers are only valid until the next frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct IslandId(pub u32);
+
+/// A simulation island, a group of dynamic bodies that are connected by contacts or [joints].
+///
+/// Static and [kinematic](RigidBody::Kinematic) bodies don't connect islands,
+/// so for example all bodies resting on the same ground can still be in separate islands.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PhysicsIsland {
+    /// The rigid bodies in the island.
+    pub bodies: Vec<Entity>,
+    /// True if all of the bodies in the island are [`Sleeping`].
+    pub sleeping: bool,
+    /// The total kinetic energy of the bodies in the island.
+    pub kinetic_energy: Scalar,
+}
+
+/// A resource that stores the simulation islands computed by the [`SleepingPlugin`].
+///
+/// An island is a group of dynamic bodies that are connected by contacts or [joints].
+/// Islands can be used for gameplay and level of detail decisions on a per-island basis,
+/// like streaming out an entire pile of sleeping bodies.
+///
+/// The islands are updated at the end of [`PhysicsStepSet::Sleeping`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn print_sleeping_piles(islands: Res<PhysicsIslands>) {
+///     for (id, island) in islands.iter() {
+///         if island.sleeping && island.bodies.len() > 10 {
+///             println!("Island {:?} is a sleeping pile of {} bodies", id, island.bodies.len());
+///         }
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct PhysicsIslands {
+    islands: HashMap<IslandId, PhysicsIsland>,
+    body_islands: HashMap<Entity, IslandId>,
+}
+
+impl PhysicsIslands {
+    /// Returns the island with the given [`IslandId`], if it exists.
+    pub fn get(&self, id: IslandId) -> Option<&PhysicsIsland> {
+        self.islands.get(&id)
+    }
+
+    /// Returns the [`IslandId`] of the island that the given rigid body belongs to.
+    ///
+    /// Static and [kinematic](RigidBody::Kinematic) bodies don't belong to any island.
+    pub fn island_id(&self, body: Entity) -> Option<IslandId> {
+        self.body_islands.get(&body).copied()
+    }
+
+    /// Returns the island that the given rigid body belongs to.
+    pub fn island_of(&self, body: Entity) -> Option<&PhysicsIsland> {
+        self.island_id(body).and_then(|id| self.get(id))
+    }
+
+    /// Returns an iterator over all islands and their identifiers.
+    pub fn iter(&self) -> impl Iterator<Item = (IslandId, &PhysicsIsland)> {
+        self.islands.iter().map(|(id, island)| (*id, island))
+    }
+
+    /// Returns the number of islands.
+    pub fn len(&self) -> usize {
+        self.islands.len()
+    }
+
+    /// Returns true if there are no islands.
+    pub fn is_empty(&self) -> bool {
+        self.islands.is_empty()
+    }
+}
+
+type IslandBodyComponents = (
+    Entity,
+    &'static RigidBody,
+    &'static Rotation,
+    &'static LinearVelocity,
+    &'static AngularVelocity,
+    &'static Mass,
+    &'static Inertia,
+    Has<Sleeping>,
+);
+
+type IslandJointQueries<'w, 's> = (
+    Query<'w, 's, &'static FixedJoint, Without<JointDisabled>>,
+    Query<'w, 's, &'static RevoluteJoint, Without<JointDisabled>>,
+    Query<'w, 's, &'static SphericalJoint, Without<JointDisabled>>,
+    Query<'w, 's, &'static PrismaticJoint, Without<JointDisabled>>,
+    Query<'w, 's, &'static DistanceJoint, Without<JointDisabled>>,
+    Query<'w, 's, &'static SplineDriveJoint, Without<JointDisabled>>,
+    Query<'w, 's, &'static SuspensionConstraint, Without<JointDisabled>>,
+    Query<'w, 's, &'static GenericJoint, Without<JointDisabled>>,
+    Query<'w, 's, &'static GearJoint, Without<JointDisabled>>,
+    Query<'w, 's, &'static PulleyJoint, Without<JointDisabled>>,
+    Query<'w, 's, (Entity, &'static MultibodyLink), Without<JointDisabled>>,
+);
+
+type SleepSetBodyComponents = (
+    Entity,
+    &'static RigidBody,
+    Option<&'static SleepGroup>,
+    Has<Sleeping>,
+    Has<SleepingDisabled>,
+    Has<RigidBodyDisabled>,
+);
+
+/// Returns the pairs of entities attached by joints.
+fn joint_entity_pairs(joints: &IslandJointQueries) -> Vec<[Entity; 2]> {
+    let (
+        fixed,
+        revolute,
+        spherical,
+        prismatic,
+        distance,
+        spline_drive,
+        suspension,
+        generic,
+        gear,
+        pulley,
+        links,
+    ) = joints;
+    fixed
+        .iter()
+        .map(|joint| joint.entities())
+        .chain(revolute.iter().map(|joint| joint.entities()))
+        .chain(spherical.iter().map(|joint| joint.entities()))
+        .chain(prismatic.iter().map(|joint| joint.entities()))
+        .chain(distance.iter().map(|joint| joint.entities()))
+        .chain(spline_drive.iter().map(|joint| joint.entities()))
+        .chain(suspension.iter().map(|joint| joint.entities()))
+        .chain(generic.iter().map(|joint| joint.entities()))
+        .chain(gear.iter().map(|joint| joint.entities()))
+        .chain(pulley.iter().map(|joint| joint.entities()))
+        .chain(links.iter().map(|(entity, link)| [link.parent, entity]))
+        .collect()
+}
+
+/// Groups the dynamic bodies into sleep sets, bodies that are connected by joints or share a [`SleepGroup`]
+/// and must fall asleep and wake up together.
+///
+/// Returns a map from each body in a sleep set of more than one body to the index of its set.
+fn compute_sleep_sets(
+    bodies: &Query<SleepSetBodyComponents>,
+    joints: &IslandJointQueries,
+) -> HashMap<Entity, usize> {
+    let dynamic_bodies = bodies
+        .iter()
+        .filter(|(_, rb, ..)| rb.is_dynamic())
+        .collect::<Vec<_>>();
+    let indices = dynamic_bodies
+        .iter()
+        .enumerate()
+        .map(|(i, (entity, ..))| (*entity, i))
+        .collect::<HashMap<_, _>>();
+
+    let mut union_find = UnionFind::new(dynamic_bodies.len());
+    let mut connected = vec![false; dynamic_bodies.len()];
+
+    // Connect bodies that share a sleep group
+    let mut groups = HashMap::<SleepGroup, usize>::new();
+    for (i, (_, _, group, ..)) in dynamic_bodies.iter().enumerate() {
+        if let Some(group) = group {
+            let first = *groups.entry(**group).or_insert(i);
+            if first != i {
+                union_find.union(first, i);
+                connected[first] = true;
+                connected[i] = true;
+            }
+        }
+    }
+
+    // Connect bodies that are attached by joints
+    for [entity1, entity2] in joint_entity_pairs(joints) {
+        if let (Some(i), Some(j)) = (indices.get(&entity1), indices.get(&entity2)) {
+            union_find.union(*i, *j);
+            connected[*i] = true;
+            connected[*j] = true;
+        }
+    }
+
+    dynamic_bodies
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| connected[*i])
+        .map(|(i, (entity, ..))| (*entity, union_find.find(i)))
+        .collect()
+}
+
+/// Computes the [`PhysicsIslands`] by finding the groups of dynamic bodies
+/// that are connected by contacts or joints.
+pub fn update_physics_islands(
+    mut islands: ResMut<PhysicsIslands>,
+    bodies: Query<IslandBodyComponents>,
+    collider_parents: Query<&ColliderParent>,
+    collisions: Res<Collisions>,
+    joints: IslandJointQueries,
+) {
+    let islands = &mut *islands;
+    islands.islands.clear();
+    islands.body_islands.clear();
+
+    // Assign an index to each dynamic body
+    let dynamic_bodies = bodies
+        .iter()
+        .filter(|(_, rb, ..)| rb.is_dynamic())
+        .collect::<Vec<_>>();
+    let indices = dynamic_bodies
+        .iter()
+        .enumerate()
+        .map(|(i, (entity, ..))| (*entity, i))
+        .collect::<HashMap<_, _>>();
+
+    let mut union_find = UnionFind::new(dynamic_bodies.len());
+    let mut connect = |entity1: Entity, entity2: Entity| {
+        if let (Some(i), Some(j)) = (indices.get(&entity1), indices.get(&entity2)) {
+            union_find.union(*i, *j);
+        }
+    };
+
+    // Connect bodies that are in contact
+    for contacts in collisions.get_internal().values() {
+        if !contacts.during_current_frame {
+            continue;
+        }
+        let body1 = collider_parents
+            .get(contacts.entity1)
+            .map_or(contacts.entity1, |p| p.get());
+        let body2 = collider_parents
+            .get(contacts.entity2)
+            .map_or(contacts.entity2, |p| p.get());
+        connect(body1, body2);
+    }
+
+    // Connect bodies that are attached by joints
+    for [entity1, entity2] in joint_entity_pairs(&joints) {
+        connect(entity1, entity2);
+    }
+
+    // Group the bodies into islands
+    let mut island_ids = HashMap::<usize, IslandId>::new();
+    for (i, (entity, _, rot, lin_vel, ang_vel, mass, inertia, sleeping)) in
+        dynamic_bodies.iter().enumerate()
+    {
+        let next_id = IslandId(island_ids.len() as u32);
+        let id = *island_ids.entry(union_find.find(i)).or_insert(next_id);
+
+        let island = islands.islands.entry(id).or_insert_with(|| PhysicsIsland {
+            sleeping: true,
+            ..default()
+        });
+
+        #[cfg(feature = "2d")]
+        let rotational_energy = 0.5 * inertia.rotated(rot).0 * ang_vel.0 * ang_vel.0;
+        #[cfg(feature = "3d")]
+        let rotational_energy = 0.5 * ang_vel.dot(inertia.rotated(rot).0 * ang_vel.0);
+
+        island.bodies.push(*entity);
+        island.sleeping &= *sleeping;
+        island.kinetic_energy += 0.5 * mass.0 * lin_vel.length_squared() + rotational_energy;
+
+        islands.body_islands.insert(*entity, id);
+    }
+}
+
+/// A disjoint-set data structure used for grouping bodies into islands.
+struct UnionFind {
+    parents: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parents[i] != i {
+            // Path halving
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+        i
+    }
+
+    fn union(&mut self, i: usize, j: usize) {
+        let (root1, root2) = (self.find(i), self.find(j));
+        if root1 != root2 {
+            self.parents[root2] = root1;
+        }
+    }
+}
@@ -10,8 +10,12 @@ use crate::{
 use bevy::{
     ecs::query::{Has, QueryData},
     prelude::*,
+    utils::HashMap,
+};
+use constraints::{
+    multibody::solve_articulation,
+    penetration::{ContactAnchor, PenetrationConstraint},
 };
-use constraints::penetration::PenetrationConstraint;
 
 /// Solves positional and angular [constraints], updates velocities and solves velocity constraints
 /// (dynamic [friction](Friction) and [restitution](Restitution) and [joint damping](joints#damping)).
@@ -32,11 +36,61 @@ use constraints::penetration::PenetrationConstraint;
 /// In the case of collisions, [`PenetrationConstraint`]s are created for each contact pair.
 /// The constraints are resolved by moving the bodies so that they no longer penetrate.
 /// Then, the velocities are updated, and velocity corrections caused by dynamic friction and restitution are applied.
+///
+/// ## Warm starting
+///
+/// Unlike impulse-based solvers, the solver doesn't warm start constraints with the impulses of the previous frame.
+/// The Lagrange multipliers of all constraints are reset at the start of each substep, and the small substeps
+/// take the role that warm starting has in other engines, so there is no warm-start coefficient to configure.
+///
+/// The only contact state that persists between substeps and frames is stored in [`ContactAnchors`],
+/// which are used for static friction against [kinematic](RigidBody::Kinematic) bodies.
 pub struct SolverPlugin;
 
 impl Plugin for SolverPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<PenetrationConstraints>();
+        app.init_resource::<PenetrationConstraints>()
+            .init_resource::<ContactAnchors>()
+            .init_resource::<OrphanedJointPolicy>()
+            .init_resource::<MassRatioStabilization>()
+            .register_type::<MassRatioStabilization>()
+            .init_resource::<MassRatioDiagnostics>()
+            .init_resource::<JointSolveOrder>()
+            .register_type::<JointSolveOrder>()
+            .init_resource::<JointDepths>()
+            .init_resource::<SolverBodies>()
+            .add_event::<JointOrphaned>()
+            .add_event::<JointBroken>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                (
+                    reset_mass_ratio_diagnostics,
+                    handle_orphaned_joints::<FixedJoint>,
+                    handle_orphaned_joints::<RevoluteJoint>,
+                    handle_orphaned_joints::<SphericalJoint>,
+                    handle_orphaned_joints::<PrismaticJoint>,
+                    handle_orphaned_joints::<DistanceJoint>,
+                    handle_orphaned_joints::<SplineDriveJoint>,
+                    handle_orphaned_joints::<SuspensionConstraint>,
+                    handle_orphaned_joints::<GenericJoint>,
+                    handle_orphaned_joints::<GearJoint>,
+                    handle_orphaned_joints::<PulleyJoint>,
+                    update_joint_depths.run_if(resource_equals(JointSolveOrder::Hierarchical)),
+                    update_solver_bodies,
+                )
+                    .chain()
+                    .before(PhysicsStepSet::BroadPhase),
+            );
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                break_joints
+                    .after(PhysicsStepSet::Substeps)
+                    .before(PhysicsStepSet::ReportContacts),
+            );
 
         let substeps = app
             .get_schedule_mut(SubstepSchedule)
@@ -50,11 +104,29 @@ impl Plugin for SolverPlugin {
                 solve_constraint::<SphericalJoint, 2>,
                 solve_constraint::<PrismaticJoint, 2>,
                 solve_constraint::<DistanceJoint, 2>,
+                solve_constraint::<SplineDriveJoint, 2>,
+                solve_constraint::<SuspensionConstraint, 2>,
+                solve_constraint::<GenericJoint, 2>,
+                solve_constraint::<GearJoint, 2>,
+                solve_constraint::<PulleyJoint, 2>,
             )
                 .chain()
                 .in_set(SubstepSet::SolveConstraints),
         );
 
+        substeps.add_systems(
+            solve_multibodies
+                .after(solve_constraint::<PulleyJoint, 2>)
+                .in_set(SubstepSet::SolveConstraints),
+        );
+
+        #[cfg(feature = "2d")]
+        substeps.add_systems(
+            solve_face_targets
+                .after(solve_multibodies)
+                .in_set(SubstepSet::SolveConstraints),
+        );
+
         substeps.add_systems((update_lin_vel, update_ang_vel).in_set(SubstepSet::UpdateVelocities));
 
         substeps.add_systems(
@@ -65,12 +137,37 @@ impl Plugin for SolverPlugin {
                 joint_damping::<SphericalJoint>,
                 joint_damping::<PrismaticJoint>,
                 joint_damping::<DistanceJoint>,
+                joint_damping::<SplineDriveJoint>,
+                joint_damping::<SuspensionConstraint>,
+                joint_damping::<GenericJoint>,
+                joint_damping::<GearJoint>,
+                joint_damping::<PulleyJoint>,
+                revolute_joint_friction,
+                prismatic_joint_friction,
+                revolute_joint_limit_restitution,
+                prismatic_joint_limit_restitution,
             )
                 .chain()
                 .in_set(SubstepSet::SolveVelocities),
         );
 
-        substeps.add_systems(store_contact_impulses.in_set(SubstepSet::StoreImpulses));
+        substeps.add_systems(
+            (
+                store_contact_impulses,
+                accumulate_joint_impulses::<FixedJoint>,
+                accumulate_joint_impulses::<RevoluteJoint>,
+                accumulate_joint_impulses::<SphericalJoint>,
+                accumulate_joint_impulses::<PrismaticJoint>,
+                accumulate_joint_impulses::<DistanceJoint>,
+                accumulate_joint_impulses::<SplineDriveJoint>,
+                accumulate_joint_impulses::<SuspensionConstraint>,
+                accumulate_joint_impulses::<GenericJoint>,
+                accumulate_joint_impulses::<GearJoint>,
+                accumulate_joint_impulses::<PulleyJoint>,
+            )
+                .chain()
+                .in_set(SubstepSet::StoreImpulses),
+        );
 
         substeps.add_systems(apply_translation.in_set(SubstepSet::ApplyTranslation));
     }
@@ -81,6 +178,226 @@ impl Plugin for SolverPlugin {
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct PenetrationConstraints(pub Vec<PenetrationConstraint>);
 
+/// Stores the [`ContactAnchor`]s of contacts between dynamic and [kinematic](RigidBody::Kinematic) bodies
+/// across substeps and frames.
+///
+/// The anchors are keyed by the collider entities, the sub-shapes of the contact manifold and the
+/// [features](FeatureId) that the contact points are on. Unlike the indices of manifolds and contacts,
+/// these stay the same when other contact points of the pair appear or disappear, so an anchor stays attached
+/// to the same physical contact point. Anchors of contacts that didn't exist during the latest substep are removed.
+#[derive(Resource, Debug, Default)]
+pub struct ContactAnchors(pub HashMap<ContactAnchorKey, ContactAnchor>);
+
+/// The key of a [`ContactAnchor`] in [`ContactAnchors`]: the collider entities, the sub-shape indices
+/// of the contact manifold and the features of the colliders that the contact point is on.
+pub type ContactAnchorKey = (
+    Entity,
+    Entity,
+    Option<u32>,
+    Option<u32>,
+    FeatureId,
+    FeatureId,
+);
+
+/// A read-only mapping between the entities of the rigid bodies simulated during the current physics step
+/// and dense indices in the range `0..len`.
+///
+/// The bodies are ordered by entity, which is also the order that the solver uses for contacts and joints.
+/// External crates like custom solvers or netcode can use the indices to store per-body data in parallel arrays
+/// that are aligned with this order, and look up the index of an entity without hashing.
+///
+/// The mapping is updated at the start of each physics step, before [`PhysicsStepSet::BroadPhase`].
+/// Disabled bodies are not included.
+#[derive(Resource, Debug, Default)]
+pub struct SolverBodies {
+    entities: Vec<Entity>,
+    /// Maps entity indices to body indices.
+    indices: Vec<Option<u32>>,
+}
+
+impl SolverBodies {
+    /// Returns the entities of the bodies in solver order.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Returns the number of bodies.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns true if there are no bodies.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Returns the index of the given body, or `None` if it is not simulated during the current step.
+    pub fn index(&self, entity: Entity) -> Option<usize> {
+        let index = (*self.indices.get(entity.index() as usize)?)? as usize;
+        // The entity index could belong to a different generation of the entity.
+        (self.entities[index] == entity).then_some(index)
+    }
+
+    /// Returns the entity of the body at the given index.
+    pub fn entity(&self, index: usize) -> Option<Entity> {
+        self.entities.get(index).copied()
+    }
+}
+
+/// Updates the [`SolverBodies`] mapping for the current physics step.
+fn update_solver_bodies(
+    bodies: Query<Entity, (With<RigidBody>, Without<RigidBodyDisabled>)>,
+    mut solver_bodies: ResMut<SolverBodies>,
+) {
+    let SolverBodies { entities, indices } = &mut *solver_bodies;
+
+    entities.clear();
+    entities.extend(bodies.iter());
+    entities.sort_unstable();
+
+    indices.clear();
+    for (index, entity) in entities.iter().enumerate() {
+        let entity_index = entity.index() as usize;
+        if entity_index >= indices.len() {
+            indices.resize(entity_index + 1, None);
+        }
+        indices[entity_index] = Some(index as u32);
+    }
+}
+
+/// Configures the automatic stabilization of constraints between bodies with extreme mass ratios.
+///
+/// Joints and contacts between dynamic bodies whose masses differ by more than the [`threshold`](Self::threshold)
+/// converge very slowly, which is a common cause of joints stretching or exploding. When a heavy body hangs
+/// from a light one, almost all of each correction moves the light body, so the heavy body lags behind
+/// and the joints stretch.
+///
+/// Such constraints are solved with conditioned masses: the lighter bodies are treated as heavier
+/// while solving the constraint so that the mass ratio is at most the threshold, and the heavier bodies
+/// take a larger share of the correction. This only affects how corrections are distributed within
+/// the constraint, and the real mass properties of the bodies are left unchanged.
+/// The number of stabilized constraints is reported in [`MassRatioDiagnostics`].
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Resource)]
+pub struct MassRatioStabilization {
+    /// The mass ratio above which constraints are stabilized. Defaults to 1000.
+    pub threshold: Scalar,
+    /// Whether the masses of constraints above the threshold are conditioned. Defaults to `true`.
+    ///
+    /// If `false`, the stabilization is disabled, but the diagnostics are still collected.
+    pub condition_masses: bool,
+}
+
+impl Default for MassRatioStabilization {
+    fn default() -> Self {
+        Self {
+            threshold: 1000.0,
+            condition_masses: true,
+        }
+    }
+}
+
+impl MassRatioStabilization {
+    /// Conditions the inverse masses and inverse inertias of the given bodies for solving a constraint
+    /// with the given mass ratio, returning the original values that are restored with [`Self::restore`].
+    fn condition<'a, 'w: 'a>(
+        &self,
+        bodies: impl IntoIterator<Item = &'a mut RigidBodyQueryItem<'w>>,
+        mass_ratio: Scalar,
+    ) -> Vec<(Entity, InverseMass, InverseInertia)> {
+        if !self.condition_masses || mass_ratio <= self.threshold {
+            return vec![];
+        }
+
+        let mut bodies = bodies
+            .into_iter()
+            .filter(|body| body.rb.is_dynamic() && body.inverse_mass.0 > 0.0)
+            .collect::<Vec<_>>();
+        let Some(min_inverse_mass) = bodies
+            .iter()
+            .map(|body| body.inverse_mass.0)
+            .reduce(Scalar::min)
+        else {
+            return vec![];
+        };
+        let max_inverse_mass = min_inverse_mass * self.threshold;
+
+        let mut original = vec![];
+        for body in bodies.iter_mut() {
+            if body.inverse_mass.0 > max_inverse_mass {
+                original.push((body.entity, *body.inverse_mass, *body.inverse_inertia));
+                let scale = max_inverse_mass / body.inverse_mass.0;
+                // The mass properties are only changed temporarily, so change detection is bypassed.
+                body.inverse_mass.bypass_change_detection().0 = max_inverse_mass;
+                body.inverse_inertia.bypass_change_detection().0 *= scale;
+            }
+        }
+        original
+    }
+
+    /// Restores the mass properties of bodies conditioned with [`Self::condition`].
+    fn restore<'a, 'w: 'a>(
+        bodies: impl IntoIterator<Item = &'a mut RigidBodyQueryItem<'w>>,
+        original: &[(Entity, InverseMass, InverseInertia)],
+    ) {
+        if original.is_empty() {
+            return;
+        }
+        for body in bodies {
+            if let Some((_, inverse_mass, inverse_inertia)) =
+                original.iter().find(|(entity, ..)| *entity == body.entity)
+            {
+                *body.inverse_mass.bypass_change_detection() = *inverse_mass;
+                *body.inverse_inertia.bypass_change_detection() = *inverse_inertia;
+            }
+        }
+    }
+}
+
+/// Diagnostics about constraints between bodies with extreme mass ratios during the latest physics step.
+/// See [`MassRatioStabilization`].
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct MassRatioDiagnostics {
+    /// The number of joint solves that exceeded the mass ratio threshold, summed over all substeps.
+    pub stabilized_joints: u32,
+    /// The number of contact solves that exceeded the mass ratio threshold, summed over all substeps.
+    pub stabilized_contacts: u32,
+    /// The largest mass ratio between bodies in a single joint or contact.
+    pub max_mass_ratio: Scalar,
+}
+
+impl MassRatioDiagnostics {
+    fn record(&mut self, mass_ratio: Scalar, threshold: Scalar, is_joint: bool) {
+        self.max_mass_ratio = self.max_mass_ratio.max(mass_ratio);
+        if mass_ratio > threshold {
+            if is_joint {
+                self.stabilized_joints += 1;
+            } else {
+                self.stabilized_contacts += 1;
+            }
+        }
+    }
+}
+
+fn reset_mass_ratio_diagnostics(mut diagnostics: ResMut<MassRatioDiagnostics>) {
+    *diagnostics = MassRatioDiagnostics::default();
+}
+
+/// Computes the ratio between the largest and smallest mass of the dynamic bodies with a finite mass.
+fn mass_ratio<'a, 'w: 'a>(bodies: impl IntoIterator<Item = &'a RigidBodyQueryItem<'w>>) -> Scalar {
+    let (min, max) = bodies
+        .into_iter()
+        .filter(|body| body.rb.is_dynamic() && body.inverse_mass.0 > 0.0)
+        .fold((Scalar::MAX, 0.0 as Scalar), |(min, max), body| {
+            (min.min(body.inverse_mass.0), max.max(body.inverse_mass.0))
+        });
+    if max > 0.0 {
+        max / min
+    } else {
+        1.0
+    }
+}
+
 /// A `WorldQuery` to make code handling colliders in collisions cleaner.
 #[derive(QueryData)]
 struct ColliderQuery<'w> {
@@ -95,28 +412,57 @@ struct ColliderQuery<'w> {
 /// Iterates through broad phase collision pairs, checks which ones are actually colliding, and uses [`PenetrationConstraint`]s to resolve the collisions.
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
+// `u32::is_multiple_of` requires Rust 1.87, which is newer than the supported toolchain.
+#[allow(clippy::manual_is_multiple_of)]
 fn penetration_constraints(
     mut commands: Commands,
-    mut bodies: Query<(
-        RigidBodyQuery,
-        Option<&Name>,
-        Option<&Sensor>,
-        Option<&Sleeping>,
-    )>,
+    mut bodies: Query<
+        (
+            RigidBodyQuery,
+            Option<&Name>,
+            Option<&Sensor>,
+            Option<&Sleeping>,
+            Option<&PhysicsActivity>,
+            Option<&KinematicCollideMode>,
+        ),
+        Without<RigidBodyDisabled>,
+    >,
     colliders: Query<ColliderQuery>,
     mut penetration_constraints: ResMut<PenetrationConstraints>,
+    mut contact_anchors: ResMut<ContactAnchors>,
     mut collisions: ResMut<Collisions>,
+    stabilization: Res<MassRatioStabilization>,
+    mut diagnostics: ResMut<MassRatioDiagnostics>,
     time: Res<Time>,
+    mut substep: Local<u32>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
 
+    *substep = substep.wrapping_add(1);
+
     penetration_constraints.0.clear();
 
-    for ((collider_entity1, collider_entity2), contacts) in collisions
-        .get_internal_mut()
-        .iter_mut()
-        .filter(|(_, contacts)| contacts.during_current_substep)
-    {
+    let previous_anchors = std::mem::take(&mut contact_anchors.0);
+
+    // Solve the contacts in the order of the entities of the collider pairs instead of the order
+    // in which the pairs were found. The latter depends on query iteration order, which changes
+    // when entities move between archetypes, and would make results differ between runs.
+    let mut pair_order = collisions
+        .get_internal()
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, contacts))| contacts.during_current_substep)
+        .map(|(index, (pair, _))| (*pair, index))
+        .collect::<Vec<_>>();
+    pair_order.sort_unstable();
+
+    for (_, index) in pair_order {
+        let Some(((collider_entity1, collider_entity2), contacts)) =
+            collisions.get_internal_mut().get_index_mut(index)
+        else {
+            continue;
+        };
+
         // Don't collide with self
         if collider_entity1 == collider_entity2 {
             continue;
@@ -136,8 +482,8 @@ fn penetration_constraints(
         contacts.during_current_substep = false;
 
         if let Ok([bundle1, bundle2]) = bodies.get_many_mut([collider_parent1, collider_parent2]) {
-            let (mut body1, name1, sensor1, sleeping1) = bundle1;
-            let (mut body2, name2, sensor2, sleeping2) = bundle2;
+            let (mut body1, name1, sensor1, sleeping1, activity1, collide_mode1) = bundle1;
+            let (mut body2, name2, sensor2, sleeping2, activity2, collide_mode2) = bundle2;
 
             let inactive1 = body1.rb.is_static() || sleeping1.is_some();
             let inactive2 = body2.rb.is_static() || sleeping2.is_some();
@@ -156,6 +502,48 @@ fn penetration_constraints(
                 continue;
             }
 
+            // Kinematic bodies that stop at contacts are pushed out of static geometry.
+            let stops1 = body1.rb.is_kinematic()
+                && body2.rb.is_static()
+                && collide_mode1 == Some(&KinematicCollideMode::StopAtContacts);
+            let stops2 = body2.rb.is_kinematic()
+                && body1.rb.is_static()
+                && collide_mode2 == Some(&KinematicCollideMode::StopAtContacts);
+            if stops1 || stops2 {
+                for contact in contacts.manifolds.iter().flat_map(|m| m.contacts.iter()) {
+                    let point1 = collider1.transform.map_or(contact.point1, |t| {
+                        t.rotation.rotate(contact.point1) + t.translation
+                    });
+                    let point2 = collider2.transform.map_or(contact.point2, |t| {
+                        t.rotation.rotate(contact.point2) + t.translation
+                    });
+                    let normal = body1.rotation.rotate(
+                        collider1
+                            .transform
+                            .map_or(contact.normal1, |t| t.rotation.rotate(contact.normal1)),
+                    );
+
+                    let p1 = body1.current_position() + body1.rotation.rotate(point1);
+                    let p2 = body2.current_position() + body2.rotation.rotate(point2);
+                    let penetration = (p1 - p2).dot(normal);
+
+                    if penetration <= Scalar::EPSILON {
+                        continue;
+                    }
+
+                    // Move the kinematic body out of the static body along the contact normal
+                    if stops1 {
+                        body1.accumulated_translation.0 -= penetration * normal;
+                    } else {
+                        body2.accumulated_translation.0 += penetration * normal;
+                    }
+
+                    contacts.during_current_frame = true;
+                    contacts.during_current_substep = true;
+                }
+                continue;
+            }
+
             // When an active body collides with a sleeping body, wake up the sleeping body.
             if sleeping1.is_some() {
                 commands.entity(body1.entity).remove::<Sleeping>();
@@ -164,15 +552,38 @@ fn penetration_constraints(
             }
 
             // Get combined friction and restitution coefficients of the colliders
-            // or the bodies they are attached to.
-            let friction = collider1
+            // or the bodies they are attached to, scaled by the contact modification of the pair.
+            let mut friction = collider1
                 .friction
                 .unwrap_or(body1.friction)
                 .combine(*collider2.friction.unwrap_or(body2.friction));
-            let restitution = collider1
+            friction.dynamic_coefficient *= contacts.friction_scale;
+            friction.static_coefficient *= contacts.friction_scale;
+            let mut restitution = collider1
                 .restitution
                 .unwrap_or(body1.restitution)
                 .combine(*collider2.restitution.unwrap_or(body2.restitution));
+            restitution.coefficient *= contacts.restitution_scale;
+
+            // Contacts against moving kinematic bodies are anchored so that bodies resting on them don't drift.
+            let anchored = (body1.rb.is_kinematic() && body2.rb.is_dynamic())
+                || (body1.rb.is_dynamic() && body2.rb.is_kinematic());
+
+            // Contacts of far-away bodies with a reduced `PhysicsActivity` are only fully solved
+            // on some of the substeps. On the other substeps, only the overlap is solved, so the bodies
+            // don't sink into each other, and the velocity solve still applies restitution and dynamic friction.
+            // The pair uses the highest activity of its dynamic bodies.
+            let substep_interval = [(&body1, activity1), (&body2, activity2)]
+                .into_iter()
+                .filter(|(body, _)| body.rb.is_dynamic())
+                .map(|(_, activity)| activity.map_or(1, |activity| activity.substep_interval))
+                .min()
+                .unwrap_or(1)
+                .max(1);
+            let full_solve = anchored || *substep % substep_interval == 0;
+
+            // Contacts between bodies with extreme mass ratios are solved with conditioned masses.
+            let ratio = mass_ratio([&body1, &body2]);
 
             // Create and solve penetration constraints for each contact.
             for (manifold_index, manifold) in contacts.manifolds.iter().enumerate() {
@@ -194,9 +605,19 @@ fn penetration_constraints(
                         ..*contact
                     };
 
+                    let anchor_key = (
+                        *collider_entity1,
+                        *collider_entity2,
+                        manifold.subshape1,
+                        manifold.subshape2,
+                        contact.feature1,
+                        contact.feature2,
+                    );
+
                     let mut constraint = PenetrationConstraint {
                         friction,
                         restitution,
+                        anchor: previous_anchors.get(&anchor_key).copied(),
                         ..PenetrationConstraint::new(
                             &body1,
                             &body2,
@@ -206,7 +627,26 @@ fn penetration_constraints(
                             manifold_index,
                         )
                     };
-                    constraint.solve([&mut body1, &mut body2], delta_secs);
+                    let original = stabilization.condition([&mut body1, &mut body2], ratio);
+                    if full_solve {
+                        constraint.solve([&mut body1, &mut body2], delta_secs);
+                    } else {
+                        constraint.solve_without_friction([&mut body1, &mut body2], delta_secs);
+                    }
+                    MassRatioStabilization::restore([&mut body1, &mut body2], &original);
+                    diagnostics.record(ratio, stabilization.threshold, false);
+
+                    // Keep the existing anchor, or anchor the contact at its current contact points.
+                    if anchored {
+                        contact_anchors.0.insert(
+                            anchor_key,
+                            constraint.anchor.unwrap_or(ContactAnchor {
+                                point1: contact.point1,
+                                point2: contact.point2,
+                            }),
+                        );
+                    }
+
                     penetration_constraints.0.push(constraint);
 
                     // Set collision as penetrating for this frame and substep.
@@ -269,8 +709,15 @@ fn penetration_constraints(
 /// ```
 pub fn solve_constraint<C: XpbdConstraint<ENTITY_COUNT> + Component, const ENTITY_COUNT: usize>(
     mut commands: Commands,
-    mut bodies: Query<(RigidBodyQuery, Option<&Sleeping>)>,
-    mut constraints: Query<&mut C, Without<RigidBody>>,
+    mut bodies: Query<(RigidBodyQuery, Option<&Sleeping>), Without<RigidBodyDisabled>>,
+    mut constraints: Query<
+        (Entity, &mut C, Option<&JointIterations>),
+        (Without<RigidBody>, Without<JointDisabled>),
+    >,
+    stabilization: Res<MassRatioStabilization>,
+    mut diagnostics: ResMut<MassRatioDiagnostics>,
+    solve_order: Res<JointSolveOrder>,
+    joint_depths: Res<JointDepths>,
     time: Res<Time>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
@@ -278,9 +725,29 @@ pub fn solve_constraint<C: XpbdConstraint<ENTITY_COUNT> + Component, const ENTIT
     // Clear Lagrange multipliers
     constraints
         .iter_mut()
-        .for_each(|mut c| c.clear_lagrange_multipliers());
+        .for_each(|(_, mut c, _)| c.clear_lagrange_multipliers());
+
+    // Sort the joints by entity so that the solve order doesn't depend on query iteration order.
+    let mut order = constraints
+        .iter()
+        .map(|(entity, ..)| entity)
+        .collect::<Vec<_>>();
+    order.sort_unstable();
+
+    // Solve constraints closer to the roots of their joint trees first.
+    if *solve_order == JointSolveOrder::Hierarchical {
+        order.sort_by_cached_key(|entity| {
+            constraints
+                .get(*entity)
+                .map_or(u32::MAX, |(_, c, _)| joint_depths.depth(c.entities()))
+        });
+    }
+
+    for entity in order {
+        let Ok((_, mut constraint, joint_iterations)) = constraints.get_mut(entity) else {
+            continue;
+        };
 
-    for mut constraint in &mut constraints {
         // Get components for entities
         if let Ok(mut bodies) = bodies.get_many_mut(constraint.entities()) {
             let none_dynamic = bodies.iter().all(|(body, _)| !body.rb.is_dynamic());
@@ -301,19 +768,208 @@ pub fn solve_constraint<C: XpbdConstraint<ENTITY_COUNT> + Component, const ENTIT
                 }
             }
 
-            // Get the bodies as an array and solve the constraint
-            if let Ok(bodies) = bodies
-                .iter_mut()
-                .map(|(ref mut body, _)| body)
-                .collect::<Vec<&mut RigidBodyQueryItem>>()
-                .try_into()
-            {
-                constraint.solve(bodies, delta_secs);
+            // Constraints between bodies with extreme mass ratios are solved with conditioned masses.
+            let ratio = mass_ratio(bodies.iter().map(|(body, _)| body));
+            diagnostics.record(ratio, stabilization.threshold, true);
+            let original = stabilization.condition(bodies.iter_mut().map(|(body, _)| body), ratio);
+
+            let iterations = joint_iterations.map_or(1, |iterations| iterations.0.max(1));
+            for _ in 0..iterations {
+                // Get the bodies as an array and solve the constraint
+                if let Ok(bodies) = bodies
+                    .iter_mut()
+                    .map(|(ref mut body, _)| body)
+                    .collect::<Vec<&mut RigidBodyQueryItem>>()
+                    .try_into()
+                {
+                    constraint.solve(bodies, delta_secs);
+                }
             }
+
+            MassRatioStabilization::restore(bodies.iter_mut().map(|(body, _)| body), &original);
         }
     }
 }
 
+/// Determines the order in which constraints of the same type are solved by [`solve_constraint`].
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum JointSolveOrder {
+    /// Constraints are solved in the order of their entities, which doesn't depend on
+    /// the iteration order of the ECS query.
+    #[default]
+    EntityOrder,
+    /// Constraints are solved from the roots of joint trees to their leaves.
+    ///
+    /// Roots are static and kinematic bodies, or the heaviest body in a tree that only contains
+    /// dynamic bodies. Solving the joints closest to the roots first makes chains like ragdolls
+    /// and ropes converge faster and stretch less, without changing the solver itself.
+    Hierarchical,
+}
+
+/// The distance of each jointed body from the root of its joint tree, in joints.
+/// Used for [`JointSolveOrder::Hierarchical`].
+#[derive(Resource, Debug, Default)]
+pub struct JointDepths(HashMap<Entity, u32>);
+
+impl JointDepths {
+    /// Returns the smallest depth of the given bodies.
+    fn depth<const N: usize>(&self, entities: [Entity; N]) -> u32 {
+        entities
+            .iter()
+            .filter_map(|entity| self.0.get(entity).copied())
+            .min()
+            .unwrap_or(u32::MAX)
+    }
+}
+
+/// Computes the [`JointDepths`] of bodies by traversing the joint graph breadth-first from its roots.
+#[allow(clippy::type_complexity)]
+fn update_joint_depths(
+    joints: (
+        Query<&FixedJoint, Without<JointDisabled>>,
+        Query<&RevoluteJoint, Without<JointDisabled>>,
+        Query<&SphericalJoint, Without<JointDisabled>>,
+        Query<&PrismaticJoint, Without<JointDisabled>>,
+        Query<&DistanceJoint, Without<JointDisabled>>,
+        Query<&SplineDriveJoint, Without<JointDisabled>>,
+        Query<&SuspensionConstraint, Without<JointDisabled>>,
+        Query<&GenericJoint, Without<JointDisabled>>,
+        Query<&GearJoint, Without<JointDisabled>>,
+        Query<&PulleyJoint, Without<JointDisabled>>,
+    ),
+    bodies: Query<(&RigidBody, Option<&Mass>)>,
+    mut depths: ResMut<JointDepths>,
+) {
+    let (
+        fixed,
+        revolute,
+        spherical,
+        prismatic,
+        distance,
+        spline_drive,
+        suspension,
+        generic,
+        gear,
+        pulley,
+    ) = &joints;
+    let edges = fixed
+        .iter()
+        .map(|joint| joint.entities())
+        .chain(revolute.iter().map(|joint| joint.entities()))
+        .chain(spherical.iter().map(|joint| joint.entities()))
+        .chain(prismatic.iter().map(|joint| joint.entities()))
+        .chain(distance.iter().map(|joint| joint.entities()))
+        .chain(spline_drive.iter().map(|joint| joint.entities()))
+        .chain(suspension.iter().map(|joint| joint.entities()))
+        .chain(generic.iter().map(|joint| joint.entities()))
+        .chain(gear.iter().map(|joint| joint.entities()))
+        .chain(pulley.iter().map(|joint| joint.entities()));
+
+    let mut neighbors = HashMap::<Entity, Vec<Entity>>::default();
+    for [entity1, entity2] in edges {
+        neighbors.entry(entity1).or_default().push(entity2);
+        neighbors.entry(entity2).or_default().push(entity1);
+    }
+
+    depths.0.clear();
+
+    let is_dynamic = |entity: &Entity| bodies.get(*entity).is_ok_and(|(rb, _)| rb.is_dynamic());
+    let mass = |entity: &Entity| {
+        bodies
+            .get(*entity)
+            .ok()
+            .and_then(|(_, mass)| mass)
+            .map_or(0.0, |mass| mass.0)
+    };
+
+    // Static and kinematic bodies are the roots of their trees.
+    let roots = neighbors
+        .keys()
+        .filter(|entity| !is_dynamic(entity))
+        .copied()
+        .collect::<Vec<_>>();
+    traverse_joint_tree(&neighbors, &mut depths.0, roots);
+
+    // Trees that only contain dynamic bodies use their heaviest body as the root.
+    let mut entities = neighbors.keys().copied().collect::<Vec<_>>();
+    entities.sort();
+    for entity in entities {
+        if depths.0.contains_key(&entity) {
+            continue;
+        }
+        let mut component = HashMap::default();
+        traverse_joint_tree(&neighbors, &mut component, vec![entity]);
+        let root = component
+            .keys()
+            .copied()
+            .max_by(|a, b| mass(a).total_cmp(&mass(b)).then(b.cmp(a)))
+            .unwrap_or(entity);
+        traverse_joint_tree(&neighbors, &mut depths.0, vec![root]);
+    }
+}
+
+/// Assigns depths to the bodies reachable from the given roots using a breadth-first traversal.
+fn traverse_joint_tree(
+    neighbors: &HashMap<Entity, Vec<Entity>>,
+    depths: &mut HashMap<Entity, u32>,
+    roots: Vec<Entity>,
+) {
+    let mut queue = std::collections::VecDeque::with_capacity(roots.len());
+    for root in roots {
+        if depths.insert(root, 0).is_none() {
+            queue.push_back(root);
+        }
+    }
+    while let Some(entity) = queue.pop_front() {
+        let depth = depths[&entity];
+        for neighbor in neighbors.get(&entity).into_iter().flatten() {
+            if !depths.contains_key(neighbor) {
+                depths.insert(*neighbor, depth + 1);
+                queue.push_back(*neighbor);
+            }
+        }
+    }
+}
+
+/// Despawns or disables joints whose attached bodies have been despawned or are no longer rigid bodies,
+/// and sends a [`JointOrphaned`] event for each of them. See [`OrphanedJointPolicy`].
+///
+/// Joints of custom types can be handled by adding this system for them before [`PhysicsStepSet::BroadPhase`].
+pub fn handle_orphaned_joints<T: Joint>(
+    mut commands: Commands,
+    joints: Query<(Entity, &T), Without<JointDisabled>>,
+    bodies: Query<(), With<RigidBody>>,
+    policy: Res<OrphanedJointPolicy>,
+    mut orphaned_events: EventWriter<JointOrphaned>,
+) {
+    for (joint_entity, joint) in &joints {
+        let Some(body) = joint
+            .entities()
+            .into_iter()
+            .find(|entity| !bodies.contains(*entity))
+        else {
+            continue;
+        };
+
+        match *policy {
+            OrphanedJointPolicy::Despawn => {
+                if let Some(mut entity_commands) = commands.get_entity(joint_entity) {
+                    entity_commands.despawn();
+                }
+            }
+            OrphanedJointPolicy::Disable => {
+                commands.entity(joint_entity).insert(JointDisabled);
+            }
+        }
+
+        orphaned_events.send(JointOrphaned {
+            joint: joint_entity,
+            body,
+        });
+    }
+}
+
 /// Updates the linear velocity of all dynamic bodies based on the change in position from the previous step.
 #[allow(clippy::type_complexity)]
 fn update_lin_vel(
@@ -326,7 +982,7 @@ fn update_lin_vel(
             &mut LinearVelocity,
             &mut PreSolveLinearVelocity,
         ),
-        Without<Sleeping>,
+        (Without<Sleeping>, Without<RigidBodyDisabled>),
     >,
     time: Res<Time>,
 ) {
@@ -361,14 +1017,16 @@ fn update_ang_vel(
             &PreviousRotation,
             &mut AngularVelocity,
             &mut PreSolveAngularVelocity,
+            Option<&LockedAxes>,
         ),
-        Without<Sleeping>,
+        (Without<Sleeping>, Without<RigidBodyDisabled>),
     >,
+    rotation_integration: Res<RotationIntegration>,
     time: Res<Time>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
 
-    for (rb, rot, prev_rot, mut ang_vel, mut pre_solve_ang_vel) in &mut bodies {
+    for (rb, rot, prev_rot, mut ang_vel, mut pre_solve_ang_vel, locked_axes) in &mut bodies {
         // Static bodies have no velocity
         if rb.is_static() && ang_vel.0 != 0.0 {
             ang_vel.0 = 0.0;
@@ -377,7 +1035,17 @@ fn update_ang_vel(
         pre_solve_ang_vel.0 = ang_vel.0;
 
         if rb.is_dynamic() {
-            let new_ang_vel = (rot.mul(prev_rot.inverse())).as_radians() / delta_secs;
+            let mut new_ang_vel = (rot.mul(prev_rot.inverse())).as_radians() / delta_secs;
+
+            // Keep the velocity that was clamped away when the rotation was integrated
+            let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
+            let effective_ang_vel = locked_axes.apply_to_angular_velocity(ang_vel.0);
+            let angle = effective_ang_vel.abs() * delta_secs;
+            if angle > rotation_integration.max_delta_rotation {
+                new_ang_vel +=
+                    effective_ang_vel * (1.0 - rotation_integration.max_delta_rotation / angle);
+            }
+
             // avoid triggering bevy's change detection unnecessarily
             if new_ang_vel != ang_vel.0 && new_ang_vel.is_finite() {
                 ang_vel.0 = new_ang_vel;
@@ -396,14 +1064,16 @@ fn update_ang_vel(
             &PreviousRotation,
             &mut AngularVelocity,
             &mut PreSolveAngularVelocity,
+            Option<&LockedAxes>,
         ),
-        Without<Sleeping>,
+        (Without<Sleeping>, Without<RigidBodyDisabled>),
     >,
+    rotation_integration: Res<RotationIntegration>,
     time: Res<Time>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
 
-    for (rb, rot, prev_rot, mut ang_vel, mut pre_solve_ang_vel) in &mut bodies {
+    for (rb, rot, prev_rot, mut ang_vel, mut pre_solve_ang_vel, locked_axes) in &mut bodies {
         // Static bodies have no velocity
         if rb.is_static() && ang_vel.0 != Vector::ZERO {
             ang_vel.0 = Vector::ZERO;
@@ -412,11 +1082,26 @@ fn update_ang_vel(
         pre_solve_ang_vel.0 = ang_vel.0;
 
         if rb.is_dynamic() {
-            let delta_rot = rot.mul_quat(prev_rot.inverse().0);
-            let mut new_ang_vel = 2.0 * delta_rot.xyz() / delta_secs;
+            let mut delta_rot = rot.mul_quat(prev_rot.inverse().0);
             if delta_rot.w < 0.0 {
-                new_ang_vel = -new_ang_vel;
+                delta_rot = -delta_rot;
             }
+            let mut new_ang_vel = if rotation_integration.exponential_map {
+                // Logarithmic map, the inverse of the exponential map used for integration
+                delta_rot.to_scaled_axis() / delta_secs
+            } else {
+                2.0 * delta_rot.xyz() / delta_secs
+            };
+
+            // Keep the velocity that was clamped away when the rotation was integrated
+            let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
+            let effective_ang_vel = locked_axes.apply_to_angular_velocity(ang_vel.0);
+            let angle = effective_ang_vel.length() * delta_secs;
+            if angle > rotation_integration.max_delta_rotation {
+                new_ang_vel +=
+                    effective_ang_vel * (1.0 - rotation_integration.max_delta_rotation / angle);
+            }
+
             // avoid triggering bevy's change detection unnecessarily
             if new_ang_vel != ang_vel.0 && new_ang_vel.is_finite() {
                 ang_vel.0 = new_ang_vel;
@@ -551,14 +1236,18 @@ pub fn joint_damping<T: Joint>(
             &InverseMass,
             Option<&Dominance>,
         ),
-        Without<Sleeping>,
+        (Without<Sleeping>, Without<RigidBodyDisabled>),
     >,
-    joints: Query<&T, Without<RigidBody>>,
+    joints: Query<(Entity, &T), (Without<RigidBody>, Without<JointDisabled>)>,
     time: Res<Time>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
 
-    for joint in &joints {
+    // Apply the damping in the order of the joint entities so that the result doesn't depend on query iteration order.
+    let mut joints = joints.iter().collect::<Vec<_>>();
+    joints.sort_unstable_by_key(|(entity, _)| *entity);
+
+    for (_, joint) in joints {
         if let Ok(
             [(rb1, mut lin_vel1, mut ang_vel1, inv_mass1, dominance1), (rb2, mut lin_vel2, mut ang_vel2, inv_mass2, dominance2)],
         ) = bodies.get_many_mut(joint.entities())
@@ -598,6 +1287,462 @@ pub fn joint_damping<T: Joint>(
     }
 }
 
+/// Solves the [`MultibodyLink`]s of each articulation as one unit, starting from its [`MultibodyRoot`].
+///
+/// Sleeping bodies in an articulation are woken up if any of its other dynamic bodies are awake.
+fn solve_multibodies(
+    mut commands: Commands,
+    mut bodies: Query<RigidBodyQuery, Without<RigidBodyDisabled>>,
+    sleeping: Query<Has<Sleeping>>,
+    roots: Query<&MultibodyRoot>,
+    mut links: Query<(Entity, &mut MultibodyLink)>,
+    time: Res<Time>,
+) {
+    if links.is_empty() {
+        return;
+    }
+
+    let delta_secs = time.delta_seconds_adjusted();
+    let parents = links
+        .iter()
+        .map(|(entity, link)| (entity, link.parent))
+        .collect::<HashMap<_, _>>();
+
+    // Group the links by the root of their tree, and compute the depth of each link.
+    // Links that don't lead to a root, for example because of a cycle, are ignored.
+    let mut articulations = HashMap::<Entity, Vec<(u32, Entity)>>::default();
+    for (entity, _) in &links {
+        let mut depth = 1;
+        let mut parent = parents[&entity];
+        while let Some(next) = parents.get(&parent) {
+            if depth as usize > parents.len() {
+                break;
+            }
+            depth += 1;
+            parent = *next;
+        }
+        if roots.contains(parent) {
+            articulations
+                .entry(parent)
+                .or_default()
+                .push((depth, entity));
+        }
+    }
+
+    let mut articulations = articulations.into_iter().collect::<Vec<_>>();
+    articulations.sort_unstable_by_key(|(root, _)| *root);
+
+    for (root, mut tree) in articulations {
+        // Eliminate the links from the leaves to the root
+        tree.sort_unstable_by_key(|&(depth, entity)| (std::cmp::Reverse(depth), entity));
+
+        let mut tree_links = tree
+            .iter()
+            .filter_map(|&(_, entity)| links.get(entity).ok().map(|(_, link)| (entity, *link)))
+            .collect::<Vec<_>>();
+
+        // No solving if none of the dynamic bodies is awake
+        let entities = std::iter::once(root).chain(tree_links.iter().map(|(entity, _)| *entity));
+        let is_active = |entity: Entity| {
+            bodies
+                .get(entity)
+                .is_ok_and(|body| body.rb.is_dynamic() && !sleeping.get(entity).unwrap_or(false))
+        };
+        if !entities.clone().any(is_active) {
+            continue;
+        }
+        for entity in entities {
+            if sleeping.get(entity).unwrap_or(false) {
+                commands.entity(entity).remove::<Sleeping>();
+            }
+        }
+
+        for (_, link) in tree_links.iter_mut() {
+            link.lagrange = Vector::ZERO;
+        }
+
+        let iterations = roots.get(root).map_or(1, |root| root.iterations.max(1));
+        for _ in 0..iterations {
+            solve_articulation(&mut tree_links, &mut bodies, delta_secs);
+        }
+
+        for (entity, link) in tree_links {
+            if let Ok((_, mut stored)) = links.get_mut(entity) {
+                *stored = link;
+            }
+        }
+    }
+}
+
+/// Rotates bodies with a [`FaceTarget`] towards their targets.
+///
+/// Sleeping bodies are woken up when their [`FaceTarget`] is changed.
+#[cfg(feature = "2d")]
+fn solve_face_targets(
+    mut commands: Commands,
+    mut bodies: Query<(RigidBodyQuery, &mut FaceTarget, Has<Sleeping>), Without<RigidBodyDisabled>>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (mut body, mut face_target, is_sleeping) in &mut bodies {
+        if is_sleeping {
+            if face_target.is_changed() {
+                commands.entity(body.entity).remove::<Sleeping>();
+            }
+            continue;
+        }
+        face_target.solve(&mut body, delta_secs);
+    }
+}
+
+/// Applies velocity corrections caused by the friction of [revolute joints](RevoluteJoint),
+/// resisting relative rotation around the joint axis with a limited torque.
+fn revolute_joint_friction(
+    mut bodies: Query<RigidBodyQuery, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    joints: Query<(Entity, &RevoluteJoint), (Without<RigidBody>, Without<JointDisabled>)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    // Apply the friction in the order of the joint entities so that the result doesn't depend on query iteration order.
+    let mut joints = joints
+        .iter()
+        .filter(|(_, joint)| joint.friction_torque > 0.0)
+        .collect::<Vec<_>>();
+    joints.sort_unstable_by_key(|(entity, _)| *entity);
+
+    for (_, joint) in joints {
+        let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) else {
+            continue;
+        };
+
+        let inv_inertia1 = body1.effective_world_inv_inertia();
+        let inv_inertia2 = body2.effective_world_inv_inertia();
+
+        // The changes in angular velocity and relative angular speed caused by a unit impulse
+        #[cfg(feature = "2d")]
+        let (relative_speed, delta_ang_vel1, delta_ang_vel2, w1, w2) = (
+            body2.angular_velocity.0 - body1.angular_velocity.0,
+            inv_inertia1,
+            inv_inertia2,
+            inv_inertia1,
+            inv_inertia2,
+        );
+        #[cfg(feature = "3d")]
+        let (relative_speed, delta_ang_vel1, delta_ang_vel2, w1, w2) = {
+            let axis = body1.rotation.rotate(joint.aligned_axis);
+            let delta_ang_vel1 = inv_inertia1 * axis;
+            let delta_ang_vel2 = inv_inertia2 * axis;
+            (
+                (body2.angular_velocity.0 - body1.angular_velocity.0).dot(axis),
+                delta_ang_vel1,
+                delta_ang_vel2,
+                axis.dot(delta_ang_vel1),
+                axis.dot(delta_ang_vel2),
+            )
+        };
+        let w1 = if body1.rb.is_dynamic() { w1 } else { 0.0 };
+        let w2 = if body2.rb.is_dynamic() { w2 } else { 0.0 };
+
+        if w1 + w2 <= Scalar::EPSILON {
+            continue;
+        }
+
+        // The impulse that stops the relative rotation, limited by the friction torque
+        let max_impulse = joint.friction_torque * delta_secs;
+        let impulse = (relative_speed / (w1 + w2)).clamp(-max_impulse, max_impulse);
+
+        if body1.rb.is_dynamic() {
+            body1.angular_velocity.0 += delta_ang_vel1 * impulse;
+        }
+        if body2.rb.is_dynamic() {
+            body2.angular_velocity.0 -= delta_ang_vel2 * impulse;
+        }
+    }
+}
+
+/// Applies velocity corrections caused by the friction of [prismatic joints](PrismaticJoint),
+/// resisting relative translation along the free axis with a limited force.
+fn prismatic_joint_friction(
+    mut bodies: Query<RigidBodyQuery, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    joints: Query<(Entity, &PrismaticJoint), (Without<RigidBody>, Without<JointDisabled>)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    // Apply the friction in the order of the joint entities so that the result doesn't depend on query iteration order.
+    let mut joints = joints
+        .iter()
+        .filter(|(_, joint)| joint.friction_force > 0.0)
+        .collect::<Vec<_>>();
+    joints.sort_unstable_by_key(|(entity, _)| *entity);
+
+    for (_, joint) in joints {
+        let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) else {
+            continue;
+        };
+
+        let axis = body1.rotation.rotate(joint.free_axis).normalize_or_zero();
+        let relative_speed = (body2.linear_velocity.0 - body1.linear_velocity.0).dot(axis);
+
+        let delta_lin_vel1 = body1.effective_inv_mass() * axis;
+        let delta_lin_vel2 = body2.effective_inv_mass() * axis;
+        let w1 = if body1.rb.is_dynamic() {
+            axis.dot(delta_lin_vel1)
+        } else {
+            0.0
+        };
+        let w2 = if body2.rb.is_dynamic() {
+            axis.dot(delta_lin_vel2)
+        } else {
+            0.0
+        };
+
+        if w1 + w2 <= Scalar::EPSILON {
+            continue;
+        }
+
+        // The impulse that stops the relative translation, limited by the friction force
+        let max_impulse = joint.friction_force * delta_secs;
+        let impulse = (relative_speed / (w1 + w2)).clamp(-max_impulse, max_impulse);
+
+        if body1.rb.is_dynamic() {
+            body1.linear_velocity.0 += delta_lin_vel1 * impulse;
+        }
+        if body2.rb.is_dynamic() {
+            body2.linear_velocity.0 -= delta_lin_vel2 * impulse;
+        }
+    }
+}
+
+/// Applies restitution at the angle limits of [revolute joints](RevoluteJoint) that have a
+/// [limit response](JointLimitResponse), making the joint bounce back from its limits.
+fn revolute_joint_limit_restitution(
+    mut bodies: Query<RigidBodyQuery, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    joints: Query<(Entity, &RevoluteJoint), (Without<RigidBody>, Without<JointDisabled>)>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    // Apply the restitution in the order of the joint entities so that the result doesn't depend on query iteration order.
+    let mut joints = joints
+        .iter()
+        .filter(|(_, joint)| {
+            // Skip joints whose limits didn't apply a correction
+            joint.angle_limit_lagrange != 0.0
+                && joint
+                    .angle_limit_response
+                    .is_some_and(|response| response.restitution > 0.0)
+        })
+        .collect::<Vec<_>>();
+    joints.sort_unstable_by_key(|(entity, _)| *entity);
+
+    for (_, joint) in joints {
+        let (Some(angle_limit), Some(response)) = (joint.angle_limit, joint.angle_limit_response)
+        else {
+            continue;
+        };
+        let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) else {
+            continue;
+        };
+
+        // Rotating towards the limit that was hit is a positive approach speed
+        let (angle, _) = joint.hinge_angle(&body1.rotation, &body2.rotation);
+        let side = if angle < 0.5 * (angle_limit.alpha + angle_limit.beta) {
+            -1.0
+        } else {
+            1.0
+        };
+
+        let inv_inertia1 = body1.effective_world_inv_inertia();
+        let inv_inertia2 = body2.effective_world_inv_inertia();
+
+        // The relative speeds and the changes in angular velocity caused by a unit impulse
+        #[cfg(feature = "2d")]
+        let (relative_speed, pre_solve_relative_speed, delta_ang_vel1, delta_ang_vel2, w1, w2) = (
+            body2.angular_velocity.0 - body1.angular_velocity.0,
+            body2.pre_solve_angular_velocity.0 - body1.pre_solve_angular_velocity.0,
+            inv_inertia1,
+            inv_inertia2,
+            inv_inertia1,
+            inv_inertia2,
+        );
+        #[cfg(feature = "3d")]
+        let (relative_speed, pre_solve_relative_speed, delta_ang_vel1, delta_ang_vel2, w1, w2) = {
+            let axis = body1.rotation.rotate(joint.aligned_axis);
+            let delta_ang_vel1 = inv_inertia1 * axis;
+            let delta_ang_vel2 = inv_inertia2 * axis;
+            (
+                (body2.angular_velocity.0 - body1.angular_velocity.0).dot(axis),
+                (body2.pre_solve_angular_velocity.0 - body1.pre_solve_angular_velocity.0).dot(axis),
+                delta_ang_vel1,
+                delta_ang_vel2,
+                axis.dot(delta_ang_vel1),
+                axis.dot(delta_ang_vel2),
+            )
+        };
+        let w1 = if body1.rb.is_dynamic() { w1 } else { 0.0 };
+        let w2 = if body2.rb.is_dynamic() { w2 } else { 0.0 };
+
+        // Only bounce back if the joint was moving towards the limit
+        if w1 + w2 <= Scalar::EPSILON || side * pre_solve_relative_speed <= 0.0 {
+            continue;
+        }
+
+        let restitution_speed = compute_restitution(
+            side * relative_speed,
+            side * pre_solve_relative_speed,
+            response.restitution,
+            gravity.0,
+            delta_secs,
+        );
+        let impulse = -side * restitution_speed / (w1 + w2);
+
+        if body1.rb.is_dynamic() {
+            body1.angular_velocity.0 += delta_ang_vel1 * impulse;
+        }
+        if body2.rb.is_dynamic() {
+            body2.angular_velocity.0 -= delta_ang_vel2 * impulse;
+        }
+    }
+}
+
+/// Applies restitution at the limits of [prismatic joints](PrismaticJoint) that have a
+/// [limit response](JointLimitResponse), making the joint bounce back from its limits.
+fn prismatic_joint_limit_restitution(
+    mut bodies: Query<RigidBodyQuery, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    joints: Query<(Entity, &PrismaticJoint), (Without<RigidBody>, Without<JointDisabled>)>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    // Apply the restitution in the order of the joint entities so that the result doesn't depend on query iteration order.
+    let mut joints = joints
+        .iter()
+        .filter(|(_, joint)| {
+            // Skip joints whose limits didn't apply a correction
+            joint.limit_lagrange != 0.0
+                && joint
+                    .free_axis_limit_response
+                    .is_some_and(|response| response.restitution > 0.0)
+        })
+        .collect::<Vec<_>>();
+    joints.sort_unstable_by_key(|(entity, _)| *entity);
+
+    for (_, joint) in joints {
+        let (Some(limits), Some(response)) =
+            (joint.free_axis_limits, joint.free_axis_limit_response)
+        else {
+            continue;
+        };
+        let Ok([mut body1, mut body2]) = bodies.get_many_mut(joint.entities()) else {
+            continue;
+        };
+
+        let axis = body1.rotation.rotate(joint.free_axis).normalize_or_zero();
+
+        // Moving towards the limit that was hit is a positive approach speed
+        let anchor1 = body1.current_position() + body1.rotation.rotate(joint.local_anchor1);
+        let anchor2 = body2.current_position() + body2.rotation.rotate(joint.local_anchor2);
+        let side = if (anchor2 - anchor1).dot(axis) < 0.5 * (limits.min + limits.max) {
+            -1.0
+        } else {
+            1.0
+        };
+
+        let relative_speed = (body2.linear_velocity.0 - body1.linear_velocity.0).dot(axis);
+        let pre_solve_relative_speed =
+            (body2.pre_solve_linear_velocity.0 - body1.pre_solve_linear_velocity.0).dot(axis);
+
+        let delta_lin_vel1 = body1.effective_inv_mass() * axis;
+        let delta_lin_vel2 = body2.effective_inv_mass() * axis;
+        let w1 = if body1.rb.is_dynamic() {
+            axis.dot(delta_lin_vel1)
+        } else {
+            0.0
+        };
+        let w2 = if body2.rb.is_dynamic() {
+            axis.dot(delta_lin_vel2)
+        } else {
+            0.0
+        };
+
+        // Only bounce back if the joint was moving towards the limit
+        if w1 + w2 <= Scalar::EPSILON || side * pre_solve_relative_speed <= 0.0 {
+            continue;
+        }
+
+        let restitution_speed = compute_restitution(
+            side * relative_speed,
+            side * pre_solve_relative_speed,
+            response.restitution,
+            gravity.0,
+            delta_secs,
+        );
+        let impulse = -side * restitution_speed / (w1 + w2);
+
+        if body1.rb.is_dynamic() {
+            body1.linear_velocity.0 += delta_lin_vel1 * impulse;
+        }
+        if body2.rb.is_dynamic() {
+            body2.linear_velocity.0 -= delta_lin_vel2 * impulse;
+        }
+    }
+}
+
+/// Adds the impulses applied by joints of type `T` during the current substep
+/// to the accumulated impulses of their [`BreakableJoint`] components.
+pub fn accumulate_joint_impulses<T: Joint>(
+    mut joints: Query<(&T, &mut BreakableJoint), Without<JointDisabled>>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (joint, mut breakable) in &mut joints {
+        breakable.accumulated_impulse += joint.total_force() * delta_secs;
+        breakable.accumulated_angular_impulse += joint.total_torque() * delta_secs;
+    }
+}
+
+/// Breaks [joints](BreakableJoint) whose average force or torque during the physics step exceeded
+/// their thresholds by disabling them, and sends a [`JointBroken`] event for each of them.
+fn break_joints(
+    mut commands: Commands,
+    mut joints: Query<(Entity, &mut BreakableJoint), Without<JointDisabled>>,
+    mut broken_events: EventWriter<JointBroken>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs <= Scalar::EPSILON {
+        return;
+    }
+
+    for (entity, mut breakable) in &mut joints {
+        let force = breakable.accumulated_impulse.length() / delta_secs;
+        #[cfg(feature = "2d")]
+        let torque = breakable.accumulated_angular_impulse.abs() / delta_secs;
+        #[cfg(feature = "3d")]
+        let torque = breakable.accumulated_angular_impulse.length() / delta_secs;
+
+        breakable.accumulated_impulse = Vector::ZERO;
+        breakable.accumulated_angular_impulse = Torque::ZERO;
+
+        let force_exceeded = breakable.break_force.is_some_and(|max| force > max);
+        let torque_exceeded = breakable.break_torque.is_some_and(|max| torque > max);
+        if force_exceeded || torque_exceeded {
+            commands.entity(entity).insert(JointDisabled);
+            broken_events.send(JointBroken {
+                joint: entity,
+                force,
+                torque,
+            });
+        }
+    }
+}
+
 fn store_contact_impulses(
     constraints: Res<PenetrationConstraints>,
     mut collisions: ResMut<Collisions>,
@@ -608,10 +1753,16 @@ fn store_contact_impulses(
         else {
             continue;
         };
-        if let Some(Some(contact)) = collision
-            .manifolds
-            .get_mut(constraint.manifold_index)
-            .map(|m| m.contacts.get_mut(constraint.contact.index))
+        // Contacts may have been removed in `PostProcessCollisions`, so look them up by their index.
+        if let Some(Some(contact)) =
+            collision
+                .manifolds
+                .get_mut(constraint.manifold_index)
+                .map(|m| {
+                    m.contacts
+                        .iter_mut()
+                        .find(|contact| contact.index == constraint.contact.index)
+                })
         {
             contact.normal_impulse = constraint.contact.normal_impulse;
             contact.tangent_impulse = constraint.contact.tangent_impulse;
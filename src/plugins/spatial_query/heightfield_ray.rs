@@ -0,0 +1,213 @@
+//! Raycasting against [heightfields](Collider::heightfield) using a DDA traversal of the grid cells.
+
+use crate::prelude::*;
+use parry::{
+    math::{Isometry, Real},
+    query::{Ray, RayCast, RayIntersection},
+    shape::{FeatureId, HeightField, Shape},
+};
+
+/// Casts a ray against the given collider, using a DDA traversal for heightfields
+/// and the shape's own raycast for all other shapes.
+pub(crate) fn cast_ray_and_get_normal(
+    collider: &Collider,
+    iso: &Isometry<Real>,
+    ray: &Ray,
+    max_time_of_impact: Scalar,
+    solid: bool,
+) -> Option<RayIntersection> {
+    let shape = collider.shape_scaled();
+    let Some(heightfield) = shape.as_heightfield() else {
+        return shape.cast_ray_and_get_normal(iso, ray, max_time_of_impact, solid);
+    };
+    let local_ray = ray.inverse_transform_by(iso);
+    cast_local_ray_heightfield(heightfield, &local_ray, max_time_of_impact, solid)
+        .map(|hit| hit.transform_by(iso))
+}
+
+/// Casts a ray in the local space of a heightfield by walking through the cells along the ray.
+///
+/// Cells that the ray passes fully above or below are skipped without testing their triangles,
+/// and the traversal stops at the first cell with a hit.
+#[cfg(feature = "3d")]
+pub(crate) fn cast_local_ray_heightfield(
+    heightfield: &HeightField,
+    ray: &Ray,
+    max_time_of_impact: Scalar,
+    solid: bool,
+) -> Option<RayIntersection> {
+    let (t_min, t_max) = heightfield.compute_local_aabb().clip_ray_parameters(ray)?;
+    let t_min = t_min.max(0.0);
+    let t_max = t_max.min(max_time_of_impact);
+    if t_min > t_max {
+        return None;
+    }
+
+    let nrows = heightfield.nrows();
+    let ncols = heightfield.ncols();
+    let heights = heightfield.heights();
+    let scale = heightfield.scale();
+    let cell_width = heightfield.cell_width();
+    let cell_height = heightfield.cell_height();
+    let (x0, z0) = (-scale.x * 0.5, -scale.z * 0.5);
+
+    // The cell that the ray enters the heightfield in.
+    let start = ray.point_at(t_min);
+    let mut j = (((start.x - x0) / cell_width).floor().max(0.0) as usize).min(ncols - 1);
+    let mut i = (((start.z - z0) / cell_height).floor().max(0.0) as usize).min(nrows - 1);
+
+    // The ray parameters at which the next cell boundaries are crossed, and the distance between them.
+    let (mut next_x, delta_x) = axis_crossing(ray.origin.x, ray.dir.x, x0, cell_width, j);
+    let (mut next_z, delta_z) = axis_crossing(ray.origin.z, ray.dir.z, z0, cell_height, i);
+
+    let mut t_enter = t_min;
+
+    loop {
+        let t_exit = next_x.min(next_z).min(t_max);
+
+        // Skip the cell if the ray segment inside it is fully above or below the terrain.
+        let y_enter = ray.origin.y + ray.dir.y * t_enter;
+        let y_exit = ray.origin.y + ray.dir.y * t_exit;
+        let corners = [
+            heights[(i, j)],
+            heights[(i + 1, j)],
+            heights[(i, j + 1)],
+            heights[(i + 1, j + 1)],
+        ];
+        let cell_min = corners.iter().copied().fold(Real::MAX, Real::min) * scale.y;
+        let cell_max = corners.iter().copied().fold(Real::MIN, Real::max) * scale.y;
+        let (segment_min, segment_max) = (y_enter.min(y_exit), y_enter.max(y_exit));
+
+        if segment_min <= cell_max && segment_max >= cell_min {
+            let (tri1, tri2) = heightfield.triangles_at(i, j);
+            let cell_index = (i + j * nrows) as u32;
+            let closest = [tri1, tri2]
+                .into_iter()
+                .enumerate()
+                .filter_map(|(k, tri)| {
+                    let mut hit = tri?.cast_local_ray_and_get_normal(ray, t_max, solid)?;
+                    hit.feature = FeatureId::Face(cell_index * 2 + k as u32);
+                    Some(hit)
+                })
+                .min_by(|a, b| a.toi.total_cmp(&b.toi));
+
+            // Cells are visited in order along the ray, so the first hit is the closest one.
+            if closest.is_some() {
+                return closest;
+            }
+        }
+
+        if t_exit >= t_max {
+            return None;
+        }
+
+        // Step to the neighboring cell whose boundary is crossed first.
+        if next_x < next_z {
+            if ray.dir.x > 0.0 {
+                j += 1;
+            } else {
+                j = j.checked_sub(1)?;
+            }
+            if j >= ncols {
+                return None;
+            }
+            t_enter = next_x;
+            next_x += delta_x;
+        } else {
+            if ray.dir.z > 0.0 {
+                i += 1;
+            } else {
+                i = i.checked_sub(1)?;
+            }
+            if i >= nrows {
+                return None;
+            }
+            t_enter = next_z;
+            next_z += delta_z;
+        }
+    }
+}
+
+/// Casts a ray in the local space of a heightfield by walking through the segments along the ray.
+///
+/// Segments that the ray passes fully above or below are skipped without testing them,
+/// and the traversal stops at the first segment with a hit.
+#[cfg(feature = "2d")]
+pub(crate) fn cast_local_ray_heightfield(
+    heightfield: &HeightField,
+    ray: &Ray,
+    max_time_of_impact: Scalar,
+    solid: bool,
+) -> Option<RayIntersection> {
+    let (t_min, t_max) = heightfield.compute_local_aabb().clip_ray_parameters(ray)?;
+    let t_min = t_min.max(0.0);
+    let t_max = t_max.min(max_time_of_impact);
+    if t_min > t_max {
+        return None;
+    }
+
+    let num_cells = heightfield.num_cells();
+    let heights = heightfield.heights();
+    let scale = heightfield.scale();
+    let cell_width = heightfield.cell_width();
+    let x0 = -scale.x * 0.5;
+
+    // The segment that the ray enters the heightfield in.
+    let start = ray.point_at(t_min);
+    let mut i = (((start.x - x0) / cell_width).floor().max(0.0) as usize).min(num_cells - 1);
+
+    // The ray parameter at which the next segment boundary is crossed, and the distance between them.
+    let (mut next_x, delta_x) = axis_crossing(ray.origin.x, ray.dir.x, x0, cell_width, i);
+
+    let mut t_enter = t_min;
+
+    loop {
+        let t_exit = next_x.min(t_max);
+
+        // Skip the segment if the ray is fully above or below it.
+        let y_enter = ray.origin.y + ray.dir.y * t_enter;
+        let y_exit = ray.origin.y + ray.dir.y * t_exit;
+        let (h1, h2) = (heights[i] * scale.y, heights[i + 1] * scale.y);
+        let (segment_min, segment_max) = (y_enter.min(y_exit), y_enter.max(y_exit));
+
+        if segment_min <= h1.max(h2) && segment_max >= h1.min(h2) {
+            if let Some(mut hit) = heightfield
+                .segment_at(i)
+                .and_then(|segment| segment.cast_local_ray_and_get_normal(ray, t_max, solid))
+            {
+                hit.feature = FeatureId::Face(i as u32);
+                return Some(hit);
+            }
+        }
+
+        if t_exit >= t_max {
+            return None;
+        }
+
+        // Step to the neighboring segment.
+        if ray.dir.x > 0.0 {
+            i += 1;
+        } else {
+            i = i.checked_sub(1)?;
+        }
+        if i >= num_cells {
+            return None;
+        }
+        t_enter = next_x;
+        next_x += delta_x;
+    }
+}
+
+/// Computes the ray parameter at which the ray crosses the next cell boundary along an axis
+/// starting from the given cell, and the ray parameter distance between consecutive boundaries.
+fn axis_crossing(origin: Real, dir: Real, min: Real, cell_size: Real, cell: usize) -> (Real, Real) {
+    if dir > 0.0 {
+        let boundary = min + (cell + 1) as Real * cell_size;
+        ((boundary - origin) / dir, cell_size / dir)
+    } else if dir < 0.0 {
+        let boundary = min + cell as Real * cell_size;
+        ((boundary - origin) / dir, -cell_size / dir)
+    } else {
+        (Real::MAX, Real::MAX)
+    }
+}
@@ -22,11 +22,12 @@
 //! There are two ways to perform raycasts.
 //!
 //! 1. For simple raycasts, use the [`RayCaster`] component. It returns the results of the raycast
-//! in the [`RayHits`] component every frame. It uses local coordinates, so it will automatically follow the entity
-//! it's attached to or its parent.
+//!    in the [`RayHits`] component every frame. It uses local coordinates, so it will automatically follow the entity
+//!    it's attached to or its parent. For sensors with several rays, like the whiskers of an AI agent,
+//!    use the [`RayCasterFan`] component, which stores the closest hit of each ray in [`RayCasterFanHits`].
 //! 2. When you need more control or don't want to cast every frame, use the raycasting methods provided by
-//! [`SpatialQuery`], like [`cast_ray`](SpatialQuery::cast_ray), [`ray_hits`](SpatialQuery::ray_hits) or
-//! [`ray_hits_callback`](SpatialQuery::ray_hits_callback).
+//!    [`SpatialQuery`], like [`cast_ray`](SpatialQuery::cast_ray), [`ray_hits`](SpatialQuery::ray_hits) or
+//!    [`ray_hits_callback`](SpatialQuery::ray_hits_callback).
 //!
 //! See the documentation of the components and methods for more information.
 //!
@@ -78,11 +79,14 @@
 //! There are two ways to perform shapecasts.
 //!
 //! 1. For simple shapecasts, use the [`ShapeCaster`] component. It returns the results of the shapecast
-//! in the [`ShapeHits`] component every frame. It uses local coordinates, so it will automatically follow the entity
-//! it's attached to or its parent.
+//!    in the [`ShapeHits`] component every frame. It uses local coordinates, so it will automatically follow the entity
+//!    it's attached to or its parent.
 //! 2. When you need more control or don't want to cast every frame, use the shapecasting methods provided by
-//! [`SpatialQuery`], like [`cast_shape`](SpatialQuery::cast_shape), [`shape_hits`](SpatialQuery::shape_hits) or
-//! [`shape_hits_callback`](SpatialQuery::shape_hits_callback).
+//!    [`SpatialQuery`], like [`cast_shape`](SpatialQuery::cast_shape), [`shape_hits`](SpatialQuery::shape_hits) or
+//!    [`shape_hits_callback`](SpatialQuery::shape_hits_callback). For shapes that also rotate during the cast,
+//!    like melee weapon arcs, use [`cast_shape_rotating`](SpatialQuery::cast_shape_rotating).
+//!    For projectiles affected by gravity, like the aim arc of a grenade, use
+//!    [`cast_trajectory`](SpatialQuery::cast_trajectory).
 //!
 //! See the documentation of the components and methods for more information.
 //!
@@ -147,6 +151,11 @@
 //!
 //! To specify which colliders should be considered in the query, use a [spatial query filter](`SpatialQueryFilter`).
 
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub(crate) mod heightfield_ray;
 #[cfg(all(
     feature = "default-collider",
     any(feature = "parry-f32", feature = "parry-f64")
@@ -154,6 +163,7 @@
 mod pipeline;
 mod query_filter;
 mod ray_caster;
+mod ray_caster_fan;
 #[cfg(all(
     feature = "default-collider",
     any(feature = "parry-f32", feature = "parry-f64")
@@ -164,6 +174,11 @@ mod shape_caster;
     any(feature = "parry-f32", feature = "parry-f64")
 ))]
 mod system_param;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+mod trajectory;
 
 #[cfg(all(
     feature = "default-collider",
@@ -172,6 +187,7 @@ mod system_param;
 pub use pipeline::*;
 pub use query_filter::*;
 pub use ray_caster::*;
+pub use ray_caster_fan::*;
 #[cfg(all(
     feature = "default-collider",
     any(feature = "parry-f32", feature = "parry-f64")
@@ -182,13 +198,18 @@ pub use shape_caster::*;
     any(feature = "parry-f32", feature = "parry-f64")
 ))]
 pub use system_param::*;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use trajectory::*;
 
 use crate::{prelude::*, prepare::PrepareSet};
 use bevy::{prelude::*, utils::intern::Interned};
 
 /// Initializes the [`SpatialQueryPipeline`] resource and handles component-based [spatial queries](spatial_query)
 /// like [raycasting](spatial_query#raycasting) and [shapecasting](spatial_query#shapecasting) with
-/// [`RayCaster`] and [`ShapeCaster`].
+/// [`RayCaster`], [`RayCasterFan`] and [`ShapeCaster`].
 pub struct SpatialQueryPlugin {
     schedule: Interned<dyn ScheduleLabel>,
 }
@@ -218,7 +239,10 @@ impl Plugin for SpatialQueryPlugin {
         ))]
         app.init_resource::<SpatialQueryPipeline>();
 
-        app.add_systems(self.schedule, init_ray_hits.in_set(PrepareSet::PreInit));
+        app.add_systems(
+            self.schedule,
+            (init_ray_hits, init_ray_caster_fan_hits).in_set(PrepareSet::PreInit),
+        );
 
         #[cfg(all(
             feature = "default-collider",
@@ -230,27 +254,40 @@ impl Plugin for SpatialQueryPlugin {
             .get_schedule_mut(PhysicsSchedule)
             .expect("add PhysicsSchedule first");
 
-        physics_schedule.add_systems(
-            (
-                update_ray_caster_positions,
-                #[cfg(all(
-                    feature = "default-collider",
-                    any(feature = "parry-f32", feature = "parry-f64")
-                ))]
-                (
-                    update_shape_caster_positions,
-                    |mut spatial_query: SpatialQuery| spatial_query.update_pipeline(),
-                    raycast,
-                    shapecast,
-                )
-                    .chain(),
-            )
-                .chain()
-                .in_set(PhysicsStepSet::SpatialQuery),
+        physics_schedule.add_systems(update_spatial_queries().in_set(PhysicsStepSet::SpatialQuery));
+
+        // Keep spatial queries up to date while physics is paused if configured by `PhysicsPausedBehavior`.
+        app.add_systems(
+            self.schedule,
+            update_spatial_queries()
+                .after(PhysicsSet::StepSimulation)
+                .before(PhysicsSet::Sync)
+                .run_if(crate::setup::update_spatial_queries_while_paused),
         );
     }
 }
 
+/// Returns the systems that update the [`SpatialQueryPipeline`], [`RayHits`], [`RayCasterFanHits`] and [`ShapeHits`].
+fn update_spatial_queries() -> bevy::ecs::schedule::SystemConfigs {
+    (
+        update_ray_caster_positions,
+        update_ray_caster_fan_positions,
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        (
+            update_shape_caster_positions,
+            |mut spatial_query: SpatialQuery| spatial_query.update_pipeline(),
+            raycast,
+            raycast_fans,
+            shapecast,
+        )
+            .chain(),
+    )
+        .chain()
+}
+
 fn init_ray_hits(mut commands: Commands, rays: Query<(Entity, &RayCaster), Added<RayCaster>>) {
     for (entity, ray) in &rays {
         let max_hits = if ray.max_hits == u32::MAX {
@@ -265,6 +302,17 @@ fn init_ray_hits(mut commands: Commands, rays: Query<(Entity, &RayCaster), Added
     }
 }
 
+fn init_ray_caster_fan_hits(
+    mut commands: Commands,
+    fans: Query<Entity, (With<RayCasterFan>, Without<RayCasterFanHits>)>,
+) {
+    for entity in &fans {
+        commands
+            .entity(entity)
+            .try_insert(RayCasterFanHits::default());
+    }
+}
+
 #[cfg(all(
     feature = "default-collider",
     any(feature = "parry-f32", feature = "parry-f64")
@@ -348,6 +396,45 @@ fn update_ray_caster_positions(
     }
 }
 
+/// Updates the global origins and directions of [`RayCasterFan`]s using the [`Position`] and [`Rotation`]
+/// of the entity or its parent.
+#[allow(clippy::type_complexity)]
+fn update_ray_caster_fan_positions(
+    mut fans: Query<(
+        &mut RayCasterFan,
+        Option<&Position>,
+        Option<&Rotation>,
+        Option<&Parent>,
+        Option<&GlobalTransform>,
+    )>,
+    parents: Query<(
+        Option<&Position>,
+        Option<&Rotation>,
+        Option<&GlobalTransform>,
+    )>,
+) {
+    for (mut fan, position, rotation, parent, transform) in &mut fans {
+        let parent = parent.and_then(|parent| parents.get(parent.get()).ok());
+
+        let global_position = position
+            .copied()
+            .or(transform.map(Position::from))
+            .or(parent.and_then(|(position, _, transform)| {
+                position.copied().or(transform.map(Position::from))
+            }))
+            .unwrap_or_default();
+        let global_rotation = rotation
+            .copied()
+            .or(transform.map(Rotation::from))
+            .or(parent.and_then(|(_, rotation, transform)| {
+                rotation.copied().or(transform.map(Rotation::from))
+            }))
+            .unwrap_or_default();
+
+        fan.set_global_transform(global_position.0, global_rotation);
+    }
+}
+
 #[cfg(all(
     feature = "default-collider",
     any(feature = "parry-f32", feature = "parry-f64")
@@ -465,6 +552,23 @@ fn raycast(mut rays: Query<(Entity, &RayCaster, &mut RayHits)>, spatial_query: S
     }
 }
 
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn raycast_fans(
+    mut fans: Query<(Entity, &RayCasterFan, &mut RayCasterFanHits)>,
+    spatial_query: SpatialQuery,
+) {
+    for (entity, fan, mut hits) in &mut fans {
+        if fan.enabled {
+            fan.cast(entity, &mut hits, &spatial_query.query_pipeline);
+        } else if hits.any() {
+            hits.clear();
+        }
+    }
+}
+
 #[cfg(all(
     feature = "default-collider",
     any(feature = "parry-f32", feature = "parry-f64")
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use super::heightfield_ray;
 use crate::prelude::*;
 use bevy::{prelude::*, utils::HashMap};
 use parry::{
@@ -14,7 +15,7 @@ use parry::{
         visitors::{
             BoundingVolumeIntersectionsVisitor, PointIntersectionsVisitor, RayIntersectionsVisitor,
         },
-        DefaultQueryDispatcher, QueryDispatcher,
+        DefaultQueryDispatcher, NonlinearRigidMotion, QueryDispatcher, TOIStatus,
     },
     shape::{Shape, TypedSimdCompositeShape},
     utils::DefaultStorage,
@@ -28,8 +29,18 @@ use parry::{
 pub struct SpatialQueryPipeline {
     pub(crate) qbvh: Qbvh<u32>,
     pub(crate) dispatcher: Arc<dyn QueryDispatcher>,
-    pub(crate) colliders: HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+    pub(crate) colliders: HashMap<
+        Entity,
+        (
+            Isometry<Scalar>,
+            Collider,
+            CollisionLayers,
+            QueryFilterFlags,
+        ),
+    >,
     pub(crate) entity_generations: HashMap<u32, u32>,
+    /// Heightfield colliders, which are raycast separately using a DDA traversal of their cells.
+    pub(crate) heightfields: Vec<Entity>,
 }
 
 impl Default for SpatialQueryPipeline {
@@ -39,6 +50,7 @@ impl Default for SpatialQueryPipeline {
             dispatcher: Arc::new(DefaultQueryDispatcher),
             colliders: HashMap::default(),
             entity_generations: HashMap::default(),
+            heightfields: vec![],
         }
     }
 }
@@ -86,15 +98,47 @@ impl SpatialQueryPipeline {
             ),
         >,
         added_colliders: impl Iterator<Item = Entity>,
+    ) {
+        self.update_with_flags(
+            colliders.map(|(entity, position, rotation, collider, layers)| {
+                (
+                    entity,
+                    position,
+                    rotation,
+                    collider,
+                    layers,
+                    QueryFilterFlags::empty(),
+                )
+            }),
+            added_colliders,
+        );
+    }
+
+    /// Updates the associated acceleration structures with a new set of entities and the
+    /// [flags](QueryFilterFlags) used for excluding them with [`SpatialQueryFilter::excluded_flags`].
+    pub fn update_with_flags<'a>(
+        &mut self,
+        colliders: impl Iterator<
+            Item = (
+                Entity,
+                &'a Position,
+                &'a Rotation,
+                &'a Collider,
+                Option<&'a CollisionLayers>,
+                QueryFilterFlags,
+            ),
+        >,
+        added_colliders: impl Iterator<Item = Entity>,
     ) {
         let colliders = colliders
-            .map(|(entity, position, rotation, collider, layers)| {
+            .map(|(entity, position, rotation, collider, layers, flags)| {
                 (
                     entity,
                     (
                         utils::make_isometry(position.0, *rotation),
                         collider.clone(),
                         layers.map_or(CollisionLayers::default(), |layers| *layers),
+                        flags,
                     ),
                 )
             })
@@ -105,11 +149,27 @@ impl SpatialQueryPipeline {
 
     fn update_internal(
         &mut self,
-        colliders: HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+        colliders: HashMap<
+            Entity,
+            (
+                Isometry<Scalar>,
+                Collider,
+                CollisionLayers,
+                QueryFilterFlags,
+            ),
+        >,
         added: impl Iterator<Item = Entity>,
     ) {
         self.colliders = colliders;
 
+        self.heightfields.clear();
+        self.heightfields.extend(
+            self.colliders
+                .iter()
+                .filter(|(_, (_, collider, _, _))| collider.shape().as_heightfield().is_some())
+                .map(|(entity, _)| *entity),
+        );
+
         // Insert or update generations of added entities
         for added in added {
             let index = added.index();
@@ -121,7 +181,15 @@ impl SpatialQueryPipeline {
         }
 
         struct DataGenerator<'a>(
-            &'a HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+            &'a HashMap<
+                Entity,
+                (
+                    Isometry<Scalar>,
+                    Collider,
+                    CollisionLayers,
+                    QueryFilterFlags,
+                ),
+            >,
         );
 
         impl<'a> parry::partitioning::QbvhDataGenerator<u32> for DataGenerator<'a> {
@@ -133,7 +201,7 @@ impl SpatialQueryPipeline {
             fn for_each(&mut self, mut f: impl FnMut(u32, parry::bounding_volume::Aabb)) {
                 for (entity, co) in self.0.iter() {
                     // Compute and return AABB
-                    let (iso, shape, _) = co;
+                    let (iso, shape, _, _) = co;
                     let aabb = shape.shape_scaled().compute_aabb(iso);
                     f(entity.index(), aabb)
                 }
@@ -148,6 +216,39 @@ impl SpatialQueryPipeline {
         entity_from_index_and_gen(index, *self.entity_generations.get(&index).unwrap())
     }
 
+    /// Computes the interpolated texture coordinates at a ray hit on a trimesh collider with stored UVs.
+    pub(crate) fn ray_hit_uv(
+        &self,
+        entity: Entity,
+        ray: &parry::query::Ray,
+        hit: &parry::query::RayIntersection,
+    ) -> Option<Vec2> {
+        let (iso, collider, _, _) = self.colliders.get(&entity)?;
+        collider.trimesh_uvs()?;
+        let parry::shape::FeatureId::Face(triangle) = hit.feature else {
+            return None;
+        };
+        let local_point = iso.inverse_transform_point(&ray.point_at(hit.toi));
+        collider.uv_at(triangle, local_point.into())
+    }
+
+    /// Computes the interpolated world-space vertex normal at a ray hit on a trimesh collider with stored normals.
+    pub(crate) fn ray_hit_smooth_normal(
+        &self,
+        entity: Entity,
+        ray: &parry::query::Ray,
+        hit: &parry::query::RayIntersection,
+    ) -> Option<Vector> {
+        let (iso, collider, _, _) = self.colliders.get(&entity)?;
+        collider.trimesh_normals()?;
+        let parry::shape::FeatureId::Face(triangle) = hit.feature else {
+            return None;
+        };
+        let local_point = iso.inverse_transform_point(&ray.point_at(hit.toi));
+        let local_normal = collider.smooth_normal_at(triangle, local_point.into())?;
+        Some((iso.rotation * parry::math::Vector::from(local_normal)).into())
+    }
+
     /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider.
     /// If there are no hits, `None` is returned.
     ///
@@ -169,22 +270,8 @@ impl SpatialQueryPipeline {
         solid: bool,
         query_filter: SpatialQueryFilter,
     ) -> Option<RayHitData> {
-        let pipeline_shape = self.as_composite_shape(query_filter);
         let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
-        let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
-            &pipeline_shape,
-            &ray,
-            max_time_of_impact,
-            solid,
-        );
-
-        self.qbvh
-            .traverse_best_first(&mut visitor)
-            .map(|(_, (entity_index, hit))| RayHitData {
-                entity: self.entity_from_index(entity_index),
-                time_of_impact: hit.toi,
-                normal: hit.normal.into(),
-            })
+        self.cast_ray_closest(&ray, max_time_of_impact, solid, query_filter, &|_| true)
     }
 
     /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider.
@@ -211,22 +298,90 @@ impl SpatialQueryPipeline {
         query_filter: SpatialQueryFilter,
         predicate: &dyn Fn(Entity) -> bool,
     ) -> Option<RayHitData> {
-        let pipeline_shape = self.as_composite_shape_with_predicate(query_filter, predicate);
         let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+        self.cast_ray_closest(&ray, max_time_of_impact, solid, query_filter, predicate)
+    }
+
+    /// Clips a ray against the bounds of all colliders in the pipeline before traversal.
+    ///
+    /// Returns the ray moved to the point where it enters the bounds, the distance that the origin was moved by,
+    /// and the remaining maximum time of impact. If the ray misses the bounds, `None` is returned.
+    ///
+    /// This keeps rays shot from far away or with a huge `max_time_of_impact`, like `Scalar::MAX`,
+    /// from losing precision or traversing more of the tree than necessary.
+    pub(crate) fn clip_ray(
+        &self,
+        ray: &parry::query::Ray,
+        max_time_of_impact: Scalar,
+    ) -> Option<(parry::query::Ray, Scalar, Scalar)> {
+        let (t_min, t_max) = self.qbvh.root_aabb().clip_ray_parameters(ray)?;
+        let t_min = t_min.max(0.0);
+        let t_max = t_max.min(max_time_of_impact);
+        if t_min > t_max {
+            return None;
+        }
+        let clipped_ray = parry::query::Ray::new(ray.point_at(t_min), ray.dir);
+        Some((clipped_ray, t_min, t_max - t_min))
+    }
+
+    /// Computes the closest ray hit with a collider that passes the query filter and the predicate.
+    ///
+    /// Heightfields are excluded from the BVH traversal and raycast separately using a DDA traversal
+    /// of their cells, limited by the closest hit found so far.
+    pub(crate) fn cast_ray_closest(
+        &self,
+        ray: &parry::query::Ray,
+        max_time_of_impact: Scalar,
+        solid: bool,
+        query_filter: SpatialQueryFilter,
+        predicate: &dyn Fn(Entity) -> bool,
+    ) -> Option<RayHitData> {
+        let (ray, offset, max_time_of_impact) = self.clip_ray(ray, max_time_of_impact)?;
+        let ray = &ray;
+
+        let is_not_heightfield =
+            |entity: Entity| !self.heightfields.contains(&entity) && predicate(entity);
+        let pipeline_shape =
+            self.as_composite_shape_with_predicate(query_filter.clone(), &is_not_heightfield);
         let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
             &pipeline_shape,
-            &ray,
+            ray,
             max_time_of_impact,
             solid,
         );
 
-        self.qbvh
+        let mut closest = self
+            .qbvh
             .traverse_best_first(&mut visitor)
-            .map(|(_, (entity_index, hit))| RayHitData {
-                entity: self.entity_from_index(entity_index),
-                time_of_impact: hit.toi,
-                normal: hit.normal.into(),
-            })
+            .map(|(_, (entity_index, hit))| (self.entity_from_index(entity_index), hit));
+
+        for entity in self.heightfields.iter().copied() {
+            let Some((iso, collider, layers, flags)) = self.colliders.get(&entity) else {
+                continue;
+            };
+            if !query_filter.test_collider(entity, *layers, *flags) || !predicate(entity) {
+                continue;
+            }
+            let max_time_of_impact = closest.map_or(max_time_of_impact, |(_, hit)| hit.toi);
+            if let Some(hit) = heightfield_ray::cast_ray_and_get_normal(
+                collider,
+                iso,
+                ray,
+                max_time_of_impact,
+                solid,
+            ) {
+                closest = Some((entity, hit));
+            }
+        }
+
+        closest.map(|(entity, hit)| RayHitData {
+            entity,
+            time_of_impact: hit.toi + offset,
+            normal: hit.normal.into(),
+            feature: hit.feature.into(),
+            uv: self.ray_hit_uv(entity, ray, &hit),
+            smooth_normal: self.ray_hit_smooth_normal(entity, ray, &hit),
+        })
     }
 
     /// Casts a [ray](spatial_query#raycasting) and computes all [hits](RayHitData) until `max_hits` is reached.
@@ -297,12 +452,17 @@ impl SpatialQueryPipeline {
         let colliders = &self.colliders;
 
         let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+        let Some((ray, offset, max_time_of_impact)) = self.clip_ray(&ray, max_time_of_impact)
+        else {
+            return;
+        };
 
         let mut leaf_callback = &mut |entity_index: &u32| {
             let entity = self.entity_from_index(*entity_index);
-            if let Some((iso, shape, layers)) = colliders.get(&entity) {
-                if query_filter.test(entity, *layers) {
-                    if let Some(hit) = shape.shape_scaled().cast_ray_and_get_normal(
+            if let Some((iso, shape, layers, flags)) = colliders.get(&entity) {
+                if query_filter.test_collider(entity, *layers, *flags) {
+                    if let Some(hit) = heightfield_ray::cast_ray_and_get_normal(
+                        shape,
                         iso,
                         &ray,
                         max_time_of_impact,
@@ -310,8 +470,11 @@ impl SpatialQueryPipeline {
                     ) {
                         let hit = RayHitData {
                             entity,
-                            time_of_impact: hit.toi,
+                            time_of_impact: hit.toi + offset,
                             normal: hit.normal.into(),
+                            feature: hit.feature.into(),
+                            uv: self.ray_hit_uv(entity, &ray, &hit),
+                            smooth_normal: self.ray_hit_smooth_normal(entity, &ray, &hit),
                         };
 
                         return callback(hit);
@@ -326,6 +489,85 @@ impl SpatialQueryPipeline {
         self.qbvh.traverse_depth_first(&mut visitor);
     }
 
+    /// Computes how visible the `target` point is from the `origin` point, accounting for partially
+    /// transparent colliders along the line of sight.
+    ///
+    /// Each collider between the points multiplies the visibility by its transparency, which is given
+    /// by the `transparency` function for the collider entity. A transparency of 0 blocks the line of sight
+    /// completely, while a transparency of 1 doesn't affect it.
+    ///
+    /// Returns a visibility factor between 0 (fully occluded) and 1 (fully visible).
+    ///
+    /// ## Arguments
+    ///
+    /// - `origin`: Where the line of sight starts.
+    /// - `target`: Where the line of sight ends.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    /// - `transparency`: A function that returns the transparency of a collider entity between 0 and 1.
+    ///
+    /// See also: [`SpatialQuery::line_of_sight`]
+    pub fn line_of_sight(
+        &self,
+        origin: Vector,
+        target: Vector,
+        query_filter: SpatialQueryFilter,
+        transparency: &dyn Fn(Entity) -> Scalar,
+    ) -> Scalar {
+        let distance = origin.distance(target);
+        let Ok(direction) = Dir::new((target - origin).f32()) else {
+            return 1.0;
+        };
+
+        let mut visibility: Scalar = 1.0;
+        self.ray_hits_callback(origin, direction, distance, true, query_filter, |hit| {
+            visibility *= transparency(hit.entity).clamp(0.0, 1.0);
+            // Stop once the line of sight is fully blocked
+            visibility > 0.0
+        });
+        visibility
+    }
+
+    /// Computes how occluded a sound emitter is from a listener by casting multiple rays
+    /// from the `listener` to points spread around the `emitter`.
+    ///
+    /// Each ray accumulates occlusion like [`SpatialQueryPipeline::line_of_sight`], using the `transparency`
+    /// function to determine how much sound each collider lets through. The points are spread over
+    /// a line (2D) or disc (3D) of the given `spread` radius around the emitter, perpendicular to the
+    /// direction from the listener to the emitter, so partial obstructions result in partial occlusion.
+    ///
+    /// Returns an occlusion factor between 0 (not occluded) and 1 (fully occluded).
+    ///
+    /// ## Arguments
+    ///
+    /// - `listener`: The position of the listener.
+    /// - `emitter`: The position of the sound emitter.
+    /// - `spread`: The radius of the area around the emitter that the rays are spread over.
+    /// - `samples`: The number of rays. A single ray is cast directly to the emitter.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    /// - `transparency`: A function that returns the transparency of a collider entity between 0 and 1.
+    ///
+    /// See also: [`SpatialQuery::sound_occlusion`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn sound_occlusion(
+        &self,
+        listener: Vector,
+        emitter: Vector,
+        spread: Scalar,
+        samples: u32,
+        query_filter: SpatialQueryFilter,
+        transparency: &dyn Fn(Entity) -> Scalar,
+    ) -> Scalar {
+        let samples = samples.max(1);
+        let direction = (emitter - listener).normalize_or_zero();
+
+        let mut visibility = 0.0;
+        for i in 0..samples {
+            let target = emitter + sample_offset(direction, spread, i, samples);
+            visibility += self.line_of_sight(listener, target, query_filter.clone(), transparency);
+        }
+        1.0 - visibility / samples as Scalar
+    }
+
     /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes the closest [hit](ShapeHits)
     /// with a collider. If there are no hits, `None` is returned.
     ///
@@ -390,6 +632,377 @@ impl SpatialQueryPipeline {
             })
     }
 
+    /// Casts a [shape](spatial_query#shapecasting) along a ballistic trajectory and returns the sampled
+    /// [`Trajectory`] up to the first hit.
+    ///
+    /// The positions of the shape are sampled every `time_step` seconds from the analytic trajectory
+    /// `origin + velocity * t + 0.5 * gravity * t^2`, and a shapecast is performed along each segment
+    /// between consecutive samples. The trajectory ends at the first hit or after `max_time` seconds.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast. The shape doesn't rotate along the trajectory.
+    /// - `velocity`: The initial velocity of the shape.
+    /// - `gravity`: The acceleration affecting the shape, typically [`Gravity`].
+    /// - `time_step`: The time between samples in seconds. Smaller values follow the curve more closely.
+    /// - `max_time`: The maximum duration of the trajectory in seconds.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::cast_trajectory`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_trajectory(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        velocity: Vector,
+        gravity: Vector,
+        time_step: Scalar,
+        max_time: Scalar,
+        query_filter: SpatialQueryFilter,
+    ) -> Trajectory {
+        let rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            rotation = Rotation::from_radians(shape_rotation);
+        }
+        #[cfg(feature = "3d")]
+        {
+            rotation = Rotation::from(shape_rotation);
+        }
+
+        let mut trajectory = Trajectory {
+            points: vec![origin],
+            hit: None,
+        };
+        if time_step <= 0.0 || max_time <= 0.0 {
+            return trajectory;
+        }
+
+        let position_at = |time: Scalar| origin + velocity * time + 0.5 * gravity * time * time;
+        let steps = (max_time / time_step).ceil() as u32;
+        let mut start = origin;
+
+        for i in 1..=steps {
+            let start_time = (i - 1) as Scalar * time_step;
+            let end_time = (i as Scalar * time_step).min(max_time);
+            let end = position_at(end_time);
+            let segment = end - start;
+            let length = segment.length();
+
+            let Ok(direction) = Dir::new(segment.f32()) else {
+                trajectory.points.push(end);
+                start = end;
+                continue;
+            };
+
+            if let Some(hit) = self.cast_shape(
+                shape,
+                start,
+                shape_rotation,
+                direction,
+                length,
+                false,
+                query_filter.clone(),
+            ) {
+                let fraction = hit.time_of_impact / length;
+                let hit_position = start + segment * fraction;
+                trajectory.points.push(hit_position);
+                trajectory.hit = Some(TrajectoryHit {
+                    entity: hit.entity,
+                    point: hit_position + rotation.rotate(hit.point2),
+                    normal: -rotation.rotate(hit.normal2),
+                    time: start_time + fraction * (end_time - start_time),
+                });
+                return trajectory;
+            }
+
+            trajectory.points.push(end);
+            start = end;
+        }
+
+        trajectory
+    }
+
+    /// Casts a [shape](spatial_query#shapecasting) that both translates and rotates, and computes the closest
+    /// [hit](ShapeHitData) with a collider. If there are no hits, `None` is returned.
+    ///
+    /// The shape moves by `translation` and rotates by `rotation_delta` around its origin over the cast,
+    /// and the [time of impact](ShapeHitData::time_of_impact) is the fraction of the motion
+    /// in the range `[0, 1]` at which the first hit occurs. This is useful for things like melee weapon arcs
+    /// and swinging doors where the rotation can't be ignored.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The initial rotation of the shape being cast.
+    /// - `translation`: How much the shape moves over the cast.
+    /// - `rotation_delta`: How much the shape rotates over the cast, in radians in 2D
+    ///   and as a scaled axis in 3D.
+    /// - `ignore_origin_penetration`: If true and the shape is already penetrating a collider at the
+    ///   shape origin, the hit will be ignored and only the next hit will be computed. Otherwise, the initial
+    ///   hit will be returned.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::cast_shape_rotating`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape_rotating(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        translation: Vector,
+        rotation_delta: AngularVector,
+        ignore_origin_penetration: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeHitData> {
+        let (shape_motion, swept_aabb) =
+            rotating_shape_motion(shape, origin, shape_rotation, translation, rotation_delta);
+
+        let mut closest_hit: Option<ShapeHitData> = None;
+
+        self.aabb_intersections_with_aabb_callback(swept_aabb, |entity| {
+            let Some((collider_isometry, collider, layers, flags)) = self.colliders.get(&entity)
+            else {
+                return true;
+            };
+            if !query_filter.test_collider(entity, *layers, *flags) {
+                return true;
+            }
+
+            let max_time = closest_hit.map_or(1.0, |hit| hit.time_of_impact);
+            if let Ok(Some(hit)) = self.dispatcher.nonlinear_time_of_impact(
+                &NonlinearRigidMotion::constant_position(*collider_isometry),
+                &**collider.shape_scaled(),
+                &shape_motion,
+                &**shape.shape_scaled(),
+                0.0,
+                max_time,
+                !ignore_origin_penetration,
+            ) {
+                if ignore_origin_penetration && hit.status == TOIStatus::Penetrating {
+                    return true;
+                }
+                closest_hit = Some(ShapeHitData {
+                    entity,
+                    time_of_impact: hit.toi,
+                    point1: hit.witness1.into(),
+                    point2: hit.witness2.into(),
+                    normal1: hit.normal1.into(),
+                    normal2: hit.normal2.into(),
+                });
+            }
+            true
+        });
+
+        closest_hit
+    }
+
+    /// Sweeps a [shape](spatial_query#shapecasting) from a start pose to an end pose, and computes
+    /// a [hit](ShapeHitData) for every collider that the shape touches along the way.
+    ///
+    /// The motion between the poses is interpolated, translating linearly and rotating around the shape origin,
+    /// so fast motions like weapon swings don't miss colliders between frames. The [time of impact](ShapeHitData::time_of_impact)
+    /// of each hit is the earliest fraction of the motion in the range `[0, 1]` at which the shape touches the collider,
+    /// and colliders that already intersect the shape at the start pose are hit at `0`.
+    /// The hits are sorted by their time of impact.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being swept represented as a [`Collider`].
+    /// - `start`: The position and rotation of the shape at the start of the sweep.
+    /// - `end`: The position and rotation of the shape at the end of the sweep.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::sweep_between`]
+    pub fn sweep_between(
+        &self,
+        shape: &Collider,
+        start: (Vector, RotationValue),
+        end: (Vector, RotationValue),
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<ShapeHitData> {
+        let (start_position, start_rotation) = start;
+        let (end_position, end_rotation) = end;
+
+        #[cfg(feature = "2d")]
+        let rotation_delta = end_rotation - start_rotation;
+        #[cfg(feature = "3d")]
+        let rotation_delta = {
+            // Rotate along the shortest arc between the start and end rotations.
+            let mut delta = end_rotation * start_rotation.inverse();
+            if delta.w < 0.0 {
+                delta = -delta;
+            }
+            delta.to_scaled_axis()
+        };
+
+        let (shape_motion, swept_aabb) = rotating_shape_motion(
+            shape,
+            start_position,
+            start_rotation,
+            end_position - start_position,
+            rotation_delta,
+        );
+
+        let mut hits = vec![];
+
+        self.aabb_intersections_with_aabb_callback(swept_aabb, |entity| {
+            let Some((collider_isometry, collider, layers, flags)) = self.colliders.get(&entity)
+            else {
+                return true;
+            };
+            if !query_filter.test_collider(entity, *layers, *flags) {
+                return true;
+            }
+
+            if let Ok(Some(hit)) = self.dispatcher.nonlinear_time_of_impact(
+                &NonlinearRigidMotion::constant_position(*collider_isometry),
+                &**collider.shape_scaled(),
+                &shape_motion,
+                &**shape.shape_scaled(),
+                0.0,
+                1.0,
+                true,
+            ) {
+                hits.push(ShapeHitData {
+                    entity,
+                    time_of_impact: hit.toi,
+                    point1: hit.witness1.into(),
+                    point2: hit.witness2.into(),
+                    normal1: hit.normal1.into(),
+                    normal2: hit.normal2.into(),
+                });
+            }
+            true
+        });
+
+        hits.sort_by(|a, b| a.time_of_impact.total_cmp(&b.time_of_impact));
+        hits
+    }
+
+    /// Casts a [shape](spatial_query#shapecasting) that is virtually inflated by the given `inflation` margin,
+    /// and computes the closest [hit](ShapeHitData) with a collider. If there are no hits, `None` is returned.
+    ///
+    /// A hit occurs when the distance between the cast shape and a collider is within `inflation`,
+    /// so the shape stops short of the collider without having to enlarge the actual shape.
+    /// This is useful for character controllers that need to keep a small separation from walls.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the shape can travel.
+    /// - `inflation`: The margin by which the shape is virtually inflated.
+    /// - `ignore_origin_penetration`: If true, colliders that are already within the inflated shape
+    ///   at the shape origin are ignored. Otherwise, the initial hit will be returned.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::cast_shape_inflated`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape_inflated(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        inflation: Scalar,
+        ignore_origin_penetration: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeHitData> {
+        let rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            rotation = Rotation::from_radians(shape_rotation);
+        }
+        #[cfg(feature = "3d")]
+        {
+            rotation = Rotation::from(shape_rotation);
+        }
+
+        let direction = direction.adjust_precision();
+        let tolerance = Scalar::EPSILON.sqrt();
+
+        // The AABB of the inflated shape swept along the cast.
+        let start_aabb = shape
+            .shape_scaled()
+            .compute_aabb(&utils::make_isometry(origin, rotation));
+        let end_aabb = shape.shape_scaled().compute_aabb(&utils::make_isometry(
+            origin + direction * max_time_of_impact.min(Scalar::MAX.sqrt()),
+            rotation,
+        ));
+        let swept_aabb = ColliderAabb::from_min_max(
+            Vector::from(start_aabb.mins).min(end_aabb.mins.into()) - Vector::splat(inflation),
+            Vector::from(start_aabb.maxs).max(end_aabb.maxs.into()) + Vector::splat(inflation),
+        );
+
+        let mut closest_hit: Option<ShapeHitData> = None;
+
+        self.aabb_intersections_with_aabb_callback(swept_aabb, |entity| {
+            let Some((collider_isometry, collider, layers, flags)) = self.colliders.get(&entity)
+            else {
+                return true;
+            };
+            if !query_filter.test_collider(entity, *layers, *flags) {
+                return true;
+            }
+
+            let max_time = closest_hit.map_or(max_time_of_impact, |hit| hit.time_of_impact);
+
+            // Conservative advancement: the distance can decrease by at most the distance travelled,
+            // so the shape can safely advance by the gap between the distance and the inflation.
+            let mut time_of_impact = 0.0;
+            for _ in 0..64 {
+                let shape_isometry =
+                    utils::make_isometry(origin + direction * time_of_impact, rotation);
+                let Ok(distance) = self.dispatcher.distance(
+                    &collider_isometry.inv_mul(&shape_isometry),
+                    &**collider.shape_scaled(),
+                    &**shape.shape_scaled(),
+                ) else {
+                    return true;
+                };
+
+                let gap = distance - inflation;
+                if gap <= tolerance {
+                    if time_of_impact == 0.0 && ignore_origin_penetration {
+                        return true;
+                    }
+                    if let Ok(Some(contact)) = self.dispatcher.contact(
+                        &collider_isometry.inv_mul(&shape_isometry),
+                        &**collider.shape_scaled(),
+                        &**shape.shape_scaled(),
+                        inflation + tolerance,
+                    ) {
+                        closest_hit = Some(ShapeHitData {
+                            entity,
+                            time_of_impact,
+                            point1: contact.point1.into(),
+                            point2: contact.point2.into(),
+                            normal1: contact.normal1.into(),
+                            normal2: contact.normal2.into(),
+                        });
+                    }
+                    return true;
+                }
+
+                time_of_impact += gap;
+                if time_of_impact > max_time {
+                    return true;
+                }
+            }
+            true
+        });
+
+        closest_hit
+    }
+
     /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes computes all [hits](ShapeHitData)
     /// in the order of the time of impact until `max_hits` is reached.
     ///
@@ -589,8 +1202,8 @@ impl SpatialQueryPipeline {
 
         let mut leaf_callback = &mut |entity_index: &u32| {
             let entity = self.entity_from_index(*entity_index);
-            if let Some((isometry, shape, layers)) = self.colliders.get(&entity) {
-                if query_filter.test(entity, *layers)
+            if let Some((isometry, shape, layers, flags)) = self.colliders.get(&entity) {
+                if query_filter.test_collider(entity, *layers, *flags)
                     && shape.shape_scaled().contains_point(isometry, &point)
                 {
                     return callback(entity);
@@ -628,7 +1241,12 @@ impl SpatialQueryPipeline {
     ) {
         let mut leaf_callback = |entity_index: &u32| {
             let entity = self.entity_from_index(*entity_index);
-            callback(entity)
+            // Disabled colliders are only stored for queries that explicitly include them.
+            let is_disabled = self
+                .colliders
+                .get(&entity)
+                .is_some_and(|(_, _, _, flags)| flags.contains(QueryFilterFlags::DISABLED));
+            is_disabled || callback(entity)
         };
 
         let mut visitor = BoundingVolumeIntersectionsVisitor::new(
@@ -673,6 +1291,67 @@ impl SpatialQueryPipeline {
         intersections
     }
 
+    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
+    /// that is intersecting the given `shape` virtually inflated by the given `inflation` margin.
+    ///
+    /// In other words, this finds all colliders whose distance to the shape is within `inflation`.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that intersections are tested against represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `inflation`: The margin by which the shape is virtually inflated.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::shape_intersections_inflated`]
+    pub fn shape_intersections_inflated(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        inflation: Scalar,
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<Entity> {
+        let rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            rotation = Rotation::from_radians(shape_rotation);
+        }
+        #[cfg(feature = "3d")]
+        {
+            rotation = Rotation::from(shape_rotation);
+        }
+
+        let shape_isometry = utils::make_isometry(shape_position, rotation);
+        let aabb = shape.shape_scaled().compute_aabb(&shape_isometry);
+        let inflated_aabb = ColliderAabb::from_min_max(
+            Vector::from(aabb.mins) - Vector::splat(inflation),
+            Vector::from(aabb.maxs) + Vector::splat(inflation),
+        );
+
+        let mut intersections = vec![];
+        self.aabb_intersections_with_aabb_callback(inflated_aabb, |entity| {
+            if let Some((collider_isometry, collider, layers, flags)) = self.colliders.get(&entity)
+            {
+                if query_filter.test_collider(entity, *layers, *flags)
+                    && self
+                        .dispatcher
+                        .distance(
+                            &shape_isometry.inv_mul(collider_isometry),
+                            &**shape.shape_scaled(),
+                            &**collider.shape_scaled(),
+                        )
+                        .is_ok_and(|distance| distance <= inflation)
+                {
+                    intersections.push(entity);
+                }
+            }
+            true
+        });
+        intersections
+    }
+
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
     /// that is intersecting the given `shape` with a given position and rotation, calling `callback` for each
     /// intersection. The search stops when `callback` returns `false` or all intersections have been found.
@@ -713,8 +1392,8 @@ impl SpatialQueryPipeline {
         let mut leaf_callback = &mut |entity_index: &u32| {
             let entity = self.entity_from_index(*entity_index);
 
-            if let Some((collider_isometry, collider, layers)) = colliders.get(&entity) {
-                if query_filter.test(entity, *layers) {
+            if let Some((collider_isometry, collider, layers, flags)) = colliders.get(&entity) {
+                if query_filter.test_collider(entity, *layers, *flags) {
                     let isometry = inverse_shape_isometry * collider_isometry;
 
                     if dispatcher.intersection_test(
@@ -734,10 +1413,76 @@ impl SpatialQueryPipeline {
         let mut visitor = BoundingVolumeIntersectionsVisitor::new(&shape_aabb, &mut leaf_callback);
         self.qbvh.traverse_depth_first(&mut visitor);
     }
+
+    /// An [intersection test](spatial_query#intersection-tests) that finds the first entity with a [`Collider`]
+    /// that is intersecting the given `shape` with a given position and rotation, or `None` if there is no intersection.
+    ///
+    /// The search stops at the first intersection, and no hits are allocated.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that intersections are tested against represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::first_blocking_overlap`]
+    pub fn first_blocking_overlap(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<Entity> {
+        let mut blocking_entity = None;
+        self.shape_intersections_callback(
+            shape,
+            shape_position,
+            shape_rotation,
+            query_filter,
+            |entity| {
+                blocking_entity = Some(entity);
+                false
+            },
+        );
+        blocking_entity
+    }
+
+    /// An [intersection test](spatial_query#intersection-tests) that checks if the given `shape` with a given
+    /// position and rotation fits in the world without intersecting any [`Collider`].
+    ///
+    /// The search stops at the first intersection, and no hits are allocated.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that intersections are tested against represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::shape_fits`]
+    pub fn shape_fits(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        query_filter: SpatialQueryFilter,
+    ) -> bool {
+        self.first_blocking_overlap(shape, shape_position, shape_rotation, query_filter)
+            .is_none()
+    }
 }
 
 pub(crate) struct QueryPipelineAsCompositeShape<'a> {
-    colliders: &'a HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+    colliders: &'a HashMap<
+        Entity,
+        (
+            Isometry<Scalar>,
+            Collider,
+            CollisionLayers,
+            QueryFilterFlags,
+        ),
+    >,
     pipeline: &'a SpatialQueryPipeline,
     query_filter: SpatialQueryFilter,
 }
@@ -752,13 +1497,13 @@ impl<'a> TypedSimdCompositeShape for QueryPipelineAsCompositeShape<'a> {
         shape_id: Self::PartId,
         mut f: impl FnMut(Option<&Isometry<Scalar>>, &Self::PartShape),
     ) {
-        if let Some((entity, (iso, shape, layers))) =
+        if let Some((entity, (iso, shape, layers, flags))) =
             self.colliders.get_key_value(&entity_from_index_and_gen(
                 shape_id,
                 *self.pipeline.entity_generations.get(&shape_id).unwrap(),
             ))
         {
-            if self.query_filter.test(*entity, *layers) {
+            if self.query_filter.test_collider(*entity, *layers, *flags) {
                 f(Some(iso), &**shape.shape_scaled());
             }
         }
@@ -778,7 +1523,15 @@ impl<'a> TypedSimdCompositeShape for QueryPipelineAsCompositeShape<'a> {
 }
 
 pub(crate) struct QueryPipelineAsCompositeShapeWithPredicate<'a, 'b> {
-    colliders: &'a HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+    colliders: &'a HashMap<
+        Entity,
+        (
+            Isometry<Scalar>,
+            Collider,
+            CollisionLayers,
+            QueryFilterFlags,
+        ),
+    >,
     pipeline: &'a SpatialQueryPipeline,
     query_filter: SpatialQueryFilter,
     predicate: &'b dyn Fn(Entity) -> bool,
@@ -794,13 +1547,15 @@ impl<'a, 'b> TypedSimdCompositeShape for QueryPipelineAsCompositeShapeWithPredic
         shape_id: Self::PartId,
         mut f: impl FnMut(Option<&Isometry<Scalar>>, &Self::PartShape),
     ) {
-        if let Some((entity, (iso, shape, layers))) =
+        if let Some((entity, (iso, shape, layers, flags))) =
             self.colliders.get_key_value(&entity_from_index_and_gen(
                 shape_id,
                 *self.pipeline.entity_generations.get(&shape_id).unwrap(),
             ))
         {
-            if self.query_filter.test(*entity, *layers) && (self.predicate)(*entity) {
+            if self.query_filter.test_collider(*entity, *layers, *flags)
+                && (self.predicate)(*entity)
+            {
                 f(Some(iso), &**shape.shape_scaled());
             }
         }
@@ -823,6 +1578,49 @@ fn entity_from_index_and_gen(index: u32, generation: u32) -> bevy::prelude::Enti
     bevy::prelude::Entity::from_bits((generation as u64) << 32 | index as u64)
 }
 
+/// Computes the motion of a shape that translates and rotates around its origin over a cast,
+/// and an AABB that bounds the volume swept by the shape.
+fn rotating_shape_motion(
+    shape: &Collider,
+    origin: Vector,
+    shape_rotation: RotationValue,
+    translation: Vector,
+    rotation_delta: AngularVector,
+) -> (NonlinearRigidMotion, ColliderAabb) {
+    let rotation: Rotation;
+    #[cfg(feature = "2d")]
+    {
+        rotation = Rotation::from_radians(shape_rotation);
+    }
+    #[cfg(feature = "3d")]
+    {
+        rotation = Rotation::from(shape_rotation);
+    }
+
+    let shape_isometry = utils::make_isometry(origin, rotation);
+    let shape_motion = NonlinearRigidMotion::new(
+        shape_isometry,
+        parry::math::Point::origin(),
+        translation.into(),
+        #[cfg(feature = "2d")]
+        rotation_delta,
+        #[cfg(feature = "3d")]
+        rotation_delta.into(),
+    );
+
+    // The shape stays within its bounding sphere around the origin while rotating,
+    // so the swept volume is bounded by the path of the origin expanded by the sphere.
+    let bounding_sphere = shape.shape_scaled().compute_local_bounding_sphere();
+    let reach = bounding_sphere.center().coords.norm() + bounding_sphere.radius();
+    let end = origin + translation;
+    let swept_aabb = ColliderAabb::from_min_max(
+        origin.min(end) - Vector::splat(reach),
+        origin.max(end) + Vector::splat(reach),
+    );
+
+    (shape_motion, swept_aabb)
+}
+
 /// The result of a [point projection](spatial_query#point-projection) on a [collider](Collider).
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -834,3 +1632,30 @@ pub struct PointProjection {
     /// True if the point was inside of the collider.
     pub is_inside: bool,
 }
+
+/// Returns the offset of the sample with the given index from the center of the line (2D) or disc (3D)
+/// perpendicular to `direction` used by [`SpatialQueryPipeline::sound_occlusion`].
+#[cfg(feature = "2d")]
+fn sample_offset(direction: Vector, spread: Scalar, index: u32, samples: u32) -> Vector {
+    if samples == 1 {
+        return Vector::ZERO;
+    }
+    // Spread the samples evenly over the line
+    let fraction = 2.0 * index as Scalar / (samples - 1) as Scalar - 1.0;
+    direction.perp() * spread * fraction
+}
+
+/// Returns the offset of the sample with the given index from the center of the line (2D) or disc (3D)
+/// perpendicular to `direction` used by [`SpatialQueryPipeline::sound_occlusion`].
+#[cfg(feature = "3d")]
+fn sample_offset(direction: Vector, spread: Scalar, index: u32, samples: u32) -> Vector {
+    if samples == 1 || direction == Vector::ZERO {
+        return Vector::ZERO;
+    }
+    // Spread the samples evenly over the disc using a golden angle spiral
+    const GOLDEN_ANGLE: Scalar = 2.399_963;
+    let (tangent, bitangent) = direction.any_orthonormal_pair();
+    let radius = spread * (index as Scalar / (samples - 1) as Scalar).sqrt();
+    let (sin, cos) = (index as Scalar * GOLDEN_ANGLE).sin_cos();
+    (tangent * cos + bitangent * sin) * radius
+}
@@ -26,15 +26,26 @@ use crate::prelude::*;
 ///
 ///     // Spawn a ray caster with the query filter
 ///     commands.spawn(RayCaster::default().with_query_filter(query_filter));
+///
+///     // A query filter that only includes static colliders, like the level geometry
+///     let static_filter = SpatialQueryFilter::default().with_excluded_flags(
+///         QueryFilterFlags::DYNAMIC | QueryFilterFlags::KINEMATIC | QueryFilterFlags::DISABLED,
+///     );
 /// }
 /// ```
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpatialQueryFilter {
     /// Specifies which [collision layers](CollisionLayers) will be included in the [spatial query](crate::spatial_query).
     pub mask: LayerMask,
     /// Entities that will not be included in [spatial queries](crate::spatial_query).
     pub excluded_entities: HashSet<Entity>,
+    /// Colliders that have any of these [flags](QueryFilterFlags) will not be included in
+    /// [spatial queries](crate::spatial_query). By default, only disabled colliders are excluded.
+    ///
+    /// Unlike entity exclusion, the flags are tested with a single bitwise operation during
+    /// the traversal of the acceleration structure.
+    pub excluded_flags: QueryFilterFlags,
 }
 
 impl Default for SpatialQueryFilter {
@@ -42,6 +53,7 @@ impl Default for SpatialQueryFilter {
         Self {
             mask: LayerMask::ALL,
             excluded_entities: default(),
+            excluded_flags: QueryFilterFlags::DISABLED,
         }
     }
 }
@@ -77,6 +89,30 @@ impl SpatialQueryFilter {
         self
     }
 
+    /// Excludes colliders that have any of the given [flags](QueryFilterFlags) from the
+    /// [spatial query](crate::spatial_query).
+    ///
+    /// Note that this replaces the default flags, so [`QueryFilterFlags::DISABLED`] should be included
+    /// to keep excluding disabled colliders.
+    pub fn with_excluded_flags(mut self, flags: QueryFilterFlags) -> Self {
+        self.excluded_flags = flags;
+        self
+    }
+
+    /// Excludes colliders attached to [sleeping](Sleeping) bodies from the [spatial query](crate::spatial_query).
+    pub fn exclude_sleeping(mut self) -> Self {
+        self.excluded_flags |= QueryFilterFlags::SLEEPING;
+        self
+    }
+
+    /// Only includes colliders attached to the given kind of [rigid body](RigidBody) in the
+    /// [spatial query](crate::spatial_query). Colliders without a rigid body are treated as static.
+    pub fn only_rigid_body(mut self, rigid_body: RigidBody) -> Self {
+        self.excluded_flags |= QueryFilterFlags::RIGID_BODY_KINDS;
+        self.excluded_flags -= QueryFilterFlags::from_rigid_body(rigid_body);
+        self
+    }
+
     /// Tests if an entity should be included in [spatial queries](crate::spatial_query) based on the
     /// filter configuration.
     pub fn test(&self, entity: Entity, layers: CollisionLayers) -> bool {
@@ -84,4 +120,51 @@ impl SpatialQueryFilter {
             && CollisionLayers::new(LayerMask::ALL, self.mask)
                 .interacts_with(CollisionLayers::new(layers.memberships, LayerMask::ALL))
     }
+
+    /// Tests if a collider with the given [flags](QueryFilterFlags) should be included in
+    /// [spatial queries](crate::spatial_query) based on the filter configuration.
+    #[inline]
+    pub(crate) fn test_collider(
+        &self,
+        entity: Entity,
+        layers: CollisionLayers,
+        flags: QueryFilterFlags,
+    ) -> bool {
+        !self.excluded_flags.intersects(flags) && self.test(entity, layers)
+    }
+}
+
+bitflags::bitflags! {
+    /// Flags describing the state of a collider and its [rigid body](RigidBody), used by
+    /// [`SpatialQueryFilter::excluded_flags`] to exclude colliders from [spatial queries](crate::spatial_query).
+    ///
+    /// The flags of each collider are stored in the [`SpatialQueryPipeline`] when it is updated.
+    #[repr(transparent)]
+    #[derive(Hash, Clone, Copy, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+    pub struct QueryFilterFlags: u8 {
+        /// The collider is attached to a [sleeping](Sleeping) body.
+        const SLEEPING = 0b0000_0001;
+        /// The collider has [`ColliderDisabled`]. Excluded by default.
+        const DISABLED = 0b0000_0010;
+        /// The collider is attached to a [dynamic](RigidBody::Dynamic) body.
+        const DYNAMIC = 0b0000_0100;
+        /// The collider is attached to a [kinematic](RigidBody::Kinematic) body.
+        const KINEMATIC = 0b0000_1000;
+        /// The collider is attached to a [static](RigidBody::Static) body or has no rigid body.
+        const STATIC = 0b0001_0000;
+        /// All of the rigid body kinds.
+        const RIGID_BODY_KINDS = Self::DYNAMIC.bits() | Self::KINEMATIC.bits() | Self::STATIC.bits();
+    }
+}
+
+impl QueryFilterFlags {
+    /// Returns the flag corresponding to the given kind of [rigid body](RigidBody).
+    pub fn from_rigid_body(rigid_body: RigidBody) -> Self {
+        match rigid_body {
+            RigidBody::Dynamic => Self::DYNAMIC,
+            RigidBody::Kinematic => Self::KINEMATIC,
+            RigidBody::Static => Self::STATIC,
+        }
+    }
 }
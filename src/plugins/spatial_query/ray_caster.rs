@@ -7,9 +7,7 @@ use bevy::{
     feature = "default-collider",
     any(feature = "parry-f32", feature = "parry-f64")
 ))]
-use parry::query::{
-    details::RayCompositeShapeToiAndNormalBestFirstVisitor, visitors::RayIntersectionsVisitor,
-};
+use parry::query::visitors::RayIntersectionsVisitor;
 
 /// A component used for [raycasting](spatial_query#raycasting).
 ///
@@ -244,24 +242,17 @@ impl RayCaster {
         hits.count = 0;
 
         if self.max_hits == 1 {
-            let pipeline_shape = query_pipeline.as_composite_shape(query_filter);
             let ray = parry::query::Ray::new(
                 self.global_origin().into(),
                 self.global_direction().adjust_precision().into(),
             );
-            let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
-                &pipeline_shape,
+
+            if let Some(hit) = query_pipeline.cast_ray_closest(
                 &ray,
                 self.max_time_of_impact,
                 self.solid,
-            );
-
-            if let Some(hit) = query_pipeline.qbvh.traverse_best_first(&mut visitor).map(
-                |(_, (entity_index, hit))| RayHitData {
-                    entity: query_pipeline.entity_from_index(entity_index),
-                    time_of_impact: hit.toi,
-                    normal: hit.normal.into(),
-                },
+                query_filter,
+                &|_| true,
             ) {
                 if (hits.vector.len() as u32) < hits.count + 1 {
                     hits.vector.push(hit);
@@ -275,28 +266,42 @@ impl RayCaster {
                 self.global_origin().into(),
                 self.global_direction().adjust_precision().into(),
             );
+            let Some((ray, offset, max_time_of_impact)) =
+                query_pipeline.clip_ray(&ray, self.max_time_of_impact)
+            else {
+                return;
+            };
 
             let mut leaf_callback = &mut |entity_index: &u32| {
                 let entity = query_pipeline.entity_from_index(*entity_index);
-                if let Some((iso, shape, layers)) = query_pipeline.colliders.get(&entity) {
-                    if query_filter.test(entity, *layers) {
-                        if let Some(hit) = shape.shape_scaled().cast_ray_and_get_normal(
+                if let Some((iso, shape, layers, flags)) = query_pipeline.colliders.get(&entity) {
+                    if query_filter.test_collider(entity, *layers, *flags) {
+                        if let Some(hit) = super::heightfield_ray::cast_ray_and_get_normal(
+                            shape,
                             iso,
                             &ray,
-                            self.max_time_of_impact,
+                            max_time_of_impact,
                             self.solid,
                         ) {
                             if (hits.vector.len() as u32) < hits.count + 1 {
                                 hits.vector.push(RayHitData {
                                     entity,
-                                    time_of_impact: hit.toi,
+                                    time_of_impact: hit.toi + offset,
                                     normal: hit.normal.into(),
+                                    feature: hit.feature.into(),
+                                    uv: query_pipeline.ray_hit_uv(entity, &ray, &hit),
+                                    smooth_normal: query_pipeline
+                                        .ray_hit_smooth_normal(entity, &ray, &hit),
                                 });
                             } else {
                                 hits.vector[hits.count as usize] = RayHitData {
                                     entity,
-                                    time_of_impact: hit.toi,
+                                    time_of_impact: hit.toi + offset,
                                     normal: hit.normal.into(),
+                                    feature: hit.feature.into(),
+                                    uv: query_pipeline.ray_hit_uv(entity, &ray, &hit),
+                                    smooth_normal: query_pipeline
+                                        .ray_hit_smooth_normal(entity, &ray, &hit),
                                 };
                             }
 
@@ -310,7 +315,7 @@ impl RayCaster {
             };
 
             let mut visitor =
-                RayIntersectionsVisitor::new(&ray, self.max_time_of_impact, &mut leaf_callback);
+                RayIntersectionsVisitor::new(&ray, max_time_of_impact, &mut leaf_callback);
             query_pipeline.qbvh.traverse_depth_first(&mut visitor);
         }
     }
@@ -418,6 +423,19 @@ pub struct RayHitData {
     pub time_of_impact: Scalar,
     /// The normal at the point of intersection.
     pub normal: Vector,
+    /// The geometric feature of the collider that was hit, like the index of a triangle for trimeshes.
+    ///
+    /// This can be used for looking up per-triangle [`SurfaceMaterials`].
+    pub feature: FeatureId,
+    /// The interpolated texture coordinates at the point of intersection.
+    ///
+    /// This is only computed for trimesh colliders with [stored UVs](Collider::with_trimesh_uvs).
+    pub uv: Option<Vec2>,
+    /// The interpolated vertex normal at the point of intersection, matching the visual surface.
+    ///
+    /// This is only computed for trimesh colliders with [stored vertex normals](Collider::with_trimesh_normals).
+    /// Unlike [`normal`](Self::normal), it isn't the geometric normal of the hit triangle.
+    pub smooth_normal: Option<Vector>,
 }
 
 impl MapEntities for RayHitData {
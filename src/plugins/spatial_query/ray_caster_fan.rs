@@ -0,0 +1,301 @@
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+
+/// A component that casts multiple rays in the local frame of an entity each physics step,
+/// like the whisker sensors of an AI agent or a vision cone.
+///
+/// Each ray starts at the shared local `origin` and goes in one of the local `directions`.
+/// The rays follow the [`Position`] and [`Rotation`] of the entity or its parent, so a [`RayCasterFan`]
+/// can be added directly to a [rigid body](RigidBody) or to one of its children.
+///
+/// The closest hit of each ray is stored in the [`RayCasterFanHits`] component, in the same order as the directions.
+/// For more control over individual rays, use [`RayCaster`] or the [`SpatialQuery`] system parameter.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// fn setup(mut commands: Commands) {
+///     // Cast five rays in a 90 degree arc in front of the agent
+///     commands.spawn((
+///         RigidBody::Kinematic,
+#[cfg_attr(
+    feature = "2d",
+    doc = "        RayCasterFan::arc(Vector::ZERO, Direction2d::X, PI / 2.0, 5).with_max_time_of_impact(10.0),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "        RayCasterFan::arc(Vector::ZERO, Direction3d::NEG_Z, Direction3d::Y, PI / 2.0, 5)\n            .with_max_time_of_impact(10.0),"
+)]
+///     ));
+/// }
+///
+/// fn steer(query: Query<&RayCasterFanHits>) {
+///     for hits in &query {
+///         for (index, hit) in hits.iter().enumerate() {
+///             if let Some(hit) = hit {
+///                 println!("Whisker {} hit {:?} at distance {}", index, hit.entity, hit.time_of_impact);
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RayCasterFan {
+    /// Controls if the ray caster fan is enabled.
+    pub enabled: bool,
+    /// The local origin of the rays relative to the [`Position`] and [`Rotation`] of the entity or its parent.
+    pub origin: Vector,
+    /// The global origin of the rays.
+    global_origin: Vector,
+    /// The local directions of the rays relative to the [`Rotation`] of the entity or its parent.
+    pub directions: Vec<Dir>,
+    /// The global directions of the rays.
+    global_directions: Vec<Dir>,
+    /// The maximum distance the rays can travel. By default this is infinite.
+    pub max_time_of_impact: Scalar,
+    /// Controls how the rays behave when the origin is inside of a [collider](Collider).
+    ///
+    /// If `solid` is true, the point of intersection will be the ray origin itself.\
+    /// If `solid` is false, the collider will be considered to have no interior, and the point of intersection
+    /// will be at the collider shape's boundary.
+    pub solid: bool,
+    /// If true, the rays ignore hits against the entity's own [`Collider`]. This is the default.
+    pub ignore_self: bool,
+    /// Rules that determine which colliders are taken into account in the query.
+    pub query_filter: SpatialQueryFilter,
+}
+
+impl Default for RayCasterFan {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            origin: Vector::ZERO,
+            global_origin: Vector::ZERO,
+            directions: vec![],
+            global_directions: vec![],
+            max_time_of_impact: Scalar::MAX,
+            solid: true,
+            ignore_self: true,
+            query_filter: SpatialQueryFilter::default(),
+        }
+    }
+}
+
+impl RayCasterFan {
+    /// Creates a new [`RayCasterFan`] with a given origin and ray directions.
+    pub fn new(origin: Vector, directions: impl IntoIterator<Item = Dir>) -> Self {
+        Self {
+            origin,
+            directions: directions.into_iter().collect(),
+            ..default()
+        }
+    }
+
+    /// Creates a new [`RayCasterFan`] with `count` rays spread evenly over an arc of the given `angle`
+    /// in radians, centered on the given `direction`.
+    #[cfg(feature = "2d")]
+    pub fn arc(origin: Vector, direction: Dir, angle: Scalar, count: usize) -> Self {
+        let directions = arc_angles(angle, count)
+            .map(|angle| Rotation::from_radians(angle) * direction)
+            .collect::<Vec<_>>();
+        Self::new(origin, directions)
+    }
+
+    /// Creates a new [`RayCasterFan`] with `count` rays spread evenly over an arc of the given `angle`
+    /// in radians around the given `axis`, centered on the given `direction`.
+    #[cfg(feature = "3d")]
+    pub fn arc(origin: Vector, direction: Dir, axis: Dir, angle: Scalar, count: usize) -> Self {
+        let directions = arc_angles(angle, count)
+            .map(|angle| {
+                Rotation(Quaternion::from_axis_angle(axis.adjust_precision(), angle)) * direction
+            })
+            .collect::<Vec<_>>();
+        Self::new(origin, directions)
+    }
+
+    /// Creates a new [`RayCasterFan`] with one ray in the given `direction` and `count` rays spread evenly
+    /// on the surface of a cone around it, with the given `half_angle` in radians.
+    #[cfg(feature = "3d")]
+    pub fn cone(origin: Vector, direction: Dir, half_angle: Scalar, count: usize) -> Self {
+        let axis = direction.adjust_precision();
+        let tilted = Quaternion::from_axis_angle(axis.any_orthonormal_vector(), half_angle) * axis;
+        let directions = std::iter::once(direction)
+            .chain((0..count).map(|i| {
+                let angle = TAU * i as Scalar / count as Scalar;
+                let ray = Quaternion::from_axis_angle(axis, angle) * tilted;
+                Dir::new_unchecked(ray.normalize().f32())
+            }))
+            .collect::<Vec<_>>();
+        Self::new(origin, directions)
+    }
+
+    /// Sets the origin of the rays.
+    pub fn with_origin(mut self, origin: Vector) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Sets if the rays treat [colliders](Collider) as solid.
+    ///
+    /// If `solid` is true, the point of intersection will be the ray origin itself.\
+    /// If `solid` is false, the collider will be considered to have no interior, and the point of intersection
+    /// will be at the collider shape's boundary.
+    pub fn with_solidness(mut self, solid: bool) -> Self {
+        self.solid = solid;
+        self
+    }
+
+    /// Sets if the rays should ignore hits against the entity's own [`Collider`].
+    /// The default is true.
+    pub fn with_ignore_self(mut self, ignore: bool) -> Self {
+        self.ignore_self = ignore;
+        self
+    }
+
+    /// Sets the maximum time of impact, i.e. the maximum distance that the rays are allowed to travel.
+    pub fn with_max_time_of_impact(mut self, max_time_of_impact: Scalar) -> Self {
+        self.max_time_of_impact = max_time_of_impact;
+        self
+    }
+
+    /// Sets the [query filter](SpatialQueryFilter) that controls which colliders
+    /// should be included or excluded by the rays.
+    pub fn with_query_filter(mut self, query_filter: SpatialQueryFilter) -> Self {
+        self.query_filter = query_filter;
+        self
+    }
+
+    /// Enables the [`RayCasterFan`].
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    /// Disables the [`RayCasterFan`].
+    pub fn disable(&mut self) {
+        self.enabled = false;
+    }
+
+    /// Returns the global origin of the rays.
+    pub fn global_origin(&self) -> Vector {
+        self.global_origin
+    }
+
+    /// Returns the global directions of the rays.
+    pub fn global_directions(&self) -> &[Dir] {
+        &self.global_directions
+    }
+
+    /// Sets the global origin and rotation of the rays.
+    pub(crate) fn set_global_transform(&mut self, position: Vector, rotation: Rotation) {
+        self.global_origin = position + rotation * self.origin;
+        self.global_directions.clear();
+        self.global_directions.extend(
+            self.directions
+                .iter()
+                .map(|direction| rotation * *direction),
+        );
+    }
+
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub(crate) fn cast(
+        &self,
+        caster_entity: Entity,
+        hits: &mut RayCasterFanHits,
+        query_pipeline: &SpatialQueryPipeline,
+    ) {
+        let mut query_filter = self.query_filter.clone();
+
+        if self.ignore_self {
+            query_filter.excluded_entities.insert(caster_entity);
+        }
+
+        hits.0.clear();
+        hits.0
+            .extend(self.global_directions.iter().map(|direction| {
+                let ray = parry::query::Ray::new(
+                    self.global_origin.into(),
+                    direction.adjust_precision().into(),
+                );
+                query_pipeline.cast_ray_closest(
+                    &ray,
+                    self.max_time_of_impact,
+                    self.solid,
+                    query_filter.clone(),
+                    &|_| true,
+                )
+            }));
+    }
+}
+
+/// Returns `count` angles spread evenly over an arc of the given `angle`, centered on zero.
+fn arc_angles(angle: Scalar, count: usize) -> impl Iterator<Item = Scalar> {
+    let step = if count > 1 {
+        angle / (count - 1) as Scalar
+    } else {
+        0.0
+    };
+    (0..count).map(move |i| -0.5 * step * (count - 1) as Scalar + step * i as Scalar)
+}
+
+/// Contains the closest hit of each ray cast by a [`RayCasterFan`], in the same order as its directions.
+///
+/// A ray that didn't hit anything has `None` as its hit.
+#[derive(Component, Clone, Debug, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RayCasterFanHits(pub(crate) Vec<Option<RayHitData>>);
+
+impl RayCasterFanHits {
+    /// Returns a slice over the hits of the rays.
+    pub fn as_slice(&self) -> &[Option<RayHitData>] {
+        &self.0
+    }
+
+    /// Returns the hit of the ray at the given index, or `None` if the ray didn't hit anything
+    /// or the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&RayHitData> {
+        self.0.get(index).and_then(Option::as_ref)
+    }
+
+    /// Returns an iterator over the hits of the rays, in the same order as the directions of the [`RayCasterFan`].
+    pub fn iter(&self) -> impl Iterator<Item = Option<&RayHitData>> {
+        self.0.iter().map(Option::as_ref)
+    }
+
+    /// Returns the index of the ray with the closest hit and the hit, or `None` if no ray hit anything.
+    pub fn closest(&self) -> Option<(usize, &RayHitData)> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(index, hit)| hit.as_ref().map(|hit| (index, hit)))
+            .min_by(|(_, a), (_, b)| a.time_of_impact.total_cmp(&b.time_of_impact))
+    }
+
+    /// Returns true if any of the rays hit something.
+    pub fn any(&self) -> bool {
+        self.0.iter().any(Option::is_some)
+    }
+
+    /// Clears the hits.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl MapEntities for RayCasterFanHits {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for hit in self.0.iter_mut().flatten() {
+            hit.map_entities(entity_mapper);
+        }
+    }
+}
@@ -100,6 +100,12 @@ pub struct ShapeCaster {
     /// the shapecast will not stop immediately, and will instead continue until another hit.\
     /// If set to false, the shapecast will stop immediately and return the hit. This is the default.
     pub ignore_origin_penetration: bool,
+    /// A margin by which the cast shape is virtually inflated. A hit occurs when the distance
+    /// between the shape and a collider is within this margin. Zero by default.
+    ///
+    /// This can be used for keeping a small separation from walls, for example for character controllers,
+    /// without enlarging the actual shape.
+    pub inflation: Scalar,
     /// If true, the shape caster ignores hits against its own [`Collider`]. This is the default.
     pub ignore_self: bool,
     /// Rules that determine which colliders are taken into account in the query.
@@ -129,6 +135,7 @@ impl Default for ShapeCaster {
             max_time_of_impact: Scalar::MAX,
             max_hits: 1,
             ignore_origin_penetration: false,
+            inflation: 0.0,
             ignore_self: true,
             query_filter: SpatialQueryFilter::default(),
         }
@@ -211,6 +218,12 @@ impl ShapeCaster {
         self
     }
 
+    /// Sets the margin by which the cast shape is virtually inflated.
+    pub fn with_inflation(mut self, inflation: Scalar) -> Self {
+        self.inflation = inflation;
+        self
+    }
+
     /// Sets the shape caster's [query filter](SpatialQueryFilter) that controls which colliders
     /// should be included or excluded by shapecasts.
     pub fn with_query_filter(mut self, query_filter: SpatialQueryFilter) -> Self {
@@ -286,6 +299,33 @@ impl ShapeCaster {
 
         hits.count = 0;
 
+        if self.inflation > 0.0 {
+            while hits.count < self.max_hits {
+                let Some(hit) = query_pipeline.cast_shape_inflated(
+                    &self.shape,
+                    self.global_origin(),
+                    self.global_shape_rotation(),
+                    self.global_direction(),
+                    self.max_time_of_impact,
+                    self.inflation,
+                    self.ignore_origin_penetration,
+                    query_filter.clone(),
+                ) else {
+                    return;
+                };
+
+                if (hits.vector.len() as u32) < hits.count + 1 {
+                    hits.vector.push(hit);
+                } else {
+                    hits.vector[hits.count as usize] = hit;
+                }
+
+                hits.count += 1;
+                query_filter.excluded_entities.insert(hit.entity);
+            }
+            return;
+        }
+
         let shape_rotation: Rotation;
         #[cfg(feature = "2d")]
         {
@@ -17,6 +17,8 @@ use bevy::{ecs::system::SystemParam, prelude::*};
 /// [`aabb_intersections_with_aabb_callback`](SpatialQuery::aabb_intersections_with_aabb_callback)
 ///     - Shape intersections: [`shape_intersections`](SpatialQuery::shape_intersections)
 /// [`shape_intersections_callback`](SpatialQuery::shape_intersections_callback)
+///     - Placement checks: [`shape_fits`](SpatialQuery::shape_fits),
+///       [`first_blocking_overlap`](SpatialQuery::first_blocking_overlap)
 ///
 /// For simple raycasts and shapecasts, consider using the [`RayCaster`] and [`ShapeCaster`] components that
 /// provide a more ECS-based approach and perform casts on every frame.
@@ -70,8 +72,11 @@ pub struct SpatialQuery<'w, 's> {
             &'static Rotation,
             &'static Collider,
             Option<&'static CollisionLayers>,
+            Option<&'static ColliderParent>,
+            Has<ColliderDisabled>,
         ),
     >,
+    pub(crate) bodies: Query<'w, 's, (&'static RigidBody, Has<Sleeping>)>,
     pub(crate) added_colliders: Query<'w, 's, Entity, Added<Collider>>,
     /// The [`SpatialQueryPipeline`].
     pub query_pipeline: ResMut<'w, SpatialQueryPipeline>,
@@ -82,8 +87,22 @@ impl<'w, 's> SpatialQuery<'w, 's> {
     /// [`PhysicsStepSet::SpatialQuery`], but if you modify colliders or their positions before that, you can
     /// call this to make sure the data is up to date when performing spatial queries using [`SpatialQuery`].
     pub fn update_pipeline(&mut self) {
+        let colliders = self.colliders.iter().map(
+            |(entity, position, rotation, collider, layers, parent, is_disabled)| {
+                // Colliders without a rigid body are treated as static
+                let (rigid_body, is_sleeping) = parent
+                    .and_then(|parent| self.bodies.get(parent.get()).ok())
+                    .unwrap_or((&RigidBody::Static, false));
+
+                let mut flags = QueryFilterFlags::from_rigid_body(*rigid_body);
+                flags.set(QueryFilterFlags::SLEEPING, is_sleeping);
+                flags.set(QueryFilterFlags::DISABLED, is_disabled);
+
+                (entity, position, rotation, collider, layers, flags)
+            },
+        );
         self.query_pipeline
-            .update(self.colliders.iter(), self.added_colliders.iter());
+            .update_with_flags(colliders, self.added_colliders.iter());
     }
 
     /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider.
@@ -323,6 +342,145 @@ impl<'w, 's> SpatialQuery<'w, 's> {
         )
     }
 
+    /// Computes how visible the `target` point is from the `origin` point, accounting for partially
+    /// transparent colliders along the line of sight. This can be used for the vision of AI agents
+    /// in stealth games, for example.
+    ///
+    /// Each collider between the points multiplies the visibility by its transparency, which is given
+    /// by the `transparency` function for the collider entity. A transparency of 0 blocks the line of sight
+    /// completely, while a transparency of 1 doesn't affect it.
+    ///
+    /// Returns a visibility factor between 0 (fully occluded) and 1 (fully visible).
+    ///
+    /// ## Arguments
+    ///
+    /// - `origin`: Where the line of sight starts.
+    /// - `target`: Where the line of sight ends.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    /// - `transparency`: A function that returns the transparency of a collider entity between 0 and 1.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Foliage;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn guard_vision(spatial_query: SpatialQuery, foliage: Query<&Foliage>) {
+    ///     let visibility = spatial_query.line_of_sight(
+    ///         Vec3::ZERO,                    // Origin
+    ///         Vec3::new(10.0, 0.0, 0.0),     // Target
+    ///         SpatialQueryFilter::default(), // Query filter
+    ///         &|entity| {                    // Transparency
+    ///             // Foliage lets some of the view through, other colliders block it.
+    ///             if foliage.contains(entity) { 0.5 } else { 0.0 }
+    ///         },
+    ///     );
+    ///
+    ///     if visibility > 0.3 {
+    ///         println!("The guard sees the player");
+    ///     }
+    /// }
+    /// ```
+    pub fn line_of_sight(
+        &self,
+        origin: Vector,
+        target: Vector,
+        query_filter: SpatialQueryFilter,
+        transparency: &dyn Fn(Entity) -> Scalar,
+    ) -> Scalar {
+        self.query_pipeline
+            .line_of_sight(origin, target, query_filter, transparency)
+    }
+
+    /// Computes how occluded a sound emitter is from a listener by casting multiple rays
+    /// from the `listener` to points spread around the `emitter`. The result can be used to control
+    /// the volume or a low-pass filter of the sound every frame, for example.
+    ///
+    /// Each ray accumulates occlusion like [`SpatialQuery::line_of_sight`], using the `transparency`
+    /// function to determine how much sound each collider lets through based on its material, for example.
+    /// The points are spread over a line (2D) or disc (3D) of the given `spread` radius around the emitter,
+    /// perpendicular to the direction from the listener to the emitter, so partial obstructions
+    /// result in partial occlusion.
+    ///
+    /// Returns an occlusion factor between 0 (not occluded) and 1 (fully occluded).
+    ///
+    /// ## Arguments
+    ///
+    /// - `listener`: The position of the listener.
+    /// - `emitter`: The position of the sound emitter.
+    /// - `spread`: The radius of the area around the emitter that the rays are spread over.
+    /// - `samples`: The number of rays. A single ray is cast directly to the emitter.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    /// - `transparency`: A function that returns the transparency of a collider entity between 0 and 1.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Listener;
+    ///
+    /// #[derive(Component)]
+    /// struct Volume(f32);
+    ///
+    /// #[derive(Component)]
+    /// struct SoundTransparency(f32);
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn occlude_sounds(
+    ///     spatial_query: SpatialQuery,
+    ///     listener: Query<&GlobalTransform, With<Listener>>,
+    ///     mut emitters: Query<(&GlobalTransform, &mut Volume)>,
+    ///     materials: Query<&SoundTransparency>,
+    /// ) {
+    ///     let listener = listener.single().translation();
+    ///     for (transform, mut volume) in &mut emitters {
+    ///         let occlusion = spatial_query.sound_occlusion(
+    ///             listener,                      // Listener
+    ///             transform.translation(),       // Emitter
+    ///             0.5,                           // Spread
+    ///             8,                             // Number of rays
+    ///             SpatialQueryFilter::default(), // Query filter
+    ///             &|entity| {                    // Transparency
+    ///                 materials.get(entity).map_or(0.0, |transparency| transparency.0)
+    ///             },
+    ///         );
+    ///         volume.0 = 1.0 - 0.8 * occlusion;
+    ///     }
+    /// }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn sound_occlusion(
+        &self,
+        listener: Vector,
+        emitter: Vector,
+        spread: Scalar,
+        samples: u32,
+        query_filter: SpatialQueryFilter,
+        transparency: &dyn Fn(Entity) -> Scalar,
+    ) -> Scalar {
+        self.query_pipeline.sound_occlusion(
+            listener,
+            emitter,
+            spread,
+            samples,
+            query_filter,
+            transparency,
+        )
+    }
+
     /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes the closest [hit](ShapeHits)
     /// with a collider. If there are no hits, `None` is returned.
     ///
@@ -387,6 +545,265 @@ impl<'w, 's> SpatialQuery<'w, 's> {
         )
     }
 
+    /// Casts a [shape](spatial_query#shapecasting) along a ballistic trajectory and returns the sampled
+    /// [`Trajectory`] up to the first hit.
+    ///
+    /// The positions of the shape are sampled every `time_step` seconds from the analytic trajectory
+    /// `origin + velocity * t + 0.5 * gravity * t^2`, and a shapecast is performed along each segment
+    /// between consecutive samples. The trajectory ends at the first hit or after `max_time` seconds.
+    /// This can be used for drawing aim arcs of thrown objects with a single call.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast. The shape doesn't rotate along the trajectory.
+    /// - `velocity`: The initial velocity of the shape.
+    /// - `gravity`: The acceleration affecting the shape, typically [`Gravity`].
+    /// - `time_step`: The time between samples in seconds. Smaller values follow the curve more closely.
+    /// - `max_time`: The maximum duration of the trajectory in seconds.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn draw_aim_arc(spatial_query: SpatialQuery, gravity: Res<Gravity>, mut gizmos: Gizmos) {
+    ///     let trajectory = spatial_query.cast_trajectory(
+    ///         &Collider::sphere(0.1),          // Shape
+    ///         Vec3::Y,                         // Origin
+    ///         Quat::default(),                 // Shape rotation
+    ///         Vec3::new(5.0, 5.0, 0.0),        // Initial velocity
+    ///         gravity.0,                       // Gravity
+    ///         0.05,                            // Time step
+    ///         3.0,                             // Maximum time
+    ///         SpatialQueryFilter::default(),   // Query filter
+    ///     );
+    ///
+    ///     gizmos.linestrip(trajectory.points.iter().copied(), Color::WHITE);
+    ///     if let Some(hit) = trajectory.hit {
+    ///         println!("Hit {:?} at {} with normal {}", hit.entity, hit.point, hit.normal);
+    ///     }
+    /// }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_trajectory(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        velocity: Vector,
+        gravity: Vector,
+        time_step: Scalar,
+        max_time: Scalar,
+        query_filter: SpatialQueryFilter,
+    ) -> Trajectory {
+        self.query_pipeline.cast_trajectory(
+            shape,
+            origin,
+            shape_rotation,
+            velocity,
+            gravity,
+            time_step,
+            max_time,
+            query_filter,
+        )
+    }
+
+    /// Casts a [shape](spatial_query#shapecasting) that both translates and rotates, and computes the closest
+    /// [hit](ShapeHitData) with a collider. If there are no hits, `None` is returned.
+    ///
+    /// The shape moves by `translation` and rotates by `rotation_delta` around its origin over the cast,
+    /// and the [time of impact](ShapeHitData::time_of_impact) is the fraction of the motion
+    /// in the range `[0, 1]` at which the first hit occurs. This is useful for things like melee weapon arcs
+    /// and swinging doors where the rotation can't be ignored.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The initial rotation of the shape being cast.
+    /// - `translation`: How much the shape moves over the cast.
+    /// - `rotation_delta`: How much the shape rotates over the cast, in radians in 2D
+    ///   and as a scaled axis in 3D.
+    /// - `ignore_origin_penetration`: If true and the shape is already penetrating a collider at the
+    ///   shape origin, the hit will be ignored and only the next hit will be computed. Otherwise, the initial
+    ///   hit will be returned.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_sword_hit(spatial_query: SpatialQuery) {
+    ///     // Swing a sword a quarter turn around the Y axis and print the first hit
+    ///     if let Some(first_hit) = spatial_query.cast_shape_rotating(
+    ///         &Collider::cuboid(0.1, 0.1, 2.0),                  // Shape
+    ///         Vec3::ZERO,                                        // Origin
+    ///         Quat::default(),                                   // Initial shape rotation
+    ///         Vec3::ZERO,                                        // Translation over the cast
+    ///         Vec3::Y * std::f32::consts::FRAC_PI_2,             // Rotation over the cast
+    ///         true,                                              // Should initial penetration at the origin be ignored
+    ///         SpatialQueryFilter::default(),                     // Query filter
+    ///     ) {
+    ///         println!("First hit: {:?}", first_hit);
+    ///     }
+    /// }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape_rotating(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        translation: Vector,
+        rotation_delta: AngularVector,
+        ignore_origin_penetration: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeHitData> {
+        self.query_pipeline.cast_shape_rotating(
+            shape,
+            origin,
+            shape_rotation,
+            translation,
+            rotation_delta,
+            ignore_origin_penetration,
+            query_filter,
+        )
+    }
+
+    /// Sweeps a [shape](spatial_query#shapecasting) from a start pose to an end pose, and computes
+    /// a [hit](ShapeHitData) for every collider that the shape touches along the way.
+    ///
+    /// The motion between the poses is interpolated, translating linearly and rotating around the shape origin,
+    /// so fast motions like weapon swings don't miss colliders between frames. The [time of impact](ShapeHitData::time_of_impact)
+    /// of each hit is the earliest fraction of the motion in the range `[0, 1]` at which the shape touches the collider,
+    /// and colliders that already intersect the shape at the start pose are hit at `0`.
+    /// The hits are sorted by their time of impact.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being swept represented as a [`Collider`].
+    /// - `start`: The position and rotation of the shape at the start of the sweep.
+    /// - `end`: The position and rotation of the shape at the end of the sweep.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_swing_hits(spatial_query: SpatialQuery) {
+    ///     // Swing a sword between the poses of the previous and the current frame
+    ///     let hits = spatial_query.sweep_between(
+    ///         &Collider::cuboid(0.1, 0.1, 2.0),                             // Shape
+    ///         (Vec3::ZERO, Quat::default()),                                // Start pose
+    ///         (Vec3::X, Quat::from_rotation_y(std::f32::consts::FRAC_PI_2)), // End pose
+    ///         SpatialQueryFilter::default(),                                // Query filter
+    ///     );
+    ///
+    ///     for hit in hits {
+    ///         println!("Hit {:?} at fraction {}", hit.entity, hit.time_of_impact);
+    ///     }
+    /// }
+    /// ```
+    pub fn sweep_between(
+        &self,
+        shape: &Collider,
+        start: (Vector, RotationValue),
+        end: (Vector, RotationValue),
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<ShapeHitData> {
+        self.query_pipeline
+            .sweep_between(shape, start, end, query_filter)
+    }
+
+    /// Casts a [shape](spatial_query#shapecasting) that is virtually inflated by the given `inflation` margin,
+    /// and computes the closest [hit](ShapeHitData) with a collider. If there are no hits, `None` is returned.
+    ///
+    /// A hit occurs when the distance between the cast shape and a collider is within `inflation`,
+    /// so the shape stops short of the collider without having to enlarge the actual shape.
+    /// This is useful for character controllers that need to keep a small separation from walls.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the shape can travel.
+    /// - `inflation`: The margin by which the shape is virtually inflated.
+    /// - `ignore_origin_penetration`: If true, colliders that are already within the inflated shape
+    ///   at the shape origin are ignored. Otherwise, the initial hit will be returned.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn move_character(spatial_query: SpatialQuery) {
+    ///     // Stop 0.05 units before hitting a wall
+    ///     if let Some(first_hit) = spatial_query.cast_shape_inflated(
+    ///         &Collider::capsule(1.0, 0.5),    // Shape
+    ///         Vec3::ZERO,                      // Origin
+    ///         Quat::default(),                 // Shape rotation
+    ///         Direction3d::X,                  // Direction
+    ///         2.0,                             // Maximum time of impact (travel distance)
+    ///         0.05,                            // Inflation
+    ///         true,                            // Should initial penetration at the origin be ignored
+    ///         SpatialQueryFilter::default(),   // Query filter
+    ///     ) {
+    ///         println!("Can move {} units", first_hit.time_of_impact);
+    ///     }
+    /// }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape_inflated(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        inflation: Scalar,
+        ignore_origin_penetration: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeHitData> {
+        self.query_pipeline.cast_shape_inflated(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            max_time_of_impact,
+            inflation,
+            ignore_origin_penetration,
+            query_filter,
+        )
+    }
+
     /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes computes all [hits](ShapeHitData)
     /// in the order of the time of impact until `max_hits` is reached.
     ///
@@ -764,6 +1181,35 @@ impl<'w, 's> SpatialQuery<'w, 's> {
             .shape_intersections(shape, shape_position, shape_rotation, query_filter)
     }
 
+    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
+    /// that is intersecting the given `shape` virtually inflated by the given `inflation` margin.
+    ///
+    /// In other words, this finds all colliders whose distance to the shape is within `inflation`.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that intersections are tested against represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `inflation`: The margin by which the shape is virtually inflated.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    pub fn shape_intersections_inflated(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        inflation: Scalar,
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<Entity> {
+        self.query_pipeline.shape_intersections_inflated(
+            shape,
+            shape_position,
+            shape_rotation,
+            inflation,
+            query_filter,
+        )
+    }
+
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
     /// that is intersecting the given `shape` with a given position and rotation, calling `callback` for each
     /// intersection. The search stops when `callback` returns `false` or all intersections have been found.
@@ -821,4 +1267,99 @@ impl<'w, 's> SpatialQuery<'w, 's> {
             callback,
         )
     }
+    /// An [intersection test](spatial_query#intersection-tests) that finds the first entity with a [`Collider`]
+    /// that is intersecting the given `shape` with a given position and rotation, or `None` if there is no intersection.
+    ///
+    /// The search stops at the first intersection, and no hits are allocated.
+    /// This can be used for finding what is blocking the placement of an object.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that intersections are tested against represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_blocking_entity(spatial_query: SpatialQuery) {
+    ///     if let Some(entity) = spatial_query.first_blocking_overlap(
+    ///         &Collider::cuboid(1.0, 2.0, 1.0), // Shape
+    ///         Vec3::new(0.0, 1.0, 0.0),         // Shape position
+    ///         Quat::default(),                  // Shape rotation
+    ///         SpatialQueryFilter::default(),    // Query filter
+    ///     ) {
+    ///         println!("Placement blocked by {:?}", entity);
+    ///     }
+    /// }
+    /// ```
+    pub fn first_blocking_overlap(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<Entity> {
+        self.query_pipeline.first_blocking_overlap(
+            shape,
+            shape_position,
+            shape_rotation,
+            query_filter,
+        )
+    }
+
+    /// An [intersection test](spatial_query#intersection-tests) that checks if the given `shape` with a given
+    /// position and rotation fits in the world without intersecting any [`Collider`].
+    ///
+    /// The search stops at the first intersection, and no hits are allocated. This can be used for
+    /// placement validation, like checking if a building can be placed or if a spawn point is free.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that intersections are tested against represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn check_spawn_point(spatial_query: SpatialQuery) {
+    ///     let is_free = spatial_query.shape_fits(
+    ///         &Collider::capsule(1.0, 0.5),   // Shape
+    ///         Vec3::new(0.0, 1.0, 0.0),       // Shape position
+    ///         Quat::default(),                // Shape rotation
+    ///         SpatialQueryFilter::default(),  // Query filter
+    ///     );
+    ///
+    ///     if is_free {
+    ///         println!("The spawn point is free");
+    ///     }
+    /// }
+    /// ```
+    pub fn shape_fits(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        query_filter: SpatialQueryFilter,
+    ) -> bool {
+        self.query_pipeline
+            .shape_fits(shape, shape_position, shape_rotation, query_filter)
+    }
 }
@@ -0,0 +1,47 @@
+use crate::prelude::*;
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+
+/// A ballistic trajectory computed with [`SpatialQuery::cast_trajectory`].
+///
+/// The trajectory is a polyline of sampled positions of the cast shape, starting at the origin.
+/// If the shape hit a collider, the last point is the position of the shape at the time of impact,
+/// and the impact is stored in [`hit`](Self::hit).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trajectory {
+    /// The sampled positions of the shape along the trajectory, up to the first impact.
+    pub points: Vec<Vector>,
+    /// The first impact along the trajectory, or `None` if the shape didn't hit anything
+    /// within the maximum time.
+    pub hit: Option<TrajectoryHit>,
+}
+
+impl Trajectory {
+    /// Returns the position of the shape at the end of the trajectory, or `None` if the trajectory is empty.
+    pub fn end(&self) -> Option<Vector> {
+        self.points.last().copied()
+    }
+}
+
+/// The first impact along a [`Trajectory`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrajectoryHit {
+    /// The entity of the collider that was hit.
+    pub entity: Entity,
+    /// The world-space point of impact.
+    pub point: Vector,
+    /// The world-space outward normal of the hit collider at the point of impact.
+    pub normal: Vector,
+    /// The time in seconds along the trajectory at which the impact happened.
+    pub time: Scalar,
+}
+
+impl MapEntities for TrajectoryHit {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.entity = entity_mapper.map_entity(self.entity);
+    }
+}
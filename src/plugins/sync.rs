@@ -26,6 +26,12 @@ use bevy::{prelude::*, utils::intern::Interned};
 ///
 /// If you would like a child entity to be rigidly attached to its parent, you could use a [`FixedJoint`]
 /// or write your own system to handle hierarchies differently.
+///
+/// ## Smoothing corrections
+///
+/// When the physics state of a body is snapped to an authoritative state, for example one received
+/// from a server, the [`CorrectionSmoothing`] component can be used to visually blend
+/// the `Transform` to the corrected pose over time.
 pub struct SyncPlugin {
     schedule: Interned<dyn ScheduleLabel>,
 }
@@ -50,7 +56,8 @@ impl Default for SyncPlugin {
 impl Plugin for SyncPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SyncConfig>()
-            .register_type::<SyncConfig>();
+            .register_type::<SyncConfig>()
+            .register_type::<CorrectionSmoothing>();
 
         app.configure_sets(
             self.schedule,
@@ -80,7 +87,8 @@ impl Plugin for SyncPlugin {
                 .chain()
                 .after(PhysicsSet::Prepare)
                 .before(PhysicsSet::StepSimulation)
-                .run_if(|config: Res<SyncConfig>| config.transform_to_position),
+                .run_if(|config: Res<SyncConfig>| config.transform_to_position)
+                .run_if(crate::setup::sync_transforms_while_paused),
         );
 
         // Apply `Transform` changes to `Position` and `Rotation`
@@ -93,15 +101,19 @@ impl Plugin for SyncPlugin {
             )
                 .chain()
                 .in_set(SyncSet::TransformToPosition)
-                .run_if(|config: Res<SyncConfig>| config.transform_to_position),
+                .run_if(|config: Res<SyncConfig>| config.transform_to_position)
+                .run_if(crate::setup::sync_transforms_while_paused),
         );
 
-        // Apply `Position` and `Rotation` changes to `Transform`
+        // Apply `Position` and `Rotation` changes to `Transform`,
+        // and blend the transforms of corrected bodies to the corrected poses
         app.add_systems(
             self.schedule,
-            position_to_transform
+            (position_to_transform, apply_correction_smoothing)
+                .chain()
                 .in_set(SyncSet::PositionToTransform)
-                .run_if(|config: Res<SyncConfig>| config.position_to_transform),
+                .run_if(|config: Res<SyncConfig>| config.position_to_transform)
+                .run_if(crate::setup::sync_transforms_while_paused),
         );
 
         // Update `PreviousGlobalTransform` for next frame's `GlobalTransform` change detection
@@ -114,7 +126,8 @@ impl Plugin for SyncPlugin {
             )
                 .chain()
                 .in_set(SyncSet::Update)
-                .run_if(|config: Res<SyncConfig>| config.transform_to_position),
+                .run_if(|config: Res<SyncConfig>| config.transform_to_position)
+                .run_if(crate::setup::sync_transforms_while_paused),
         );
     }
 }
@@ -356,3 +369,166 @@ pub fn update_previous_global_transforms(
         previous_transform.0 = *transform;
     }
 }
+
+/// Visually smooths out corrections of a body's [`Position`] and [`Rotation`],
+/// for example when a client snaps the body to an authoritative state received from a server.
+///
+/// Corrections should be applied using [`CorrectionSmoothing::correct`]. The physics state is snapped
+/// to the corrected pose immediately, but the `Transform` is blended from the previously rendered pose
+/// to the corrected pose over the given [`duration`](CorrectionSmoothing::duration).
+///
+/// The smoothing is applied by the [`SyncPlugin`] after [`position_to_transform`],
+/// so it requires [`SyncConfig::position_to_transform`] to be enabled. It only affects bodies
+/// that don't have a parent.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// #[derive(Event)]
+/// struct ServerState {
+///     entity: Entity,
+///     position: Vector,
+///     rotation: Rotation,
+/// }
+///
+/// fn apply_server_state(
+///     mut events: EventReader<ServerState>,
+///     mut bodies: Query<(&mut CorrectionSmoothing, &mut Position, &mut Rotation)>,
+/// ) {
+///     for state in events.read() {
+///         if let Ok((mut smoothing, mut position, mut rotation)) = bodies.get_mut(state.entity) {
+///             smoothing.correct(&mut position, &mut rotation, state.position, state.rotation);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component)]
+pub struct CorrectionSmoothing {
+    /// The time in seconds that it takes for the `Transform` to be blended to the corrected pose.
+    /// Defaults to 0.1.
+    pub duration: Scalar,
+    /// The translation from the corrected position to the rendered position at the time of the correction.
+    translation_offset: Vector,
+    /// The rotation from the corrected rotation to the rendered rotation at the time of the correction.
+    rotation_offset: Quat,
+    /// The time in seconds since the last correction.
+    elapsed: Scalar,
+}
+
+impl Default for CorrectionSmoothing {
+    fn default() -> Self {
+        Self::new(0.1)
+    }
+}
+
+impl CorrectionSmoothing {
+    /// Creates a [`CorrectionSmoothing`] component that blends corrections over the given duration in seconds.
+    pub fn new(duration: Scalar) -> Self {
+        Self {
+            duration,
+            translation_offset: Vector::ZERO,
+            rotation_offset: Quat::IDENTITY,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Snaps the given [`Position`] and [`Rotation`] to the corrected pose and starts blending
+    /// the `Transform` from the currently rendered pose to the corrected pose.
+    ///
+    /// If a previous correction is still being blended, the blending continues from the currently
+    /// rendered pose, so there is no visible jump.
+    #[allow(clippy::unnecessary_cast)]
+    pub fn correct(
+        &mut self,
+        position: &mut Position,
+        rotation: &mut Rotation,
+        new_position: Vector,
+        new_rotation: Rotation,
+    ) {
+        let weight = self.weight();
+        let rendered_position = position.0 + self.translation_offset * weight;
+        let rendered_rotation = Quat::IDENTITY.slerp(self.rotation_offset, weight as f32)
+            * Quaternion::from(*rotation).f32();
+
+        self.translation_offset = rendered_position - new_position;
+        self.rotation_offset =
+            (rendered_rotation * Quaternion::from(new_rotation).f32().inverse()).normalize();
+        self.elapsed = 0.0;
+
+        position.0 = new_position;
+        *rotation = new_rotation;
+    }
+
+    /// Returns true if a correction is currently being blended.
+    pub fn is_smoothing(&self) -> bool {
+        self.translation_offset != Vector::ZERO || self.rotation_offset != Quat::IDENTITY
+    }
+
+    /// Returns the translation from the physics [`Position`] to the rendered position.
+    pub fn translation_offset(&self) -> Vector {
+        self.translation_offset * self.weight()
+    }
+
+    /// Returns how much of the offsets remain, from 1.0 right after a correction to 0.0 when the blending is done.
+    fn weight(&self) -> Scalar {
+        if self.duration <= 0.0 {
+            return 0.0;
+        }
+        // Smoothstep for a gradual start and end of the blending
+        let t = (self.elapsed / self.duration).clamp(0.0, 1.0);
+        1.0 - t * t * (3.0 - 2.0 * t)
+    }
+}
+
+/// Offsets the `Transform`s of bodies with [`CorrectionSmoothing`] to blend them
+/// from the previously rendered poses to the corrected poses.
+///
+/// The `Transform` is computed from [`Position`] and [`Rotation`] every frame while blending,
+/// and [`PreviousGlobalTransform`] is updated afterwards, so the offset is not applied to the physics state.
+#[allow(clippy::unnecessary_cast)]
+pub fn apply_correction_smoothing(
+    mut bodies: Query<
+        (
+            &mut Transform,
+            &Position,
+            &Rotation,
+            &mut CorrectionSmoothing,
+        ),
+        Without<Parent>,
+    >,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_f64().adjust_precision();
+
+    for (mut transform, position, rotation, mut smoothing) in &mut bodies {
+        if !smoothing.is_smoothing() {
+            continue;
+        }
+
+        smoothing.elapsed += delta_secs;
+        let weight = smoothing.weight();
+
+        // Reset the offsets when done so that the exact pose is written once
+        if weight <= 0.0 {
+            smoothing.translation_offset = Vector::ZERO;
+            smoothing.rotation_offset = Quat::IDENTITY;
+        }
+
+        let translation = (position.0 + smoothing.translation_offset * weight).f32();
+        #[cfg(feature = "2d")]
+        {
+            transform.translation = translation.extend(transform.translation.z);
+        }
+        #[cfg(feature = "3d")]
+        {
+            transform.translation = translation;
+        }
+        transform.rotation = Quat::IDENTITY.slerp(smoothing.rotation_offset, weight as f32)
+            * Quaternion::from(*rotation).f32();
+    }
+}
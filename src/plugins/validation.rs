@@ -0,0 +1,251 @@
+//! Detects common physics misconfigurations and logs actionable warnings.
+//!
+//! See [`PhysicsValidationPlugin`].
+
+use std::collections::BTreeMap;
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A plugin that scans new colliders and joints for common misconfigurations
+/// and logs warnings that describe how to fix them.
+///
+/// The following issues are detected:
+///
+/// - Ball colliders with a non-uniform scale. Parry can't represent scaled balls exactly,
+///   so they are approximated with a convex polygon or polyhedron.
+/// - Joints attached to entities that don't exist or aren't [rigid bodies](RigidBody).
+/// - [`CollisionLayers`] that can never collide with any other collider.
+/// - Joints between two dynamic bodies with a mass ratio above
+///   [`PhysicsValidationConfig::max_joint_mass_ratio`], which makes joints unstable.
+///
+/// The checks are run each time colliders or joints are added, and issues of the same kind
+/// are grouped into a single warning. Each issue is also sent as a [`PhysicsValidationIssue`] event.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added manually.
+/// It is mainly intended for development builds.
+pub struct PhysicsValidationPlugin;
+
+impl Plugin for PhysicsValidationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsValidationConfig>()
+            .register_type::<PhysicsValidationConfig>()
+            .init_resource::<PendingValidationIssues>()
+            .add_event::<PhysicsValidationIssue>();
+
+        app.add_systems(
+            PostUpdate,
+            (
+                (
+                    validate_colliders,
+                    validate_joints::<FixedJoint>,
+                    validate_joints::<RevoluteJoint>,
+                    validate_joints::<SphericalJoint>,
+                    validate_joints::<PrismaticJoint>,
+                    validate_joints::<DistanceJoint>,
+                    validate_joints::<SplineDriveJoint>,
+                    validate_joints::<SuspensionConstraint>,
+                    validate_joints::<GenericJoint>,
+                    validate_joints::<GearJoint>,
+                    validate_joints::<PulleyJoint>,
+                ),
+                report_validation_issues,
+            )
+                .chain()
+                .after(PhysicsSet::Prepare)
+                .before(PhysicsSet::StepSimulation),
+        );
+    }
+}
+
+/// Configures the checks of the [`PhysicsValidationPlugin`].
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Resource)]
+pub struct PhysicsValidationConfig {
+    /// The largest allowed ratio between the masses of two dynamic bodies attached by a joint.
+    /// Defaults to 100.
+    pub max_joint_mass_ratio: Scalar,
+}
+
+impl Default for PhysicsValidationConfig {
+    fn default() -> Self {
+        Self {
+            max_joint_mass_ratio: 100.0,
+        }
+    }
+}
+
+/// The kind of a [`PhysicsValidationIssue`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PhysicsValidationIssueKind {
+    /// A ball collider has a non-uniform scale.
+    NonUniformBallScale,
+    /// A joint is attached to an entity that doesn't exist or isn't a rigid body.
+    MissingJointBody,
+    /// A collider has collision layers that can never collide with any other collider.
+    UnreachableCollisionLayers,
+    /// The masses of the two bodies attached by a joint differ by too much.
+    JointMassRatio,
+}
+
+impl PhysicsValidationIssueKind {
+    /// Returns a description of the issue and how to fix it.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::NonUniformBallScale => {
+                "ball colliders with a non-uniform scale are approximated with a convex hull. \
+                Use a uniform scale, or a capsule or convex collider instead"
+            }
+            Self::MissingJointBody => {
+                "joints are attached to entities that don't exist or aren't rigid bodies, \
+                so the joints will be orphaned. Check the entities passed to the joints"
+            }
+            Self::UnreachableCollisionLayers => {
+                "colliders have collision layers that can never collide with any other collider. \
+                Check the memberships and filters of their `CollisionLayers`"
+            }
+            Self::JointMassRatio => {
+                "joints connect bodies with very different masses, which makes them unstable. \
+                Reduce the mass ratio or increase the substep count"
+            }
+        }
+    }
+}
+
+/// An event that is sent by the [`PhysicsValidationPlugin`] for each detected misconfiguration.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PhysicsValidationIssue {
+    /// The kind of the issue.
+    pub kind: PhysicsValidationIssueKind,
+    /// The collider or joint entity that the issue was detected for.
+    pub entity: Entity,
+}
+
+/// Issues detected during the current validation pass. They are reported at the end of the pass.
+#[derive(Resource, Default)]
+struct PendingValidationIssues(Vec<PhysicsValidationIssue>);
+
+/// Checks new colliders for non-uniformly scaled balls and collision layers that can never collide.
+fn validate_colliders(
+    new_colliders: Query<(Entity, &Collider, Option<&CollisionLayers>), Added<Collider>>,
+    colliders: Query<Option<&CollisionLayers>, With<Collider>>,
+    mut issues: ResMut<PendingValidationIssues>,
+) {
+    if new_colliders.is_empty() {
+        return;
+    }
+
+    // Count the colliders that are members of or filter each layer once per pass,
+    // so that the layers of the other colliders can be combined for each new collider without
+    // iterating over all colliders again.
+    let mut membership_counts = [0_usize; 32];
+    let mut filter_counts = [0_usize; 32];
+    let mut collider_count = 0;
+    for layers in &colliders {
+        let layers = layers.copied().unwrap_or_default();
+        for bit in 0..32 {
+            membership_counts[bit] += (layers.memberships.0 >> bit & 1) as usize;
+            filter_counts[bit] += (layers.filters.0 >> bit & 1) as usize;
+        }
+        collider_count += 1;
+    }
+
+    for (entity, collider, layers) in &new_colliders {
+        if collider.shape().as_ball().is_some() {
+            let scale = collider.scale();
+            if (scale - Vector::splat(scale.x)).abs().max_element() > Scalar::EPSILON {
+                issues.0.push(PhysicsValidationIssue {
+                    kind: PhysicsValidationIssueKind::NonUniformBallScale,
+                    entity,
+                });
+            }
+        }
+
+        let layers = layers.copied().unwrap_or_default();
+
+        // Combine the layers of all other colliders. A pair can only collide if the memberships of each
+        // collider intersect the filters of the other one, so if either combination is empty,
+        // the collider can never collide with anything.
+        let mut other_memberships = LayerMask::NONE;
+        let mut other_filters = LayerMask::NONE;
+        for bit in 0..32 {
+            if membership_counts[bit] > (layers.memberships.0 >> bit & 1) as usize {
+                other_memberships.0 |= 1 << bit;
+            }
+            if filter_counts[bit] > (layers.filters.0 >> bit & 1) as usize {
+                other_filters.0 |= 1 << bit;
+            }
+        }
+        let has_others = collider_count > 1;
+
+        let is_unreachable = layers.memberships == LayerMask::NONE
+            || layers.filters == LayerMask::NONE
+            || (has_others
+                && ((layers.filters & other_memberships) == LayerMask::NONE
+                    || (layers.memberships & other_filters) == LayerMask::NONE));
+
+        if is_unreachable {
+            issues.0.push(PhysicsValidationIssue {
+                kind: PhysicsValidationIssueKind::UnreachableCollisionLayers,
+                entity,
+            });
+        }
+    }
+}
+
+/// Checks new joints for missing bodies and large mass ratios.
+fn validate_joints<T: Joint>(
+    joints: Query<(Entity, &T), Added<T>>,
+    bodies: Query<(&RigidBody, Option<&Mass>)>,
+    config: Res<PhysicsValidationConfig>,
+    mut issues: ResMut<PendingValidationIssues>,
+) {
+    for (entity, joint) in &joints {
+        let [entity1, entity2] = joint.entities();
+        let (Ok((rb1, mass1)), Ok((rb2, mass2))) = (bodies.get(entity1), bodies.get(entity2))
+        else {
+            issues.0.push(PhysicsValidationIssue {
+                kind: PhysicsValidationIssueKind::MissingJointBody,
+                entity,
+            });
+            continue;
+        };
+
+        let (mass1, mass2) = (mass1.map_or(0.0, |m| m.0), mass2.map_or(0.0, |m| m.0));
+
+        if rb1.is_dynamic() && rb2.is_dynamic() && mass1 > 0.0 && mass2 > 0.0 {
+            let ratio = mass1.max(mass2) / mass1.min(mass2);
+            if ratio > config.max_joint_mass_ratio {
+                issues.0.push(PhysicsValidationIssue {
+                    kind: PhysicsValidationIssueKind::JointMassRatio,
+                    entity,
+                });
+            }
+        }
+    }
+}
+
+/// Logs the detected issues grouped by their kind and sends them as events.
+fn report_validation_issues(
+    mut issues: ResMut<PendingValidationIssues>,
+    mut events: EventWriter<PhysicsValidationIssue>,
+) {
+    if issues.0.is_empty() {
+        return;
+    }
+
+    let mut groups = BTreeMap::<PhysicsValidationIssueKind, Vec<Entity>>::new();
+    for issue in issues.0.iter() {
+        groups.entry(issue.kind).or_default().push(issue.entity);
+    }
+
+    for (kind, entities) in groups {
+        warn!(
+            "{} physics validation issue(s): {}. Entities: {entities:?}",
+            entities.len(),
+            kind.description()
+        );
+    }
+
+    events.send_batch(issues.0.drain(..));
+}
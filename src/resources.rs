@@ -41,7 +41,21 @@ impl Default for SubstepCount {
     }
 }
 
-/// A threshold that indicates the maximum linear and angular velocity allowed for a body to be deactivated.
+/// The index of the substep that is currently being run in the [`SubstepSchedule`],
+/// in the range `0..SubstepCount`.
+///
+/// This is used for finding out when something happened within a physics step,
+/// like the substep at which a collision started (see [`ContactStart`]).
+#[derive(Reflect, Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct SubstepIndex(pub u32);
+
+/// A threshold that indicates how still a body needs to be for it to be deactivated.
+///
+/// By default, separate thresholds are used for the linear and angular velocity of bodies.
+/// The [`Energy`](SleepingThreshold::Energy) threshold uses the kinetic energy of bodies normalized by their mass instead,
+/// which takes the size of the bodies into account for rotation, so that one threshold works for both
+/// tiny and huge bodies.
 ///
 /// Setting a negative sleeping threshold disables sleeping entirely.
 ///
@@ -49,23 +63,47 @@ impl Default for SubstepCount {
 #[derive(Reflect, Resource, Clone, Copy, PartialEq, PartialOrd, Debug)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[reflect(Resource)]
-pub struct SleepingThreshold {
-    /// The maximum linear velocity allowed for a body to be marked as sleeping.
-    pub linear: Scalar,
-    /// The maximum angular velocity allowed for a body to be marked as sleeping.
-    pub angular: Scalar,
+pub enum SleepingThreshold {
+    /// Separate thresholds for the linear and angular velocity of bodies.
+    Velocity {
+        /// The maximum linear velocity allowed for a body to be marked as sleeping.
+        linear: Scalar,
+        /// The maximum angular velocity allowed for a body to be marked as sleeping.
+        angular: Scalar,
+    },
+    /// The maximum kinetic energy per unit of mass allowed for a body to be marked as sleeping, in J/kg (m²/s²).
+    ///
+    /// The energy is measured in meters using the [`PhysicsLengthUnit`], so the same threshold works
+    /// regardless of the scale of the world.
+    Energy(Scalar),
 }
 
 impl Default for SleepingThreshold {
     fn default() -> Self {
-        Self {
+        Self::Velocity {
             linear: 0.1,
             angular: 0.2,
         }
     }
 }
 
-/// How long in seconds the linear and angular velocity of a body need to be below
+/// The number of world units per meter, used for scaling thresholds that are defined in meters
+/// to the scale of the world, like the [energy sleeping threshold](SleepingThreshold::Energy).
+///
+/// For example, a 2D game that uses pixels as units with 100 pixels per meter should use a length unit of 100.
+/// Defaults to 1.
+#[derive(Reflect, Resource, Clone, Copy, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct PhysicsLengthUnit(pub Scalar);
+
+impl Default for PhysicsLengthUnit {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// How long in seconds the velocity of a body needs to be below
 /// the [`SleepingThreshold`] before the body is deactivated. Defaults to 1 second.
 ///
 /// See [`Sleeping`] for further information about sleeping.
@@ -80,6 +118,151 @@ impl Default for DeactivationTime {
     }
 }
 
+/// Configures whether disabling a [rigid body](RigidBody) with [`RigidBodyDisabled`] also disables
+/// its colliders and the [joints] attached to it.
+///
+/// When propagation is enabled, [`ColliderDisabled`] is added to the colliders of disabled bodies,
+/// including child colliders, and [`JointDisabled`] is added to joints attached to them. When the body
+/// is enabled again, the components added by the propagation are removed. Colliders and joints
+/// that were disabled manually stay disabled.
+///
+/// Both colliders and joints are disabled by default.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         // Keep the colliders of disabled bodies active
+///         .insert_resource(DisablePropagation {
+///             colliders: false,
+///             joints: true,
+///         })
+///         .run();
+/// }
+/// ```
+#[derive(Reflect, Resource, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct DisablePropagation {
+    /// If true, the colliders of disabled bodies are disabled.
+    pub colliders: bool,
+    /// If true, joints attached to disabled bodies are disabled.
+    pub joints: bool,
+}
+
+impl Default for DisablePropagation {
+    fn default() -> Self {
+        Self {
+            colliders: true,
+            joints: true,
+        }
+    }
+}
+
+/// Configures which parts of the physics engine keep running while [`Time<Physics>`](Physics) is paused.
+///
+/// When physics is paused, the [`PhysicsSchedule`] is not run, so the simulation is frozen.
+/// Editors and debug tools often still move [kinematic](RigidBody::Kinematic) bodies around
+/// and cast rays while gameplay is frozen, so some updates can be kept running:
+///
+/// - `update_spatial_queries`: Keeps updating child collider positions, [`ColliderAabb`]s,
+/// the [`SpatialQueryPipeline`], [`RayHits`] and [`ShapeHits`], so queries see the latest positions.
+/// - `sync_transforms`: Keeps synchronizing `Transform` with [`Position`] and [`Rotation`]
+/// as configured by [`SyncConfig`].
+///
+/// By default, transforms are synchronized, but spatial queries are not updated while paused.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         // Keep raycasts working in the editor while physics is paused
+///         .insert_resource(PhysicsPausedBehavior {
+///             update_spatial_queries: true,
+///             sync_transforms: true,
+///         })
+///         .run();
+/// }
+/// ```
+#[derive(Reflect, Resource, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct PhysicsPausedBehavior {
+    /// If true, spatial queries are updated while physics is paused.
+    pub update_spatial_queries: bool,
+    /// If true, transforms are synchronized while physics is paused.
+    pub sync_transforms: bool,
+}
+
+impl Default for PhysicsPausedBehavior {
+    fn default() -> Self {
+        Self {
+            update_spatial_queries: false,
+            sync_transforms: true,
+        }
+    }
+}
+
+/// A resource that configures how rotations are integrated from [angular velocity](AngularVelocity).
+///
+/// Bodies that spin very fast, like flywheels and drills, can rotate so much during a single substep
+/// that the integrated rotation becomes inaccurate and the simulation explodes. `max_delta_rotation`
+/// limits the angle that a body can rotate by in a single substep. Unlike clamping the angular velocity,
+/// the velocity that was clamped away is kept, so fast spins don't lose energy.
+///
+/// In 3D, `exponential_map` integrates rotations using the exponential map instead of the default
+/// first-order quaternion update, which stays accurate for large rotations.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         // Rotate by at most half a radian per substep
+///         .insert_resource(RotationIntegration {
+///             exponential_map: true,
+///             max_delta_rotation: 0.5,
+///         })
+///         .run();
+/// }
+/// ```
+#[derive(Reflect, Resource, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct RotationIntegration {
+    /// If true, rotations are integrated using the exponential map in 3D. Has no effect in 2D,
+    /// where rotations are always integrated exactly.
+    pub exponential_map: bool,
+    /// The maximum angle in radians that a body can rotate by in a single substep.
+    pub max_delta_rotation: Scalar,
+}
+
+impl Default for RotationIntegration {
+    fn default() -> Self {
+        Self {
+            exponential_map: false,
+            max_delta_rotation: Scalar::INFINITY,
+        }
+    }
+}
+
 /// A resource for the global gravitational acceleration.
 ///
 /// The default is an acceleration of 9.81 m/s^2 pointing down, which is approximate to the gravitational
@@ -130,3 +313,120 @@ impl Gravity {
     /// Zero gravity.
     pub const ZERO: Gravity = Gravity(Vector::ZERO);
 }
+
+/// Ambient forces like wind that affect all bodies with a [`WindInfluence`].
+///
+/// The wind is a velocity made of a constant base [`wind`](Self::wind) and optional random gusts.
+/// During integration, each body with a [`WindInfluence`] is pushed towards the wind velocity
+/// with a force proportional to the velocity of the body relative to the wind.
+/// This makes light bodies like leaves and debris follow the wind quickly, while heavy bodies barely react.
+///
+/// The gusts are smooth noise that depends only on the elapsed physics time and the [seed](Self::seed),
+/// so the wind is deterministic.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::{math::*, prelude::*};")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::{math::*, prelude::*};")]
+///
+/// fn start_storm(mut ambient_forces: ResMut<PhysicsAmbientForces>) {
+///     ambient_forces.wind = Vector::X * 15.0;
+///     ambient_forces.gust_strength = 5.0;
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     // A light crate that is blown around by the wind
+///     commands.spawn((RigidBody::Dynamic, WindInfluence(0.5)));
+/// }
+/// ```
+#[derive(Reflect, Resource, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct PhysicsAmbientForces {
+    /// The base velocity of the wind.
+    ///
+    /// Default: zero
+    pub wind: Vector,
+    /// The maximum speed of random gusts added to the base wind along each axis. Zero disables gusts.
+    ///
+    /// Default: `0.0`
+    pub gust_strength: Scalar,
+    /// How many times per second the gusts change on average.
+    ///
+    /// Default: `0.5`
+    pub gust_frequency: Scalar,
+    /// The seed of the random gusts.
+    ///
+    /// Default: `0`
+    pub seed: u32,
+}
+
+impl Default for PhysicsAmbientForces {
+    fn default() -> Self {
+        Self {
+            wind: Vector::ZERO,
+            gust_strength: 0.0,
+            gust_frequency: 0.5,
+            seed: 0,
+        }
+    }
+}
+
+impl PhysicsAmbientForces {
+    /// Creates a new [`PhysicsAmbientForces`] with the given base wind velocity and no gusts.
+    pub fn new(wind: Vector) -> Self {
+        Self {
+            wind,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the maximum speed and frequency of random gusts.
+    pub fn with_gusts(self, strength: Scalar, frequency: Scalar) -> Self {
+        Self {
+            gust_strength: strength,
+            gust_frequency: frequency,
+            ..self
+        }
+    }
+
+    /// Returns the wind velocity at the given elapsed physics time in seconds, including gusts.
+    pub fn wind_at(&self, elapsed_secs: Scalar) -> Vector {
+        if self.gust_strength == 0.0 {
+            return self.wind;
+        }
+
+        let t = elapsed_secs * self.gust_frequency;
+        #[cfg(feature = "2d")]
+        let gust = Vector::new(
+            smooth_noise(t, self.seed),
+            smooth_noise(t, self.seed.wrapping_add(1)),
+        );
+        #[cfg(feature = "3d")]
+        let gust = Vector::new(
+            smooth_noise(t, self.seed),
+            smooth_noise(t, self.seed.wrapping_add(1)),
+            smooth_noise(t, self.seed.wrapping_add(2)),
+        );
+        self.wind + gust * self.gust_strength
+    }
+}
+
+/// Smoothly interpolated value noise in the range `[-1.0, 1.0]`.
+fn smooth_noise(t: Scalar, seed: u32) -> Scalar {
+    let hash = |i: i64| {
+        let mut x = (i as u64 as u32) ^ seed.wrapping_mul(0x9E37_79B9);
+        x = (x ^ (x >> 16)).wrapping_mul(0x7FEB_352D);
+        x = (x ^ (x >> 15)).wrapping_mul(0x846C_A68B);
+        x ^= x >> 16;
+        x as Scalar / u32::MAX as Scalar * 2.0 - 1.0
+    };
+
+    let i = t.floor();
+    let f = t - i;
+    let smooth = f * f * (3.0 - 2.0 * f);
+    let (a, b) = (hash(i as i64), hash(i as i64 + 1));
+    a + (b - a) * smooth
+}
@@ -0,0 +1,575 @@
+//! Utilities for writing headless physics tests.
+//!
+//! See [`PhysicsScenario`] and [`ScenarioCase`].
+
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Instant};
+
+use crate::prelude::*;
+
+/// A headless physics scene for testing, with a fixed timestep of 60 updates per second.
+///
+/// Bodies can be spawned into the scenario, the simulation can be stepped a given number of times,
+/// and the resulting positions and velocities can be asserted to be within a tolerance.
+/// This can be used to lock in physics behavior against regressions.
+///
+/// The underlying `App` has the `MinimalPlugins`, `TransformPlugin`, `AssetPlugin` and [`PhysicsPlugins`],
+/// and it can be accessed using [`PhysicsScenario::app_mut`] for further setup.
+///
+/// This module is only available with the `test-utils` feature.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "test-utils", doc = "```")]
+#[cfg_attr(not(feature = "test-utils"), doc = "```ignore")]
+/// use bevy::prelude::*;
+#[cfg_attr(
+    feature = "2d",
+    doc = "use bevy_xpbd_2d::{math::*, prelude::*, test_utils::PhysicsScenario};"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "use bevy_xpbd_3d::{math::*, prelude::*, test_utils::PhysicsScenario};"
+)]
+///
+/// let mut scenario = PhysicsScenario::new();
+///
+/// // A body falling onto the ground
+/// scenario.spawn((
+///     RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "    Collider::rectangle(10.0, 1.0),")]
+#[cfg_attr(feature = "3d", doc = "    Collider::cuboid(10.0, 1.0, 10.0),")]
+///     Position(Vector::NEG_Y * 0.5),
+/// ));
+/// let ball = scenario.spawn((
+///     RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "    Collider::circle(0.5),")]
+#[cfg_attr(feature = "3d", doc = "    Collider::sphere(0.5),")]
+///     Position(Vector::Y * 2.0),
+/// ));
+///
+/// scenario.step(180);
+///
+/// // The ball should be resting on the ground
+/// scenario.assert_resting_height(ball, 0.5, 0.05);
+/// scenario.assert_at_rest(ball, 0.1);
+/// ```
+pub struct PhysicsScenario {
+    app: App,
+}
+
+impl Default for PhysicsScenario {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PhysicsScenario {
+    /// Creates a new [`PhysicsScenario`] with the default physics configuration.
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            PhysicsPlugins::default(),
+            bevy::asset::AssetPlugin::default(),
+            #[cfg(feature = "bevy_scene")]
+            bevy::scene::ScenePlugin,
+        ))
+        // Collider constructors need the mesh assets
+        .init_resource::<Assets<Mesh>>()
+        .insert_resource(TimeUpdateStrategy::ManualInstant(Instant::now()));
+        Self { app }
+    }
+
+    /// Inserts a resource, for example to configure [`Gravity`] or the [`SubstepCount`].
+    pub fn insert_resource<R: Resource>(&mut self, resource: R) -> &mut Self {
+        self.app.insert_resource(resource);
+        self
+    }
+
+    /// Spawns an entity with the given bundle and returns it.
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        self.app.world.spawn(bundle).id()
+    }
+
+    /// Updates the app the given number of times, advancing time by 1/60 seconds on each update.
+    pub fn step(&mut self, steps: usize) -> &mut Self {
+        for _ in 0..steps {
+            let mut update_strategy = self.app.world.resource_mut::<TimeUpdateStrategy>();
+            let TimeUpdateStrategy::ManualInstant(prev_time) = *update_strategy else {
+                panic!("the time update strategy of a `PhysicsScenario` must be `ManualInstant`");
+            };
+            *update_strategy =
+                TimeUpdateStrategy::ManualInstant(prev_time + Duration::from_secs_f64(1.0 / 60.0));
+            self.app.update();
+        }
+        self
+    }
+
+    /// Returns a reference to the underlying `App`.
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    /// Returns a mutable reference to the underlying `App`.
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    /// Returns the underlying `App`.
+    pub fn into_app(self) -> App {
+        self.app
+    }
+
+    /// Returns the [`Position`] of the given entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entity doesn't have a [`Position`].
+    pub fn position(&self, entity: Entity) -> Vector {
+        self.get::<Position>(entity).0
+    }
+
+    /// Returns the [`LinearVelocity`] of the given entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entity doesn't have a [`LinearVelocity`].
+    pub fn linear_velocity(&self, entity: Entity) -> Vector {
+        self.get::<LinearVelocity>(entity).0
+    }
+
+    /// Returns the [`AngularVelocity`] of the given entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entity doesn't have an [`AngularVelocity`].
+    pub fn angular_velocity(&self, entity: Entity) -> AngularVelocity {
+        *self.get::<AngularVelocity>(entity)
+    }
+
+    /// Asserts that the [`Position`] of the given entity is within `tolerance` of `expected`.
+    #[track_caller]
+    pub fn assert_position(&self, entity: Entity, expected: Vector, tolerance: Scalar) {
+        let position = self.position(entity);
+        assert!(
+            position.distance(expected) <= tolerance,
+            "expected position of {entity:?} to be {expected} ± {tolerance}, but it was {position}"
+        );
+    }
+
+    /// Asserts that the height (the y coordinate of the [`Position`]) of the given entity
+    /// is within `tolerance` of `expected`.
+    #[track_caller]
+    pub fn assert_resting_height(&self, entity: Entity, expected: Scalar, tolerance: Scalar) {
+        let height = self.position(entity).y;
+        assert!(
+            (height - expected).abs() <= tolerance,
+            "expected height of {entity:?} to be {expected} ± {tolerance}, but it was {height}"
+        );
+    }
+
+    /// Asserts that the [`LinearVelocity`] of the given entity is within `tolerance` of `expected`.
+    #[track_caller]
+    pub fn assert_linear_velocity(&self, entity: Entity, expected: Vector, tolerance: Scalar) {
+        let velocity = self.linear_velocity(entity);
+        assert!(
+            velocity.distance(expected) <= tolerance,
+            "expected linear velocity of {entity:?} to be {expected} ± {tolerance}, but it was {velocity}"
+        );
+    }
+
+    /// Asserts that the linear and angular speeds of the given entity are at most `tolerance`.
+    #[track_caller]
+    pub fn assert_at_rest(&self, entity: Entity, tolerance: Scalar) {
+        let linear_speed = self.linear_velocity(entity).length();
+        #[cfg(feature = "2d")]
+        let angular_speed = self.angular_velocity(entity).0.abs();
+        #[cfg(feature = "3d")]
+        let angular_speed = self.angular_velocity(entity).0.length();
+        assert!(
+            linear_speed <= tolerance && angular_speed <= tolerance,
+            "expected {entity:?} to be at rest with a tolerance of {tolerance}, \
+            but its linear speed was {linear_speed} and angular speed was {angular_speed}"
+        );
+    }
+
+    #[track_caller]
+    fn get<T: Component>(&self, entity: Entity) -> &T {
+        self.app.world.get::<T>(entity).unwrap_or_else(|| {
+            panic!(
+                "{entity:?} doesn't have a {} component",
+                std::any::type_name::<T>()
+            )
+        })
+    }
+}
+
+/// An expected value that is checked when running a [`ScenarioCase`].
+///
+/// Bodies are referred to by their index in the list of entities returned by the setup of the case.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Expectation {
+    /// The [`Position`] of the body is within `tolerance` of `expected` at the end of the case.
+    Position {
+        /// The index of the body.
+        body: usize,
+        /// The expected position.
+        expected: Vector,
+        /// The allowed distance from the expected position.
+        tolerance: Scalar,
+    },
+    /// The height (the y coordinate of the [`Position`]) of the body is within `tolerance`
+    /// of `expected` at the end of the case.
+    RestingHeight {
+        /// The index of the body.
+        body: usize,
+        /// The expected height.
+        expected: Scalar,
+        /// The allowed difference from the expected height.
+        tolerance: Scalar,
+    },
+    /// The linear and angular speeds of the body are at most `tolerance` at the end of the case.
+    AtRest {
+        /// The index of the body.
+        body: usize,
+        /// The maximum allowed speed.
+        tolerance: Scalar,
+    },
+    /// The highest height (the y coordinate of the [`Position`]) reached by the body after the given step
+    /// is within `tolerance` of `expected`. This can be used for checking bounce heights.
+    PeakHeight {
+        /// The index of the body.
+        body: usize,
+        /// The step after which the height is tracked.
+        after_step: usize,
+        /// The expected peak height.
+        expected: Scalar,
+        /// The allowed difference from the expected peak height.
+        tolerance: Scalar,
+    },
+}
+
+impl Expectation {
+    /// Returns the index of the body that the expectation is about.
+    pub fn body(&self) -> usize {
+        match *self {
+            Self::Position { body, .. }
+            | Self::RestingHeight { body, .. }
+            | Self::AtRest { body, .. }
+            | Self::PeakHeight { body, .. } => body,
+        }
+    }
+}
+
+/// A data-driven physics scenario with expected values that are validated against the simulation,
+/// like the resting height of a box on a plane or the bounce height of a ball.
+///
+/// The setup function spawns the bodies into a new [`PhysicsScenario`] and returns the bodies
+/// that the [`Expectation`]s refer to. Running the case simulates the given number of steps
+/// and checks all expectations, returning a description of each failed expectation.
+///
+/// A set of canonical solver scenarios is available in [`canonical_scenarios`].
+///
+/// ## Example
+///
+/// ```
+#[cfg_attr(
+    feature = "2d",
+    doc = "use bevy_xpbd_2d::{math::*, prelude::*, test_utils::*};"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "use bevy_xpbd_3d::{math::*, prelude::*, test_utils::*};"
+)]
+///
+/// // A body falling freely for one second
+/// let case = ScenarioCase::new("free fall", 60, |scenario| {
+#[cfg_attr(
+    feature = "2d",
+    doc = "    vec![scenario.spawn((RigidBody::Dynamic, Collider::circle(0.5)))]"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    vec![scenario.spawn((RigidBody::Dynamic, Collider::sphere(0.5)))]"
+)]
+/// })
+/// .expect(Expectation::RestingHeight {
+///     body: 0,
+///     expected: -0.5 * 9.81,
+///     tolerance: 0.1,
+/// });
+///
+/// case.assert();
+/// ```
+pub struct ScenarioCase {
+    /// The name of the case, used in failure messages.
+    pub name: String,
+    /// The number of steps to simulate.
+    pub steps: usize,
+    /// The expectations that are checked when running the case.
+    pub expectations: Vec<Expectation>,
+    setup: Box<dyn Fn(&mut PhysicsScenario) -> Vec<Entity>>,
+}
+
+impl ScenarioCase {
+    /// Creates a new [`ScenarioCase`] that simulates the given number of steps after running `setup`.
+    pub fn new(
+        name: impl Into<String>,
+        steps: usize,
+        setup: impl Fn(&mut PhysicsScenario) -> Vec<Entity> + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            steps,
+            expectations: vec![],
+            setup: Box::new(setup),
+        }
+    }
+
+    /// Adds an expectation to the case.
+    pub fn expect(mut self, expectation: Expectation) -> Self {
+        self.expectations.push(expectation);
+        self
+    }
+
+    /// Runs the case in a new [`PhysicsScenario`] and checks its expectations.
+    ///
+    /// Returns a description of each failed expectation.
+    pub fn run(&self) -> Result<(), Vec<String>> {
+        let mut scenario = PhysicsScenario::new();
+        let bodies = (self.setup)(&mut scenario);
+
+        if let Some(expectation) = self
+            .expectations
+            .iter()
+            .find(|expectation| expectation.body() >= bodies.len())
+        {
+            return Err(vec![format!(
+                "{}: {expectation:?} refers to a body that the setup didn't return",
+                self.name
+            )]);
+        }
+
+        let mut peaks = vec![Scalar::NEG_INFINITY; self.expectations.len()];
+        for step in 1..=self.steps {
+            scenario.step(1);
+            for (expectation, peak) in self.expectations.iter().zip(peaks.iter_mut()) {
+                if let Expectation::PeakHeight {
+                    body, after_step, ..
+                } = *expectation
+                {
+                    if step > after_step {
+                        *peak = peak.max(scenario.position(bodies[body]).y);
+                    }
+                }
+            }
+        }
+
+        let failures = self
+            .expectations
+            .iter()
+            .zip(peaks)
+            .filter_map(|(expectation, peak)| {
+                let entity = bodies[expectation.body()];
+                let failure = match *expectation {
+                    Expectation::Position {
+                        expected,
+                        tolerance,
+                        ..
+                    } => {
+                        let position = scenario.position(entity);
+                        (position.distance(expected) > tolerance).then(|| {
+                            format!("expected position {expected} ± {tolerance}, but it was {position}")
+                        })
+                    }
+                    Expectation::RestingHeight {
+                        expected,
+                        tolerance,
+                        ..
+                    } => {
+                        let height = scenario.position(entity).y;
+                        ((height - expected).abs() > tolerance).then(|| {
+                            format!("expected height {expected} ± {tolerance}, but it was {height}")
+                        })
+                    }
+                    Expectation::AtRest { tolerance, .. } => {
+                        let linear_speed = scenario.linear_velocity(entity).length();
+                        #[cfg(feature = "2d")]
+                        let angular_speed = scenario.angular_velocity(entity).0.abs();
+                        #[cfg(feature = "3d")]
+                        let angular_speed = scenario.angular_velocity(entity).0.length();
+                        (linear_speed > tolerance || angular_speed > tolerance).then(|| {
+                            format!(
+                                "expected to be at rest with a tolerance of {tolerance}, but the linear speed \
+                                was {linear_speed} and the angular speed was {angular_speed}"
+                            )
+                        })
+                    }
+                    Expectation::PeakHeight {
+                        expected,
+                        tolerance,
+                        ..
+                    } => ((peak - expected).abs() > tolerance).then(|| {
+                        format!("expected peak height {expected} ± {tolerance}, but it was {peak}")
+                    }),
+                };
+                failure.map(|failure| {
+                    format!(
+                        "{}: body {} ({entity:?}): {failure}",
+                        self.name,
+                        expectation.body()
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
+    /// Runs the case and panics with a description of the failed expectations if any of them fail.
+    #[track_caller]
+    pub fn assert(&self) {
+        if let Err(failures) = self.run() {
+            panic!("scenario case failed:\n{}", failures.join("\n"));
+        }
+    }
+}
+
+/// Runs all of the given cases and panics with a description of every failed expectation
+/// if any of them fail.
+#[track_caller]
+pub fn assert_scenarios(cases: &[ScenarioCase]) {
+    let failures = cases
+        .iter()
+        .filter_map(|case| case.run().err())
+        .flatten()
+        .collect::<Vec<_>>();
+    if !failures.is_empty() {
+        panic!("scenario cases failed:\n{}", failures.join("\n"));
+    }
+}
+
+/// Returns the canonical solver scenarios with analytically derived expected values:
+///
+/// - A box resting on a plane
+/// - A stack of two boxes
+/// - The bounce height of a ball with a [`Restitution`] of 0.5
+/// - The slide distance of a box with a [`Friction`] of 0.5
+///
+/// The scenarios use the default [`Gravity`] of 9.81 m/s² and can be used with [`assert_scenarios`]
+/// to protect solver changes against regressions.
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub fn canonical_scenarios() -> Vec<ScenarioCase> {
+    fn ground(scenario: &mut PhysicsScenario) {
+        scenario.spawn((
+            RigidBody::Static,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(20.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(20.0, 1.0, 20.0),
+            Position(Vector::NEG_Y * 0.5),
+        ));
+    }
+
+    fn unit_box(position: Vector) -> impl Bundle {
+        (
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 1.0, 1.0),
+            Position(position),
+        )
+    }
+
+    vec![
+        // The box should settle with its bottom on the plane.
+        ScenarioCase::new("box on plane", 180, |scenario| {
+            ground(scenario);
+            vec![scenario.spawn(unit_box(Vector::Y))]
+        })
+        .expect(Expectation::RestingHeight {
+            body: 0,
+            expected: 0.5,
+            tolerance: 0.02,
+        })
+        .expect(Expectation::AtRest {
+            body: 0,
+            tolerance: 0.05,
+        }),
+        // The boxes should settle on top of each other.
+        ScenarioCase::new("two-box stack", 240, |scenario| {
+            ground(scenario);
+            vec![
+                scenario.spawn(unit_box(Vector::Y * 0.55)),
+                scenario.spawn(unit_box(Vector::Y * 1.65)),
+            ]
+        })
+        .expect(Expectation::RestingHeight {
+            body: 0,
+            expected: 0.5,
+            tolerance: 0.03,
+        })
+        .expect(Expectation::RestingHeight {
+            body: 1,
+            expected: 1.5,
+            tolerance: 0.03,
+        })
+        .expect(Expectation::AtRest {
+            body: 1,
+            tolerance: 0.05,
+        }),
+        // A ball dropped from a height of 2 should bounce back up to a height of e^2 * 2 = 0.5.
+        // The first impact happens at t ≈ 0.64 s and the peak of the bounce at t ≈ 0.96 s.
+        ScenarioCase::new("restitution bounce height", 75, |scenario| {
+            ground(scenario);
+            vec![scenario.spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+                Position(Vector::Y * 2.5),
+                Restitution::new(0.5).with_combine_rule(CoefficientCombine::Max),
+                Friction::ZERO,
+            ))]
+        })
+        .expect(Expectation::PeakHeight {
+            body: 0,
+            after_step: 45,
+            expected: 1.0,
+            tolerance: 0.1,
+        }),
+        // A box sliding at 3 m/s would analytically stop after v^2 / (2 * mu * g) ≈ 0.917 m.
+        // The position solve only carries part of the box's weight and the velocity solve carries
+        // the rest, but dynamic friction is bounded by the normal impulse of the position solve alone.
+        // The box sees less friction than mu * m * g, so it slides further: about 8% further with
+        // the two contact points in 2D, and about 29% further with the four contact points in 3D.
+        ScenarioCase::new("friction slide distance", 120, |scenario| {
+            ground(scenario);
+            vec![scenario.spawn((
+                unit_box(Vector::Y * 0.5),
+                LinearVelocity(Vector::X * 3.0),
+                Friction::new(0.5).with_combine_rule(CoefficientCombine::Max),
+            ))]
+        })
+        .expect(Expectation::Position {
+            body: 0,
+            #[cfg(feature = "2d")]
+            expected: Vector::new(0.99, 0.5),
+            #[cfg(feature = "3d")]
+            expected: Vector::new(1.18, 0.5, 0.0),
+            tolerance: 0.05,
+        }),
+    ]
+}
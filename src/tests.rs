@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use crate::test_utils::PhysicsScenario;
 #[cfg(all(
     feature = "default-collider",
     any(feature = "parry-f32", feature = "parry-f64")
@@ -184,6 +185,59 @@ fn body_with_velocity_moves() {
     }
 }
 
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn relative_body_is_carried_by_rotating_frame() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        let platform = commands
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Kinematic,
+                #[cfg(feature = "2d")]
+                AngularVelocity(0.5),
+                #[cfg(feature = "3d")]
+                AngularVelocity(Vector::Y * 0.5),
+            ))
+            .id();
+
+        // The body starts with the velocity of the platform at its location
+        commands.spawn((
+            SpatialBundle::from_transform(Transform::from_xyz(5.0, 0.0, 0.0)),
+            RigidBody::Dynamic,
+            RelativeTo(platform),
+            #[cfg(feature = "2d")]
+            LinearVelocity(Vector::Y * 2.5),
+            #[cfg(feature = "3d")]
+            LinearVelocity(Vector::NEG_Z * 2.5),
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+        ));
+    });
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    let mut query = app.world.query::<(&Position, &RelativeTo)>();
+    let (position, _) = query.single(&app.world);
+
+    // The body follows the circular motion of the platform instead of drifting outwards
+    assert_relative_eq!(position.length(), 5.0, epsilon = 0.05);
+    #[cfg(feature = "2d")]
+    assert!(position.y > 3.5);
+    #[cfg(feature = "3d")]
+    assert!(position.z < -3.5);
+}
+
 #[derive(Component, Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
 #[cfg(feature = "3d")]
 struct Id(usize);
@@ -244,6 +298,4085 @@ fn cubes_simulation_is_locally_deterministic() {
     }
 }
 
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn jointed_bodies_share_island() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let mut spawn_body = |x: Scalar| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(Vector::X * x),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            ))
+            .id()
+    };
+    let body1 = spawn_body(0.0);
+    let body2 = spawn_body(2.0);
+    let body3 = spawn_body(10.0);
+    app.world.spawn(DistanceJoint::new(body1, body2));
+
+    tick_60_fps(&mut app);
+
+    let islands = app.world.resource::<PhysicsIslands>();
+    assert_eq!(islands.len(), 2);
+    assert_eq!(islands.island_id(body1), islands.island_id(body2));
+    assert_ne!(islands.island_id(body1), islands.island_id(body3));
+    assert_eq!(islands.island_of(body1).unwrap().bodies.len(), 2);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn disabled_joints_dont_join_islands() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let mut spawn_body = |x: Scalar| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(Vector::X * x),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            ))
+            .id()
+    };
+    let body1 = spawn_body(0.0);
+    let body2 = spawn_body(2.0);
+    app.world
+        .spawn((DistanceJoint::new(body1, body2), JointDisabled));
+
+    tick_60_fps(&mut app);
+
+    let islands = app.world.resource::<PhysicsIslands>();
+    assert_eq!(islands.len(), 2);
+    assert_ne!(islands.island_id(body1), islands.island_id(body2));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn far_islands_have_reduced_activity() {
+    let mut app = create_app();
+
+    app.add_plugins(PhysicsActivityScalingPlugin)
+        .insert_resource(Gravity::ZERO)
+        .insert_resource(ActivityScalingConfig {
+            near_distance: 10.0,
+            far_distance: 20.0,
+            ..default()
+        });
+
+    app.world.spawn((
+        SpatialBundle::default(),
+        Position::default(),
+        ActivityListener,
+    ));
+
+    let mut spawn_body = |x: Scalar| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(Vector::X * x),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            ))
+            .id()
+    };
+    let near_body = spawn_body(5.0);
+    let far_body = spawn_body(100.0);
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    assert!(app.world.get::<PhysicsActivity>(near_body).is_none());
+
+    let config = *app.world.resource::<ActivityScalingConfig>();
+    let activity = app
+        .world
+        .get::<PhysicsActivity>(far_body)
+        .expect("far body should have reduced activity");
+    assert_eq!(activity.substep_interval, config.max_substep_interval);
+    assert_eq!(
+        activity.sleep_threshold_scale,
+        config.max_sleep_threshold_scale
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn far_bodies_with_reduced_activity_rest_on_ground() {
+    let mut app = create_app();
+
+    app.add_plugins(PhysicsActivityScalingPlugin)
+        .insert_resource(ActivityScalingConfig {
+            near_distance: 10.0,
+            far_distance: 20.0,
+            ..default()
+        });
+
+    app.world.spawn((
+        SpatialBundle::default(),
+        Position(Vector::X * -100.0),
+        ActivityListener,
+    ));
+
+    #[cfg(feature = "2d")]
+    let (ground_collider, box_collider) = (
+        Collider::rectangle(10.0, 1.0),
+        Collider::rectangle(1.0, 1.0),
+    );
+    #[cfg(feature = "3d")]
+    let (ground_collider, box_collider) = (
+        Collider::cuboid(10.0, 1.0, 10.0),
+        Collider::cuboid(1.0, 1.0, 1.0),
+    );
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        ground_collider,
+        Position(Vector::NEG_Y * 0.5),
+    ));
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            box_collider,
+            Position(Vector::Y * 0.5),
+        ))
+        .id();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // Contacts are still solved on the substeps between full solves, so the box doesn't sink
+    assert!(
+        app.world
+            .get::<PhysicsActivity>(body)
+            .unwrap()
+            .substep_interval
+            > 1
+    );
+    let position = app.world.get::<Position>(body).unwrap().0;
+    assert_relative_eq!(position.y, 0.5, epsilon = 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn inflated_shape_intersections_include_nearby_colliders() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let wall = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::X * 1.1),
+            collider.clone(),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    let rotation = RotationValue::default();
+
+    let intersections = pipeline.shape_intersections_inflated(
+        &collider,
+        Vector::ZERO,
+        rotation,
+        0.05,
+        SpatialQueryFilter::default(),
+    );
+    assert!(intersections.is_empty());
+
+    let intersections = pipeline.shape_intersections_inflated(
+        &collider,
+        Vector::ZERO,
+        rotation,
+        0.2,
+        SpatialQueryFilter::default(),
+    );
+    assert_eq!(intersections, vec![wall]);
+
+    let hit = pipeline
+        .cast_shape_inflated(
+            &collider,
+            Vector::NEG_X,
+            rotation,
+            Dir::X,
+            10.0,
+            0.1,
+            false,
+            SpatialQueryFilter::default(),
+        )
+        .expect("inflated shape should hit the wall");
+    assert!((hit.time_of_impact - 1.0).abs() < 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn query_filter_flags_exclude_colliders_by_state() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let disabled = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::X),
+            collider.clone(),
+            ColliderDisabled,
+        ))
+        .id();
+    let sleeping = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::X * 3.0),
+            collider.clone(),
+        ))
+        .id();
+    let wall = app
+        .world
+        .spawn((RigidBody::Static, Position(Vector::X * 5.0), collider))
+        .id();
+
+    // Let the dynamic body fall asleep
+    for _ in 0..90 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world.get::<Sleeping>(sleeping).is_some());
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    let first_hit = |query_filter: SpatialQueryFilter| {
+        pipeline
+            .cast_ray(Vector::ZERO, Dir::X, 10.0, true, query_filter)
+            .map(|hit| hit.entity)
+    };
+
+    // Disabled colliders are excluded by default
+    assert_eq!(first_hit(SpatialQueryFilter::default()), Some(sleeping));
+    assert_eq!(
+        first_hit(SpatialQueryFilter::default().with_excluded_flags(QueryFilterFlags::empty())),
+        Some(disabled)
+    );
+    assert_eq!(
+        first_hit(SpatialQueryFilter::default().exclude_sleeping()),
+        Some(wall)
+    );
+    assert_eq!(
+        first_hit(SpatialQueryFilter::default().only_rigid_body(RigidBody::Static)),
+        Some(wall)
+    );
+    assert_eq!(
+        first_hit(SpatialQueryFilter::default().only_rigid_body(RigidBody::Dynamic)),
+        Some(sleeping)
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn heightfield_raycasts_match_parry() {
+    #[cfg(feature = "2d")]
+    let collider = Collider::heightfield(
+        (0..32).map(|i| (i as Scalar * 0.4).sin()).collect(),
+        Vector::new(32.0, 1.0),
+    );
+    #[cfg(feature = "3d")]
+    let collider = Collider::heightfield(
+        (0..32)
+            .map(|i| {
+                (0..32)
+                    .map(|j| (i as Scalar * 0.4).sin() + (j as Scalar * 0.3).cos())
+                    .collect()
+            })
+            .collect(),
+        Vector::new(32.0, 1.0, 32.0),
+    );
+
+    let entity = Entity::from_raw(0);
+    let mut pipeline = SpatialQueryPipeline::new();
+    pipeline.update(
+        std::iter::once((
+            entity,
+            &Position::default(),
+            &Rotation::default(),
+            &collider,
+            None,
+        )),
+        std::iter::once(entity),
+    );
+
+    for i in 0..16 {
+        let offset = i as Scalar - 8.0;
+        #[cfg(feature = "2d")]
+        let (origin, direction) = (Vector::new(-20.0, 2.5), Vector::new(1.0, -0.05 * offset));
+        #[cfg(feature = "3d")]
+        let (origin, direction) = (
+            Vector::new(-20.0, 2.5, offset),
+            Vector::new(1.0, -0.02 * offset, 0.3),
+        );
+        let direction = Dir::new(direction.f32()).unwrap();
+
+        let hit = pipeline.cast_ray(
+            origin,
+            direction,
+            100.0,
+            true,
+            SpatialQueryFilter::default(),
+        );
+        let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+        let expected = collider
+            .shape_scaled()
+            .cast_local_ray_and_get_normal(&ray, 100.0, true);
+
+        assert_eq!(hit.is_some(), expected.is_some());
+        if let (Some(hit), Some(expected)) = (hit, expected) {
+            assert_relative_eq!(hit.time_of_impact, expected.toi, epsilon = 0.0001);
+        }
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn line_of_sight_accumulates_transparency() {
+    #[cfg(feature = "2d")]
+    let collider = Collider::rectangle(1.0, 1.0);
+    #[cfg(feature = "3d")]
+    let collider = Collider::cuboid(1.0, 1.0, 1.0);
+
+    let positions = [Position(Vector::X * 2.0), Position(Vector::X * 4.0)];
+    let rotation = Rotation::default();
+    let entities = [Entity::from_raw(0), Entity::from_raw(1)];
+    let mut pipeline = SpatialQueryPipeline::new();
+    pipeline.update(
+        entities
+            .iter()
+            .zip(positions.iter())
+            .map(|(entity, position)| (*entity, position, &rotation, &collider, None)),
+        entities.into_iter(),
+    );
+
+    let target = Vector::X * 10.0;
+    let half_transparent = |_: Entity| -> Scalar { 0.5 };
+    assert_relative_eq!(
+        pipeline.line_of_sight(
+            Vector::ZERO,
+            target,
+            SpatialQueryFilter::default(),
+            &half_transparent
+        ),
+        0.25
+    );
+    assert_relative_eq!(
+        pipeline.line_of_sight(
+            Vector::ZERO,
+            Vector::X,
+            SpatialQueryFilter::default(),
+            &half_transparent
+        ),
+        1.0
+    );
+    assert_relative_eq!(
+        pipeline.line_of_sight(
+            Vector::ZERO,
+            target,
+            SpatialQueryFilter::default().with_excluded_entities([entities[1]]),
+            &|_| 0.0
+        ),
+        0.0
+    );
+    assert_relative_eq!(
+        pipeline.line_of_sight(
+            Vector::Y * 5.0,
+            target + Vector::Y * 5.0,
+            SpatialQueryFilter::default(),
+            &|_| 0.0
+        ),
+        1.0
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn sound_occlusion_is_partial_for_partial_obstructions() {
+    #[cfg(feature = "2d")]
+    let collider = Collider::rectangle(1.0, 1.0);
+    #[cfg(feature = "3d")]
+    let collider = Collider::cuboid(1.0, 1.0, 1.0);
+
+    let entity = Entity::from_raw(0);
+    let mut pipeline = SpatialQueryPipeline::new();
+    pipeline.update(
+        std::iter::once((
+            entity,
+            &Position(Vector::X * 5.0),
+            &Rotation::default(),
+            &collider,
+            None,
+        )),
+        std::iter::once(entity),
+    );
+
+    let emitter = Vector::X * 10.0;
+    let direct = pipeline.sound_occlusion(
+        Vector::ZERO,
+        emitter,
+        0.0,
+        1,
+        SpatialQueryFilter::default(),
+        &|_| 0.0,
+    );
+    assert_relative_eq!(direct, 1.0);
+
+    let spread = pipeline.sound_occlusion(
+        Vector::ZERO,
+        emitter,
+        5.0,
+        9,
+        SpatialQueryFilter::default(),
+        &|_| 0.0,
+    );
+    assert!(spread > 0.0 && spread < 1.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn shape_fits_finds_blocking_overlaps() {
+    #[cfg(feature = "2d")]
+    let collider = Collider::rectangle(1.0, 1.0);
+    #[cfg(feature = "3d")]
+    let collider = Collider::cuboid(1.0, 1.0, 1.0);
+
+    let position = Position(Vector::X * 2.0);
+    let rotation = Rotation::default();
+    let entity = Entity::from_raw(0);
+    let mut pipeline = SpatialQueryPipeline::new();
+    pipeline.update(
+        std::iter::once((entity, &position, &rotation, &collider, None)),
+        std::iter::once(entity),
+    );
+
+    let shape_rotation = RotationValue::default();
+
+    assert!(pipeline.shape_fits(
+        &collider,
+        Vector::ZERO,
+        shape_rotation,
+        SpatialQueryFilter::default()
+    ));
+    assert_eq!(
+        pipeline.first_blocking_overlap(
+            &collider,
+            Vector::X * 1.5,
+            shape_rotation,
+            SpatialQueryFilter::default()
+        ),
+        Some(entity)
+    );
+    assert!(pipeline.shape_fits(
+        &collider,
+        Vector::X * 1.5,
+        shape_rotation,
+        SpatialQueryFilter::default().with_excluded_entities([entity])
+    ));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn far_away_rays_are_clipped_to_world_bounds() {
+    #[cfg(feature = "2d")]
+    let collider = Collider::rectangle(1.0, 1.0);
+    #[cfg(feature = "3d")]
+    let collider = Collider::cuboid(1.0, 1.0, 1.0);
+
+    let entity = Entity::from_raw(0);
+    let mut pipeline = SpatialQueryPipeline::new();
+    pipeline.update(
+        std::iter::once((
+            entity,
+            &Position::default(),
+            &Rotation::default(),
+            &collider,
+            None,
+        )),
+        std::iter::once(entity),
+    );
+
+    let hit = pipeline
+        .cast_ray(
+            Vector::NEG_X * 1.0e6,
+            Dir::X,
+            Scalar::MAX,
+            true,
+            SpatialQueryFilter::default(),
+        )
+        .expect("ray should hit the collider");
+    assert_relative_eq!(hit.time_of_impact, 1.0e6 - 0.5, epsilon = 0.1);
+
+    let hits = pipeline.ray_hits(
+        Vector::NEG_X * 1.0e6,
+        Dir::X,
+        Scalar::MAX,
+        10,
+        true,
+        SpatialQueryFilter::default(),
+    );
+    assert_eq!(hits.len(), 1);
+
+    // Rays that miss the bounds of all colliders have no hits.
+    assert!(pipeline
+        .cast_ray(
+            Vector::NEG_X * 1.0e6,
+            Dir::NEG_X,
+            Scalar::MAX,
+            true,
+            SpatialQueryFilter::default(),
+        )
+        .is_none());
+}
+
+#[test]
+fn editor_manipulation_makes_bodies_temporarily_kinematic() {
+    let mut app = create_app();
+    app.add_plugins(EditorManipulationPlugin);
+
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            LinearVelocity(Vector::X * 5.0),
+            MassPropertiesBundle::default(),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    app.world.entity_mut(body).insert(EditorManipulation);
+    tick_60_fps(&mut app);
+
+    assert_eq!(
+        app.world.get::<RigidBody>(body),
+        Some(&RigidBody::Kinematic)
+    );
+    assert_eq!(
+        app.world.get::<LinearVelocity>(body).unwrap().0,
+        Vector::ZERO
+    );
+
+    app.world.entity_mut(body).remove::<EditorManipulation>();
+    tick_60_fps(&mut app);
+
+    assert_eq!(app.world.get::<RigidBody>(body), Some(&RigidBody::Dynamic));
+    assert!(app.world.get::<ManipulatedBody>(body).is_none());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn validation_detects_joints_with_missing_bodies() {
+    let mut app = create_app();
+    app.add_plugins(PhysicsValidationPlugin);
+
+    let body = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Dynamic))
+        .id();
+    let missing = app.world.spawn_empty().id();
+    app.world.despawn(missing);
+    let joint = app.world.spawn(FixedJoint::new(body, missing)).id();
+
+    tick_60_fps(&mut app);
+
+    let events = app.world.resource::<Events<PhysicsValidationIssue>>();
+    let issues = events.iter_current_update_events().collect::<Vec<_>>();
+    assert_eq!(
+        issues,
+        vec![&PhysicsValidationIssue {
+            kind: PhysicsValidationIssueKind::MissingJointBody,
+            entity: joint,
+        }]
+    );
+}
+
+#[test]
+fn bone_colliders_follow_their_bones() {
+    let mut app = create_app();
+    app.add_plugins(BoneColliderPlugin);
+
+    let root = app
+        .world
+        .spawn(TransformBundle::from_transform(Transform::from_xyz(
+            1.0, 0.0, 0.0,
+        )))
+        .id();
+    let bone = app
+        .world
+        .spawn(TransformBundle::from_transform(Transform::from_xyz(
+            0.0, 2.0, 0.0,
+        )))
+        .set_parent(root)
+        .id();
+    let hitbox = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Kinematic,
+            BoneCollider::new(bone),
+        ))
+        .id();
+
+    for _ in 0..5 {
+        tick_60_fps(&mut app);
+    }
+
+    let position = app.world.get::<Position>(hitbox).unwrap().0;
+    assert!((position.x - 1.0).abs() < 0.01);
+    assert!((position.y - 2.0).abs() < 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn hitboxes_hit_each_target_once_per_activation() {
+    use bevy::ecs::event::ManualEventReader;
+
+    let mut app = create_app();
+    app.add_plugins(HitDetectionPlugin);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let hitbox = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Kinematic,
+            LinearVelocity(Vector::Y * 0.1),
+            collider.clone(),
+            Sensor,
+            Hitbox::new().with_team(0),
+        ))
+        .id();
+    let enemy = app
+        .world
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_xyz(0.5, 0.0, 0.0)),
+            RigidBody::Static,
+            collider.clone(),
+            Sensor,
+            Hurtbox::new().with_team(1),
+        ))
+        .id();
+    app.world.spawn((
+        SpatialBundle::from_transform(Transform::from_xyz(-0.5, 0.0, 0.0)),
+        RigidBody::Static,
+        collider,
+        Sensor,
+        Hurtbox::new().with_team(0),
+    ));
+
+    let mut reader = ManualEventReader::<HitEvent>::default();
+    let mut read_hits = |app: &App| {
+        reader
+            .read(app.world.resource::<Events<HitEvent>>())
+            .map(|hit| (hit.hitbox, hit.target, hit.activation))
+            .collect::<Vec<_>>()
+    };
+
+    // Inactive hitboxes don't hit anything.
+    tick_60_fps(&mut app);
+    assert!(read_hits(&app).is_empty());
+
+    // The target is hit once even though the hitbox overlaps it for several frames.
+    app.world.get_mut::<Hitbox>(hitbox).unwrap().activate();
+    let mut hits = vec![];
+    for _ in 0..3 {
+        tick_60_fps(&mut app);
+        hits.extend(read_hits(&app));
+    }
+    assert_eq!(hits, vec![(hitbox, enemy, 1)]);
+
+    app.world.get_mut::<Hitbox>(hitbox).unwrap().activate();
+    tick_60_fps(&mut app);
+    assert_eq!(read_hits(&app), vec![(hitbox, enemy, 2)]);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn sweep_between_finds_every_collider_along_the_motion() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let (collider, rotation) = (Collider::circle(0.5), 0.0);
+    #[cfg(feature = "3d")]
+    let (collider, rotation) = (Collider::sphere(0.5), Quaternion::IDENTITY);
+
+    let far = app
+        .world
+        .spawn((
+            TransformBundle::from_transform(Transform::from_xyz(2.5, 0.0, 0.0)),
+            RigidBody::Static,
+            collider.clone(),
+        ))
+        .id();
+    let near = app
+        .world
+        .spawn((
+            TransformBundle::from_transform(Transform::from_xyz(-2.5, 0.0, 0.0)),
+            RigidBody::Static,
+            collider.clone(),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let hits = app.world.resource::<SpatialQueryPipeline>().sweep_between(
+        &collider,
+        (Vector::X * -5.0, rotation),
+        (Vector::X * 5.0, rotation),
+        SpatialQueryFilter::default(),
+    );
+
+    assert_eq!(
+        hits.iter().map(|hit| hit.entity).collect::<Vec<_>>(),
+        vec![near, far]
+    );
+    assert_relative_eq!(hits[0].time_of_impact, 0.15, epsilon = 0.01);
+    assert_relative_eq!(hits[1].time_of_impact, 0.65, epsilon = 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn projectiles_penetrate_until_out_of_energy() {
+    let mut app = create_app();
+    app.add_plugins(ProjectilePenetrationPlugin);
+
+    #[cfg(feature = "2d")]
+    let wall = Collider::rectangle(1.0, 4.0);
+    #[cfg(feature = "3d")]
+    let wall = Collider::cuboid(1.0, 4.0, 4.0);
+
+    let walls = [2.0, 5.0, 8.0].map(|x| {
+        app.world
+            .spawn((
+                TransformBundle::from_transform(Transform::from_xyz(x, 0.0, 0.0)),
+                RigidBody::Static,
+                wall.clone(),
+                PenetrationResistance(10.0),
+            ))
+            .id()
+    });
+
+    tick_60_fps(&mut app);
+
+    let projectile = app
+        .world
+        .spawn((
+            Position::default(),
+            PenetratingProjectile::new(Dir::X, 100.0, 15.0),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let events = app.world.resource::<Events<PenetrationHit>>();
+    let hits = events.iter_current_update_events().collect::<Vec<_>>();
+
+    assert_eq!(hits.len(), 2);
+    assert!(hits.iter().all(|hit| hit.projectile == projectile));
+    assert_eq!(hits[0].entity, walls[0]);
+    assert_relative_eq!(hits[0].entry_point.x, 1.5, epsilon = 0.001);
+    assert_relative_eq!(hits[0].exit_point.unwrap().x, 2.5, epsilon = 0.001);
+    assert_relative_eq!(hits[0].remaining_energy, 5.0, epsilon = 0.001);
+    assert_eq!(hits[1].entity, walls[1]);
+    assert!(hits[1].stopped());
+}
+
+#[test]
+#[cfg(all(
+    feature = "2d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn chain_colliders_ignore_internal_vertices_and_back_sides() {
+    use crate::plugins::collision::contact_query::contact_manifolds;
+
+    let vertices = vec![
+        Vector::new(-2.0, 0.0),
+        Vector::new(-1.0, 0.0),
+        Vector::new(0.0, 0.0),
+        Vector::new(1.0, 0.0),
+    ];
+    // Segments going to the left have their right-hand side facing up.
+    let reversed = vertices.iter().rev().copied().collect::<Vec<_>>();
+    let chain = Collider::chain(reversed.clone(), false);
+    let one_sided_chain = Collider::one_sided_chain(reversed, false);
+    let circle = Collider::circle(0.5);
+
+    // A circle sliding over an internal vertex only gets upward normals.
+    let manifolds = contact_manifolds(
+        &chain,
+        Vector::ZERO,
+        0.0,
+        &circle,
+        Vector::new(-0.95, 0.45),
+        0.0,
+        0.0,
+    );
+    assert!(!manifolds.is_empty());
+    for manifold in &manifolds {
+        assert!(manifold.normal1.dot(Vector::Y) > 0.999);
+    }
+
+    // Two-sided chains collide on both sides, one-sided chains only on the right side.
+    let below = Vector::new(-0.5, -0.45);
+    assert!(!contact_manifolds(&chain, Vector::ZERO, 0.0, &circle, below, 0.0, 0.0).is_empty());
+    assert!(contact_manifolds(
+        &one_sided_chain,
+        Vector::ZERO,
+        0.0,
+        &circle,
+        below,
+        0.0,
+        0.0
+    )
+    .is_empty());
+}
+
+#[test]
+#[cfg(all(
+    feature = "3d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn capsules_lying_on_trimeshes_have_two_contacts() {
+    use crate::plugins::collision::contact_query::contact_manifolds;
+
+    let floor = Collider::trimesh(
+        vec![
+            Vector::new(-5.0, 0.0, -5.0),
+            Vector::new(5.0, 0.0, -5.0),
+            Vector::new(5.0, 0.0, 5.0),
+            Vector::new(-5.0, 0.0, 5.0),
+        ],
+        vec![[0, 2, 1], [0, 3, 2]],
+    );
+    let capsule = Collider::capsule(2.0, 0.5);
+    // Lay the capsule along the X axis, slightly penetrating the floor.
+    let rotation = Quaternion::from_rotation_z(FRAC_PI_2);
+    let position = Vector::new(0.0, 0.49, 2.0);
+
+    for (manifolds, capsule_is_first) in [
+        (
+            contact_manifolds(
+                &floor,
+                Vector::ZERO,
+                Quaternion::IDENTITY,
+                &capsule,
+                position,
+                rotation,
+                0.0,
+            ),
+            false,
+        ),
+        (
+            contact_manifolds(
+                &capsule,
+                position,
+                rotation,
+                &floor,
+                Vector::ZERO,
+                Quaternion::IDENTITY,
+                0.0,
+            ),
+            true,
+        ),
+    ] {
+        assert_eq!(manifolds.len(), 1);
+        let contacts = &manifolds[0].contacts;
+        assert_eq!(contacts.len(), 2);
+        for contact in contacts {
+            assert_relative_eq!(contact.penetration, 0.01, epsilon = 1e-4);
+        }
+        let point_on_floor = |contact: &ContactData| {
+            if capsule_is_first {
+                contact.point2
+            } else {
+                contact.point1
+            }
+        };
+        assert_relative_eq!(
+            (point_on_floor(&contacts[0]) - point_on_floor(&contacts[1])).length(),
+            2.0,
+            epsilon = 1e-4
+        );
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "3d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn cylinders_resting_on_boxes_have_rim_contacts() {
+    use crate::plugins::collision::contact_query::contact_manifolds;
+
+    let floor = Collider::cuboid(10.0, 1.0, 10.0);
+    let cylinder = Collider::cylinder(1.0, 0.5);
+
+    // Standing on a cap.
+    let manifolds = contact_manifolds(
+        &floor,
+        Vector::ZERO,
+        Quaternion::IDENTITY,
+        &cylinder,
+        Vector::Y * 0.99,
+        Quaternion::IDENTITY,
+        0.0,
+    );
+    assert_eq!(manifolds.len(), 1);
+    assert_eq!(manifolds[0].contacts.len(), 4);
+
+    // Lying on the side.
+    let manifolds = contact_manifolds(
+        &cylinder,
+        Vector::Y * 0.99,
+        Quaternion::from_rotation_x(FRAC_PI_2),
+        &floor,
+        Vector::ZERO,
+        Quaternion::IDENTITY,
+        0.0,
+    );
+    assert_eq!(manifolds.len(), 1);
+    assert_eq!(manifolds[0].contacts.len(), 2);
+    for contact in &manifolds[0].contacts {
+        assert_relative_eq!(contact.penetration, 0.01, epsilon = 1e-4);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "3d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn extrusions_and_revolutions_follow_concave_outlines() {
+    // An L-shaped outline
+    let l_shape = Collider::extrusion(
+        &[
+            Vector2::new(0.0, 0.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(2.0, 1.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(1.0, 2.0),
+            Vector2::new(0.0, 2.0),
+        ],
+        1.0,
+    )
+    .unwrap();
+    let contains = |collider: &Collider, point: Vector| {
+        collider.contains_point(Vector::ZERO, Quaternion::IDENTITY, point)
+    };
+    assert!(contains(&l_shape, Vector::new(0.5, 1.5, 0.0)));
+    assert!(contains(&l_shape, Vector::new(1.5, 0.5, 0.4)));
+    assert!(!contains(&l_shape, Vector::new(1.5, 1.5, 0.0)));
+    assert!(!contains(&l_shape, Vector::new(0.5, 0.5, 0.6)));
+
+    // A bottle with a narrow neck
+    let bottle = Collider::revolution(
+        &[
+            Vector2::new(0.5, 0.0),
+            Vector2::new(0.5, 1.0),
+            Vector2::new(0.15, 1.4),
+            Vector2::new(0.15, 1.8),
+        ],
+        16,
+    )
+    .unwrap();
+    assert!(contains(&bottle, Vector::new(0.4, 0.5, 0.0)));
+    assert!(contains(&bottle, Vector::new(0.0, 1.6, 0.1)));
+    assert!(!contains(&bottle, Vector::new(0.4, 1.6, 0.0)));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn colliders_can_be_inflated_and_deflated() {
+    #[cfg(feature = "2d")]
+    let (ball, cuboid) = (Collider::circle(1.0), Collider::rectangle(2.0, 4.0));
+    #[cfg(feature = "3d")]
+    let (ball, cuboid) = (Collider::sphere(1.0), Collider::cuboid(2.0, 4.0, 2.0));
+
+    let radius = |collider: &Collider| collider.shape_scaled().as_ball().unwrap().radius;
+    assert_relative_eq!(radius(&ball.inflated(0.5)), 1.5);
+    assert_relative_eq!(radius(&ball.deflated(0.5)), 0.5);
+
+    let size = |collider: &Collider| ColliderAabb::from_shape(collider.shape_scaled()).size();
+    let inflated = cuboid.inflated(0.25);
+    assert_relative_eq!(size(&inflated).y, 4.5, epsilon = 1e-4);
+    // Deflating the rounded cuboid by the same margin restores the original cuboid.
+    assert_relative_eq!(size(&inflated.deflated(0.25)).y, 4.0, epsilon = 1e-4);
+    assert_relative_eq!(size(&cuboid.deflated(0.5)).x, 1.0, epsilon = 1e-4);
+}
+
+#[cfg(all(
+    feature = "3d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn closed_trimeshes_have_exact_mass_properties() {
+    let cuboid = Collider::cuboid(2.0, 4.0, 6.0);
+    let (points, indices) = cuboid.shape().as_cuboid().unwrap().to_trimesh();
+    let offset = Vector::new(3.0, 0.0, 0.0);
+    let vertices = points
+        .into_iter()
+        .map(|point| Vector::from(point) + offset)
+        .collect::<Vec<_>>();
+
+    let trimesh = Collider::trimesh(vertices.clone(), indices.clone());
+    assert!(trimesh.is_closed_trimesh());
+
+    let expected = cuboid.mass_properties(2.0);
+    let props = trimesh.mass_properties(2.0);
+    assert_relative_eq!(props.mass.0, expected.mass.0, epsilon = 1e-3);
+    assert_relative_eq!(props.center_of_mass.0, offset, epsilon = 1e-4);
+    assert_relative_eq!(props.inertia.0, expected.inertia.0, epsilon = 1e-2);
+
+    // Removing a triangle opens the mesh.
+    let open = Collider::trimesh(vertices, indices[1..].to_vec());
+    assert!(!open.is_closed_trimesh());
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn collider_shapes_can_be_edited_in_place() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(1.0);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(1.0);
+    let body = app.world.spawn((RigidBody::Dynamic, collider)).id();
+    tick_60_fps(&mut app);
+    let mass = app.world.get::<Mass>(body).unwrap().0;
+
+    let mut collider = app.world.get_mut::<Collider>(body).unwrap();
+    assert!(!collider.set_half_extents(Vector::ONE));
+    assert!(collider.set_radius(2.0));
+    tick_60_fps(&mut app);
+
+    // AABBs are expanded by the prediction distance on each side
+    let prediction_distance = app
+        .world
+        .resource::<NarrowPhaseConfig>()
+        .prediction_distance;
+    let aabb = app.world.get::<ColliderAabb>(body).unwrap();
+    assert_relative_eq!(
+        aabb.size().x,
+        4.0 + 2.0 * prediction_distance,
+        epsilon = 1e-4
+    );
+    #[cfg(feature = "2d")]
+    assert_relative_eq!(
+        app.world.get::<Mass>(body).unwrap().0,
+        mass * 4.0,
+        epsilon = 1e-3
+    );
+    #[cfg(feature = "3d")]
+    assert_relative_eq!(
+        app.world.get::<Mass>(body).unwrap().0,
+        mass * 8.0,
+        epsilon = 1e-3
+    );
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn collider_morphs_interpolate_shapes_and_mass() {
+    #[cfg(feature = "2d")]
+    let (small, large, cuboid) = (
+        Collider::circle(1.0),
+        Collider::circle(2.0),
+        Collider::rectangle(2.0, 2.0),
+    );
+    #[cfg(feature = "3d")]
+    let (small, large, cuboid) = (
+        Collider::sphere(1.0),
+        Collider::sphere(2.0),
+        Collider::cuboid(2.0, 2.0, 2.0),
+    );
+
+    // Balls are interpolated parameter-wise.
+    let morph = ColliderMorph::new(small.clone(), large);
+    let shape = morph.shape_at(0.5).unwrap();
+    assert_relative_eq!(shape.as_ball().unwrap().radius, 1.5);
+
+    // Different shapes are interpolated using their support points.
+    let morph = ColliderMorph::new(small.clone(), cuboid);
+    let aabb = ColliderAabb::from_shape(&morph.shape_at(1.0).unwrap());
+    assert_relative_eq!(aabb.size().x, 2.0, epsilon = 1e-3);
+
+    let mut app = create_app();
+    app.add_plugins(ColliderMorphPlugin)
+        .insert_resource(Gravity::ZERO);
+    let body = app
+        .world
+        .spawn((RigidBody::Dynamic, small.clone(), morph))
+        .id();
+    tick_60_fps(&mut app);
+    let mass = app.world.get::<Mass>(body).unwrap().0;
+
+    app.world.get_mut::<ColliderMorph>(body).unwrap().progress = 1.0;
+    tick_60_fps(&mut app);
+    assert!(app.world.get::<Mass>(body).unwrap().0 > mass);
+}
+
+#[cfg(all(
+    feature = "pbd_fluid",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn fluid_particles_push_dynamic_bodies() {
+    let mut app = create_app();
+    app.add_plugins(FluidPlugin).insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let (collider, min, max) = (
+        Collider::rectangle(1.0, 1.0),
+        Vector::new(-1.0, -0.5),
+        Vector::new(0.0, 0.5),
+    );
+    #[cfg(feature = "3d")]
+    let (collider, min, max) = (
+        Collider::cuboid(1.0, 1.0, 1.0),
+        Vector::new(-1.0, -0.5, -0.5),
+        Vector::new(0.0, 0.5, 0.5),
+    );
+
+    let body = app
+        .world
+        .spawn((RigidBody::Dynamic, collider, Position(Vector::X * 1.0)))
+        .id();
+
+    // Shoot a block of particles at the body.
+    let mut fluid = Fluid::default().with_block(min, max);
+    for particle in fluid.particles.iter_mut() {
+        particle.velocity = Vector::X * 5.0;
+    }
+    let fluid = app.world.spawn(fluid).id();
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    assert!(app.world.get::<LinearVelocity>(body).unwrap().x > 0.0);
+
+    // The particles don't pass through the body.
+    let fluid = app.world.get::<Fluid>(fluid).unwrap();
+    let collider = app.world.get::<Collider>(body).unwrap();
+    let position = app.world.get::<Position>(body).unwrap();
+    let rotation = app.world.get::<Rotation>(body).unwrap();
+    assert!(fluid
+        .particles
+        .iter()
+        .all(|particle| !collider.contains_point(position.0, *rotation, particle.position)));
+}
+
+#[cfg(all(
+    feature = "pbd_fluid",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn fluid_particles_push_each_touching_body() {
+    let mut app = create_app();
+    app.add_plugins(FluidPlugin).insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::rectangle(1.0, 1.0);
+    #[cfg(feature = "3d")]
+    let collider = Collider::cuboid(1.0, 1.0, 1.0);
+
+    // A particle squeezed between two bodies pushes both of them away.
+    let left = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            collider.clone(),
+            Position(Vector::NEG_X * 0.58),
+        ))
+        .id();
+    let right = app
+        .world
+        .spawn((RigidBody::Dynamic, collider, Position(Vector::X * 0.58)))
+        .id();
+
+    let mut fluid = Fluid::default();
+    fluid.add_particle(Vector::ZERO, Vector::ZERO);
+    app.world.spawn(fluid);
+
+    for _ in 0..2 {
+        tick_60_fps(&mut app);
+    }
+
+    assert!(app.world.get::<LinearVelocity>(left).unwrap().x < 0.0);
+    assert!(app.world.get::<LinearVelocity>(right).unwrap().x > 0.0);
+}
+
+#[cfg(all(
+    feature = "pbd_fluid",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn granular_particles_pile_up_on_the_ground() {
+    let mut app = create_app();
+    app.add_plugins(GranularPlugin);
+
+    #[cfg(feature = "2d")]
+    let (ground, min, max) = (
+        Collider::rectangle(10.0, 1.0),
+        Vector::new(-0.25, 0.1),
+        Vector::new(0.25, 0.6),
+    );
+    #[cfg(feature = "3d")]
+    let (ground, min, max) = (
+        Collider::cuboid(10.0, 1.0, 10.0),
+        Vector::new(-0.25, 0.1, -0.25),
+        Vector::new(0.25, 0.6, 0.25),
+    );
+    app.world
+        .spawn((RigidBody::Static, ground, Position(Vector::NEG_Y * 0.5)));
+    let sand = app
+        .world
+        .spawn(GranularMaterial::default().with_block(min, max))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    let sand = app.world.get::<GranularMaterial>(sand).unwrap();
+    let radius = sand.config.particle_radius;
+    // The particles rest on the ground and on each other.
+    assert!(sand
+        .particles
+        .iter()
+        .all(|particle| particle.position.y > 0.5 * radius && particle.velocity.length() < 1.0));
+    assert!(sand
+        .particles
+        .iter()
+        .any(|particle| particle.position.y > 4.0 * radius));
+}
+
+#[test]
+fn wind_pushes_bodies_with_wind_influence() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO)
+        .insert_resource(PhysicsAmbientForces::new(Vector::X * 10.0));
+
+    let leaf = app
+        .world
+        .spawn((RigidBody::Dynamic, Mass(0.1), WindInfluence(1.0)))
+        .id();
+    let rock = app.world.spawn((RigidBody::Dynamic, Mass(0.1))).id();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The leaf approaches the speed of the wind without exceeding it.
+    let leaf_velocity = app.world.get::<LinearVelocity>(leaf).unwrap().0;
+    assert!(leaf_velocity.x > 9.0 && leaf_velocity.x <= 10.0);
+    assert_eq!(
+        app.world.get::<LinearVelocity>(rock).unwrap().0,
+        Vector::ZERO
+    );
+}
+
+#[test]
+fn force_applications_are_logged_with_sources() {
+    let mut app = create_app();
+    app.add_plugins(ForceLogPlugin);
+
+    let body = app.world.spawn((RigidBody::Dynamic, Mass(1.0))).id();
+    app.add_systems(
+        Update,
+        move |mut applier: ForceApplier, mut applied: Local<bool>| {
+            if !*applied {
+                assert!(applier.apply_impulse(body, Vector::X, ForceSource("jump")));
+                *applied = true;
+            }
+        },
+    );
+
+    tick_60_fps(&mut app);
+
+    let events = app.world.resource::<Events<ExternalForceApplied>>();
+    let logged = events
+        .get_reader()
+        .read(events)
+        .copied()
+        .collect::<Vec<_>>();
+    assert_eq!(
+        logged,
+        vec![ExternalForceApplied {
+            entity: body,
+            source: ForceSource("jump"),
+            force: AppliedForce::Impulse {
+                impulse: Vector::X,
+                point: None,
+            },
+        }]
+    );
+    assert!(app.world.get::<LinearVelocity>(body).unwrap().x > 0.0);
+}
+
+#[test]
+fn fixed_rate_schedules_run_at_their_own_rate() {
+    #[derive(bevy::ecs::schedule::ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct FastSchedule;
+
+    #[derive(Resource, Default)]
+    struct RunCount(u32);
+
+    let mut app = create_app();
+    app.add_plugins(FixedRateSchedulePlugin::new(FastSchedule, 120.0))
+        .init_resource::<RunCount>()
+        .add_systems(
+            FastSchedule,
+            |mut count: ResMut<RunCount>, time: Res<Time>| {
+                assert_eq!(time.delta(), Duration::from_secs_f64(1.0 / 120.0));
+                count.0 += 1;
+            },
+        );
+
+    for _ in 0..61 {
+        tick_60_fps(&mut app);
+    }
+
+    // One second of real time has passed after the first frame.
+    let count = app.world.resource::<RunCount>().0;
+    assert!((119..=120).contains(&count), "ran {count} times");
+}
+
+#[test]
+fn physics_worlds_step_at_their_own_rates() {
+    use bevy::app::AppLabel;
+
+    #[derive(AppLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    struct VehicleWorld;
+
+    #[derive(AppLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    struct DebrisWorld;
+
+    #[derive(Resource, Default)]
+    struct StepCount(u32);
+
+    let mut app = create_app();
+    app.add_plugins((
+        PhysicsWorldPlugin::new(VehicleWorld, 120.0),
+        PhysicsWorldPlugin::new(DebrisWorld, 30.0),
+    ));
+
+    for label in [VehicleWorld.intern(), DebrisWorld.intern()] {
+        app.sub_app_mut(label)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1.0 / 60.0,
+            )))
+            .init_resource::<StepCount>()
+            .add_systems(
+                PhysicsSchedule,
+                (|mut count: ResMut<StepCount>| {
+                    count.0 += 1;
+                })
+                .before(PhysicsStepSet::BroadPhase),
+            );
+    }
+
+    let debris = app
+        .sub_app_mut(DebrisWorld)
+        .world
+        .spawn((RigidBody::Dynamic, Mass(1.0)))
+        .id();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // One second has passed in each world
+    let vehicle_steps = app.sub_app(VehicleWorld).world.resource::<StepCount>().0;
+    let debris_steps = app.sub_app(DebrisWorld).world.resource::<StepCount>().0;
+    assert!(
+        (119..=121).contains(&vehicle_steps),
+        "{vehicle_steps} steps"
+    );
+    assert!((29..=31).contains(&debris_steps), "{debris_steps} steps");
+    assert_eq!(
+        app.world
+            .resource::<PhysicsWorlds>()
+            .initial_hz(DebrisWorld),
+        Some(30.0)
+    );
+
+    // The body is simulated in its own world
+    let debris_world = &app.sub_app(DebrisWorld).world;
+    assert!(debris_world.get::<LinearVelocity>(debris).unwrap().y < -9.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn spatial_queries_are_updated_while_paused() {
+    let mut app = create_app();
+    app.insert_resource(PhysicsPausedBehavior {
+        update_spatial_queries: true,
+        sync_transforms: true,
+    });
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::rectangle(1.0, 1.0);
+    #[cfg(feature = "3d")]
+    let collider = Collider::cuboid(1.0, 1.0, 1.0);
+    let body = app
+        .world
+        .spawn((RigidBody::Kinematic, collider, Position(Vector::X * 5.0)))
+        .id();
+
+    tick_60_fps(&mut app);
+    app.world.resource_mut::<Time<Physics>>().pause();
+
+    // Move the body like an editor would while gameplay is frozen.
+    app.world.get_mut::<Position>(body).unwrap().0 = Vector::ZERO;
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    assert_eq!(
+        pipeline.point_intersections(Vector::ZERO, SpatialQueryFilter::default()),
+        vec![body]
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn solve_order_is_independent_of_archetypes() {
+    #[derive(Component)]
+    struct Marker;
+
+    let simulate = |move_archetypes: bool| {
+        let mut app = create_app();
+
+        #[cfg(feature = "2d")]
+        let (ground, block) = (
+            Collider::rectangle(10.0, 1.0),
+            Collider::rectangle(1.0, 1.0),
+        );
+        #[cfg(feature = "3d")]
+        let (ground, block) = (
+            Collider::cuboid(10.0, 1.0, 10.0),
+            Collider::cuboid(1.0, 1.0, 1.0),
+        );
+        app.world.spawn((RigidBody::Static, ground));
+
+        let blocks = (0..4)
+            .map(|i| {
+                let position = Vector::Y * (1.0 + i as Scalar) + Vector::X * 0.1 * i as Scalar;
+                app.world
+                    .spawn((RigidBody::Dynamic, block.clone(), Position(position)))
+                    .id()
+            })
+            .collect::<Vec<_>>();
+
+        // Moving some of the bodies to another archetype changes the query iteration order.
+        if move_archetypes {
+            for entity in blocks.iter().step_by(2) {
+                app.world.entity_mut(*entity).insert(Marker);
+            }
+        }
+
+        for _ in 0..60 {
+            tick_60_fps(&mut app);
+        }
+
+        blocks
+            .iter()
+            .map(|entity| app.world.get::<Position>(*entity).unwrap().0)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(simulate(false), simulate(true));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn contact_user_data_persists_for_the_pair() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    // Count the substeps that each pair has been in contact for.
+    app.add_systems(
+        PostProcessCollisions,
+        |mut collisions: ResMut<Collisions>| {
+            for contacts in collisions.iter_mut() {
+                contacts.user_data += 1;
+            }
+        },
+    );
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+    let entity1 = app
+        .world
+        .spawn((RigidBody::Static, collider.clone(), Position(Vector::ZERO)))
+        .id();
+    let entity2 = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            collider,
+            Sensor,
+            Position(Vector::X * 0.9),
+        ))
+        .id();
+
+    for _ in 0..3 {
+        tick_60_fps(&mut app);
+    }
+
+    let collisions = app.world.resource::<Collisions>();
+    let contacts = collisions.get(entity1, entity2).unwrap();
+    assert!(contacts.user_data > 1);
+}
+
+#[test]
+fn solver_bodies_map_entities_to_dense_indices() {
+    let mut app = create_app();
+
+    let bodies = (0..3)
+        .map(|_| app.world.spawn(RigidBody::Dynamic).id())
+        .collect::<Vec<_>>();
+    let disabled = app
+        .world
+        .spawn((RigidBody::Dynamic, RigidBodyDisabled))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let solver_bodies = app.world.resource::<SolverBodies>();
+    assert_eq!(solver_bodies.entities(), bodies.as_slice());
+    for (index, entity) in bodies.iter().enumerate() {
+        assert_eq!(solver_bodies.index(*entity), Some(index));
+        assert_eq!(solver_bodies.entity(index), Some(*entity));
+    }
+    assert_eq!(solver_bodies.index(disabled), None);
+
+    app.world.despawn(bodies[0]);
+    tick_60_fps(&mut app);
+
+    let solver_bodies = app.world.resource::<SolverBodies>();
+    assert_eq!(solver_bodies.len(), 2);
+    assert_eq!(solver_bodies.index(bodies[0]), None);
+    assert_eq!(solver_bodies.index(bodies[2]), Some(1));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn sub_colliders_are_registered_without_entities() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let (block, ball) = (Collider::rectangle(1.0, 1.0), Collider::circle(0.5));
+    #[cfg(feature = "3d")]
+    let (block, ball) = (Collider::cuboid(1.0, 1.0, 1.0), Collider::sphere(0.5));
+
+    let mut sub_colliders = SubColliders::default();
+    let first = sub_colliders.insert(Vector::ZERO, Rotation::default(), block.clone());
+    let second = sub_colliders.insert(Vector::X * 5.0, Rotation::default(), block);
+    let chunk = app.world.spawn((RigidBody::Static, sub_colliders)).id();
+
+    let ball = app
+        .world
+        .spawn((RigidBody::Dynamic, ball, Sensor, Position(Vector::X * 5.5)))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let collisions = app.world.resource::<Collisions>();
+    let contacts = collisions.get(chunk, ball).unwrap();
+    let sub_colliders = app.world.get::<SubColliders>(chunk).unwrap();
+    let id = sub_colliders.id_for_contact(&contacts.manifolds[0], contacts.entity1 == chunk);
+    assert_eq!(id, Some(second));
+
+    // Removing the sub-collider rebuilds the collider without it.
+    app.world
+        .get_mut::<SubColliders>(chunk)
+        .unwrap()
+        .remove(second);
+    tick_60_fps(&mut app);
+
+    assert!(app
+        .world
+        .resource::<Collisions>()
+        .get(chunk, ball)
+        .is_none());
+    let sub_colliders = app.world.get::<SubColliders>(chunk).unwrap();
+    assert_eq!(sub_colliders.len(), 1);
+    assert!(sub_colliders.get(first).is_some());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn pair_state_is_restored_for_respawned_physics_ids() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let ground = app
+        .world
+        .spawn((RigidBody::Static, collider.clone(), PhysicsId(1)))
+        .id();
+    let spawn_crate = |app: &mut App| {
+        app.world
+            .spawn((
+                RigidBody::Dynamic,
+                collider.clone(),
+                Sensor,
+                Position(Vector::X * 0.9),
+                PhysicsId(2),
+            ))
+            .id()
+    };
+    let old_crate = spawn_crate(&mut app);
+
+    tick_60_fps(&mut app);
+    app.world
+        .resource_mut::<Collisions>()
+        .get_mut(ground, old_crate)
+        .unwrap()
+        .user_data = 7;
+
+    // Despawn and respawn the entity, like when its network authority changes.
+    app.world.despawn(old_crate);
+    tick_60_fps(&mut app);
+    let new_crate = spawn_crate(&mut app);
+    tick_60_fps(&mut app);
+
+    let collisions = app.world.resource::<Collisions>();
+    assert_eq!(collisions.get(ground, new_crate).unwrap().user_data, 7);
+}
+
+#[test]
+fn clamped_rotation_keeps_angular_velocity() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+    app.insert_resource(RotationIntegration {
+        exponential_map: true,
+        max_delta_rotation: 0.01,
+    });
+
+    #[cfg(feature = "2d")]
+    let ang_vel = AngularVelocity(200.0);
+    #[cfg(feature = "3d")]
+    let ang_vel = AngularVelocity(Vector::Z * 200.0);
+    let entity = app
+        .world
+        .spawn((RigidBody::Dynamic, ang_vel, MassPropertiesBundle::default()))
+        .id();
+
+    for _ in 0..5 {
+        tick_60_fps(&mut app);
+    }
+
+    let ang_vel = app.world.get::<AngularVelocity>(entity).unwrap();
+    #[cfg(feature = "2d")]
+    assert!((ang_vel.0 - 200.0).abs() < 0.01);
+    #[cfg(feature = "3d")]
+    assert!((ang_vel.0 - Vector::Z * 200.0).length() < 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn divergence_detector_measures_divergence() {
+    fn create_scene(gravity: Gravity) -> App {
+        let mut app = create_app();
+        app.insert_resource(gravity);
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn((
+                RigidBody::Dynamic,
+                PhysicsId(0),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            ));
+        });
+        app
+    }
+
+    let mut identical = DivergenceDetector::new(
+        create_scene(Gravity::default()),
+        create_scene(Gravity::default()),
+    );
+    let divergence = identical.run(10);
+    assert!(divergence.iter().all(|d| !d.exceeds(0.0)));
+    assert_eq!(divergence.last().unwrap().matched_bodies, 1);
+
+    let mut different = DivergenceDetector::new(
+        create_scene(Gravity::default()),
+        create_scene(Gravity::ZERO),
+    );
+    let divergence = different.run(10);
+    assert!(divergence.last().unwrap().position_rms > 0.0);
+    assert!(divergence.last().unwrap().linear_velocity_rms > 0.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn body_comes_to_rest_on_ground() {
+    let mut scenario = PhysicsScenario::new();
+
+    scenario.spawn((
+        RigidBody::Static,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(10.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(10.0, 1.0, 10.0),
+        Position(Vector::NEG_Y * 0.5),
+    ));
+    let ball = scenario.spawn((
+        RigidBody::Dynamic,
+        #[cfg(feature = "2d")]
+        Collider::circle(0.5),
+        #[cfg(feature = "3d")]
+        Collider::sphere(0.5),
+        Position(Vector::Y * 2.0),
+    ));
+
+    scenario.step(180);
+
+    scenario.assert_resting_height(ball, 0.5, 0.05);
+    scenario.assert_at_rest(ball, 0.1);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn sensor_overlaps_track_stay_duration() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+    let sensor = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            collider.clone(),
+            Sensor,
+            SensorOverlaps::default(),
+        ))
+        .id();
+    let body = app
+        .world
+        .spawn((RigidBody::Dynamic, collider, Position(Vector::X * 0.5)))
+        .id();
+
+    tick_60_fps(&mut app);
+    let entry_time = app
+        .world
+        .get::<SensorOverlaps>(sensor)
+        .unwrap()
+        .entry_time(body)
+        .expect("body should be overlapping the sensor");
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let overlaps = app.world.get::<SensorOverlaps>(sensor).unwrap();
+    let elapsed = app.world.resource::<Time<Physics>>().elapsed();
+    assert_eq!(overlaps.hit_count(), 1);
+    assert_eq!(overlaps.entry_time(body), Some(entry_time));
+    assert!(overlaps.stay_duration(body, elapsed).unwrap().as_secs_f32() >= 0.4);
+
+    // Moving the body out of the sensor ends the overlap
+    app.world.get_mut::<Position>(body).unwrap().0 = Vector::X * 10.0;
+    tick_60_fps(&mut app);
+    let overlaps = app.world.get::<SensorOverlaps>(sensor).unwrap();
+    assert!(!overlaps.contains(body));
+    assert_eq!(overlaps.hit_count(), 1);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn pending_impulses_are_applied_once() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+        ))
+        .id();
+    let inv_mass = app.world.get::<InverseMass>(body).unwrap().0;
+
+    // Queue the impulse from a clone of the resource, like an async task would
+    let pending_impulses = app.world.resource::<PendingImpulses>().clone();
+    pending_impulses.apply_impulse(body, Vector::X * 2.0);
+    assert_eq!(pending_impulses.len(), 1);
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    assert!(pending_impulses.is_empty());
+    let lin_vel = app.world.get::<LinearVelocity>(body).unwrap();
+    assert_relative_eq!(lin_vel.x, 2.0 * inv_mass, epsilon = 0.0001);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn velocity_changes_are_independent_of_mass() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let bodies = [1.0, 10.0].map(|density| {
+        app.world
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), density),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), density),
+            ))
+            .id()
+    });
+
+    #[cfg(feature = "2d")]
+    let angular_velocity_change = 2.0;
+    #[cfg(feature = "3d")]
+    let angular_velocity_change = Vector::Z * 2.0;
+
+    let pending_impulses = app.world.resource::<PendingImpulses>().clone();
+    for body in bodies {
+        pending_impulses.apply_velocity_change(body, Vector::X * 5.0);
+        pending_impulses.apply_angular_velocity_change(body, angular_velocity_change);
+    }
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    assert!(pending_impulses.is_empty());
+    for body in bodies {
+        let lin_vel = app.world.get::<LinearVelocity>(body).unwrap();
+        let ang_vel = app.world.get::<AngularVelocity>(body).unwrap();
+        assert_relative_eq!(lin_vel.x, 5.0, epsilon = 0.0001);
+        assert_relative_eq!(ang_vel.0, angular_velocity_change, epsilon = 0.0001);
+    }
+
+    // Accelerations are also independent of mass
+    for body in bodies {
+        pending_impulses.accelerate(body, Vector::Y * 10.0);
+    }
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let light_vel = app.world.get::<LinearVelocity>(bodies[0]).unwrap().y;
+    let heavy_vel = app.world.get::<LinearVelocity>(bodies[1]).unwrap().y;
+    assert!(light_vel > 0.0);
+    assert_relative_eq!(light_vel, heavy_vel, epsilon = 0.0001);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn activity_events_are_sent_on_sleep_transitions() {
+    #[derive(Resource, Default)]
+    struct ActivityLog {
+        started_moving: Vec<Entity>,
+        came_to_rest: Vec<Entity>,
+    }
+
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO)
+        .init_resource::<ActivityLog>()
+        .add_systems(
+            Update,
+            |mut log: ResMut<ActivityLog>,
+             mut started_moving: EventReader<BodyStartedMoving>,
+             mut came_to_rest: EventReader<BodyCameToRest>| {
+                log.started_moving
+                    .extend(started_moving.read().map(|event| event.0));
+                log.came_to_rest
+                    .extend(came_to_rest.read().map(|event| event.0));
+            },
+        );
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world.get::<Sleeping>(body).is_some());
+    assert_eq!(app.world.resource::<ActivityLog>().came_to_rest, vec![body]);
+
+    app.world.get_mut::<LinearVelocity>(body).unwrap().0 = Vector::X;
+    for _ in 0..2 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world.get::<Sleeping>(body).is_none());
+    assert_eq!(
+        app.world.resource::<ActivityLog>().started_moving,
+        vec![body]
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn restored_settled_bodies_stay_asleep() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    let poses = PhysicsPersistence::extract_settled(&mut app.world);
+    assert_eq!(poses.len(), 1);
+    assert_eq!(poses[0].0, body);
+
+    // Restore the body somewhere else, as if loading a save file
+    let pose = Pose {
+        position: Vector::X * 5.0,
+        ..poses[0].1
+    };
+    PhysicsPersistence::restore_settled(&mut app.world, [(body, pose)]);
+
+    for _ in 0..2 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world.get::<Sleeping>(body).is_some());
+    assert_eq!(app.world.get::<Position>(body).unwrap().0, Vector::X * 5.0);
+
+    // Later changes should wake the body up as usual
+    app.world.get_mut::<LinearVelocity>(body).unwrap().0 = Vector::X;
+    tick_60_fps(&mut app);
+    assert!(app.world.get::<Sleeping>(body).is_none());
+}
+
+#[test]
+fn correction_smoothing_blends_transform() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Kinematic,
+            TransformBundle::default(),
+            CorrectionSmoothing::new(0.5),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    // Snap the body to a corrected position
+    let mut entity_mut = app.world.entity_mut(body);
+    let mut position = *entity_mut.get::<Position>().unwrap();
+    let mut rotation = *entity_mut.get::<Rotation>().unwrap();
+    let target_rotation = rotation;
+    entity_mut
+        .get_mut::<CorrectionSmoothing>()
+        .unwrap()
+        .correct(
+            &mut position,
+            &mut rotation,
+            Vector::X * 2.0,
+            target_rotation,
+        );
+    entity_mut.insert((position, rotation));
+
+    tick_60_fps(&mut app);
+
+    // The physics state is snapped, but the transform is still close to the old position
+    assert_eq!(app.world.get::<Position>(body).unwrap().0, Vector::X * 2.0);
+    let translation = app.world.get::<Transform>(body).unwrap().translation;
+    assert!(translation.x < 0.1);
+
+    for _ in 0..40 {
+        tick_60_fps(&mut app);
+    }
+
+    // The transform has been blended to the corrected position without affecting the physics state
+    assert_eq!(app.world.get::<Position>(body).unwrap().0, Vector::X * 2.0);
+    let translation = app.world.get::<Transform>(body).unwrap().translation;
+    assert_relative_eq!(translation.x, 2.0, epsilon = 0.0001);
+    assert!(!app
+        .world
+        .get::<CorrectionSmoothing>(body)
+        .unwrap()
+        .is_smoothing());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn settle_simulation_drops_props_to_ground() {
+    let mut simulation = SettleSimulation::new();
+
+    simulation.spawn((
+        RigidBody::Static,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(20.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(20.0, 1.0, 20.0),
+        Position(Vector::NEG_Y * 0.5),
+    ));
+    let prop = simulation.spawn((
+        RigidBody::Dynamic,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(1.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(1.0, 1.0, 1.0),
+        Position(Vector::Y * 3.0),
+    ));
+
+    let transforms = simulation.settle(600);
+
+    assert!(simulation.is_settled());
+    assert!(simulation.steps() < 600);
+    assert_eq!(transforms.len(), 1);
+    assert_eq!(transforms[0].0, prop);
+    assert_relative_eq!(transforms[0].1.translation.y, 0.5, epsilon = 0.05);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn spline_drive_joint_follows_curve() {
+    use bevy::math::cubic_splines::{CubicBezier, CubicGenerator};
+
+    let mut app = create_app();
+    app.insert_resource(Gravity(Vector::NEG_Y * 9.81));
+
+    let track = app.world.spawn(RigidBody::Static).id();
+    let cart = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+        ))
+        .id();
+
+    // A straight track along the X axis
+    let curve = CubicBezier::new([[
+        SplinePoint::ZERO,
+        SplinePoint::X * 10.0,
+        SplinePoint::X * 20.0,
+        SplinePoint::X * 30.0,
+    ]])
+    .to_curve();
+    app.world.spawn(
+        SplineDriveJoint::new(track, cart)
+            .with_curve(curve)
+            .with_motor(2.0, 1000.0),
+    );
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The cart should stay on the track despite gravity and be driven along it
+    let position = app.world.get::<Position>(cart).unwrap().0;
+    assert_relative_eq!(position.y, 0.0, epsilon = 0.01);
+    assert!(position.x > 1.5 && position.x < 2.5);
+    let velocity = app.world.get::<LinearVelocity>(cart).unwrap().0;
+    assert_relative_eq!(velocity.x, 2.0, epsilon = 0.1);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn suspension_constraint_settles_at_spring_equilibrium() {
+    let mut app = create_app();
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    let chassis = app.world.spawn(RigidBody::Static).id();
+    let wheel = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            Position(Vector::NEG_Y),
+        ))
+        .id();
+    let mass = app.world.get::<Mass>(wheel).unwrap().0;
+
+    let stiffness = 1000.0;
+    app.world.spawn(
+        SuspensionConstraint::new(chassis, wheel)
+            .with_rest_length(1.0)
+            .with_stiffness(stiffness)
+            .with_suspension_damping(50.0),
+    );
+
+    for _ in 0..300 {
+        tick_60_fps(&mut app);
+    }
+
+    // The spring should be compressed by gravity by m * g / k
+    let position = app.world.get::<Position>(wheel).unwrap().0;
+    assert_relative_eq!(position.y, -1.0 - mass * 10.0 / stiffness, epsilon = 0.01);
+    assert_relative_eq!(position.x, 0.0, epsilon = 0.001);
+
+    // Travel limits should stop the wheel
+    let mut app_limited = create_app();
+    app_limited.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+    let chassis = app_limited.world.spawn(RigidBody::Static).id();
+    let wheel = app_limited
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            Position(Vector::NEG_Y),
+            // The damped wheel creeps towards the limit slowly enough to fall asleep
+            SleepingDisabled,
+        ))
+        .id();
+    app_limited.world.spawn(
+        SuspensionConstraint::new(chassis, wheel)
+            .with_rest_length(1.0)
+            .with_travel_limits(0.5, 1.2)
+            .with_stiffness(1.0),
+    );
+
+    for _ in 0..300 {
+        tick_60_fps(&mut app_limited);
+    }
+
+    let position = app_limited.world.get::<Position>(wheel).unwrap().0;
+    assert_relative_eq!(position.y, -1.2, epsilon = 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn revolute_soft_zone_slows_down_before_limit() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let frame = app.world.spawn(RigidBody::Static).id();
+    let door = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            #[cfg(feature = "2d")]
+            AngularVelocity(3.0),
+            #[cfg(feature = "3d")]
+            AngularVelocity(Vector::Z * 3.0),
+        ))
+        .id();
+    app.world.spawn(
+        RevoluteJoint::new(frame, door)
+            .with_angle_limits(-1.0, 1.0)
+            .with_angle_limit_soft_zone(SoftLimitZone::new(0.5, 0.01).with_damping(10.0))
+            .with_angular_velocity_damping(0.0),
+    );
+
+    let mut max_angle: Scalar = 0.0;
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+        let rotation = app.world.get::<Rotation>(door).unwrap();
+        #[cfg(feature = "2d")]
+        let angle = rotation.as_radians();
+        #[cfg(feature = "3d")]
+        let angle = rotation.0.to_scaled_axis().z;
+        max_angle = max_angle.max(angle);
+    }
+
+    // The door should enter the soft zone but stop before the hard limit
+    assert!(max_angle > 0.5);
+    assert!(max_angle < 0.8);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn revolute_joint_friction_holds_under_small_loads() {
+    let run = |torque: Scalar| {
+        let mut app = create_app();
+        app.insert_resource(Gravity::ZERO);
+
+        let frame = app.world.spawn(RigidBody::Static).id();
+        let door = app
+            .world
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+                #[cfg(feature = "2d")]
+                ExternalTorque::new(torque).with_persistence(true),
+                #[cfg(feature = "3d")]
+                ExternalTorque::new(Vector::Z * torque).with_persistence(true),
+            ))
+            .id();
+        app.world.spawn(
+            RevoluteJoint::new(frame, door)
+                .with_friction_torque(1.0)
+                .with_angular_velocity_damping(0.0),
+        );
+
+        for _ in 0..60 {
+            tick_60_fps(&mut app);
+        }
+
+        let angular_velocity = app.world.get::<AngularVelocity>(door).unwrap();
+        #[cfg(feature = "2d")]
+        let angular_speed = angular_velocity.0;
+        #[cfg(feature = "3d")]
+        let angular_speed = angular_velocity.z;
+        angular_speed
+    };
+
+    // Torques below the friction torque don't rotate the door
+    assert_relative_eq!(run(0.5), 0.0, epsilon = 0.001);
+    // Larger torques do
+    assert!(run(2.0) > 1.0);
+}
+
+#[test]
+fn sleep_group_sleeps_and_wakes_together() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let mut spawn_body = |velocity: Vector| {
+        app.world
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+                LinearVelocity(velocity),
+                SleepGroup(1),
+            ))
+            .id()
+    };
+    let still = spawn_body(Vector::ZERO);
+    let moving = spawn_body(Vector::X);
+
+    // The still body can't fall asleep while the rest of its group is moving
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world.get::<Sleeping>(still).is_none());
+    assert!(app.world.get::<Sleeping>(moving).is_none());
+
+    // Once the whole group is still, it falls asleep
+    app.world.get_mut::<LinearVelocity>(moving).unwrap().0 = Vector::ZERO;
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world.get::<Sleeping>(still).is_some());
+    assert!(app.world.get::<Sleeping>(moving).is_some());
+
+    // Waking up one body wakes up the whole group
+    app.world.get_mut::<LinearVelocity>(moving).unwrap().0 = Vector::X;
+    tick_60_fps(&mut app);
+    assert!(app.world.get::<Sleeping>(still).is_none());
+    assert!(app.world.get::<Sleeping>(moving).is_none());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn broken_joint_lets_bodies_sleep_independently() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let mut spawn_body = |x: Scalar| {
+        app.world
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::X * x),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+                LinearVelocity(Vector::ZERO),
+            ))
+            .id()
+    };
+    let still = spawn_body(0.0);
+    let moving = spawn_body(2.0);
+    let joint = app
+        .world
+        .spawn((
+            DistanceJoint::new(still, moving).with_rest_length(2.0),
+            BreakableJoint::from_force(0.001),
+        ))
+        .id();
+
+    // Pulling the bodies apart breaks the joint
+    app.world.get_mut::<LinearVelocity>(moving).unwrap().0 = Vector::X;
+    tick_60_fps(&mut app);
+    assert!(app.world.get::<JointDisabled>(joint).is_some());
+
+    // The still half falls asleep while the other half keeps moving
+    app.world.get_mut::<LinearVelocity>(still).unwrap().0 = Vector::ZERO;
+    app.world.get_mut::<LinearVelocity>(moving).unwrap().0 = Vector::X;
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world.get::<Sleeping>(still).is_some());
+    assert!(app.world.get::<Sleeping>(moving).is_none());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn collision_backend_casts_rays_and_shapes() {
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let (time_of_impact, normal) = CollisionBackend::cast_ray(
+        &collider,
+        Vector::X * 5.0,
+        Rotation::default(),
+        Vector::ZERO,
+        Dir::X,
+        100.0,
+        true,
+    )
+    .expect("ray should hit the collider");
+    assert_relative_eq!(time_of_impact, 4.5, epsilon = 0.0001);
+    assert_relative_eq!(normal, Vector::NEG_X, epsilon = 0.0001);
+
+    let hit = CollisionBackend::cast_shape(
+        &collider,
+        Vector::ZERO,
+        Rotation::default(),
+        Vector::X,
+        &collider,
+        Vector::X * 5.0,
+        Rotation::default(),
+        Vector::ZERO,
+        100.0,
+    )
+    .expect("shapes should hit each other");
+    assert_relative_eq!(hit.time_of_impact, 4.0, epsilon = 0.0001);
+
+    assert!(CollisionBackend::cast_ray(
+        &collider,
+        Vector::X * 5.0,
+        Rotation::default(),
+        Vector::ZERO,
+        Dir::Y,
+        100.0,
+        true,
+    )
+    .is_none());
+}
+
+#[test]
+#[cfg(all(
+    feature = "2d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn accumulated_angle_counts_full_turns() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            AngularVelocity(10.0),
+            AccumulatedAngle::default(),
+        ))
+        .id();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The rotation has wrapped around, but the accumulated angle hasn't
+    let angle = app.world.get::<AccumulatedAngle>(body).unwrap();
+    assert_relative_eq!(angle.0, 10.0, epsilon = 0.01);
+    assert_eq!(angle.turns(), 1);
+}
+
+#[test]
+#[cfg(all(
+    feature = "2d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn face_target_rotates_body_towards_target() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let mut spawn_body = |face_target: FaceTarget| {
+        app.world
+            .spawn((
+                RigidBody::Dynamic,
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                face_target,
+            ))
+            .id()
+    };
+    let fast = spawn_body(FaceTarget::point(Vector::Y * 5.0));
+    let weak = spawn_body(FaceTarget::point(Vector::Y * 5.0).with_max_torque(0.001));
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The forward axis of the body should point towards the target
+    let rotation = app.world.get::<Rotation>(fast).unwrap();
+    assert_relative_eq!(rotation.as_radians(), FRAC_PI_2, epsilon = 0.01);
+
+    // The weak motor can't turn the body much
+    let rotation = app.world.get::<Rotation>(weak).unwrap();
+    assert!(rotation.as_radians() < 0.1);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn kinematic_body_stops_at_static_contacts() {
+    let mut app = create_app();
+
+    // A wall whose surface is at x = 2.5
+    app.world.spawn((
+        RigidBody::Static,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(1.0, 10.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(1.0, 10.0, 10.0),
+        Position(Vector::X * 3.0),
+    ));
+
+    let mut spawn_mover = |mode: KinematicCollideMode, y: Scalar| {
+        app.world
+            .spawn((
+                RigidBody::Kinematic,
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+                Position(Vector::Y * y),
+                LinearVelocity(Vector::X * 2.0),
+                mode,
+            ))
+            .id()
+    };
+    let stopping = spawn_mover(KinematicCollideMode::StopAtContacts, 0.0);
+    let passing = spawn_mover(KinematicCollideMode::PassThrough, 2.0);
+
+    for _ in 0..180 {
+        tick_60_fps(&mut app);
+    }
+
+    let stopping_x = app.world.get::<Position>(stopping).unwrap().x;
+    assert_relative_eq!(stopping_x, 2.0, epsilon = 0.05);
+    assert_eq!(
+        app.world.get::<LinearVelocity>(stopping).unwrap().0,
+        Vector::X * 2.0
+    );
+    assert!(app.world.get::<Position>(passing).unwrap().x > 5.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn canonical_solver_scenarios() {
+    crate::test_utils::assert_scenarios(&crate::test_utils::canonical_scenarios());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn energy_diagnostics_conserve_energy_in_free_fall() {
+    let mut app = create_app();
+    app.add_plugins(PhysicsEnergyDiagnosticsPlugin);
+
+    app.world.spawn((
+        RigidBody::Dynamic,
+        #[cfg(feature = "2d")]
+        MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+        #[cfg(feature = "3d")]
+        MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+    ));
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // Potential energy is converted into kinetic energy without changing the total energy
+    let energy = app.world.resource::<PhysicsEnergyDiagnostics>();
+    assert!(energy.kinetic_energy > 1.0);
+    assert!(energy.total_drift().abs() < 0.01 * energy.kinetic_energy);
+    assert!(energy.average_drift_rate.abs() < 0.01 * energy.kinetic_energy);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn collision_started_at_reports_impact_substep() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let (wall_collider, ball_collider) = (Collider::rectangle(1.0, 4.0), Collider::circle(0.5));
+    #[cfg(feature = "3d")]
+    let (wall_collider, ball_collider) = (Collider::cuboid(1.0, 4.0, 4.0), Collider::sphere(0.5));
+
+    let wall = app
+        .world
+        .spawn((
+            TransformBundle::from_transform(Transform::from_xyz(2.0, 0.0, 0.0)),
+            RigidBody::Static,
+            wall_collider,
+        ))
+        .id();
+    let ball = app
+        .world
+        .spawn((
+            TransformBundle::default(),
+            RigidBody::Dynamic,
+            MassPropertiesBundle::new_computed(&ball_collider, 1.0),
+            ball_collider,
+            LinearVelocity(Vector::X * 30.0),
+        ))
+        .id();
+
+    let mut started = None;
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+        let events = app.world.resource::<Events<CollisionStartedAt>>();
+        if let Some(event) = events.iter_current_update_events().next() {
+            started = Some(event.clone());
+            break;
+        }
+    }
+    let started = started.expect("the ball should hit the wall");
+
+    assert!(started.substep < started.substep_count);
+    assert!(started.time() <= started.step_elapsed);
+    assert!(started.time() > started.step_elapsed - started.step_delta);
+
+    let ball_pose = if started.entity1 == ball {
+        assert_eq!(started.entity2, wall);
+        started.pose1
+    } else {
+        assert_eq!(started.entity1, wall);
+        started.pose2
+    };
+    let ball_position = app.world.get::<Position>(ball).unwrap().0;
+    assert!(ball_pose.position.x <= ball_position.x + Scalar::EPSILON);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn collision_events_carry_pair_id() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let (sensor_collider, ball_collider) = (Collider::rectangle(1.0, 4.0), Collider::circle(0.5));
+    #[cfg(feature = "3d")]
+    let (sensor_collider, ball_collider) = (Collider::cuboid(1.0, 4.0, 4.0), Collider::sphere(0.5));
+
+    app.world.spawn((
+        TransformBundle::from_transform(Transform::from_xyz(2.0, 0.0, 0.0)),
+        RigidBody::Static,
+        sensor_collider,
+        Sensor,
+    ));
+    app.world.spawn((
+        TransformBundle::default(),
+        RigidBody::Dynamic,
+        MassPropertiesBundle::new_computed(&ball_collider, 1.0),
+        ball_collider,
+        LinearVelocity(Vector::X * 10.0),
+    ));
+
+    // The ball passes through the sensor, so the collision starts and ends.
+    let mut started = None;
+    let mut pair_ids = vec![];
+    let mut ended = None;
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+        let events = app.world.resource::<Events<CollisionStarted>>();
+        if let Some(event) = events.iter_current_update_events().next() {
+            started = Some(event.clone());
+        }
+        let events = app.world.resource::<Events<Collision>>();
+        pair_ids.extend(
+            events
+                .iter_current_update_events()
+                .map(|Collision(contacts)| contacts.pair_id),
+        );
+        let events = app.world.resource::<Events<CollisionEnded>>();
+        if let Some(event) = events.iter_current_update_events().next() {
+            ended = Some(event.clone());
+        }
+    }
+    let CollisionStarted(entity1, entity2, pair_id) = started.expect("the collision should start");
+    let ended = ended.expect("the collision should end");
+
+    assert_eq!(ended, CollisionEnded(entity1, entity2, pair_id));
+    assert!(!pair_ids.is_empty());
+    assert!(pair_ids.iter().all(|id| *id == pair_id));
+}
+
+#[test]
+#[cfg(all(
+    feature = "3d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn trimesh_smooth_normal_is_interpolated() {
+    let collider = Collider::trimesh(vec![Vector::ZERO, Vector::X, Vector::Y], vec![[0, 1, 2]])
+        .with_trimesh_normals(vec![[Vector::Z, Vector::X, Vector::Y]]);
+
+    let centroid = Vector::new(1.0, 1.0, 0.0) / 3.0;
+    let normal = collider.smooth_normal_at(0, centroid).unwrap();
+    assert_relative_eq!(normal, Vector::ONE.normalize(), epsilon = 0.0001);
+
+    // Back faces are offset by the number of triangles and have flipped normals.
+    let back_normal = collider.smooth_normal_at(1, Vector::ZERO).unwrap();
+    assert_relative_eq!(back_normal, -Vector::Z, epsilon = 0.0001);
+
+    assert!(
+        Collider::trimesh(vec![Vector::ZERO, Vector::X, Vector::Y], vec![[0, 1, 2]])
+            .smooth_normal_at(0, centroid)
+            .is_none()
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn joint_motors_drive_towards_targets() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let mass_properties = MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0);
+    #[cfg(feature = "3d")]
+    let mass_properties = MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0);
+
+    let anchor = app.world.spawn(RigidBody::Static).id();
+    let wheel = app
+        .world
+        .spawn((RigidBody::Dynamic, mass_properties.clone()))
+        .id();
+    let slider = app.world.spawn((RigidBody::Dynamic, mass_properties)).id();
+
+    app.world
+        .spawn(RevoluteJoint::new(anchor, wheel).with_velocity_motor(3.0, 1000.0));
+    app.world.spawn(
+        PrismaticJoint::new(anchor, slider)
+            .with_free_axis(Vector::X)
+            .with_position_motor(1.5, 1000.0),
+    );
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The wheel spins at the target speed around the Z axis
+    let angular_velocity = app.world.get::<AngularVelocity>(wheel).unwrap();
+    #[cfg(feature = "2d")]
+    assert_relative_eq!(angular_velocity.0, 3.0, epsilon = 0.1);
+    #[cfg(feature = "3d")]
+    assert_relative_eq!(angular_velocity.z, 3.0, epsilon = 0.1);
+
+    // The slider moves to the target translation and stops there
+    let position = app.world.get::<Position>(slider).unwrap().0;
+    assert_relative_eq!(position.x, 1.5, epsilon = 0.01);
+    let velocity = app.world.get::<LinearVelocity>(slider).unwrap().0;
+    assert!(velocity.length() < 0.1);
+}
+
+#[test]
+fn contact_patch_area_and_pressure() {
+    #[cfg(feature = "2d")]
+    let points = [Vector::new(-0.5, 0.0), Vector::new(0.5, 0.0)];
+    #[cfg(feature = "3d")]
+    let points = [
+        Vector::new(-0.5, 0.0, -0.5),
+        Vector::new(0.5, 0.0, -0.5),
+        Vector::new(0.5, 0.0, 0.5),
+        Vector::new(-0.5, 0.0, 0.5),
+        // Points inside the patch don't affect the area
+        Vector::ZERO,
+    ];
+
+    let contacts = points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            let mut contact = ContactData::new(*point, *point, Vector::Y, Vector::NEG_Y, 0.0, i);
+            contact.normal_impulse = 1.0;
+            contact
+        })
+        .collect::<Vec<_>>();
+    let count = contacts.len() as Scalar;
+
+    let manifold = ContactManifold {
+        contacts,
+        normal1: Vector::Y,
+        normal2: Vector::NEG_Y,
+        index: 0,
+        subshape1: None,
+        subshape2: None,
+    };
+
+    assert_relative_eq!(manifold.patch_area(), 1.0, epsilon = 0.0001);
+    assert_relative_eq!(manifold.total_normal_impulse(), count);
+    assert_relative_eq!(
+        manifold.patch_pressure(0.5, 0.01),
+        2.0 * count,
+        epsilon = 0.0001
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn prismatic_joint_limit_restitution_bounces() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let anchor = app.world.spawn(RigidBody::Static).id();
+    let slider = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            LinearVelocity(Vector::X * 2.0),
+        ))
+        .id();
+
+    app.world.spawn(
+        PrismaticJoint::new(anchor, slider)
+            .with_free_axis(Vector::X)
+            .with_limits(-1.0, 1.0)
+            .with_limit_response(JointLimitResponse::RIGID.with_restitution(0.5))
+            .with_linear_velocity_damping(0.0),
+    );
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The slider hits the upper limit and bounces back at half of its speed
+    let position = app.world.get::<Position>(slider).unwrap().0;
+    assert!(position.x < 1.0);
+    let velocity = app.world.get::<LinearVelocity>(slider).unwrap().0;
+    assert_relative_eq!(velocity.x, -1.0, epsilon = 0.1);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn breakable_joint_breaks_under_load() {
+    use bevy::ecs::event::ManualEventReader;
+
+    let mut app = create_app();
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    let anchor = app.world.spawn(RigidBody::Static).id();
+    let spawn_weight = |app: &mut App| {
+        app.world
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::NEG_Y),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            ))
+            .id()
+    };
+    let weight1 = spawn_weight(&mut app);
+    let weight2 = spawn_weight(&mut app);
+    let mass = app.world.get::<Mass>(weight1).unwrap().0;
+
+    // The joints carry the weight of the bodies
+    let weak = app
+        .world
+        .spawn((
+            DistanceJoint::new(anchor, weight1).with_rest_length(1.0),
+            BreakableJoint::from_force(0.5 * mass * 10.0),
+        ))
+        .id();
+    let strong = app
+        .world
+        .spawn((
+            DistanceJoint::new(anchor, weight2).with_rest_length(1.0),
+            BreakableJoint::from_force(2.0 * mass * 10.0),
+        ))
+        .id();
+
+    let mut reader = ManualEventReader::<JointBroken>::default();
+    let mut broken = vec![];
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+        let events = app.world.resource::<Events<JointBroken>>();
+        broken.extend(reader.read(events).map(|event| event.joint));
+    }
+
+    assert_eq!(broken, vec![weak]);
+    assert!(app.world.get::<JointDisabled>(weak).is_some());
+    assert!(app.world.get::<JointDisabled>(strong).is_none());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn resting_box_is_inside_support_polygon() {
+    use bevy::ecs::system::SystemState;
+
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let (floor_collider, box_collider) = (
+        Collider::rectangle(10.0, 1.0),
+        Collider::rectangle(1.0, 1.0),
+    );
+    #[cfg(feature = "3d")]
+    let (floor_collider, box_collider) = (
+        Collider::cuboid(10.0, 1.0, 10.0),
+        Collider::cuboid(1.0, 1.0, 1.0),
+    );
+
+    app.world.spawn((
+        RigidBody::Static,
+        floor_collider,
+        Position(Vector::NEG_Y * 0.5),
+    ));
+    let body = app
+        .world
+        .spawn((RigidBody::Dynamic, box_collider, Position(Vector::Y * 0.5)))
+        .id();
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let mut state = SystemState::<ContactSupport>::new(&mut app.world);
+    let support = state.get(&app.world);
+
+    let polygon = support
+        .support_polygon(body, Vector::Y)
+        .expect("the box should be supported by the floor");
+    let position = app.world.get::<Position>(body).unwrap().0;
+
+    // The box rests on its bottom face, so its center is well inside of the support polygon
+    assert!(polygon.contains(position));
+    assert!(polygon.stability_margin(position) > 0.4);
+    assert!(!polygon.contains(position + Vector::X));
+    assert!(polygon.total_normal_impulse > 0.0);
+    assert!(polygon.center_of_pressure.y.abs() < 0.1);
+
+    // Nothing supports the box from above
+    assert!(support.support_polygon(body, Vector::NEG_Y).is_none());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn generic_joint_constrains_each_axis_independently() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let anchor = app.world.spawn(RigidBody::Static).id();
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            // The velocity along the locked Y axis should be removed by the joint
+            LinearVelocity(Vector::Y * 2.0),
+            #[cfg(feature = "2d")]
+            AngularVelocity(1.0),
+            #[cfg(feature = "3d")]
+            AngularVelocity(Vector::new(1.0, 0.0, 1.0)),
+        ))
+        .id();
+
+    // Drive the X axis with a motor, lock the other linear axes and the angular X axis,
+    // and let the body rotate freely around the Z axis
+    let joint = GenericJoint::new(anchor, body)
+        .with_linear_axis(
+            0,
+            JointAxisMode::Motorized {
+                target: MotorTarget::Velocity(1.0),
+                max_force: 100.0,
+            },
+        )
+        .with_angular_axes(JointAxisMode::Free)
+        .with_linear_velocity_damping(0.0)
+        .with_angular_velocity_damping(0.0);
+    #[cfg(feature = "3d")]
+    let joint = joint.with_angular_axis(0, JointAxisMode::Locked);
+    app.world.spawn(joint);
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let position = app.world.get::<Position>(body).unwrap().0;
+    assert!(position.x > 0.8 && position.x < 1.1);
+    assert!(position.y.abs() < 0.01);
+
+    let angular_velocity = app.world.get::<AngularVelocity>(body).unwrap().0;
+    #[cfg(feature = "2d")]
+    assert_relative_eq!(angular_velocity, 1.0, epsilon = 0.01);
+    #[cfg(feature = "3d")]
+    {
+        assert!(angular_velocity.x.abs() < 0.01);
+        assert!(angular_velocity.z > 0.5);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn energy_sleeping_threshold_accounts_for_size() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO)
+        .insert_resource(SleepingThreshold::Energy(0.01));
+
+    let mut spawn_spinning = |radius: Scalar| {
+        app.world
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(radius), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(radius), 1.0),
+                #[cfg(feature = "2d")]
+                AngularVelocity(0.3),
+                #[cfg(feature = "3d")]
+                AngularVelocity(Vector::Z * 0.3),
+            ))
+            .id()
+    };
+    let coin = spawn_spinning(0.1);
+    let boulder = spawn_spinning(10.0);
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The surface of the boulder moves much faster than the surface of the coin
+    assert!(app.world.get::<Sleeping>(coin).is_some());
+    assert!(app.world.get::<Sleeping>(boulder).is_none());
+
+    // With 100 units per meter, the boulder is only 10 centimeters in size
+    app.insert_resource(PhysicsLengthUnit(100.0));
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    assert!(app.world.get::<Sleeping>(boulder).is_some());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn ray_caster_fan_follows_body_rotation() {
+    let mut app = create_app();
+
+    // A wall on the right
+    app.world.spawn((
+        RigidBody::Static,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(1.0, 10.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(1.0, 10.0, 10.0),
+        Position(Vector::X * 5.0),
+    ));
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Kinematic,
+            RayCasterFan::new(Vector::ZERO, [Dir::X, Dir::Y, Dir::NEG_X]),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let hits = app.world.get::<RayCasterFanHits>(body).unwrap();
+    assert_eq!(hits.as_slice().len(), 3);
+    assert_relative_eq!(hits.get(0).unwrap().time_of_impact, 4.5, epsilon = 0.001);
+    assert!(hits.get(1).is_none());
+    assert!(hits.get(2).is_none());
+
+    // Rotate the body clockwise so that the second ray points right
+    #[cfg(feature = "2d")]
+    let rotation = Rotation::from_radians(-FRAC_PI_2);
+    #[cfg(feature = "3d")]
+    let rotation = Rotation(Quaternion::from_rotation_z(-FRAC_PI_2));
+    *app.world.get_mut::<Rotation>(body).unwrap() = rotation;
+
+    tick_60_fps(&mut app);
+
+    let hits = app.world.get::<RayCasterFanHits>(body).unwrap();
+    assert!(hits.get(0).is_none());
+    assert_eq!(hits.closest().map(|(index, _)| index), Some(1));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn gear_joint_couples_angular_velocities() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let mut spawn_gear = |angular_velocity: Scalar| {
+        app.world
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+                #[cfg(feature = "2d")]
+                AngularVelocity(angular_velocity),
+                #[cfg(feature = "3d")]
+                AngularVelocity(Vector::Z * angular_velocity),
+            ))
+            .id()
+    };
+    let gear1 = spawn_gear(1.0);
+    let gear2 = spawn_gear(0.0);
+
+    app.world
+        .spawn(GearJoint::new(gear1, gear2).with_ratio(2.0));
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let angular_velocity1 = app.world.get::<AngularVelocity>(gear1).unwrap().0;
+    let angular_velocity2 = app.world.get::<AngularVelocity>(gear2).unwrap().0;
+    #[cfg(feature = "3d")]
+    let (angular_velocity1, angular_velocity2) = (angular_velocity1.z, angular_velocity2.z);
+
+    // The second gear should rotate twice as fast in the opposite direction
+    assert!(angular_velocity1.abs() > 0.1);
+    assert_relative_eq!(angular_velocity2, -2.0 * angular_velocity1, epsilon = 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn pulley_joint_lifts_lighter_body() {
+    let mut app = create_app();
+
+    let mut spawn_weight = |x: Scalar, density: Scalar| {
+        app.world
+            .spawn((
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                Position(Vector::new(x, 0.0)),
+                #[cfg(feature = "3d")]
+                Position(Vector::new(x, 0.0, 0.0)),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), density),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), density),
+            ))
+            .id()
+    };
+    let light = spawn_weight(-2.0, 1.0);
+    let heavy = spawn_weight(2.0, 2.0);
+
+    #[cfg(feature = "2d")]
+    let (ground_anchor1, ground_anchor2) = (Vector::new(-2.0, 5.0), Vector::new(2.0, 5.0));
+    #[cfg(feature = "3d")]
+    let (ground_anchor1, ground_anchor2) =
+        (Vector::new(-2.0, 5.0, 0.0), Vector::new(2.0, 5.0, 0.0));
+    app.world
+        .spawn(PulleyJoint::new(light, heavy).with_ground_anchors(ground_anchor1, ground_anchor2));
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let light_position = app.world.get::<Position>(light).unwrap().0;
+    let heavy_position = app.world.get::<Position>(heavy).unwrap().0;
+
+    // The heavier body should pull the lighter body up while keeping the rope length
+    assert!(light_position.y > 0.1);
+    assert!(heavy_position.y < -0.1);
+    assert_relative_eq!(
+        light_position.distance(ground_anchor1) + heavy_position.distance(ground_anchor2),
+        10.0,
+        epsilon = 0.01
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn mass_ratio_stabilization_reduces_joint_stretch() {
+    // A heavy body hangs from a light body that hangs from a static anchor, returning how much
+    // the chain has stretched and the number of stabilized joint solves.
+    let simulate = |condition_masses: bool| {
+        let mut app = create_app();
+        app.insert_resource(MassRatioStabilization {
+            condition_masses,
+            ..default()
+        });
+
+        let anchor = app.world.spawn(RigidBody::Static).id();
+        let mut spawn_body = |y: Scalar, density: Scalar| {
+            app.world
+                .spawn((
+                    RigidBody::Dynamic,
+                    Position(Vector::Y * y),
+                    #[cfg(feature = "2d")]
+                    MassPropertiesBundle::new_computed(&Collider::circle(0.5), density),
+                    #[cfg(feature = "3d")]
+                    MassPropertiesBundle::new_computed(&Collider::sphere(0.5), density),
+                ))
+                .id()
+        };
+        let light = spawn_body(-1.0, 1.0);
+        let heavy = spawn_body(-2.0, 10_000.0);
+        app.world
+            .spawn(DistanceJoint::new(anchor, light).with_rest_length(1.0));
+        app.world
+            .spawn(DistanceJoint::new(light, heavy).with_rest_length(1.0));
+
+        for _ in 0..60 {
+            tick_60_fps(&mut app);
+        }
+
+        let heavy_position = app.world.get::<Position>(heavy).unwrap().0;
+        let stabilized_joints = app
+            .world
+            .resource::<MassRatioDiagnostics>()
+            .stabilized_joints;
+        (heavy_position.length() - 2.0, stabilized_joints)
+    };
+
+    let (stretch_without, stabilized_without) = simulate(false);
+    let (stretch_with, stabilized_with) = simulate(true);
+
+    // The joint between the light and heavy body exceeds the threshold in both cases
+    assert!(stabilized_without > 0);
+    assert_eq!(stabilized_with, stabilized_without);
+
+    // The heavy body takes a larger share of the correction, so the chain stretches much less
+    assert!(stretch_without > 0.05, "{stretch_without}");
+    assert!(
+        stretch_with < stretch_without * 0.5,
+        "{stretch_with} {stretch_without}"
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn long_articulation_does_not_stretch() {
+    let mut app = create_app();
+
+    // A horizontal chain of 20 links with a heavy weight at the end swings down from a static anchor
+    let root = app
+        .world
+        .spawn((RigidBody::Static, MultibodyRoot::default()))
+        .id();
+    let mut parent = root;
+    for i in 1..=20 {
+        let density = if i == 20 { 50.0 } else { 1.0 };
+        parent = app
+            .world
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::X * (i as Scalar - 0.5)),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.2), density),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.2), density),
+                MultibodyLink::new(parent)
+                    .with_local_anchor_1(if i == 1 {
+                        Vector::ZERO
+                    } else {
+                        Vector::X * 0.5
+                    })
+                    .with_local_anchor_2(Vector::NEG_X * 0.5),
+            ))
+            .id();
+    }
+    let last = parent;
+
+    // The chain shouldn't stretch at any point of the swing
+    for _ in 0..90 {
+        tick_60_fps(&mut app);
+        let position = app.world.get::<Position>(last).unwrap().0;
+        assert!(position.length() < 19.5 + 0.1);
+    }
+
+    // After 1.5 seconds, the chain should have swung down
+    let position = app.world.get::<Position>(last).unwrap().0;
+    assert!(position.y < -5.0);
+
+    let force = app.world.get::<MultibodyLink>(last).unwrap().force;
+    assert!(force.length() > 0.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "2d",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn articulated_link_faces_target() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    // A link that is free to rotate around its anchor at the static root, turning to face upwards
+    let root = app
+        .world
+        .spawn((RigidBody::Static, MultibodyRoot::default()))
+        .id();
+    let link = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            MultibodyLink::new(root),
+            FaceTarget::point(Vector::Y * 5.0),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The link should stay at its anchor while facing the target
+    let position = app.world.get::<Position>(link).unwrap().0;
+    assert_relative_eq!(position, Vector::ZERO, epsilon = 0.01);
+    let rotation = app.world.get::<Rotation>(link).unwrap();
+    assert_relative_eq!(rotation.as_radians(), FRAC_PI_2, epsilon = 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn contact_modification_scales_friction_and_removes_contacts() {
+    // Simulates a box sliding on the ground, returning its final position and velocity.
+    let simulate = |friction_scale: Scalar, keep_contacts: bool| {
+        let mut app = create_app();
+
+        app.add_systems(
+            PostProcessCollisions,
+            move |mut collisions: ResMut<Collisions>| {
+                for contacts in collisions.iter_mut() {
+                    contacts.friction_scale = friction_scale;
+                    contacts.retain_contacts(|_| keep_contacts);
+                }
+            },
+        );
+
+        #[cfg(feature = "2d")]
+        let (ground, block) = (
+            Collider::rectangle(100.0, 1.0),
+            Collider::rectangle(1.0, 1.0),
+        );
+        #[cfg(feature = "3d")]
+        let (ground, block) = (
+            Collider::cuboid(100.0, 1.0, 100.0),
+            Collider::cuboid(1.0, 1.0, 1.0),
+        );
+        app.world
+            .spawn((RigidBody::Static, ground, Position(Vector::ZERO)));
+        let entity = app
+            .world
+            .spawn((
+                RigidBody::Dynamic,
+                block,
+                Position(Vector::Y),
+                LinearVelocity(Vector::X * 2.0),
+            ))
+            .id();
+
+        for _ in 0..60 {
+            tick_60_fps(&mut app);
+        }
+
+        (
+            app.world.get::<Position>(entity).unwrap().0,
+            app.world.get::<LinearVelocity>(entity).unwrap().0,
+        )
+    };
+
+    let (_, velocity) = simulate(1.0, true);
+    assert!(velocity.x < 1.0);
+
+    let (position, velocity) = simulate(0.0, true);
+    assert!(position.y > 0.5);
+    assert_relative_eq!(velocity.x, 2.0, epsilon = 0.05);
+
+    let (position, _) = simulate(1.0, false);
+    assert!(position.y < -1.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn disable_physics_recursive_preserves_velocity_and_manual_state() {
+    use bevy::ecs::system::RunSystemOnce;
+
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+    // Joints should be disabled by the command even without propagation.
+    app.insert_resource(DisablePropagation {
+        colliders: false,
+        joints: false,
+    });
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let mut child = Entity::PLACEHOLDER;
+    let mut manually_disabled = Entity::PLACEHOLDER;
+    let root = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            collider.clone(),
+            LinearVelocity(Vector::X),
+            TransformBundle::default(),
+        ))
+        .with_children(|children| {
+            child = children
+                .spawn((collider.clone(), TransformBundle::default()))
+                .id();
+            manually_disabled = children
+                .spawn((
+                    collider.clone(),
+                    ColliderDisabled,
+                    TransformBundle::from_transform(Transform::from_xyz(0.0, 2.0, 0.0)),
+                ))
+                .id();
+        })
+        .id();
+    let anchor = app
+        .world
+        .spawn((RigidBody::Static, Position(Vector::Y * 5.0)))
+        .id();
+    let joint = app
+        .world
+        .spawn(DistanceJoint::new(anchor, root).with_rest_length(5.0))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    app.world.run_system_once(move |mut commands: Commands| {
+        commands.entity(root).disable_physics_recursive();
+    });
+    let position = app.world.get::<Position>(root).unwrap().0;
+    let velocity = app.world.get::<LinearVelocity>(root).unwrap().0;
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    assert_eq!(app.world.get::<Position>(root).unwrap().0, position);
+    assert_eq!(app.world.get::<LinearVelocity>(root).unwrap().0, velocity);
+    assert!(app.world.get::<ColliderDisabled>(root).is_some());
+    assert!(app.world.get::<ColliderDisabled>(child).is_some());
+    assert!(app.world.get::<JointDisabled>(joint).is_some());
+
+    app.world.run_system_once(move |mut commands: Commands| {
+        commands.entity(root).enable_physics_recursive();
+    });
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    assert!(app.world.get::<Position>(root).unwrap().0.x > position.x);
+    assert!(app.world.get::<RigidBodyDisabled>(root).is_none());
+    assert!(app.world.get::<ColliderDisabled>(child).is_none());
+    assert!(app
+        .world
+        .get::<ColliderDisabled>(manually_disabled)
+        .is_some());
+    assert!(app.world.get::<JointDisabled>(joint).is_none());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn predicted_pose_matches_simulation() {
+    use bevy::ecs::system::RunSystemOnce;
+
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+    let entity = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            collider,
+            LinearVelocity(Vector::X * 3.0 + Vector::Y * 5.0),
+            #[cfg(feature = "2d")]
+            AngularVelocity(1.0),
+            #[cfg(feature = "3d")]
+            AngularVelocity(Vector::Z),
+            LinearDamping(0.2),
+        ))
+        .id();
+
+    // Run one step so that the substep size is known.
+    tick_60_fps(&mut app);
+
+    let predicted = app.world.run_system_once(move |predictor: PosePredictor| {
+        predictor.predict_pose(entity, 0.5).unwrap()
+    });
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let position = app.world.get::<Position>(entity).unwrap().0;
+    let rotation = *app.world.get::<Rotation>(entity).unwrap();
+    assert!(position.distance(predicted.position) < 1e-3);
+    #[cfg(feature = "2d")]
+    assert!((rotation.as_radians() - predicted.rotation.as_radians()).abs() < 1e-3);
+    #[cfg(feature = "3d")]
+    assert!(rotation.0.angle_between(predicted.rotation.0) < 1e-3);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn trajectory_cast_stops_at_first_impact() {
+    #[cfg(feature = "2d")]
+    let (ground, projectile) = (Collider::rectangle(100.0, 1.0), Collider::circle(0.25));
+    #[cfg(feature = "3d")]
+    let (ground, projectile) = (Collider::cuboid(100.0, 1.0, 100.0), Collider::sphere(0.25));
+
+    let ground_entity = Entity::from_raw(0);
+    let position = Position(Vector::ZERO);
+    let rotation = Rotation::default();
+    let mut pipeline = SpatialQueryPipeline::new();
+    pipeline.update(
+        [(ground_entity, &position, &rotation, &ground, None)].into_iter(),
+        [ground_entity].into_iter(),
+    );
+
+    let origin = Vector::Y * 5.0;
+    let velocity = Vector::X * 2.0;
+    let gravity = Vector::NEG_Y * 10.0;
+    let trajectory = pipeline.cast_trajectory(
+        &projectile,
+        origin,
+        Default::default(),
+        velocity,
+        gravity,
+        0.05,
+        5.0,
+        SpatialQueryFilter::default(),
+    );
+
+    // The projectile touches the ground when it has fallen by 4.25 units.
+    let expected_time = (2.0 * 4.25 / 10.0 as Scalar).sqrt();
+    let hit = trajectory.hit.expect("trajectory should hit the ground");
+    assert_eq!(hit.entity, ground_entity);
+    assert_relative_eq!(hit.time, expected_time, epsilon = 0.01);
+    assert_relative_eq!(hit.point.y, 0.5, epsilon = 0.01);
+    assert_relative_eq!(hit.point.x, velocity.x * expected_time, epsilon = 0.05);
+    assert!(hit.normal.y > 0.99);
+    let end = trajectory.end().unwrap();
+    assert_relative_eq!(end.y, 0.75, epsilon = 0.01);
+    assert_eq!(trajectory.points[0], origin);
+
+    // Without reaching the ground, the whole arc is sampled.
+    let trajectory = pipeline.cast_trajectory(
+        &projectile,
+        origin,
+        Default::default(),
+        velocity,
+        gravity,
+        0.05,
+        0.5,
+        SpatialQueryFilter::default(),
+    );
+    assert!(trajectory.hit.is_none());
+    assert_eq!(trajectory.points.len(), 11);
+    assert_relative_eq!(
+        trajectory.end().unwrap().y,
+        origin.y + 0.5 * gravity.y * 0.25,
+        epsilon = 1e-4
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn character_controller_steps_up_and_stops_at_walls() {
+    let mut app = create_app();
+    app.add_plugins(CharacterControllerPlugin);
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let (floor, step, wall, character) = (
+        Collider::rectangle(100.0, 1.0),
+        Collider::rectangle(7.0, 0.2),
+        Collider::rectangle(1.0, 10.0),
+        Collider::rectangle(1.0, 1.0),
+    );
+    #[cfg(feature = "3d")]
+    let (floor, step, wall, character) = (
+        Collider::cuboid(100.0, 1.0, 100.0),
+        Collider::cuboid(7.0, 0.2, 100.0),
+        Collider::cuboid(1.0, 10.0, 100.0),
+        Collider::cuboid(1.0, 1.0, 1.0),
+    );
+
+    app.world
+        .spawn((RigidBody::Static, floor, Position(Vector::ZERO)));
+    app.world.spawn((
+        RigidBody::Static,
+        step,
+        Position(Vector::X * 6.5 + Vector::Y * 0.6),
+    ));
+    app.world
+        .spawn((RigidBody::Static, wall, Position(Vector::X * 8.5)));
+    let entity = app
+        .world
+        .spawn((
+            RigidBody::Kinematic,
+            character,
+            Position(Vector::Y * 1.01),
+            KinematicCharacterController {
+                velocity: Vector::X * 3.0,
+                ..default()
+            }
+            .with_step_offset(0.3),
+        ))
+        .id();
+
+    for _ in 0..2 {
+        tick_60_fps(&mut app);
+    }
+    let output = app
+        .world
+        .get::<KinematicCharacterControllerOutput>(entity)
+        .unwrap();
+    assert!(output.grounded);
+    assert!(!output.touching_wall());
+
+    for _ in 0..240 {
+        tick_60_fps(&mut app);
+    }
+
+    // The character stepped onto the step and stopped at the wall.
+    let position = app.world.get::<Position>(entity).unwrap().0;
+    assert_relative_eq!(position.x, 7.5, epsilon = 0.05);
+    assert_relative_eq!(position.y, 1.21, epsilon = 0.05);
+    let output = app
+        .world
+        .get::<KinematicCharacterControllerOutput>(entity)
+        .unwrap();
+    assert!(output.grounded);
+    assert!(output.touching_wall());
+    assert!(!output.touching_ceiling());
+    assert!(output.effective_translation.x.abs() < 1e-3);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn character_controller_is_carried_by_moving_platform() {
+    let mut app = create_app();
+    app.add_plugins(CharacterControllerPlugin);
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let (platform_collider, character) =
+        (Collider::rectangle(4.0, 0.5), Collider::rectangle(1.0, 1.0));
+    #[cfg(feature = "3d")]
+    let (platform_collider, character) = (
+        Collider::cuboid(4.0, 0.5, 4.0),
+        Collider::cuboid(1.0, 1.0, 1.0),
+    );
+
+    let platform = app
+        .world
+        .spawn((
+            RigidBody::Kinematic,
+            platform_collider,
+            LinearVelocity(Vector::X * 2.0),
+        ))
+        .id();
+    let entity = app
+        .world
+        .spawn((
+            RigidBody::Kinematic,
+            character,
+            Position(Vector::Y * 0.76),
+            KinematicCharacterController::default(),
+        ))
+        .id();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let platform_position = app.world.get::<Position>(platform).unwrap().0;
+    let position = app.world.get::<Position>(entity).unwrap().0;
+    assert!(platform_position.x > 1.5);
+    assert_relative_eq!(position.x, platform_position.x, epsilon = 0.1);
+    assert_relative_eq!(position.y, 0.76, epsilon = 0.02);
+    let output = app
+        .world
+        .get::<KinematicCharacterControllerOutput>(entity)
+        .unwrap();
+    assert_eq!(output.ground.map(|ground| ground.entity), Some(platform));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn contact_anchors_survive_changing_contact_count() {
+    use crate::plugins::solver::ContactAnchors;
+
+    let mut app = create_app();
+
+    // Drop the first contact point of every manifold on every other substep, and renumber the rest
+    // like the narrow phase would, so the remaining contacts move to different indices.
+    app.add_systems(
+        PostProcessCollisions,
+        |mut collisions: ResMut<Collisions>, mut drop_contact: Local<bool>| {
+            *drop_contact = !*drop_contact;
+            if !*drop_contact {
+                return;
+            }
+            for contacts in collisions.iter_mut() {
+                for manifold in contacts.manifolds.iter_mut() {
+                    if manifold.contacts.len() > 1 {
+                        manifold.contacts.remove(0);
+                    }
+                    for (index, contact) in manifold.contacts.iter_mut().enumerate() {
+                        contact.index = index;
+                    }
+                }
+            }
+        },
+    );
+
+    #[cfg(feature = "2d")]
+    let (platform_collider, cargo_collider) =
+        (Collider::rectangle(4.0, 0.5), Collider::rectangle(1.0, 1.0));
+    #[cfg(feature = "3d")]
+    let (platform_collider, cargo_collider) = (
+        Collider::cuboid(4.0, 0.5, 4.0),
+        Collider::cuboid(1.0, 1.0, 1.0),
+    );
+
+    let platform = app
+        .world
+        .spawn((
+            RigidBody::Kinematic,
+            platform_collider,
+            LinearVelocity(Vector::X * 2.0),
+        ))
+        .id();
+    let cargo = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            cargo_collider,
+            Position(Vector::Y * 0.75),
+            LinearVelocity(Vector::X * 2.0),
+        ))
+        .id();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+
+        // Each contact keeps its own anchor, so the anchors stay at different corners of the cargo
+        // instead of moving to whichever contact ends up with the same index.
+        let anchors = app
+            .world
+            .resource::<ContactAnchors>()
+            .0
+            .values()
+            .copied()
+            .collect::<Vec<_>>();
+        assert!(anchors.len() > 1);
+        for (i, anchor1) in anchors.iter().enumerate() {
+            for anchor2 in &anchors[i + 1..] {
+                assert!(anchor1.point2.distance(anchor2.point2) > 0.5);
+            }
+        }
+    }
+
+    let platform_position = app.world.get::<Position>(platform).unwrap().0;
+    let position = app.world.get::<Position>(cargo).unwrap().0;
+    assert!(platform_position.x > 1.5);
+    assert_relative_eq!(position.x, platform_position.x, epsilon = 0.01);
+}
+
+#[test]
+fn orphaned_joint_is_despawned() {
+    let mut app = create_app();
+
+    let body1 = app.world.spawn(RigidBody::Dynamic).id();
+    let body2 = app.world.spawn(RigidBody::Dynamic).id();
+    let joint = app.world.spawn(FixedJoint::new(body1, body2)).id();
+
+    tick_60_fps(&mut app);
+    assert!(app.world.get_entity(joint).is_some());
+
+    app.world.despawn(body2);
+    tick_60_fps(&mut app);
+
+    assert!(app.world.get_entity(joint).is_none());
+    let events = app.world.resource::<Events<JointOrphaned>>();
+    let orphaned = events
+        .get_reader()
+        .read(events)
+        .copied()
+        .collect::<Vec<_>>();
+    assert_eq!(orphaned, vec![JointOrphaned { joint, body: body2 }]);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn removing_and_adding_rigid_body_restores_mass() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+    let expected_mass = collider.mass_properties(1.0).mass.0;
+
+    let body = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Dynamic, collider))
+        .id();
+
+    tick_60_fps(&mut app);
+    assert_relative_eq!(app.world.get::<Mass>(body).unwrap().0, expected_mass);
+
+    app.world.entity_mut(body).remove::<RigidBody>();
+    tick_60_fps(&mut app);
+    assert_relative_eq!(app.world.get::<Mass>(body).unwrap().0, 0.0);
+    assert!(app.world.get::<ColliderParent>(body).is_none());
+
+    app.world.entity_mut(body).insert(RigidBody::Dynamic);
+    tick_60_fps(&mut app);
+    assert_relative_eq!(app.world.get::<Mass>(body).unwrap().0, expected_mass);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn disabled_body_propagates_to_colliders_and_joints() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(0.5);
+
+    let body1 = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            RigidBodyDisabled,
+            collider.clone(),
+        ))
+        .id();
+    let body2 = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::X * 2.0),
+            collider,
+        ))
+        .id();
+    let joint = app.world.spawn(DistanceJoint::new(body1, body2)).id();
+
+    tick_60_fps(&mut app);
+
+    // The disabled body doesn't fall, and its collider and joint are disabled.
+    assert_eq!(app.world.get::<Position>(body1).unwrap().0, Vector::ZERO);
+    assert!(app.world.get::<ColliderDisabled>(body1).is_some());
+    assert!(app.world.get::<ColliderDisabled>(body2).is_none());
+    assert!(app.world.get::<JointDisabled>(joint).is_some());
+
+    app.world.entity_mut(body1).remove::<RigidBodyDisabled>();
+    tick_60_fps(&mut app);
+
+    assert!(app.world.get::<ColliderDisabled>(body1).is_none());
+    assert!(app.world.get::<JointDisabled>(joint).is_none());
+    assert!(app.world.get::<Position>(body1).unwrap().y < 0.0);
+}
+
 #[test]
 fn no_ambiguity_errors() {
     #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]